@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use bson::{Bson, doc, Document, Regex as BsonRegex};
 
 use maplit::hashmap;
+use crate::core::error::Error;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
 use crate::core::input::Input;
 use crate::core::model::Model;
@@ -177,6 +178,10 @@ impl Aggregation {
         } else {
             None
         };
+        // `$text` is only allowed in the first `$match` stage
+        if let Some(search) = Self::text_search(r#where, order_by) {
+            retval.push(doc!{"$match": {"$text": {"$search": search}}});
+        }
         // build `$lookup`s for relation where
         if let Some(r#where) = r#where {
             let lookups_for_relation_where = Self::build_lookups_for_relation_where(model, graph, r#where)?;
@@ -216,7 +221,11 @@ impl Aggregation {
                     None => false
                 };
                 let sort = Self::build_order_by(model, order_by, reverse)?;
-                if !sort.is_empty() {
+                if sort.contains_key("_relevance") {
+                    retval.push(doc!{"$addFields": {"_relevance": {"$meta": "textScore"}}});
+                    retval.push(doc!{"$sort": sort});
+                    retval.push(doc!{"$unset": "_relevance"});
+                } else if !sort.is_empty() {
                     retval.push(doc!{"$sort": sort});
                 }
             } else if let Some(take) = take {
@@ -328,7 +337,14 @@ impl Aggregation {
         let mut retval = doc!{};
         for sort in order_by.as_vec().unwrap().iter() {
             let (key, value) = Input::key_value(sort.as_hashmap().unwrap());
-            let key = model.field(key).unwrap().column_name();
+            let (key, value) = if key == "_relevance" {
+                match value.get("sort") {
+                    Some(sort) => (key, sort),
+                    None => return Err(Error::invalid_operation("`_relevance` requires a `sort`.")),
+                }
+            } else {
+                (model.field(key).unwrap().column_name(), value)
+            };
             if value.is_string() {
                 let str_val = value.as_str().unwrap();
                 if str_val == "asc" {
@@ -367,6 +383,18 @@ impl Aggregation {
                 _ => {
                     if let Some(field) = model.field(key) {
                         let column_name = field.column_name();
+                        if field.field_type().is_json() {
+                            retval.extend(Self::build_where_for_json(column_name, value));
+                            continue;
+                        }
+                        // full-text search is matched with `$text` before other stages
+                        let mut value = value.clone();
+                        if let Some(map) = value.as_hashmap_mut() {
+                            if map.remove("search").is_some() && map.is_empty() {
+                                continue;
+                            }
+                        }
+                        let value = &value;
                         retval.insert(column_name, Self::build_where_item(model, graph, field.field_type(), field.is_optional(), value)?);
                     } else if let Some(relation) = model.relation(key) {
                         let relation_model = graph.model(relation.model()).unwrap();
@@ -439,6 +467,68 @@ impl Aggregation {
         }
     }
 
+    /// Json filters are applied to the nested value at `path`. `hasKey` checks the existence of a
+    /// key inside of that value.
+    fn build_where_for_json(column_name: &str, value: &Value) -> Document {
+        let map = value.as_hashmap().unwrap();
+        let mut target = column_name.to_owned();
+        if let Some(path) = map.get("path") {
+            for segment in path.as_vec().unwrap() {
+                target += ".";
+                target += segment.as_str().unwrap();
+            }
+        }
+        let mut operators = doc!{};
+        let mut retval = doc!{};
+        for (key, value) in map {
+            match key.as_str() {
+                "path" => (),
+                "equals" => { operators.insert("$eq", Bson::from(value)); }
+                "stringContains" => {
+                    operators.insert("$regex", Bson::RegularExpression(BsonRegex {
+                        pattern: regex::escape(value.as_str().unwrap()),
+                        options: "".to_string(),
+                    }));
+                }
+                "arrayContains" => {
+                    let elements = match value {
+                        Value::Vec(elements) => elements.iter().map(|e| Bson::from(e)).collect::<Vec<Bson>>(),
+                        _ => vec![Bson::from(value)],
+                    };
+                    operators.insert("$all", elements);
+                }
+                "hasKey" => {
+                    retval.insert(format!("{}.{}", target, value.as_str().unwrap()), doc!{"$exists": true});
+                }
+                _ => panic!("Unhandled key."),
+            }
+        }
+        if !operators.is_empty() {
+            retval.insert(target, operators);
+        }
+        retval
+    }
+
+    /// A collection has at most one text index, so a search is not bound to fields. The query of
+    /// `where` takes precedence over the query of `_relevance`.
+    fn text_search(r#where: Option<&Value>, order_by: Option<&Value>) -> Option<String> {
+        if let Some(r#where) = r#where {
+            for (_, value) in r#where.as_hashmap().unwrap() {
+                if let Some(search) = value.as_hashmap().and_then(|m| m.get("search")) {
+                    return Some(search.as_str().unwrap().to_owned());
+                }
+            }
+        }
+        if let Some(order_by) = order_by {
+            for item in order_by.as_vec().unwrap() {
+                if let Some(search) = item.get("_relevance").and_then(|r| r.get("search")).and_then(|s| s.as_str()) {
+                    return Some(search.to_owned());
+                }
+            }
+        }
+        None
+    }
+
     fn build_where_key(key: &str) -> Bson {
         Bson::String(match key {
             "equals" => "$eq",
@@ -493,7 +583,7 @@ impl Aggregation {
         }
         for (jt_field, foreign_field) in foreign_relation_on_join_table.iter() {
             let jt_column_name = join_model.field(jt_field).unwrap().column_name();
-            let foreign_column_name = model.field(foreign_field).unwrap().column_name();
+            let foreign_column_name = opposite_model.field(foreign_field).unwrap().column_name();
             inner_let_value.insert(jt_column_name, format!("${jt_column_name}"));
             inner_eq_values.push(doc! {"$eq": [format!("${foreign_column_name}"), format!("$${jt_column_name}")]});
         }
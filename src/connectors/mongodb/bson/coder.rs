@@ -1,5 +1,8 @@
 use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
 use bson::Bson;
+use indexmap::IndexMap;
 use key_path::KeyPath;
 
 use crate::core::error::Error;
@@ -24,6 +27,27 @@ impl BsonCoder {
         }
     }
 
+    /// Decodes a value without a schema, used for raw query results.
+    pub(crate) fn decode_raw(bson_value: &Bson) -> Value {
+        match bson_value {
+            Bson::Null | Bson::Undefined => Value::Null,
+            Bson::Boolean(b) => Value::Bool(*b),
+            Bson::Int32(n) => Value::I32(*n),
+            Bson::Int64(n) => Value::I64(*n),
+            Bson::Double(n) => Value::F64(*n),
+            Bson::Decimal128(d) => match BigDecimal::from_str(&d.to_string()) {
+                Ok(d) => Value::Decimal(d),
+                Err(_) => Value::String(d.to_string()),
+            },
+            Bson::String(s) => Value::String(s.clone()),
+            Bson::ObjectId(oid) => Value::ObjectId(*oid),
+            Bson::DateTime(val) => Value::DateTime(val.to_chrono()),
+            Bson::Array(arr) => Value::Vec(arr.iter().map(|v| Self::decode_raw(v)).collect()),
+            Bson::Document(doc) => Value::IndexMap(doc.iter().map(|(k, v)| (k.clone(), Self::decode_raw(v))).collect::<IndexMap<String, Value>>()),
+            _ => Value::String(bson_value.to_string()),
+        }
+    }
+
     pub(crate) fn decode<'a>(model: &Model, graph: &Graph, r#type: &FieldType, optional: bool, bson_value: &Bson, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         if bson_value.as_null().is_some() && optional {
             return Ok(Value::Null);
@@ -77,6 +101,7 @@ impl BsonCoder {
                 },
                 None => Err(Error::record_decoding_error(model.name(), path, "string")),
             }
+            FieldType::Json => Ok(Self::decode_raw(bson_value)),
             FieldType::Vec(inner_field) => {
                 match bson_value.as_array() {
                     Some(arr) => Ok(Value::Vec(arr.iter().enumerate().map(|(i, v)| {
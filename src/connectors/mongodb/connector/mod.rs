@@ -3,27 +3,32 @@ pub mod save_session;
 use std::collections::{HashMap};
 use std::fmt::{Debug};
 use std::ops::Neg;
-use std::sync::Arc;
+use std::sync::{Arc, Once, RwLock};
 use std::sync::atomic::{Ordering};
+use std::time::Instant;
 use async_trait::async_trait;
 use bson::{Bson, doc, Document};
 use futures_util::StreamExt;
+use itertools::Itertools;
 use key_path::path;
-use mongodb::{options::ClientOptions, Client, Database, Collection, IndexModel};
+use mongodb::{options::ClientOptions, Client, ClientSession, Database, Collection};
 use mongodb::error::{ErrorKind, WriteFailure, Error as MongoDBError};
-use mongodb::options::{FindOneAndUpdateOptions, IndexOptions, ReturnDocument};
+use mongodb::options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument};
 use regex::Regex;
+use tokio::sync::Mutex;
 use crate::connectors::mongodb::aggregation::Aggregation;
 use crate::connectors::mongodb::bson::coder::BsonCoder;
 use crate::connectors::mongodb::connector::save_session::MongoDBSaveSession;
+use crate::connectors::mongodb::migration::migrate::MongoDBMigration;
 use crate::core::action::{Action, FIND, MANY, NESTED, SINGLE};
 use crate::core::action::source::ActionSource;
+use crate::core::app::conf::LogLevel;
+use crate::core::app::logger::{log_message, log_queries, log_query};
 use crate::core::connector::Connector;
 use crate::core::object::Object;
-use crate::core::field::Sort;
 use crate::core::graph::Graph;
 use crate::core::model::{Model};
-use crate::core::model::index::{ModelIndex, ModelIndexType};
+use crate::core::r#enum::Enum;
 use crate::core::connector::SaveSession;
 use crate::core::database::r#type::DatabaseType;
 use crate::core::teon::Value;
@@ -33,12 +38,14 @@ use crate::core::input::Input;
 use crate::core::result::Result;
 use crate::teon;
 
+static TRANSACTIONS_UNSUPPORTED: Once = Once::new();
+
 #[derive(Debug)]
 pub struct MongoDBConnector {
     loaded: bool,
     client: Client,
     database: Database,
-    collections: HashMap<String, Collection<Document>>
+    collections: RwLock<HashMap<String, Collection<Document>>>
 }
 
 impl MongoDBConnector {
@@ -64,10 +71,67 @@ impl MongoDBConnector {
             loaded: false,
             client,
             database,
-            collections: HashMap::new()
+            collections: RwLock::new(HashMap::new())
+        }
+    }
+
+    fn collection(&self, model: &Model) -> Collection<Document> {
+        self.collections.read().unwrap()[model.name()].clone()
+    }
+
+    fn client_session(session: Option<&Arc<dyn SaveSession>>) -> Option<&Mutex<ClientSession>> {
+        match session {
+            Some(session) => match session.as_any().downcast_ref::<MongoDBSaveSession>() {
+                Some(mongodb_session) => mongodb_session.session.as_ref(),
+                None => None,
+            },
+            None => None,
         }
     }
 
+    fn transactions_unsupported(error: &MongoDBError) -> bool {
+        match error.kind.as_ref() {
+            ErrorKind::Transaction { message, .. } => message == "Transactions are not supported by this deployment",
+            _ => false,
+        }
+    }
+
+    fn raw_document(value: &Value) -> Result<Document> {
+        match BsonCoder::encode_without_default_type(value) {
+            Bson::Document(document) => Ok(document),
+            _ => Err(Error::internal_server_error("Raw MongoDB queries expect documents.")),
+        }
+    }
+
+    // a mongo shell like rendering of a collection call for the query log
+    fn shell_statement(col: &Collection<Document>, method: &str, args: Vec<Bson>) -> String {
+        format!("db.{}.{}({})", col.name(), method, args.iter().map(|a| a.to_string()).join(", "))
+    }
+
+    async fn aggregate_documents(&self, col: &Collection<Document>, pipeline: Vec<Document>, session: Option<&Arc<dyn SaveSession>>) -> std::result::Result<Vec<std::result::Result<Document, MongoDBError>>, MongoDBError> {
+        let statement = log_queries().then(|| Self::shell_statement(col, "aggregate", vec![Bson::from(pipeline.clone())]));
+        let start = Instant::now();
+        let result = match Self::client_session(session) {
+            Some(client_session) => {
+                let mut client_session = client_session.lock().await;
+                match col.aggregate_with_session(pipeline, None, &mut client_session).await {
+                    Ok(mut cur) => Ok(cur.stream(&mut client_session).collect().await),
+                    Err(err) => Err(err),
+                }
+            }
+            None => {
+                match col.aggregate(pipeline, None).await {
+                    Ok(cur) => Ok(cur.collect().await),
+                    Err(err) => Err(err),
+                }
+            }
+        };
+        if let Some(statement) = statement {
+            log_query("mongodb", &statement, start, result.is_ok());
+        }
+        result
+    }
+
     fn document_to_object(&self, document: &Document, object: &Object, select: Option<&Value>, include: Option<&Value>) -> Result<()> {
         for key in document.keys() {
             let object_field = object.model().fields().iter().find(|f| f.column_name() == key);
@@ -156,14 +220,13 @@ impl MongoDBConnector {
 
     async fn aggregate_or_group_by(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Vec<Value>> {
         let aggregate_input = Aggregation::build_for_aggregate(model, graph, finder)?;
-        let col = &self.collections[model.name()];
-        let cur = col.aggregate(aggregate_input, None).await;
-        if cur.is_err() {
-            println!("{:?}", cur);
+        let col = &self.collection(model);
+        let results = self.aggregate_documents(col, aggregate_input, graph.session()).await;
+        if results.is_err() {
+            println!("{:?}", results);
             return Err(Error::unknown_database_find_error());
         }
-        let cur = cur.unwrap();
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
+        let results = results.unwrap();
         let mut final_retval: Vec<Value> = vec![];
         for result in results.iter() {
             // there are records
@@ -203,10 +266,10 @@ impl MongoDBConnector {
         Ok(final_retval)
     }
 
-    async fn create_object(&self, object: &Object) -> Result<()> {
+    async fn create_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
         let model = object.model();
         let keys = object.keys_for_save();
-        let col = &self.collections[model.name()];
+        let col = &self.collection(model);
         let auto_keys = model.auto_keys();
         // create
         let mut doc = doc!{};
@@ -224,7 +287,15 @@ impl MongoDBConnector {
                 }
             }
         }
-        let result = col.insert_one(doc, None).await;
+        let statement = log_queries().then(|| Self::shell_statement(col, "insertOne", vec![Bson::Document(doc.clone())]));
+        let start = Instant::now();
+        let result = match Self::client_session(Some(&session)) {
+            Some(client_session) => col.insert_one_with_session(doc, None, &mut *client_session.lock().await).await,
+            None => col.insert_one(doc, None).await,
+        };
+        if let Some(statement) = statement {
+            log_query("mongodb", &statement, start, result.is_ok());
+        }
         match result {
             Ok(insert_one_result) => {
                 let id = insert_one_result.inserted_id;
@@ -243,12 +314,12 @@ impl MongoDBConnector {
         Ok(())
     }
 
-    async fn update_object(&self, object: &Object) -> Result<()> {
+    async fn update_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
         let model = object.model();
         let keys = object.keys_for_save();
-        let col = &self.collections[model.name()];
+        let col = &self.collection(model);
         let identifier: Bson = object.db_identifier().into();
-        let identifier = identifier.as_document().unwrap();
+        let mut identifier = identifier.as_document().unwrap().clone();
         let mut set = doc!{};
         let mut unset = doc!{};
         let mut inc = doc!{};
@@ -256,6 +327,9 @@ impl MongoDBConnector {
         let mut push = doc!{};
         for key in keys {
             if let Some(field) = model.field(key) {
+                if field.version {
+                    continue
+                }
                 let column_name = field.column_name();
                 if let Some(updator) = object.get_atomic_updator(key) {
                     let (key, val) = Input::key_value(updator.as_hashmap().unwrap());
@@ -307,19 +381,52 @@ impl MongoDBConnector {
         if update_doc.is_empty() {
             return Ok(());
         }
+        let version_update = object.version_update();
+        if let Some((field, expected, next)) = &version_update {
+            identifier.insert(field.column_name(), BsonCoder::encode(field.field_type(), expected.clone())?);
+            let next_bson = BsonCoder::encode(field.field_type(), next.clone())?;
+            match update_doc.get_document_mut("$set") {
+                Ok(set) => { set.insert(field.column_name(), next_bson); }
+                Err(_) => { update_doc.insert("$set", doc!{field.column_name(): next_bson}); }
+            }
+        }
+        let statement = log_queries().then(|| Self::shell_statement(col, if return_new { "findOneAndUpdate" } else { "updateOne" }, vec![Bson::Document(identifier.clone()), Bson::Document(update_doc.clone())]));
+        let start = Instant::now();
         if !return_new {
-            let result = col.update_one(identifier.clone(), update_doc, None).await;
+            let result = match Self::client_session(Some(&session)) {
+                Some(client_session) => col.update_one_with_session(identifier.clone(), update_doc, None, &mut *client_session.lock().await).await,
+                None => col.update_one(identifier.clone(), update_doc, None).await,
+            };
+            if let Some(statement) = statement {
+                log_query("mongodb", &statement, start, result.is_ok());
+            }
             return match result {
-                Ok(_) => Ok(()),
+                Ok(result) if result.matched_count == 0 && version_update.is_some() => Err(Error::version_conflict()),
+                Ok(_) => {
+                    if let Some((field, _, next)) = version_update {
+                        object.inner.value_map.lock().unwrap().insert(field.name().to_string(), next);
+                    }
+                    Ok(())
+                }
                 Err(error) => {
                     Err(self._handle_write_error(&error.kind, object))
                 }
             }
         } else {
             let options = FindOneAndUpdateOptions::builder().return_document(ReturnDocument::After).build();
-            let result = col.find_one_and_update(identifier.clone(), update_doc, options).await;
+            let result = match Self::client_session(Some(&session)) {
+                Some(client_session) => col.find_one_and_update_with_session(identifier.clone(), update_doc, options, &mut *client_session.lock().await).await,
+                None => col.find_one_and_update(identifier.clone(), update_doc, options).await,
+            };
+            if let Some(statement) = statement {
+                log_query("mongodb", &statement, start, result.is_ok());
+            }
             match result {
+                Ok(None) if version_update.is_some() => return Err(Error::version_conflict()),
                 Ok(updated_document) => {
+                    if let Some((field, _, next)) = version_update {
+                        object.inner.value_map.lock().unwrap().insert(field.name().to_string(), next);
+                    }
                     for key in object.inner.atomic_updator_map.lock().unwrap().keys() {
                         let bson_new_val = updated_document.as_ref().unwrap().get(key).unwrap();
                         let field = object.model().field(key).unwrap();
@@ -339,7 +446,7 @@ impl MongoDBConnector {
 
 #[async_trait]
 impl Connector for MongoDBConnector {
-    fn default_database_type(&self, field_type: &FieldType) -> DatabaseType {
+    fn default_database_type(&self, field_type: &FieldType, _enums: &HashMap<String, Enum>) -> DatabaseType {
         match field_type {
             FieldType::ObjectId => DatabaseType::ObjectId,
             FieldType::Bool => DatabaseType::Bool,
@@ -352,6 +459,7 @@ impl Connector for MongoDBConnector {
             FieldType::Date => DatabaseType::DateTime(3),
             FieldType::DateTime => DatabaseType::DateTime(3),
             FieldType::Enum(_) => DatabaseType::String,
+            FieldType::Json => DatabaseType::Json,
             FieldType::Vec(_) => panic!(""),
             FieldType::HashMap(_) => panic!(""),
             FieldType::BTreeMap(_) => panic!(""),
@@ -359,121 +467,131 @@ impl Connector for MongoDBConnector {
         }
     }
 
-    async fn load(&mut self, models: &Vec<Model>) -> Result<()> {
+    async fn load(&self, models: &Vec<Model>) -> Result<()> {
         let mut collections: HashMap<String, Collection<Document>> = HashMap::new();
         for model in models {
             let collection: Collection<Document> = self.database.collection(model.table_name());
             collections.insert(model.name().to_owned(), collection);
         }
-        self.collections = collections;
+        *self.collections.write().unwrap() = collections;
         Ok(())
     }
 
-    async fn migrate(&mut self, models: &Vec<Model>, reset_database: bool) -> Result<()> {
-        if reset_database {
-            let _ = self.database.drop(None).await;
-        }
-        for model in models {
-            let name = model.name();
-            let collection = self.collections.get(name).unwrap();
-            let mut reviewed_names: Vec<String> = Vec::new();
-            let cursor_result = collection.list_indexes(None).await;
-            if cursor_result.is_ok() {
-                let mut cursor = cursor_result.unwrap();
-                while let Some(Ok(index)) = cursor.next().await {
-                    if index.keys == doc!{"_id": 1} {
-                        continue
-                    }
-                    let name = (&index).options.as_ref().unwrap().name.as_ref().unwrap();
-                    let result = model.indices().iter().find(|i| &i.mongodb_name() == name);
-                    if result.is_none() {
-                        // not in our model definition, but in the database
-                        // drop this index
-                        let _ = collection.drop_index(name, None).await.unwrap();
-                    } else {
-                        let result = result.unwrap();
-                        let our_format_index: ModelIndex = (&index).into();
-                        if result != &our_format_index {
-                            // alter this index
-                            // drop first
-                            let _ = collection.drop_index(name, None).await.unwrap();
-                            // create index
-                            let index_options = IndexOptions::builder()
-                                .name(result.mongodb_name())
-                                .unique(result.r#type() == ModelIndexType::Unique || result.r#type() == ModelIndexType::Primary)
-                                .sparse(true)
-                                .build();
-                            let mut keys = doc!{};
-                            for item in result.items() {
-                                let field = model.field(item.field_name()).unwrap();
-                                let column_name = field.column_name();
-                                keys.insert(column_name, if item.sort() == Sort::Asc { 1 } else { -1 });
-                            }
-                            let index_model = IndexModel::builder().keys(keys).options(index_options).build();
-                            let _result = collection.create_index(index_model, None).await;
-                        }
-                    }
-                    reviewed_names.push(name.clone());
-                }
+    async fn migrate(&self, models: &Vec<Model>, _enums: &HashMap<String, Enum>, reset_database: bool, dry_run: bool) -> Result<Vec<String>> {
+        MongoDBMigration::migrate(&self.client, &self.database, models, reset_database, dry_run).await
+    }
+
+    async fn applied_migrations(&self) -> Result<Vec<String>> {
+        Err(Error::migration_failed("Versioned migrations are not supported by the MongoDB connector."))
+    }
+
+    async fn apply_migration(&self, _name: &str, _statements: &str) -> Result<()> {
+        Err(Error::migration_failed("Versioned migrations are not supported by the MongoDB connector."))
+    }
+
+    async fn revert_migration(&self, _name: &str, _statements: &str) -> Result<()> {
+        Err(Error::migration_failed("Versioned migrations are not supported by the MongoDB connector."))
+    }
+
+    async fn query_raw(&self, session: Option<&Arc<dyn SaveSession>>, query: &Value) -> Result<Value> {
+        let collection_name = match query.get("collection").map(|c| c.as_str()).flatten() {
+            Some(name) => name,
+            None => return Err(Error::internal_server_error("Raw MongoDB queries require a `collection`.")),
+        };
+        let col: Collection<Document> = self.database.collection(collection_name);
+        let documents = if let Some(pipeline) = query.get("pipeline") {
+            let pipeline = match pipeline.as_vec() {
+                Some(stages) => stages.iter().map(|s| Self::raw_document(s)).collect::<Result<Vec<Document>>>()?,
+                None => return Err(Error::internal_server_error("`pipeline` should be an array of stages.")),
+            };
+            self.aggregate_documents(&col, pipeline, session).await
+        } else if let Some(filter) = query.get("find") {
+            let filter = Self::raw_document(filter)?;
+            let mut options = FindOptions::default();
+            if let Some(sort) = query.get("sort") {
+                options.sort = Some(Self::raw_document(sort)?);
             }
-            for index in model.indices() {
-                if !reviewed_names.contains(&index.mongodb_name()) {
-                    // ignore primary
-                    if index.keys().len() == 1 {
-                        let field = model.field(index.keys().get(0).unwrap()).unwrap();
-                        if field.column_name() == "_id" {
-                            continue
-                        }
-                    }
-                    // create this index
-                    let index_options = IndexOptions::builder()
-                        .name(index.mongodb_name())
-                        .unique(index.r#type() == ModelIndexType::Unique || index.r#type() == ModelIndexType::Primary)
-                        .sparse(true)
-                        .build();
-                    let mut keys = doc!{};
-                    for item in index.items() {
-                        let field = model.field(item.field_name()).unwrap();
-                        let column_name = field.column_name();
-                        keys.insert(column_name, if item.sort() == Sort::Asc { 1 } else { -1 });
-                    }
-                    let index_model = IndexModel::builder().keys(keys).options(index_options).build();
-                    let result = collection.create_index(index_model, None).await;
-                    if result.is_err() {
-                        println!("index create error: {:?}", result.err().unwrap());
+            if let Some(projection) = query.get("projection") {
+                options.projection = Some(Self::raw_document(projection)?);
+            }
+            options.skip = query.get("skip").map(|v| v.as_i64()).flatten().map(|v| v as u64);
+            options.limit = query.get("limit").map(|v| v.as_i64()).flatten();
+            let statement = log_queries().then(|| Self::shell_statement(&col, "find", vec![Bson::Document(filter.clone())]));
+            let start = Instant::now();
+            let result = match Self::client_session(session) {
+                Some(client_session) => {
+                    let mut client_session = client_session.lock().await;
+                    match col.find_with_session(filter, options, &mut client_session).await {
+                        Ok(mut cur) => Ok(cur.stream(&mut client_session).collect().await),
+                        Err(err) => Err(err),
                     }
                 }
+                None => match col.find(filter, options).await {
+                    Ok(cur) => Ok(cur.collect().await),
+                    Err(err) => Err(err),
+                },
+            };
+            if let Some(statement) = statement {
+                log_query("mongodb", &statement, start, result.is_ok());
             }
+            result
+        } else if let Some(command) = query.get("runCommand") {
+            let command = Self::raw_document(command)?;
+            let statement = log_queries().then(|| format!("db.runCommand({})", command));
+            let start = Instant::now();
+            let result = match Self::client_session(session) {
+                Some(client_session) => self.database.run_command_with_session(command, None, &mut *client_session.lock().await).await,
+                None => self.database.run_command(command, None).await,
+            };
+            if let Some(statement) = statement {
+                log_query("mongodb", &statement, start, result.is_ok());
+            }
+            return match result {
+                Ok(document) => Ok(BsonCoder::decode_raw(&Bson::Document(document))),
+                Err(err) => Err(Error::internal_server_error(err.to_string())),
+            };
+        } else {
+            return Err(Error::internal_server_error("Raw MongoDB queries require a `pipeline`, `find` or `runCommand`."));
+        };
+        let documents = match documents {
+            Ok(documents) => documents.into_iter().collect::<std::result::Result<Vec<Document>, MongoDBError>>(),
+            Err(err) => Err(err),
+        };
+        match documents {
+            Ok(documents) => if documents.is_empty() {
+                Ok(Value::Null)
+            } else {
+                Ok(Value::Vec(documents.into_iter().map(|d| BsonCoder::decode_raw(&Bson::Document(d))).collect()))
+            },
+            Err(err) => Err(Error::internal_server_error(err.to_string())),
         }
-        Ok(())
-    }
-
-    async fn query_raw(&self, _query: &Value) -> Result<Value> {
-        unreachable!()
-        // let collection = self.collections.get(table.unwrap()).unwrap();
-        // let result = collection.aggregate(BsonCoder::encode_without_default_type(query), None).await;
-        // if result.is_err() {
-        //
-        // }
     }
     
-    async fn save_object(&self, object: &Object, _session: Arc<dyn SaveSession>) -> Result<()> {
+    async fn save_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
         if object.inner.is_new.load(Ordering::SeqCst) {
-            self.create_object(object).await
+            self.create_object(object, session).await
         } else {
-            self.update_object(object).await
+            self.update_object(object, session).await
         }
     }
 
-    async fn delete_object(&self, object: &Object, _session: Arc<dyn SaveSession>) -> Result<()> {
+    async fn delete_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
         if object.inner.is_new.load(Ordering::SeqCst) {
             return Err(Error::object_is_not_saved_thus_cant_be_deleted());
         }
         let model = object.model();
-        let col = &self.collections[model.name()];
+        let col = &self.collection(model);
         let bson_identifier: Bson = object.db_identifier().into();
         let document_identifier = bson_identifier.as_document().unwrap();
-        let result = col.delete_one(document_identifier.clone(), None).await;
+        let statement = log_queries().then(|| Self::shell_statement(col, "deleteOne", vec![Bson::Document(document_identifier.clone())]));
+        let start = Instant::now();
+        let result = match Self::client_session(Some(&session)) {
+            Some(client_session) => col.delete_one_with_session(document_identifier.clone(), None, &mut *client_session.lock().await).await,
+            None => col.delete_one(document_identifier.clone(), None).await,
+        };
+        if let Some(statement) = statement {
+            log_query("mongodb", &statement, start, result.is_ok());
+        }
         return match result {
             Ok(_result) => Ok(()),
             Err(_err) => {
@@ -487,13 +605,12 @@ impl Connector for MongoDBConnector {
         let include = finder.get("include");
 
         let aggregate_input = Aggregation::build(model, graph, finder)?;
-        let col = &self.collections[model.name()];
-        let cur = col.aggregate(aggregate_input, None).await;
-        if cur.is_err() {
+        let col = &self.collection(model);
+        let results = self.aggregate_documents(col, aggregate_input, graph.session()).await;
+        if results.is_err() {
             return Err(Error::unknown_database_find_unique_error());
         }
-        let cur = cur.unwrap();
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
+        let results = results.unwrap();
         if results.is_empty() {
             return Err(Error::object_not_found());
         }
@@ -510,16 +627,15 @@ impl Connector for MongoDBConnector {
         let include = finder.get("include");
         let aggregate_input = Aggregation::build(model, graph, finder)?;
         let reverse = Input::has_negative_take(finder);
-        let col = &self.collections[model.name()];
+        let col = &self.collection(model);
         // println!("see aggregate input: {:?}", aggregate_input);
-        let cur = col.aggregate(aggregate_input, None).await;
-        if cur.is_err() {
-            println!("{:?}", cur);
+        let results = self.aggregate_documents(col, aggregate_input, graph.session()).await;
+        if results.is_err() {
+            println!("{:?}", results);
             return Err(Error::unknown_database_find_error());
         }
-        let cur = cur.unwrap();
+        let results = results.unwrap();
         let mut result: Vec<Object> = vec![];
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
         for doc in results {
             let obj = graph.new_object(model.name(), action, action_source.clone())?;
             match self.document_to_object(&doc.unwrap(), &obj, select, include) {
@@ -540,14 +656,13 @@ impl Connector for MongoDBConnector {
 
     async fn count(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<usize> {
         let input = Aggregation::build_for_count(model, graph, finder)?;
-        let col = &self.collections[model.name()];
-        let cur = col.aggregate(input, None).await;
-        if cur.is_err() {
-            println!("{:?}", cur);
+        let col = &self.collection(model);
+        let results = self.aggregate_documents(col, input, graph.session()).await;
+        if results.is_err() {
+            println!("{:?}", results);
             return Err(Error::unknown_database_find_error());
         }
-        let cur = cur.unwrap();
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
+        let results = results.unwrap();
         if results.is_empty() {
             Ok(0)
         } else {
@@ -584,7 +699,36 @@ impl Connector for MongoDBConnector {
     }
 
     fn new_save_session(&self) -> Arc<dyn SaveSession> {
-        Arc::new(MongoDBSaveSession {})
+        Arc::new(MongoDBSaveSession { session: None })
+    }
+
+    async fn new_connection_session(&self) -> Result<Arc<dyn SaveSession>> {
+        // the driver pools connections by itself, operations don't need to share one
+        Ok(self.new_save_session())
+    }
+
+    async fn new_transaction(&self, _session: Option<&Arc<dyn SaveSession>>) -> Result<Arc<dyn SaveSession>> {
+        let mut session = match self.client.start_session(None).await {
+            Ok(session) => session,
+            Err(err) => {
+                log_message(LogLevel::Error, &format!("Cannot start a session: {}", err));
+                return Err(Error::unknown_database_transaction_error());
+            }
+        };
+        match session.start_transaction(None).await {
+            Ok(_) => Ok(Arc::new(MongoDBSaveSession { session: Some(Mutex::new(session)) })),
+            // standalone servers don't support transactions, save without one
+            Err(err) if Self::transactions_unsupported(&err) => {
+                TRANSACTIONS_UNSUPPORTED.call_once(|| {
+                    log_message(LogLevel::Warn, "Transactions are not supported by this MongoDB deployment, objects are saved without them.");
+                });
+                Ok(self.new_save_session())
+            }
+            Err(err) => {
+                log_message(LogLevel::Error, &format!("Cannot start a transaction: {}", err));
+                Err(Error::unknown_database_transaction_error())
+            }
+        }
     }
 }
 
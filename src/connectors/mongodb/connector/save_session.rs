@@ -1,10 +1,49 @@
+use std::any::Any;
 use std::fmt::{Debug};
+use async_trait::async_trait;
+use mongodb::ClientSession;
+use tokio::sync::Mutex;
 use crate::core::connector::SaveSession;
-
+use crate::core::error::Error;
+use crate::core::app::conf::LogLevel;
+use crate::core::app::logger::log_message;
+use crate::core::result::Result;
 
 #[derive(Debug)]
-pub struct MongoDBSaveSession { }
+pub struct MongoDBSaveSession {
+    pub(crate) session: Option<Mutex<ClientSession>>,
+}
 
+#[async_trait]
 impl SaveSession for MongoDBSaveSession {
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.session.is_some()
+    }
+
+    fn release(&self) { }
+
+    async fn commit(&self) -> Result<()> {
+        if let Some(session) = &self.session {
+            if let Err(err) = session.lock().await.commit_transaction().await {
+                log_message(LogLevel::Error, &format!("Cannot commit the transaction: {}", err));
+                return Err(Error::unknown_database_transaction_error());
+            }
+        }
+        Ok(())
+    }
+
+    async fn abort(&self) -> Result<()> {
+        if let Some(session) = &self.session {
+            if let Err(err) = session.lock().await.abort_transaction().await {
+                log_message(LogLevel::Error, &format!("Cannot abort the transaction: {}", err));
+                return Err(Error::unknown_database_transaction_error());
+            }
+        }
+        Ok(())
+    }
 }
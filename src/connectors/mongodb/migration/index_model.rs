@@ -9,6 +9,12 @@ impl From<&IndexModel> for ModelIndex {
             Some(bool) => bool,
             None => false
         };
+        // text indices are keyed by `_fts` and `_ftsx`, the fields are listed in the weights
+        if index_model.keys.contains_key("_fts") {
+            let weights = index_model.options.as_ref().unwrap().weights.clone().unwrap_or_default();
+            let items = weights.keys().map(|k| ModelIndexItem::new(k, Sort::Asc, None)).collect();
+            return ModelIndex::new(ModelIndexType::FullText, Some(index_model.options.as_ref().unwrap().name.as_ref().unwrap().to_string()), items);
+        }
         let mut items: Vec<ModelIndexItem> = Vec::new();
         for (k, v) in &index_model.keys {
             let item = ModelIndexItem::new(k, if v.as_i32().unwrap() == 1 { Sort::Asc } else { Sort::Desc }, None);
@@ -0,0 +1,208 @@
+use bson::{doc, Document};
+use futures_util::StreamExt;
+use mongodb::{Client, Collection, Database, IndexModel};
+use mongodb::options::IndexOptions;
+use crate::connectors::mongodb::bson::coder::BsonCoder;
+use crate::core::error::Error;
+use crate::core::field::r#type::FieldTypeOwner;
+use crate::core::field::Sort;
+use crate::core::model::Model;
+use crate::core::model::index::ModelIndex;
+use crate::core::result::Result;
+
+pub(crate) struct MongoDBMigration { }
+
+impl MongoDBMigration {
+
+    pub(crate) async fn migrate(client: &Client, database: &Database, models: &Vec<Model>, reset_database: bool, dry_run: bool) -> Result<Vec<String>> {
+        let mut statements = vec![];
+        if reset_database {
+            if !dry_run {
+                let _ = database.drop(None).await;
+            }
+            statements.push("db.dropDatabase()".to_owned());
+        }
+        let mut db_collections = database.list_collection_names(None).await.unwrap_or(vec![]);
+        let mut errors: Vec<(String, String)> = vec![];
+        for model in models {
+            if model.r#virtual() { continue }
+            let collection_name = model.table_name();
+            let result = Self::migrate_collection(client, database, model, &mut db_collections, dry_run, &mut statements).await;
+            if let Err(reason) = result {
+                errors.push((collection_name.to_owned(), reason));
+            }
+        }
+        if errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(Error::migration_error(errors))
+        }
+    }
+
+    async fn migrate_collection(client: &Client, database: &Database, model: &Model, db_collections: &mut Vec<String>, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        let collection_name = model.table_name();
+        if let Some(migration) = model.migration() {
+            if !db_collections.iter().any(|c| c == collection_name) {
+                for old_name in &migration.renamed {
+                    if db_collections.contains(old_name) {
+                        Self::rename_collection(client, database, old_name, collection_name, dry_run, statements).await?;
+                        db_collections.retain(|c| c != old_name);
+                        db_collections.push(collection_name.to_owned());
+                        break;
+                    }
+                }
+            }
+        }
+        let collection: Collection<Document> = database.collection(collection_name);
+        // fields are only migrated for existing collections
+        if db_collections.iter().any(|c| c == collection_name) {
+            Self::migrate_fields(&collection, model, dry_run, statements).await?;
+        }
+        Self::migrate_indices(&collection, model, dry_run, statements).await
+    }
+
+    async fn rename_collection(client: &Client, database: &Database, old_name: &str, new_name: &str, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        let database_name = database.name();
+        let command = doc!{"renameCollection": format!("{database_name}.{old_name}"), "to": format!("{database_name}.{new_name}")};
+        if !dry_run {
+            client.database("admin").run_command(command.clone(), None).await.map_err(|err| err.to_string())?;
+        }
+        statements.push(format!("db.adminCommand({})", command));
+        Ok(())
+    }
+
+    async fn update_many(collection: &Collection<Document>, query: Document, update: Document, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        let statement = format!("db.{}.updateMany({}, {})", collection.name(), query, update);
+        if !dry_run {
+            collection.update_many(query, update, None).await.map_err(|err| err.to_string())?;
+        }
+        statements.push(statement);
+        Ok(())
+    }
+
+    async fn migrate_fields(collection: &Collection<Document>, model: &Model, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        for field in model.fields() {
+            let column_name = field.column_name();
+            if let Some(migration) = field.migration() {
+                // rename
+                for old_name in &migration.renamed {
+                    let query = doc!{old_name: {"$exists": true}, column_name: {"$exists": false}};
+                    let update = doc!{"$rename": {old_name: column_name}};
+                    Self::update_many(collection, query, update, dry_run, statements).await?;
+                }
+                // backfill required fields
+                if field.is_required() {
+                    if let Some(default) = &migration.default {
+                        let value = BsonCoder::encode(field.field_type(), default.clone()).map_err(|err| err.message)?;
+                        let query = doc!{column_name: {"$exists": false}};
+                        let update = doc!{"$set": {column_name: value}};
+                        Self::update_many(collection, query, update, dry_run, statements).await?;
+                    }
+                }
+            }
+        }
+        for field in model.dropped_fields() {
+            let column_name = field.column_name();
+            let query = doc!{column_name: {"$exists": true}};
+            let update = doc!{"$unset": {column_name: ""}};
+            Self::update_many(collection, query, update, dry_run, statements).await?;
+        }
+        Ok(())
+    }
+
+    async fn migrate_indices(collection: &Collection<Document>, model: &Model, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        let mut reviewed_names: Vec<String> = Vec::new();
+        if let Ok(mut cursor) = collection.list_indexes(None).await {
+            while let Some(Ok(index)) = cursor.next().await {
+                if index.keys == doc!{"_id": 1} {
+                    continue
+                }
+                let name = (&index).options.as_ref().unwrap().name.as_ref().unwrap();
+                let result = model.indices().iter().find(|i| &i.mongodb_name() == name);
+                if result.is_none() {
+                    // not in our model definition, but in the database
+                    // drop this index
+                    Self::drop_index(collection, name, dry_run, statements).await?;
+                } else {
+                    let result = result.unwrap();
+                    let db_index: ModelIndex = (&index).into();
+                    if !Self::index_matches(model, result, &db_index) {
+                        // alter this index
+                        Self::drop_index(collection, name, dry_run, statements).await?;
+                        Self::create_index(collection, model, result, dry_run, statements).await?;
+                    }
+                }
+                reviewed_names.push(name.clone());
+            }
+        }
+        for index in model.indices() {
+            if !reviewed_names.contains(&index.mongodb_name()) {
+                // ignore primary
+                if index.keys().len() == 1 {
+                    let field = model.field(index.keys().get(0).unwrap()).unwrap();
+                    if field.column_name() == "_id" {
+                        continue
+                    }
+                }
+                // create this index
+                Self::create_index(collection, model, index, dry_run, statements).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Database indices are keyed by column names, while model indices are keyed by field names.
+    fn index_matches(model: &Model, index: &ModelIndex, db_index: &ModelIndex) -> bool {
+        if index.r#type().is_unique() != db_index.r#type().is_unique() {
+            return false;
+        }
+        if index.r#type().is_fulltext() || db_index.r#type().is_fulltext() {
+            // the weights of text indices are not ordered
+            return index.r#type() == db_index.r#type() && index.items().len() == db_index.items().len() && index.items().iter().all(|item| {
+                let column_name = model.field(item.field_name()).map(|f| f.column_name()).unwrap_or(item.field_name());
+                db_index.items().iter().any(|db_item| db_item.field_name() == column_name)
+            });
+        }
+        if index.items().len() != db_index.items().len() {
+            return false;
+        }
+        index.items().iter().zip(db_index.items().iter()).all(|(item, db_item)| {
+            let column_name = model.field(item.field_name()).map(|f| f.column_name()).unwrap_or(item.field_name());
+            column_name == db_item.field_name() && item.sort() == db_item.sort()
+        })
+    }
+
+    async fn drop_index(collection: &Collection<Document>, name: &str, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        if !dry_run {
+            collection.drop_index(name, None).await.map_err(|err| err.to_string())?;
+        }
+        statements.push(format!("db.{}.dropIndex(\"{}\")", collection.name(), name));
+        Ok(())
+    }
+
+    async fn create_index(collection: &Collection<Document>, model: &Model, index: &ModelIndex, dry_run: bool, statements: &mut Vec<String>) -> std::result::Result<(), String> {
+        let unique = index.r#type().is_unique();
+        let mut keys = doc!{};
+        for item in index.items() {
+            let field = model.field(item.field_name()).unwrap();
+            let column_name = field.column_name();
+            if index.r#type().is_fulltext() {
+                keys.insert(column_name, "text");
+            } else {
+                keys.insert(column_name, if item.sort() == Sort::Asc { 1 } else { -1 });
+            }
+        }
+        let statement = format!("db.{}.createIndex({}, {})", collection.name(), keys, doc!{"name": index.mongodb_name(), "unique": unique, "sparse": true});
+        if !dry_run {
+            let index_options = IndexOptions::builder()
+                .name(index.mongodb_name())
+                .unique(unique)
+                .sparse(true)
+                .build();
+            let index_model = IndexModel::builder().keys(keys).options(index_options).build();
+            collection.create_index(index_model, None).await.map_err(|err| err.to_string())?;
+        }
+        statements.push(statement);
+        Ok(())
+    }
+}
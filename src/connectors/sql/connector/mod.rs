@@ -1,15 +1,20 @@
 pub mod save_session;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use async_trait::async_trait;
-use quaint_forked::{prelude::*, pooled::Quaint, ast::Query as QuaintQuery};
+use quaint_forked::{prelude::*, pooled::Quaint};
+use quaint_forked::connector::start_owned_transaction;
 use quaint_forked::error::DatabaseConstraint;
 use quaint_forked::error::ErrorKind::UniqueConstraintViolation;
 use crate::core::model::Model;
+use crate::core::r#enum::Enum;
 use crate::connectors::sql::schema::r#type::field::ToDatabaseType;
 use crate::connectors::sql::connector::save_session::SQLSaveSession;
 use crate::connectors::sql::execution::Execution;
+use crate::connectors::sql::migration::history::SQLMigrationHistory;
 use crate::connectors::sql::migration::migrate::SQLMigration;
 use crate::connectors::sql::query::Query;
 use crate::connectors::sql::stmts::SQL;
@@ -19,10 +24,13 @@ use crate::connectors::sql::schema::value::encode::ToSQLString;
 use crate::connectors::sql::schema::value::encode::PSQLArrayToSQLString;
 use crate::connectors::sql::url::url_utils;
 use crate::core::action::Action;
+use crate::core::app::conf::PoolConf;
 use crate::core::action::source::ActionSource;
 use crate::core::connector::{Connector, SaveSession};
 use crate::core::database::r#type::DatabaseType;
 use crate::core::error::Error;
+use crate::core::app::conf::LogLevel;
+use crate::core::app::logger::log_message;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
 use crate::core::input::Input;
 use crate::core::result::Result;
@@ -36,15 +44,62 @@ pub(crate) struct SQLConnector {
 
 impl SQLConnector {
 
-    pub(crate) async fn new(dialect: SQLDialect, url: &str, reset: bool) -> Self {
+    pub(crate) async fn new(dialect: SQLDialect, url: &str, pool_conf: &PoolConf, reset: bool) -> Self {
         SQLMigration::create_database_if_needed(dialect, url, reset).await;
-        let url = url_utils::normalized_url(dialect, url);
-        let pool = Quaint::builder(url.as_str()).unwrap().build();
+        // every connection to an in-memory SQLite database opens a new empty database, keep one
+        // connection alive for the whole process
+        let memory = dialect == SQLDialect::SQLite && url_utils::is_memory_url(url_utils::remove_scheme(url));
+        let url = if dialect == SQLDialect::MSSQL {
+            url_utils::mssql_normalized_url(url)
+        } else {
+            url_utils::normalized_url(dialect, url).to_string()
+        };
+        let mut builder = Quaint::builder(url.as_str()).unwrap();
+        if memory {
+            builder.connection_limit(1);
+        } else if let Some(size) = pool_conf.size {
+            builder.connection_limit(size);
+        }
+        if let Some(timeout) = pool_conf.timeout {
+            builder.pool_timeout(Duration::from_secs(timeout));
+        }
+        if let Some(max_idle) = pool_conf.max_idle.filter(|_| !memory) {
+            builder.max_idle(max_idle);
+        }
+        if !memory {
+            if let Some(idle_timeout) = pool_conf.idle_timeout {
+                builder.max_idle_lifetime(Duration::from_secs(idle_timeout));
+            }
+            if let Some(max_lifetime) = pool_conf.max_lifetime {
+                builder.max_lifetime(Duration::from_secs(max_lifetime));
+            }
+        }
+        let pool = builder.build();
         Self { dialect, pool }
     }
 
-    async fn create_object(&self, object: &Object) -> Result<()> {
-        let conn = self.pool.check_out().await.unwrap();
+    async fn conn(&self, session: Option<&Arc<dyn SaveSession>>) -> Result<Arc<dyn Queryable>> {
+        if let Some(session) = session {
+            if let Some(sql_session) = session.as_any().downcast_ref::<SQLSaveSession>() {
+                if let Some(transaction) = sql_session.transaction() {
+                    return Ok(transaction);
+                }
+                if let Some(connection) = sql_session.connection() {
+                    return Ok(connection);
+                }
+            }
+        }
+        match self.pool.check_out().await {
+            Ok(conn) => Ok(Arc::new(conn)),
+            Err(err) => {
+                log_message(LogLevel::Error, &format!("Cannot check out a database connection: {}", err));
+                Err(Error::internal_server_error("Cannot check out a database connection."))
+            }
+        }
+    }
+
+    async fn create_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        let conn = self.conn(Some(&session)).await?;
         let model = object.model();
         let keys = object.keys_for_save();
         let auto_keys = model.auto_keys();
@@ -63,8 +118,9 @@ impl SQLConnector {
         }
         let value_refs: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
         let stmt = SQL::insert_into(model.table_name()).values(value_refs).returning(auto_keys).to_string(self.dialect);
-        if self.dialect == SQLDialect::PostgreSQL {
-            match conn.query(QuaintQuery::from(stmt)).await {
+        // PostgreSQL and SQL Server return the generated keys from the insert statement itself
+        if self.dialect == SQLDialect::PostgreSQL || self.dialect == SQLDialect::MSSQL {
+            match Execution::query_rows(conn.as_ref(), &stmt).await {
                 Ok(result_set) => {
                     let columns = result_set.columns().clone();
                     let result = result_set.into_iter().next();
@@ -82,7 +138,7 @@ impl SQLConnector {
                 }
             }
         } else {
-            match conn.query(QuaintQuery::from(stmt)).await {
+            match Execution::query_rows(conn.as_ref(), &stmt).await {
                 Ok(result) => {
                     let id = result.last_insert_id().unwrap();
                     for key in auto_keys {
@@ -102,13 +158,16 @@ impl SQLConnector {
         }
     }
 
-    async fn update_object(&self, object: &Object) -> Result<()> {
-        let conn = self.pool.check_out().await.unwrap();
+    async fn update_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        let conn = self.conn(Some(&session)).await?;
         let model = object.model();
         let keys = object.keys_for_save();
         let mut values: Vec<(&str, String)> = vec![];
         for key in &keys {
             if let Some(field) = model.field(key) {
+                if field.version {
+                    continue
+                }
                 let column_name = field.column_name();
                 if let Some(updator) = object.get_atomic_updator(key) {
                     let (key, val) = Input::key_value(updator.as_hashmap().unwrap());
@@ -129,18 +188,28 @@ impl SQLConnector {
                 values.push((key, PSQLArrayToSQLString::to_string_with_ft(&val, self.dialect, property.field_type())));
             }
         }
-        let value_refs: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
         let identifier = object.identifier();
-        let r#where = Query::where_from_identifier(object, self.dialect);
+        let mut r#where = Query::where_from_identifier(object, self.dialect);
+        let version_update = if values.is_empty() { None } else { object.version_update() };
+        if let Some((field, expected, next)) = &version_update {
+            values.push((field.column_name(), PSQLArrayToSQLString::to_string_with_ft(next, self.dialect, field.field_type())));
+            let mut versioned_identifier = identifier.as_hashmap().unwrap().clone();
+            versioned_identifier.insert(field.name().to_owned(), expected.clone());
+            r#where = Query::where_from_value(model, object.graph(), &Value::HashMap(versioned_identifier), self.dialect);
+        }
+        let value_refs: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
         if !value_refs.is_empty() {
             let stmt = SQL::update(model.table_name()).values(value_refs).r#where(&r#where).to_string(self.dialect);
-            let result = conn.execute(QuaintQuery::from(stmt)).await;
-            if result.is_err() {
-                println!("{:?}", result.err().unwrap());
-                return Err(Error::unknown_database_write_error());
+            match Execution::execute(conn.as_ref(), &stmt).await {
+                Ok(0) if version_update.is_some() => return Err(Error::version_conflict()),
+                Ok(_) => (),
+                Err(err) => {
+                    println!("{:?}", err);
+                    return Err(Error::unknown_database_write_error());
+                }
             }
         }
-        let result = Execution::query(&self.pool, model, object.graph(), &teon!({"where": identifier, "take": 1}), self.dialect).await?;
+        let result = Execution::query(conn.as_ref(), model, object.graph(), &teon!({"where": identifier, "take": 1}), self.dialect).await?;
         if result.is_empty() {
             Err(Error::object_not_found())
         } else {
@@ -174,26 +243,41 @@ impl SQLConnector {
 #[async_trait]
 impl Connector for SQLConnector {
 
-    fn default_database_type(&self, field_type: &FieldType) -> DatabaseType {
-        field_type.to_database_type(self.dialect)
+    fn default_database_type(&self, field_type: &FieldType, enums: &HashMap<String, Enum>) -> DatabaseType {
+        field_type.to_database_type(self.dialect, enums)
     }
 
-    async fn load(&mut self, _models: &Vec<Model>) -> Result<()> {
+    async fn load(&self, _models: &Vec<Model>) -> Result<()> {
         Ok(())
     }
 
-    async fn migrate(&mut self, models: &Vec<Model>, _reset_database: bool) -> Result<()> {
-        SQLMigration::migrate(self.dialect, &self.pool, models).await;
-        Ok(())
+    async fn migrate(&self, models: &Vec<Model>, enums: &HashMap<String, Enum>, _reset_database: bool, dry_run: bool) -> Result<Vec<String>> {
+        SQLMigration::migrate(self.dialect, &self.pool, models, enums, dry_run).await
     }
 
-    async fn query_raw(&self, query: &Value) -> Result<Value> {
-        let conn = self.pool.check_out().await.unwrap();
-        let result = conn.query(QuaintQuery::from(query.as_str().unwrap())).await;
+    async fn applied_migrations(&self) -> Result<Vec<String>> {
+        SQLMigrationHistory::applied(self.dialect, &self.pool).await
+    }
+
+    async fn apply_migration(&self, name: &str, statements: &str) -> Result<()> {
+        SQLMigrationHistory::apply(self.dialect, &self.pool, name, statements).await
+    }
+
+    async fn revert_migration(&self, name: &str, statements: &str) -> Result<()> {
+        SQLMigrationHistory::revert(self.dialect, &self.pool, name, statements).await
+    }
+
+    async fn query_raw(&self, session: Option<&Arc<dyn SaveSession>>, query: &Value) -> Result<Value> {
+        let sql = match query.as_str() {
+            Some(sql) => sql,
+            None => return Err(Error::internal_server_error("Raw SQL queries should be strings.")),
+        };
+        let conn = self.conn(session).await?;
+        let result = Execution::query_rows(conn.as_ref(), sql).await;
         if result.is_err() {
             let err = result.unwrap_err();
-            let msg = err.original_message();
-            return Err(Error::internal_server_error(msg.unwrap()));
+            let msg = err.original_message().map(|m| m.to_owned()).unwrap_or(err.to_string());
+            return Err(Error::internal_server_error(msg));
         } else {
             let result = result.unwrap();
             if result.is_empty() {
@@ -204,24 +288,24 @@ impl Connector for SQLConnector {
         }
     }
 
-    async fn save_object(&self, object: &Object, _session: Arc<dyn SaveSession>) -> Result<()> {
+    async fn save_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
         let is_new = object.inner.is_new.load(Ordering::SeqCst);
         if is_new {
-            self.create_object(object).await
+            self.create_object(object, session).await
         } else {
-            self.update_object(object).await
+            self.update_object(object, session).await
         }
     }
 
-    async fn delete_object(&self, object: &Object, _session: Arc<dyn SaveSession>) -> Result<()> {
-        let conn = self.pool.check_out().await.unwrap();
+    async fn delete_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        let conn = self.conn(Some(&session)).await?;
         if object.inner.is_new.load(Ordering::SeqCst) {
             return Err(Error::object_is_not_saved_thus_cant_be_deleted());
         }
         let model = object.model();
         let r#where = Query::where_from_identifier(object, self.dialect);
         let stmt = SQL::delete_from(model.table_name()).r#where(r#where).to_string(self.dialect);
-        let result = conn.execute(QuaintQuery::from(stmt)).await;
+        let result = Execution::execute(conn.as_ref(), &stmt).await;
         if result.is_err() {
             println!("{:?}", result.err().unwrap());
             return Err(Error::unknown_database_write_error());
@@ -231,7 +315,8 @@ impl Connector for SQLConnector {
     }
 
     async fn find_unique(&self, graph: &Graph, model: &Model, finder: &Value, _mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
-        let objects = Execution::query_objects(&self.pool, model, graph, finder, self.dialect, action, action_source.clone()).await?;
+        let conn = self.conn(graph.session()).await?;
+        let objects = Execution::query_objects(conn.as_ref(), model, graph, finder, self.dialect, action, action_source.clone()).await?;
         if objects.is_empty() {
             Err(Error::object_not_found())
         } else {
@@ -240,25 +325,50 @@ impl Connector for SQLConnector {
     }
 
     async fn find_many(&self, graph: &Graph, model: &Model, finder: &Value, _mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
-        Execution::query_objects(&self.pool, model, graph, finder, self.dialect, action, action_source).await
+        let conn = self.conn(graph.session()).await?;
+        Execution::query_objects(conn.as_ref(), model, graph, finder, self.dialect, action, action_source).await
     }
 
     async fn count(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<usize> {
-        match Execution::query_count(&self.pool, model, graph, finder, self.dialect).await {
+        let conn = self.conn(graph.session()).await?;
+        match Execution::query_count(conn.as_ref(), model, graph, finder, self.dialect).await {
             Ok(c) => Ok(c as usize),
             Err(e) => Err(e),
         }
     }
 
     async fn aggregate(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Value> {
-        Execution::query_aggregate(&self.pool, model, graph, finder, self.dialect).await
+        let conn = self.conn(graph.session()).await?;
+        Execution::query_aggregate(conn.as_ref(), model, graph, finder, self.dialect).await
     }
 
     async fn group_by(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Value> {
-        Execution::query_group_by(&self.pool, model, graph, finder, self.dialect).await
+        let conn = self.conn(graph.session()).await?;
+        Execution::query_group_by(conn.as_ref(), model, graph, finder, self.dialect).await
     }
 
     fn new_save_session(&self) -> Arc<dyn SaveSession> {
-        Arc::new(SQLSaveSession { })
+        Arc::new(SQLSaveSession::new(None, None))
+    }
+
+    async fn new_connection_session(&self) -> Result<Arc<dyn SaveSession>> {
+        match self.pool.check_out().await {
+            Ok(conn) => Ok(Arc::new(SQLSaveSession::new(None, Some(Arc::new(conn))))),
+            Err(err) => {
+                log_message(LogLevel::Error, &format!("Cannot check out a database connection: {}", err));
+                Err(Error::internal_server_error("Cannot check out a database connection."))
+            }
+        }
+    }
+
+    async fn new_transaction(&self, session: Option<&Arc<dyn SaveSession>>) -> Result<Arc<dyn SaveSession>> {
+        let conn = self.conn(session).await?;
+        match start_owned_transaction(conn.clone(), None).await {
+            Ok(transaction) => Ok(Arc::new(SQLSaveSession::new(Some(Arc::new(transaction)), Some(conn)))),
+            Err(err) => {
+                log_message(LogLevel::Error, &format!("Cannot start a transaction: {}", err));
+                Err(Error::unknown_database_transaction_error())
+            }
+        }
     }
 }
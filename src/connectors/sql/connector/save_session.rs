@@ -1,9 +1,84 @@
-use std::fmt::Debug;
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use quaint_forked::connector::{OwnedTransaction, Queryable};
 use crate::core::connector::SaveSession;
+use crate::core::error::Error;
+use crate::core::app::conf::LogLevel;
+use crate::core::app::logger::log_message;
+use crate::core::result::Result;
 
-#[derive(Debug)]
-pub struct SQLSaveSession { }
+/// A save session of SQL connectors. The transaction and the connection are given back once the
+/// session is committed, aborted or released, even if objects still hold on to the session.
+pub struct SQLSaveSession {
+    transaction: Mutex<Option<Arc<OwnedTransaction>>>,
+    connection: Mutex<Option<Arc<dyn Queryable>>>,
+}
+
+impl SQLSaveSession {
+
+    pub(crate) fn new(transaction: Option<Arc<OwnedTransaction>>, connection: Option<Arc<dyn Queryable>>) -> Self {
+        Self { transaction: Mutex::new(transaction), connection: Mutex::new(connection) }
+    }
+
+    pub(crate) fn transaction(&self) -> Option<Arc<OwnedTransaction>> {
+        self.transaction.lock().unwrap().clone()
+    }
+
+    pub(crate) fn connection(&self) -> Option<Arc<dyn Queryable>> {
+        self.connection.lock().unwrap().clone()
+    }
+
+    fn take_transaction(&self) -> Option<Arc<OwnedTransaction>> {
+        let transaction = self.transaction.lock().unwrap().take();
+        self.connection.lock().unwrap().take();
+        transaction
+    }
+}
 
+impl Debug for SQLSaveSession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SQLSaveSession")
+            .field("transaction", &self.transaction.lock().unwrap().is_some())
+            .field("connection", &self.connection.lock().unwrap().is_some())
+            .finish()
+    }
+}
+
+#[async_trait]
 impl SaveSession for SQLSaveSession {
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.transaction.lock().unwrap().is_some()
+    }
+
+    fn release(&self) {
+        self.transaction.lock().unwrap().take();
+        self.connection.lock().unwrap().take();
+    }
+
+    async fn commit(&self) -> Result<()> {
+        if let Some(transaction) = self.take_transaction() {
+            if let Err(err) = transaction.commit().await {
+                log_message(LogLevel::Error, &format!("Cannot commit the transaction: {}", err));
+                return Err(Error::unknown_database_transaction_error());
+            }
+        }
+        Ok(())
+    }
+
+    async fn abort(&self) -> Result<()> {
+        if let Some(transaction) = self.take_transaction() {
+            if let Err(err) = transaction.rollback().await {
+                log_message(LogLevel::Error, &format!("Cannot roll back the transaction: {}", err));
+                return Err(Error::unknown_database_transaction_error());
+            }
+        }
+        Ok(())
+    }
 }
@@ -2,14 +2,15 @@ use std::borrow::Cow;
 use array_tool::vec::Uniq;
 use std::collections::HashMap;
 use async_recursion::async_recursion;
-use quaint_forked::pooled::Quaint;
-use quaint_forked::prelude::{Queryable, ResultRow};
+use std::time::Instant;
+use quaint_forked::prelude::{Queryable, ResultRow, ResultSet};
 use quaint_forked::ast::{Query as QuaintQuery};
 use crate::connectors::sql::query::Query;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::connectors::sql::schema::value::decode::RowDecoder;
 use crate::connectors::sql::schema::value::encode::{SQLEscape, ToSQLString, ToWrapped};
 use crate::core::action::Action;
+use crate::core::app::logger::log_query;
 use crate::core::action::source::ActionSource;
 use crate::core::error::Error;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
@@ -23,6 +24,20 @@ pub(crate) struct Execution { }
 
 impl Execution {
 
+    pub(crate) async fn query_rows(conn: &dyn Queryable, stmt: &str) -> quaint_forked::Result<ResultSet> {
+        let start = Instant::now();
+        let result = conn.query(QuaintQuery::from(stmt)).await;
+        log_query("sql", stmt, start, result.is_ok());
+        result
+    }
+
+    pub(crate) async fn execute(conn: &dyn Queryable, stmt: &str) -> quaint_forked::Result<u64> {
+        let start = Instant::now();
+        let result = conn.execute(QuaintQuery::from(stmt)).await;
+        log_query("sql", stmt, start, result.is_ok());
+        result
+    }
+
     pub(crate) fn row_to_value(model: &Model, graph: &Graph, row: &ResultRow, columns: &Vec<String>, dialect: SQLDialect) -> Value {
 
         Value::HashMap(columns.iter().filter_map(|column_name| {
@@ -82,8 +97,8 @@ impl Execution {
         Value::HashMap(retval)
     }
 
-    pub(crate) async fn query_objects(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
-        let values = Self::query(pool, model, graph, finder, dialect).await?;
+    pub(crate) async fn query_objects(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
+        let values = Self::query(conn, model, graph, finder, dialect).await?;
         let select = finder.as_hashmap().unwrap().get("select");
         let include = finder.as_hashmap().unwrap().get("include");
         let mut results = vec![];
@@ -96,8 +111,7 @@ impl Execution {
     }
 
     #[async_recursion]
-    async fn query_internal(pool: &Quaint, model: &Model, graph: &Graph, value: &Value, dialect: SQLDialect, additional_where: Option<String>, additional_left_join: Option<String>, join_table_results: Option<Vec<String>>, force_negative_take: bool, additional_distinct: Option<Vec<String>>) -> Result<Vec<Value>> {
-        let conn = pool.check_out().await.unwrap();
+    async fn query_internal(conn: &dyn Queryable, model: &Model, graph: &Graph, value: &Value, dialect: SQLDialect, additional_where: Option<String>, additional_left_join: Option<String>, join_table_results: Option<Vec<String>>, force_negative_take: bool, additional_distinct: Option<Vec<String>>) -> Result<Vec<Value>> {
         let _select = value.get("select");
         let include = value.get("include");
         let original_distinct = value.get("distinct").map(|v| if v.as_vec().unwrap().is_empty() { None } else { Some(v.as_vec().unwrap()) }).flatten();
@@ -110,10 +124,9 @@ impl Execution {
         } else {
             Cow::Borrowed(value)
         };
-        let stmt = Query::build(model, graph, value_for_build.as_ref(), dialect, additional_where, additional_left_join, join_table_results, force_negative_take);
-        // println!("sql stmt: {}", &stmt);
+        let stmt = Query::build(model, graph, value_for_build.as_ref(), dialect, additional_where, additional_left_join, join_table_results, force_negative_take)?;
         let reverse = Input::has_negative_take(value);
-        let rows = match conn.query(QuaintQuery::from(stmt)).await {
+        let rows = match Self::query_rows(conn, &stmt).await {
             Ok(rows) => rows,
             Err(err) => {
                 println!("{:?}", err);
@@ -157,30 +170,27 @@ impl Execution {
                 if !relation.has_join_table() {
                     let fields = relation.fields();
                     let opposite_fields = relation.references();
-                    let names = if opposite_fields.len() == 1 {
-                        opposite_model.field(opposite_fields.get(0).unwrap()).unwrap().column_name().escape(dialect)
-                    } else {
-                        opposite_fields.iter().map(|f| opposite_model.field(f).unwrap().column_name().escape(dialect)).collect::<Vec<String>>().join(",").to_wrapped()
-                    };
-                    let values = if opposite_fields.len() == 1 {
+                    let where_addition = if opposite_fields.len() == 1 {
+                        let names = opposite_model.field(opposite_fields.get(0).unwrap()).unwrap().column_name().escape(dialect);
                         // in a (?,?,?,?,?) format
                         let field_name = fields.get(0).unwrap();
-                        results.iter().map(|v| {
+                        let values = results.iter().map(|v| {
                             v.as_hashmap().unwrap().get(field_name).unwrap().to_string(dialect)
-                        }).collect::<Vec<String>>().join(",").to_wrapped()
+                        }).collect::<Vec<String>>().join(",").to_wrapped();
+                        Query::where_item(&names, "IN", &values)
                     } else {
-                        // in a (VALUES (?,?),(?,?)) format
-                        format!("(VALUES {})", results.iter().map(|o| {
-                            fields.iter().map(|f| o.as_hashmap().unwrap().get(f).unwrap().to_string(dialect)).collect::<Vec<String>>().join(",").to_wrapped()
-                        }).collect::<Vec<String>>().join(","))
+                        let names = opposite_fields.iter().map(|f| opposite_model.field(f).unwrap().column_name().escape(dialect)).collect::<Vec<String>>();
+                        let rows = results.iter().map(|o| {
+                            fields.iter().map(|f| o.as_hashmap().unwrap().get(f).unwrap().to_string(dialect)).collect::<Vec<String>>()
+                        }).collect::<Vec<Vec<String>>>();
+                        Query::where_rows_in(&names, &rows)
                     };
-                    let where_addition = Query::where_item(&names, "IN", &values);
                     let nested_query = if value.is_hashmap() {
                         Self::without_paging_and_skip_take_distinct(value)
                     } else {
                         Cow::Owned(teon!({}))
                     };
-                    let included_values = Self::query_internal(pool, opposite_model, graph, &nested_query, dialect, Some(where_addition), None, None, negative_take, None).await?;
+                    let included_values = Self::query_internal(conn, opposite_model, graph, &nested_query, dialect, Some(where_addition), None, None, negative_take, None).await?;
                     // println!("see included: {:?}", included_values);
                     for result in results.iter_mut() {
                         let mut skipped = 0;
@@ -232,23 +242,20 @@ impl Execution {
                     let joins = join_parts.join(" AND ");
                     let left_join = format!("{} AS j ON {}", through_model.table_name(), joins);
                     let (through_table, through_relation) = graph.through_relation(relation);
-                    let names = if through_relation.len() == 1 { // todo: column name
-                        format!("j.{}", through_table.field(through_relation.fields().get(0).unwrap()).unwrap().column_name().escape(dialect))
-                    } else {
-                        through_relation.fields().iter().map(|f| format!("j.{}", through_table.field(f).unwrap().column_name().escape(dialect))).collect::<Vec<String>>().join(",").to_wrapped()
-                    };
-                    let values = if through_relation.len() == 1 { // (?,?,?,?,?) format
+                    let where_addition = if through_relation.len() == 1 { // (?,?,?,?,?) format
+                        let names = format!("j.{}", through_table.field(through_relation.fields().get(0).unwrap()).unwrap().column_name().escape(dialect));
                         let field_name = through_relation.references().get(0).unwrap();
-                        results.iter().map(|v| {
+                        let values = results.iter().map(|v| {
                             v.as_hashmap().unwrap().get(field_name).unwrap().to_string(dialect)
-                        }).collect::<Vec<String>>().join(",").to_wrapped()
-                    } else { // (VALUES (?,?),(?,?)) format
-                        let pairs = results.iter().map(|o| {
-                            through_relation.references().iter().map(|f| o.as_hashmap().unwrap().get(f).unwrap().to_string(dialect)).collect::<Vec<String>>().join(",").to_wrapped()
-                        }).collect::<Vec<String>>().join(",");
-                        format!("(VALUES {})", pairs)
+                        }).collect::<Vec<String>>().join(",").to_wrapped();
+                        Query::where_item(&names, "IN", &values)
+                    } else {
+                        let names = through_relation.fields().iter().map(|f| format!("j.{}", through_table.field(f).unwrap().column_name().escape(dialect))).collect::<Vec<String>>();
+                        let rows = results.iter().map(|o| {
+                            through_relation.references().iter().map(|f| o.as_hashmap().unwrap().get(f).unwrap().to_string(dialect)).collect::<Vec<String>>()
+                        }).collect::<Vec<Vec<String>>>();
+                        Query::where_rows_in(&names, &rows)
                     };
-                    let where_addition = Query::where_item(&names, "IN", &values);
                     let nested_query = if value.is_hashmap() {
                         Self::without_paging_and_skip_take(value)
                     } else {
@@ -256,7 +263,7 @@ impl Execution {
                     };
                     let join_table_results = through_relation.iter().map(|(f, r)| {
                         let through_column_name = through_model.field(f).unwrap().column_name().to_string();
-                        if dialect == SQLDialect::PostgreSQL {
+                        if dialect == SQLDialect::PostgreSQL || dialect == SQLDialect::MSSQL {
                             format!("j.{} AS \"{}.{}\"", through_column_name.as_str().escape(dialect), opposite_relation.unwrap().name(), r)
                         } else {
                             format!("j.{} AS `{}.{}`", through_column_name, opposite_relation.unwrap().name(), r)
@@ -269,7 +276,7 @@ impl Execution {
                     } else {
                         None
                     };
-                    let included_values = Self::query_internal(pool, opposite_model, graph, &nested_query, dialect, Some(where_addition), Some(left_join), Some(join_table_results), negative_take, additional_inner_distinct).await?;
+                    let included_values = Self::query_internal(conn, opposite_model, graph, &nested_query, dialect, Some(where_addition), Some(left_join), Some(join_table_results), negative_take, additional_inner_distinct).await?;
                     // println!("see included {:?}", included_values);
                     for result in results.iter_mut() {
                         let mut skipped = 0;
@@ -313,14 +320,13 @@ impl Execution {
         Ok(results)
     }
 
-    pub(crate) async fn query(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Vec<Value>> {
-       Self::query_internal(pool, model, graph, finder, dialect, None, None, None, false, None).await
+    pub(crate) async fn query(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Vec<Value>> {
+       Self::query_internal(conn, model, graph, finder, dialect, None, None, None, false, None).await
     }
 
-    pub(crate) async fn query_aggregate(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
-        let conn = pool.check_out().await.unwrap();
-        let stmt = Query::build_for_aggregate(model, graph, finder, dialect);
-        match conn.query(QuaintQuery::from(&*stmt)).await {
+    pub(crate) async fn query_aggregate(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
+        let stmt = Query::build_for_aggregate(model, graph, finder, dialect)?;
+        match Self::query_rows(conn, &stmt).await {
             Ok(result_set) => {
                 let columns = result_set.columns().clone();
                 let result = result_set.into_iter().next().unwrap();
@@ -333,10 +339,9 @@ impl Execution {
         }
     }
 
-    pub(crate) async fn query_group_by(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
-        let conn = pool.check_out().await.unwrap();
-        let stmt = Query::build_for_group_by(model, graph, finder, dialect);
-        let rows = match conn.query(QuaintQuery::from(stmt)).await {
+    pub(crate) async fn query_group_by(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
+        let stmt = Query::build_for_group_by(model, graph, finder, dialect)?;
+        let rows = match Self::query_rows(conn, &stmt).await {
             Ok(rows) => rows,
             Err(err) => {
                 println!("{:?}", err);
@@ -349,10 +354,9 @@ impl Execution {
         }).collect::<Vec<Value>>()))
     }
 
-    pub(crate) async fn query_count(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<u64> {
-        let conn = pool.check_out().await.unwrap();
-        let stmt = Query::build_for_count(model, graph, finder, dialect, None, None, None, false);
-        match conn.query(QuaintQuery::from(stmt)).await {
+    pub(crate) async fn query_count(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<u64> {
+        let stmt = Query::build_for_count(model, graph, finder, dialect, None, None, None, false)?;
+        match Self::query_rows(conn, &stmt).await {
             Ok(result) => {
                 let result = result.into_iter().next().unwrap();
                 let count: i64 = result.into_iter().next().unwrap().as_i64().unwrap();
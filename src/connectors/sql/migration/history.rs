@@ -0,0 +1,80 @@
+use chrono::Utc;
+use quaint_forked::pooled::{PooledConnection, Quaint};
+use quaint_forked::prelude::{Queryable, TransactionCapable};
+use quaint_forked::ast::Query;
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::core::error::Error;
+
+pub(crate) const MIGRATION_HISTORY_TABLE: &str = "teo_migrations";
+
+pub(crate) struct SQLMigrationHistory { }
+
+impl SQLMigrationHistory {
+
+    async fn create_table_if_needed(dialect: SQLDialect, conn: &PooledConnection) -> Result<(), Error> {
+        let escape = dialect.escape();
+        let columns = format!("{escape}name{escape} VARCHAR(255) NOT NULL PRIMARY KEY, {escape}applied_at{escape} VARCHAR(64) NOT NULL");
+        let sql = if dialect == SQLDialect::MSSQL {
+            format!("IF OBJECT_ID(N'{MIGRATION_HISTORY_TABLE}', N'U') IS NULL CREATE TABLE {escape}{MIGRATION_HISTORY_TABLE}{escape}( {columns} )")
+        } else {
+            format!("CREATE TABLE IF NOT EXISTS {escape}{MIGRATION_HISTORY_TABLE}{escape}( {columns} )")
+        };
+        match conn.execute(Query::from(sql)).await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::migration_error(vec![(MIGRATION_HISTORY_TABLE.to_owned(), err.to_string())])),
+        }
+    }
+
+    pub(crate) async fn applied(dialect: SQLDialect, pool: &Quaint) -> Result<Vec<String>, Error> {
+        let conn = Self::check_out(pool).await?;
+        Self::create_table_if_needed(dialect, &conn).await?;
+        let escape = dialect.escape();
+        let sql = format!("SELECT {escape}name{escape} FROM {escape}{MIGRATION_HISTORY_TABLE}{escape} ORDER BY {escape}name{escape}");
+        match conn.query(Query::from(sql)).await {
+            Ok(result_set) => Ok(result_set.into_iter().map(|row| row.into_single().unwrap().to_string().unwrap()).collect()),
+            Err(err) => Err(Error::migration_error(vec![(MIGRATION_HISTORY_TABLE.to_owned(), err.to_string())])),
+        }
+    }
+
+    pub(crate) async fn apply(dialect: SQLDialect, pool: &Quaint, name: &str, sql: &str) -> Result<(), Error> {
+        let escape = dialect.escape();
+        let applied_at = Utc::now().to_rfc3339();
+        let record = format!("INSERT INTO {escape}{MIGRATION_HISTORY_TABLE}{escape} ({escape}name{escape}, {escape}applied_at{escape}) VALUES ('{}', '{}')", name.replace('\'', "''"), applied_at);
+        Self::run(dialect, pool, name, sql, record).await
+    }
+
+    pub(crate) async fn revert(dialect: SQLDialect, pool: &Quaint, name: &str, sql: &str) -> Result<(), Error> {
+        let escape = dialect.escape();
+        let record = format!("DELETE FROM {escape}{MIGRATION_HISTORY_TABLE}{escape} WHERE {escape}name{escape} = '{}'", name.replace('\'', "''"));
+        Self::run(dialect, pool, name, sql, record).await
+    }
+
+    /// Runs the migration statements together with the history record. MySQL commits DDL
+    /// statements implicitly, so the two only share a transaction on the other databases.
+    async fn run(dialect: SQLDialect, pool: &Quaint, name: &str, sql: &str, record: String) -> Result<(), Error> {
+        let error = |err: quaint_forked::error::Error| Error::migration_error(vec![(name.to_owned(), err.to_string())]);
+        let conn = Self::check_out(pool).await?;
+        Self::create_table_if_needed(dialect, &conn).await?;
+        if dialect == SQLDialect::MySQL {
+            conn.raw_cmd(sql).await.map_err(error)?;
+            conn.execute(Query::from(record)).await.map_err(error)?;
+            return Ok(());
+        }
+        let transaction = conn.start_transaction(None).await.map_err(error)?;
+        let result = match transaction.raw_cmd(sql).await {
+            Ok(_) => transaction.execute(Query::from(record)).await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(_) => transaction.commit().await.map_err(error),
+            Err(err) => {
+                let _ = transaction.rollback().await;
+                Err(error(err))
+            }
+        }
+    }
+
+    async fn check_out(pool: &Quaint) -> Result<PooledConnection, Error> {
+        pool.check_out().await.map_err(|err| Error::migration_failed(err.to_string()))
+    }
+}
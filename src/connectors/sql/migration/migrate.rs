@@ -1,24 +1,35 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use itertools::Itertools;
+use regex::Regex;
 use maplit::hashset;
 use quaint_forked::pooled::{PooledConnection, Quaint};
 use quaint_forked::prelude::Queryable;
 use quaint_forked::ast::Query;
-use crate::connectors::sql::migration::sql::{sqlite_auto_increment_query, sqlite_list_indices_query};
+use quaint_forked::prelude::ResultSet;
+use crate::connectors::sql::migration::history::MIGRATION_HISTORY_TABLE;
+use crate::connectors::sql::migration::sql::{mssql_columns_query, mssql_list_indices_query, sqlite_auto_increment_query, sqlite_fts_create_statements, sqlite_fts_drop_statements, sqlite_list_fts_tables_query, sqlite_list_indices_query};
 use super::super::url::url_utils;
 use crate::connectors::sql::schema::column::decoder::{ColumnDecoder, ColumnManipulation};
 use crate::connectors::sql::stmts::create::table::SQLCreateTableStatement;
 use crate::connectors::sql::stmts::SQL;
 use crate::connectors::sql::schema::column::SQLColumn;
 use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::foreign_key::SQLForeignKey;
 use crate::core::model::Model;
+use crate::core::database::r#type::DatabaseType;
+use crate::core::r#enum::Enum;
+use crate::core::field::r#type::FieldTypeOwner;
 use crate::connectors::sql::schema::value::encode::ToSQLString;
 use crate::core::field::Sort;
 use crate::core::model::index::{ModelIndex, ModelIndexItem, ModelIndexType};
+use crate::core::error::Error;
 use crate::core::pipeline::ctx::Ctx;
 use crate::prelude::Value;
 
+/// Marks the PostgreSQL enum types which teo manages.
+const PSQL_ENUM_COMMENT: &str = "created by teo";
+
 pub(crate) struct SQLMigration { }
 
 impl SQLMigration {
@@ -28,10 +39,31 @@ impl SQLMigration {
     pub(crate) async fn create_database_if_needed(dialect: SQLDialect, url: &str, reset: bool) {
         match dialect {
             SQLDialect::SQLite => Self::create_sqlite_database_if_needed(url, reset).await,
+            SQLDialect::MSSQL => Self::create_mssql_database_if_needed(url, reset).await,
             _ => Self::create_server_database_if_needed(dialect, url, reset).await,
         }
     }
 
+    pub(crate) async fn create_mssql_database_if_needed(url: &str, reset: bool) {
+        let url = url_utils::mssql_normalized_url(url);
+        // without a database, SQL Server connects to `master`
+        let db_name = match url_utils::mssql_database_name(&url) {
+            Some(db_name) => db_name,
+            None => return,
+        };
+        let url_without_db = url_utils::mssql_remove_database(&url);
+        let pool = Quaint::builder(url_without_db.as_str()).unwrap().build();
+        let conn = pool.check_out().await.unwrap();
+        // drop database if needed
+        if reset {
+            let stmt = SQL::drop().database(&db_name).if_exists().to_string(SQLDialect::MSSQL);
+            conn.execute(Query::from(stmt)).await.unwrap();
+        }
+        // create database if needed
+        let stmt = format!("IF DB_ID('{db_name}') IS NULL {}", SQL::create().database(&db_name).to_string(SQLDialect::MSSQL));
+        conn.raw_cmd(&stmt).await.unwrap();
+    }
+
     pub(crate) async fn create_sqlite_database_if_needed(url: &str, reset: bool) {
         let url = url_utils::remove_scheme(url);
         if url_utils::is_memory_url(url) {
@@ -83,195 +115,493 @@ impl SQLMigration {
 
     // Migrate
 
-    pub(crate) async fn db_columns(conn: &PooledConnection, dialect: SQLDialect, table_name: &str) -> HashSet<SQLColumn> {
+    pub(crate) async fn db_columns(conn: &PooledConnection, dialect: SQLDialect, table_name: &str) -> Result<HashSet<SQLColumn>, String> {
         match dialect {
             SQLDialect::SQLite => {
-                let columns_result = conn.query(Query::from(format!("pragma table_info('{}')", table_name))).await.unwrap();
-                let indices_result = conn.query(Query::from(sqlite_list_indices_query(table_name))).await.unwrap();
-                let auto_increment_result = conn.query(Query::from(sqlite_auto_increment_query(table_name))).await.unwrap();
-                let db_columns = ColumnDecoder::decode_sqlite_columns(columns_result, indices_result, auto_increment_result);
-                db_columns
+                let columns_result = Self::introspect(conn, format!("pragma table_info('{}')", table_name)).await?;
+                let indices_result = Self::introspect(conn, sqlite_list_indices_query(table_name)).await?;
+                let auto_increment_result = Self::introspect(conn, sqlite_auto_increment_query(table_name)).await?;
+                Ok(ColumnDecoder::decode_sqlite_columns(columns_result, indices_result, auto_increment_result))
             }
             _ => {
                 let mut results = hashset! {};
-                let db_table_columns = conn.query(if dialect == SQLDialect::PostgreSQL {
-                    let desc = format!("SELECT * FROM information_schema.columns where table_name = '{}'", table_name);
-                    Query::from(desc)
+                let db_table_columns = Self::introspect(conn, if dialect == SQLDialect::PostgreSQL {
+                    format!("SELECT * FROM information_schema.columns where table_name = '{}'", table_name)
+                } else if dialect == SQLDialect::MSSQL {
+                    mssql_columns_query(table_name)
                 } else {
-                    let desc = SQL::describe(table_name).to_string(dialect);
-                    Query::from(desc)
-                }).await.unwrap();
+                    SQL::describe(table_name).to_string(dialect)
+                }).await?;
                 for db_table_column in db_table_columns {
                     let db_column = ColumnDecoder::decode(db_table_column, dialect, conn, table_name).await;
                     results.insert(db_column);
                 }
-                results
+                Ok(results)
             }
         }
     }
 
-    pub(crate) async fn get_db_user_tables(dialect: SQLDialect, conn: &PooledConnection) -> Vec<String> {
-        match dialect {
+    pub(crate) async fn get_db_user_tables(dialect: SQLDialect, conn: &PooledConnection) -> Result<Vec<String>, String> {
+        let tables: Vec<String> = match dialect {
             SQLDialect::MySQL => {
                 let sql = "SHOW TABLES";
-                let db_result = conn.query(Query::from(sql)).await.unwrap();
+                let db_result = Self::introspect(conn, sql).await?;
                 db_result.into_iter().map(|result| { result.into_single().unwrap().to_string().unwrap() }).collect()
             }
             SQLDialect::PostgreSQL => {
                 let sql = "SELECT tablename FROM pg_catalog.pg_tables WHERE schemaname != 'pg_catalog' AND schemaname != 'information_schema'";
-                let db_result = conn.query(Query::from(sql)).await.unwrap();
+                let db_result = Self::introspect(conn, sql).await?;
                 db_result.into_iter().map(|result| { result.into_single().unwrap().to_string().unwrap() }).collect()
             }
             SQLDialect::SQLite => {
-                let sql = "SELECT name FROM sqlite_master WHERE type in ('table') AND name not like 'sqlite?_%' escape '?'";
-                let db_result = conn.query(Query::from(sql)).await.unwrap();
+                let sql = "SELECT name, sql FROM sqlite_master WHERE type in ('table') AND name not like 'sqlite?_%' escape '?'";
+                let db_result = Self::introspect(conn, sql).await?;
+                let mut tables = vec![];
+                let mut virtual_tables = vec![];
+                for row in db_result {
+                    let name = row.get("name").unwrap().to_string().unwrap();
+                    if row.get("sql").unwrap().as_str().unwrap().starts_with("CREATE VIRTUAL TABLE") {
+                        virtual_tables.push(name);
+                    } else {
+                        tables.push(name);
+                    }
+                }
+                // full-text indices are virtual tables with shadow tables, they are migrated with indices
+                tables.into_iter().filter(|t| !virtual_tables.iter().any(|v| {
+                    ["_data", "_idx", "_content", "_docsize", "_config"].iter().any(|suffix| *t == format!("{v}{suffix}"))
+                })).collect()
+            }
+            SQLDialect::MSSQL => {
+                let sql = "SELECT TABLE_NAME FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_TYPE = 'BASE TABLE' AND TABLE_SCHEMA = SCHEMA_NAME()";
+                let db_result = Self::introspect(conn, sql).await?;
                 db_result.into_iter().map(|result| { result.into_single().unwrap().to_string().unwrap() }).collect()
             }
-            _ => unreachable!()
-        }
+        };
+        // the migration history table is not managed by models
+        Ok(tables.into_iter().filter(|t| t != MIGRATION_HISTORY_TABLE).collect())
     }
 
-    pub(crate) async fn rename_table(dialect: SQLDialect, conn: &PooledConnection, old_name: &str, new_name: &str) {
+    pub(crate) async fn rename_table(dialect: SQLDialect, conn: &PooledConnection, old_name: &str, new_name: &str, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
         let escape = dialect.escape();
-        let sql = format!("ALTER TABLE {escape}{old_name}{escape} RENAME TO {escape}{new_name}{escape}");
-        conn.execute(Query::from(sql)).await.unwrap();
+        let sql = if dialect == SQLDialect::MSSQL {
+            format!("EXEC sp_rename '{old_name}', '{new_name}'")
+        } else {
+            format!("ALTER TABLE {escape}{old_name}{escape} RENAME TO {escape}{new_name}{escape}")
+        };
+        Self::execute(conn, sql, dry_run, statements).await
     }
 
-    pub(crate) async fn table_has_records(dialect: SQLDialect, conn: &PooledConnection, table_name: &str) -> bool {
+    pub(crate) async fn table_has_records(dialect: SQLDialect, conn: &PooledConnection, table_name: &str) -> Result<bool, String> {
         let escape = dialect.escape();
-        let sql = format!("select * from {escape}{table_name}{escape} limit 1");
-        !conn.query(Query::from(sql)).await.unwrap().is_empty()
+        let sql = if dialect == SQLDialect::MSSQL {
+            format!("select top 1 * from {escape}{table_name}{escape}")
+        } else {
+            format!("select * from {escape}{table_name}{escape} limit 1")
+        };
+        match conn.query(Query::from(sql)).await {
+            Ok(result_set) => Ok(!result_set.is_empty()),
+            Err(err) => Err(err.to_string()),
+        }
     }
 
-    pub(crate) async fn migrate(dialect: SQLDialect, pool: &Quaint, models: &Vec<Model>) {
-        let conn = pool.check_out().await.unwrap();
-        let mut db_tables = Self::get_db_user_tables(dialect, &conn).await;
+    /// Executes a migration statement and records it. In dry run mode, the statement is only recorded.
+    async fn execute(conn: &PooledConnection, sql: impl Into<String>, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
+        let sql = sql.into();
+        if !dry_run {
+            if let Err(err) = conn.execute(Query::from(sql.as_str())).await {
+                return Err(format!("{}: {}", sql, err));
+            }
+        }
+        statements.push(sql);
+        Ok(())
+    }
+
+    /// Runs a query which reads the database structure.
+    async fn introspect(conn: &PooledConnection, sql: impl Into<String>) -> Result<ResultSet, String> {
+        let sql = sql.into();
+        match conn.query(Query::from(sql.as_str())).await {
+            Ok(result_set) => Ok(result_set),
+            Err(err) => Err(format!("{}: {}", sql, err)),
+        }
+    }
+
+    pub(crate) async fn migrate(dialect: SQLDialect, pool: &Quaint, models: &Vec<Model>, enums: &HashMap<String, Enum>, dry_run: bool) -> Result<Vec<String>, Error> {
+        let conn = match pool.check_out().await {
+            Ok(conn) => conn,
+            Err(err) => return Err(Error::migration_failed(err.to_string())),
+        };
+        let mut db_tables = match Self::get_db_user_tables(dialect, &conn).await {
+            Ok(tables) => tables,
+            Err(reason) => return Err(Error::migration_failed(reason)),
+        };
+        let mut errors: Vec<(String, String)> = vec![];
+        let mut statements = vec![];
+        // enum types should exist before columns use them
+        let mut db_enums = if dialect == SQLDialect::PostgreSQL {
+            match Self::psql_db_enums(&conn).await {
+                Ok(db_enums) => db_enums,
+                Err(reason) => return Err(Error::migration_failed(reason)),
+            }
+        } else {
+            vec![]
+        };
+        if dialect == SQLDialect::PostgreSQL {
+            for name in Self::psql_used_enum_names(models) {
+                let Some(r#enum) = enums.get(&name) else { continue };
+                if let Err(reason) = Self::migrate_psql_enum(&conn, r#enum, &db_enums, dry_run, &mut statements).await {
+                    errors.push((name.clone(), reason));
+                }
+                db_enums.retain(|(n, _)| n != &name);
+            }
+        }
         // compare each table and do migration
         for model in models {
             if model.r#virtual() { continue }
-            let table_name = model.table_name();
-            if let Some(migration) = model.migration() {
-                if !db_tables.iter().any(|x| x == table_name) {
-                    for old_name in &migration.renamed {
-                        if db_tables.contains(old_name) {
-                            // rename
-                            Self::rename_table(dialect, &conn, old_name.as_str(), table_name).await;
-                            let index = db_tables.clone().iter().find_position(|v| *v == old_name).unwrap().0;
-                            db_tables.remove(index);
-                            db_tables.push(table_name.to_string());
-                            break;
-                        }
+            if let Err(reason) = Self::migrate_table(dialect, &conn, model, enums, &mut db_tables, dry_run, &mut statements).await {
+                errors.push((model.table_name().to_owned(), reason));
+            }
+        }
+        // drop tables
+        for table in db_tables {
+            if let Err(reason) = Self::drop_table(dialect, &conn, &table, dry_run, &mut statements).await {
+                errors.push((table, reason));
+            }
+        }
+        // drop enum types which teo created and which are removed from the schema, types
+        // which another connector sharing this database still declares are kept
+        for (name, _) in db_enums {
+            if !Self::psql_managed_enum(&conn, &name).await || enums.contains_key(&name) { continue }
+            if let Err(reason) = Self::execute(&conn, format!("DROP TYPE \"{name}\""), dry_run, &mut statements).await {
+                errors.push((name, reason));
+            }
+        }
+        // SQLite can't alter constraints and only enforces them with a per connection pragma, delete
+        // and update rules are enforced by the engine alone there
+        if dialect != SQLDialect::SQLite {
+            for model in models {
+                if model.r#virtual() { continue }
+                if let Err(reason) = Self::migrate_foreign_keys(dialect, &conn, model, models, dry_run, &mut statements).await {
+                    errors.push((model.table_name().to_owned(), reason));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(Error::migration_error(errors))
+        }
+    }
+
+    /// Lists the enum types of the current schema, with their members in declaration order.
+    async fn psql_db_enums(conn: &PooledConnection) -> Result<Vec<(String, Vec<String>)>, String> {
+        let sql = "SELECT t.typname AS name, e.enumlabel AS label FROM pg_type t JOIN pg_enum e ON t.oid = e.enumtypid JOIN pg_namespace n ON n.oid = t.typnamespace WHERE n.nspname = current_schema() ORDER BY t.typname, e.enumsortorder";
+        let result_set = Self::introspect(conn, sql).await?;
+        let mut result: Vec<(String, Vec<String>)> = vec![];
+        for row in result_set {
+            let name = row.get("name").unwrap().to_string().unwrap();
+            let label = row.get("label").unwrap().to_string().unwrap();
+            match result.iter_mut().find(|(n, _)| n == &name) {
+                Some((_, labels)) => labels.push(label),
+                None => result.push((name, vec![label])),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Whether the enum type was created by teo, these carry a comment.
+    async fn psql_managed_enum(conn: &PooledConnection, name: &str) -> bool {
+        let sql = format!("SELECT obj_description('\"{name}\"'::regtype, 'pg_type') AS comment");
+        let Ok(result_set) = Self::introspect(conn, sql).await else { return false };
+        result_set.into_iter().next()
+            .and_then(|row| row.get("comment").and_then(|c| c.to_string()))
+            .map_or(false, |comment| comment == PSQL_ENUM_COMMENT)
+    }
+
+    async fn create_psql_enum(conn: &PooledConnection, name: &str, values: &Vec<String>, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
+        let quoted_values = values.iter().map(|v| format!("'{}'", v.replace('\'', "''"))).join(", ");
+        Self::execute(conn, format!("CREATE TYPE \"{name}\" AS ENUM ({quoted_values})"), dry_run, statements).await?;
+        Self::execute(conn, format!("COMMENT ON TYPE \"{name}\" IS '{PSQL_ENUM_COMMENT}'"), dry_run, statements).await
+    }
+
+    fn psql_used_enum_names(models: &Vec<Model>) -> Vec<String> {
+        let mut names = vec![];
+        for model in models {
+            if model.r#virtual() { continue }
+            let types = model.fields().iter().map(|f| f.database_type())
+                .chain(model.properties().iter().filter(|p| p.cached).map(|p| p.database_type()));
+            for database_type in types {
+                if let DatabaseType::NamedEnum { name } = database_type {
+                    if !names.contains(name) {
+                        names.push(name.clone());
                     }
                 }
+            }
+        }
+        names
+    }
 
+    async fn migrate_psql_enum(conn: &PooledConnection, r#enum: &Enum, db_enums: &Vec<(String, Vec<String>)>, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
+        let name = r#enum.name();
+        let Some((_, db_values)) = db_enums.iter().find(|(n, _)| n == name) else {
+            return Self::create_psql_enum(conn, name, r#enum.values(), dry_run, statements).await;
+        };
+        let mut db_values = db_values.clone();
+        // renamed members keep the rows which use them
+        for choice in r#enum.choices() {
+            if db_values.contains(&choice.name().to_owned()) { continue }
+            let old = choice.renamed().iter().find(|old| db_values.contains(old) && !r#enum.values().contains(old));
+            if let Some(old) = old {
+                let stmt = format!("ALTER TYPE \"{name}\" RENAME VALUE '{}' TO '{}'", old.replace('\'', "''"), choice.name().replace('\'', "''"));
+                Self::execute(conn, stmt, dry_run, statements).await?;
+                let index = db_values.iter().position(|v| v == old).unwrap();
+                db_values[index] = choice.name().to_owned();
             }
-            let is_table_exist = db_tables.iter().any(|x| x == table_name);
-            if !is_table_exist {
-                // table not exist, create table
-                Self::create_table(dialect, &conn, model).await;
-            } else {
-                // remove from list
-                let index = db_tables.clone().iter().find_position(|x| *x == table_name).unwrap().0;
-                db_tables.remove(index);
-                // start migrate for this table
-                let model_columns = ColumnDecoder::decode_model_columns(model);
-                let db_columns = Self::db_columns(&conn, dialect, table_name).await;
-                let need_to_alter_any_column = ColumnDecoder::need_to_alter_any_columns(&db_columns, &model_columns);
-                if need_to_alter_any_column && dialect == SQLDialect::SQLite {
-                    panic!("SQLite doesn't support column altering");
+        }
+        if db_values.iter().any(|v| !r#enum.values().contains(v)) {
+            // PostgreSQL cannot remove enum members, replace the type instead
+            Self::execute(conn, format!("ALTER TYPE \"{name}\" RENAME TO \"{name}_old\""), dry_run, statements).await?;
+            Self::create_psql_enum(conn, name, r#enum.values(), dry_run, statements).await?;
+            let sql = format!("SELECT table_name, column_name FROM information_schema.columns WHERE udt_name = '{name}' AND table_schema = current_schema()");
+            let columns = conn.query(Query::from(sql)).await.map_err(|e| e.to_string())?;
+            for row in columns {
+                let table = row.get("table_name").unwrap().to_string().unwrap();
+                let column = row.get("column_name").unwrap().to_string().unwrap();
+                let stmt = format!("ALTER TABLE \"{table}\" ALTER COLUMN \"{column}\" TYPE \"{name}\" USING \"{column}\"::text::\"{name}\"");
+                Self::execute(conn, stmt, dry_run, statements).await?;
+            }
+            Self::execute(conn, format!("DROP TYPE \"{name}_old\""), dry_run, statements).await?;
+        } else {
+            for value in r#enum.values() {
+                if !db_values.contains(value) {
+                    let stmt = format!("ALTER TYPE \"{name}\" ADD VALUE '{}'", value.replace('\'', "''"));
+                    Self::execute(conn, stmt, dry_run, statements).await?;
                 }
-                let table_has_records = Self::table_has_records(dialect, &conn, table_name).await;
-                let db_indices = Self::db_indices(dialect, &conn, model).await;
-                let model_indices = Self::normalized_model_indices(model.indices(), dialect, table_name);
-                // here update columns and indices
-                let manipulations = ColumnDecoder::manipulations(&db_columns, &model_columns, &db_indices, &model_indices, model);
-                if table_has_records && manipulations.iter().find(|m| m.is_add_column_non_null()).is_some() && model.allows_drop_when_migrate() {
-                    Self::drop_table(dialect, &conn, table_name).await;
-                    Self::create_table(dialect, &conn, model).await;
-                } else {
-                    for m in manipulations.iter() {
-                        match m {
-                            ColumnManipulation::CreateIndex(index) => {
-                                let create = index.to_sql_create(dialect, table_name);
-                                conn.execute(Query::from(create)).await.unwrap();
-                            }
-                            ColumnManipulation::DropIndex(index) => {
-                                let drop = index.to_sql_drop(dialect, table_name);
-                                conn.execute(Query::from(drop)).await.unwrap();
-                            }
-                            ColumnManipulation::AddColumn(column, action, default) => {
-                                if column.not_null() && default.is_none() {
-                                    // if any records, just raise here
-                                    let has_records = Self::table_has_records(dialect, &conn, table_name).await;
-                                    if has_records {
-                                        panic!("Cannot add new non null column `{}', table `{}' has records. Consider add a default value or drop the table.", column.name(), table_name)
-                                    }
-                                }
-                                let mut c = column.clone().clone();
-                                if default.is_some() {
-                                    c.set_default(Some(default.as_ref().unwrap().to_string(dialect)));
-                                }
-                                let stmt = SQL::alter_table(table_name).add(c).to_string(dialect);
-                                conn.execute(Query::from(stmt)).await.unwrap();
-                                if let Some(action)= action {
-                                    let ctx = Ctx::initial_state_with_value(Value::Null);
-                                    action.process(ctx).await.unwrap();
-                                }
-                            }
-                            ColumnManipulation::AlterColumn(old_column, new_column, _action) => {
-                                if dialect != SQLDialect::PostgreSQL {
-                                    let alter = SQL::alter_table(table_name).modify(new_column.clone().clone()).to_string(dialect);
-                                    conn.execute(Query::from(alter)).await.unwrap();
-                                } else {
-                                    let clauses = Self::psql_alter_clauses(table_name, *old_column, *new_column);
-                                    for clause in clauses {
-                                        conn.execute(Query::from(clause)).await.unwrap();
+            }
+        }
+        Ok(())
+    }
+
+    /// Renamed members of a MySQL enum column, as pairs of old and new names.
+    fn mysql_enum_renames(model: &Model, old_column: &SQLColumn, new_column: &SQLColumn, enums: &HashMap<String, Enum>) -> Vec<(String, String)> {
+        let (DatabaseType::Enum { choices: old_choices }, DatabaseType::Enum { choices: new_choices }) = (old_column.r#type(), new_column.r#type()) else {
+            return vec![];
+        };
+        let Some(field) = model.field_with_column_name(new_column.name()) else { return vec![] };
+        if !field.field_type().is_enum() { return vec![] }
+        let Some(r#enum) = enums.get(field.field_type().enum_name()) else { return vec![] };
+        let mut renames = vec![];
+        for choice in r#enum.choices() {
+            if old_choices.contains(&choice.name().to_owned()) { continue }
+            if let Some(old) = choice.renamed().iter().find(|old| old_choices.contains(old) && !new_choices.contains(old)) {
+                renames.push((old.clone(), choice.name().to_owned()));
+            }
+        }
+        renames
+    }
+
+    async fn migrate_table(dialect: SQLDialect, conn: &PooledConnection, model: &Model, enums: &HashMap<String, Enum>, db_tables: &mut Vec<String>, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
+        let table_name = model.table_name();
+        // in dry run mode, renamed tables are still read with their old names
+        let mut db_table_name = table_name.to_owned();
+        if let Some(migration) = model.migration() {
+            if !db_tables.iter().any(|x| x == table_name) {
+                for old_name in &migration.renamed {
+                    if db_tables.contains(old_name) {
+                        // rename
+                        Self::rename_table(dialect, conn, old_name.as_str(), table_name, dry_run, statements).await?;
+                        let index = db_tables.clone().iter().find_position(|v| *v == old_name).unwrap().0;
+                        db_tables.remove(index);
+                        db_tables.push(table_name.to_string());
+                        if dry_run {
+                            db_table_name = old_name.clone();
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        let is_table_exist = db_tables.iter().any(|x| x == table_name);
+        if !is_table_exist {
+            // table not exist, create table
+            return Self::create_table(dialect, conn, model, dry_run, statements).await;
+        }
+        // remove from list
+        let index = db_tables.clone().iter().find_position(|x| *x == table_name).unwrap().0;
+        db_tables.remove(index);
+        // start migrate for this table
+        let model_columns = ColumnDecoder::decode_model_columns(model);
+        let db_columns = Self::db_columns(conn, dialect, &db_table_name).await?;
+        let need_to_alter_any_column = ColumnDecoder::need_to_alter_any_columns(&db_columns, &model_columns);
+        if need_to_alter_any_column && dialect == SQLDialect::SQLite {
+            return Err("SQLite doesn't support column altering.".to_owned());
+        }
+        let table_has_records = Self::table_has_records(dialect, conn, &db_table_name).await?;
+        let db_indices = Self::db_indices(dialect, conn, &db_table_name).await?;
+        let model_indices = Self::normalized_model_indices(model, dialect);
+        // here update columns and indices
+        let manipulations = ColumnDecoder::manipulations(&db_columns, &model_columns, &db_indices, &model_indices, model);
+        if table_has_records && manipulations.iter().find(|m| m.is_add_column_non_null()).is_some() && model.allows_drop_when_migrate() {
+            Self::drop_table(dialect, conn, table_name, dry_run, statements).await?;
+            return Self::create_table(dialect, conn, model, dry_run, statements).await;
+        }
+        for m in manipulations.iter() {
+            match m {
+                ColumnManipulation::CreateIndex(index) => {
+                    for create in Self::create_index_statements(dialect, index, table_name) {
+                        Self::execute(conn, create, dry_run, statements).await?;
+                    }
+                }
+                ColumnManipulation::DropIndex(index) => {
+                    for drop in Self::drop_index_statements(dialect, index, table_name) {
+                        Self::execute(conn, drop, dry_run, statements).await?;
+                    }
+                }
+                ColumnManipulation::AddColumn(column, action, default) => {
+                    if column.not_null() && default.is_none() && table_has_records {
+                        return Err(format!("Cannot add new non null column `{}', table `{}' has records. Consider add a default value or drop the table.", column.name(), table_name));
+                    }
+                    let mut c = column.clone().clone();
+                    if default.is_some() {
+                        c.set_default(Some(default.as_ref().unwrap().to_string(dialect)));
+                    }
+                    let stmt = SQL::alter_table(table_name).add(c).to_string(dialect);
+                    Self::execute(conn, stmt, dry_run, statements).await?;
+                    if let Some(action) = action {
+                        if !dry_run {
+                            let ctx = Ctx::initial_state_with_value(Value::Null);
+                            action.process(ctx).await.map_err(|e| e.message)?;
+                        }
+                    }
+                }
+                ColumnManipulation::AlterColumn(old_column, new_column, _action) => {
+                    if dialect == SQLDialect::MySQL {
+                        let renames = Self::mysql_enum_renames(model, *old_column, *new_column, enums);
+                        if !renames.is_empty() {
+                            // allow both names while rows are updated
+                            let mut choices = old_column.r#type().clone();
+                            if let (DatabaseType::Enum { choices }, DatabaseType::Enum { choices: new_choices }) = (&mut choices, new_column.r#type()) {
+                                for choice in new_choices {
+                                    if !choices.contains(choice) {
+                                        choices.push(choice.clone());
                                     }
                                 }
                             }
-                            ColumnManipulation::RemoveColumn(name, action) => {
-                                if let Some(action)= action {
-                                    let ctx = Ctx::initial_state_with_value(Value::Null);
-                                    action.process(ctx).await.unwrap();
-                                }
-                                let stmt = SQL::alter_table(table_name).drop_column(name).to_string(dialect);
-                                conn.execute(Query::from(stmt)).await.unwrap();
-                            }
-                            ColumnManipulation::RenameColumn { old, new } => {
-                                let stmt = if dialect == SQLDialect::PostgreSQL {
-                                    format!("ALTER TABLE {} RENAME COLUMN '{}' TO '{}'", table_name, old, new)
-                                } else {
-                                    format!("ALTER TABLE {} RENAME COLUMN `{}` TO `{}`", table_name, old, new)
-                                };
-                                conn.execute(Query::from(stmt)).await.unwrap();
+                            let default = new_column.default().map(|d| d.to_owned());
+                            let union = SQLColumn::new(new_column.name().to_owned(), choices, new_column.not_null(), new_column.auto_increment(), default, new_column.primary_key());
+                            let alter = SQL::alter_table(table_name).modify(union).to_string(dialect);
+                            Self::execute(conn, alter, dry_run, statements).await?;
+                            let column = new_column.name();
+                            for (old, new) in renames {
+                                let stmt = format!("UPDATE `{table_name}` SET `{column}` = '{}' WHERE `{column}` = '{}'", new.replace('\'', "''"), old.replace('\'', "''"));
+                                Self::execute(conn, stmt, dry_run, statements).await?;
                             }
                         }
                     }
+                    if dialect != SQLDialect::PostgreSQL {
+                        let alter = SQL::alter_table(table_name).modify(new_column.clone().clone()).to_string(dialect);
+                        Self::execute(conn, alter, dry_run, statements).await?;
+                    } else {
+                        let clauses = Self::psql_alter_clauses(table_name, *old_column, *new_column);
+                        for clause in clauses {
+                            Self::execute(conn, clause, dry_run, statements).await?;
+                        }
+                    }
+                }
+                ColumnManipulation::RemoveColumn(name, action) => {
+                    if let Some(action) = action {
+                        if !dry_run {
+                            let ctx = Ctx::initial_state_with_value(Value::Null);
+                            action.process(ctx).await.map_err(|e| e.message)?;
+                        }
+                    }
+                    let stmt = SQL::alter_table(table_name).drop_column(name).to_string(dialect);
+                    Self::execute(conn, stmt, dry_run, statements).await?;
+                }
+                ColumnManipulation::RenameColumn { old, new } => {
+                    let escape = dialect.escape();
+                    let stmt = if dialect == SQLDialect::MSSQL {
+                        format!("EXEC sp_rename '{table_name}.{old}', '{new}', 'COLUMN'")
+                    } else {
+                        format!("ALTER TABLE {escape}{table_name}{escape} RENAME COLUMN {escape}{old}{escape} TO {escape}{new}{escape}")
+                    };
+                    Self::execute(conn, stmt, dry_run, statements).await?;
                 }
             }
         }
-        // drop tables
-        for table in db_tables {
-            Self::drop_table(dialect, &conn, &table).await;
+        Ok(())
+    }
+
+    async fn migrate_foreign_keys(dialect: SQLDialect, conn: &PooledConnection, model: &Model, models: &Vec<Model>, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
+        let table_name = model.table_name();
+        let model_foreign_keys = SQLForeignKey::from_model(model, models);
+        let db_foreign_keys = Self::db_foreign_keys(dialect, conn, table_name).await?;
+        for (name, on_delete, on_update) in &db_foreign_keys {
+            let keep = model_foreign_keys.iter().any(|f| f.name() == name && f.on_delete(dialect) == on_delete && f.on_update(dialect) == on_update);
+            if !keep {
+                let stmt = SQL::alter_table(table_name).drop_foreign_key(name).to_string(dialect);
+                Self::execute(conn, stmt, dry_run, statements).await?;
+            }
+        }
+        for foreign_key in model_foreign_keys {
+            let exist = db_foreign_keys.iter().any(|(name, on_delete, on_update)| foreign_key.name() == name && foreign_key.on_delete(dialect) == on_delete && foreign_key.on_update(dialect) == on_update);
+            if !exist {
+                let stmt = SQL::alter_table(table_name).add_foreign_key(foreign_key).to_string(dialect);
+                Self::execute(conn, stmt, dry_run, statements).await?;
+            }
         }
+        Ok(())
     }
 
-    async fn drop_table(dialect: SQLDialect, conn: &PooledConnection, table: &str) {
+    async fn db_foreign_keys(dialect: SQLDialect, conn: &PooledConnection, table_name: &str) -> Result<Vec<(String, String, String)>, String> {
+        let sql = match dialect {
+            SQLDialect::MySQL => format!("SELECT CONSTRAINT_NAME AS name, DELETE_RULE AS on_delete, UPDATE_RULE AS on_update FROM information_schema.REFERENTIAL_CONSTRAINTS WHERE CONSTRAINT_SCHEMA = DATABASE() AND TABLE_NAME = '{table_name}'"),
+            SQLDialect::PostgreSQL | SQLDialect::MSSQL => format!("SELECT rc.constraint_name AS name, rc.delete_rule AS on_delete, rc.update_rule AS on_update FROM information_schema.referential_constraints rc JOIN information_schema.table_constraints tc ON rc.constraint_name = tc.constraint_name AND rc.constraint_schema = tc.constraint_schema WHERE tc.table_name = '{table_name}'"),
+            _ => unreachable!(),
+        };
+        let result_set = Self::introspect(conn, sql).await?;
+        Ok(result_set.into_iter().map(|row| {
+            (
+                row.get("name").unwrap().to_string().unwrap(),
+                row.get("on_delete").unwrap().to_string().unwrap(),
+                row.get("on_update").unwrap().to_string().unwrap(),
+            )
+        }).collect())
+    }
+
+    async fn drop_table(dialect: SQLDialect, conn: &PooledConnection, table: &str, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
         let escape = dialect.escape();
         let sql = format!("DROP TABLE {escape}{table}{escape}");
-        conn.execute(Query::from(sql)).await.unwrap();
+        Self::execute(conn, sql, dry_run, statements).await
     }
 
-    async fn create_table(dialect: SQLDialect, conn: &PooledConnection, model: &Model) {
+    async fn create_table(dialect: SQLDialect, conn: &PooledConnection, model: &Model, dry_run: bool, statements: &mut Vec<String>) -> Result<(), String> {
         // create table
         let stmt = SQLCreateTableStatement::from(model).to_string(dialect);
-        conn.execute(Query::from(stmt)).await.unwrap();
+        Self::execute(conn, stmt, dry_run, statements).await?;
         // create indices
-        for index in model.indices() {
+        for index in Self::normalized_model_indices(model, dialect).iter() {
             // primary is created when creating table
             if index.r#type().is_primary() { continue }
-            let stmt = index.to_sql_create(dialect, model.table_name());
-            conn.execute(Query::from(stmt)).await.unwrap();
+            for stmt in Self::create_index_statements(dialect, index, model.table_name()) {
+                Self::execute(conn, stmt, dry_run, statements).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn create_index_statements(dialect: SQLDialect, index: &ModelIndex, table_name: &str) -> Vec<String> {
+        if dialect == SQLDialect::SQLite && index.r#type().is_fulltext() {
+            sqlite_fts_create_statements(index.sql_name(table_name, dialect).as_ref(), table_name, index.keys())
+        } else {
+            vec![index.to_sql_create(dialect, table_name)]
+        }
+    }
+
+    fn drop_index_statements(dialect: SQLDialect, index: &ModelIndex, table_name: &str) -> Vec<String> {
+        if dialect == SQLDialect::SQLite && index.r#type().is_fulltext() {
+            sqlite_fts_drop_statements(index.sql_name(table_name, dialect).as_ref())
+        } else {
+            vec![index.to_sql_drop(dialect, table_name)]
         }
     }
 
@@ -280,7 +610,14 @@ impl SQLMigration {
         let name = new_column.name();
         let escape = SQLDialect::PostgreSQL.escape();
         if old_column.r#type() != new_column.r#type() {
-            result.push(format!("ALTER TABLE {escape}{table}{escape} ALTER COLUMN {escape}{name}{escape} TYPE {}", new_column.r#type().to_string(SQLDialect::PostgreSQL)));
+            let type_string = new_column.r#type().to_string(SQLDialect::PostgreSQL);
+            // text columns are not implicitly castable into enum types
+            let using = if let DatabaseType::NamedEnum { .. } = new_column.r#type() {
+                format!(" USING {escape}{name}{escape}::text::{type_string}")
+            } else {
+                "".to_owned()
+            };
+            result.push(format!("ALTER TABLE {escape}{table}{escape} ALTER COLUMN {escape}{name}{escape} TYPE {type_string}{using}"));
         }
         if old_column.default().is_none() && new_column.default().is_some() {
             result.push(format!("ALTER TABLE {escape}{table}{escape} ALTER COLUMN {escape}{name}{escape} SET DEFAULT {}", new_column.default().unwrap()));
@@ -294,53 +631,55 @@ impl SQLMigration {
         result
     }
 
-    fn normalized_model_indices(indices: &Vec<ModelIndex>, dialect: SQLDialect, table_name: &str) -> HashSet<ModelIndex> {
-        indices.iter().map(|index| {
-            let mut index = index.clone();
-            let sql_name_cow = index.sql_name(table_name, dialect);
-            let sql_name = sql_name_cow.as_ref().to_owned();
-            index.set_name(sql_name);
-            index
+    /// Model indices are named after fields, database indices after columns.
+    fn normalized_model_indices(model: &Model, dialect: SQLDialect) -> HashSet<ModelIndex> {
+        model.indices().iter().map(|index| {
+            let sql_name = index.sql_name(model.table_name(), dialect).as_ref().to_owned();
+            let items = index.items().iter().map(|item| {
+                let column_name = model.field(item.field_name()).map_or(item.field_name(), |f| f.column_name());
+                ModelIndexItem::new(column_name, item.sort(), item.len())
+            }).collect();
+            ModelIndex::new(index.r#type(), Some(sql_name), items)
         }).collect()
     }
 
-    async fn db_indices(dialect: SQLDialect, conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
+    async fn db_indices(dialect: SQLDialect, conn: &PooledConnection, table_name: &str) -> Result<HashSet<ModelIndex>, String> {
         match dialect {
-            SQLDialect::PostgreSQL => Self::psql_db_indices(conn, model).await,
-            SQLDialect::MySQL => Self::mysql_db_indices(conn, model).await,
-            SQLDialect::SQLite => Self::sqlite_db_indices(conn, model).await,
-            _ => unreachable!(),
+            SQLDialect::PostgreSQL => Self::psql_db_indices(conn, table_name).await,
+            SQLDialect::MySQL => Self::mysql_db_indices(conn, table_name).await,
+            SQLDialect::SQLite => Self::sqlite_db_indices(conn, table_name).await,
+            SQLDialect::MSSQL => Self::mssql_db_indices(conn, table_name).await,
         }
     }
 
-    async fn mysql_db_indices(conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
-        let table_name = model.table_name();
+    async fn mysql_db_indices(conn: &PooledConnection, table_name: &str) -> Result<HashSet<ModelIndex>, String> {
         let sql = format!("SHOW INDEX FROM `{}`", table_name);
-        let result_set = conn.query(Query::from(sql)).await.unwrap();
+        let result_set = Self::introspect(conn, sql).await?;
         let mut indices = vec![];
         for row in result_set {
             let index_name = row.get("Key_name").unwrap().as_str().unwrap();
             let column_name = row.get("Column_name").unwrap().as_str().unwrap();
-            let order = Sort::from_mysql_str(row.get("Collation").unwrap().as_str().unwrap()).unwrap();
+            // full-text indices have no collation
+            let order = row.get("Collation").unwrap().as_str().map(|c| Sort::from_mysql_str(c).unwrap()).unwrap_or(Sort::Asc);
             if let Some(position) = indices.iter().position(|m: &ModelIndex| m.name().unwrap() == index_name) {
                 let model_index = indices.get_mut(position).unwrap();
                 let item = ModelIndexItem::new(column_name, order, None);
                 model_index.append_item(item);
             } else {
                 let is_unique = !row.get("Non_unique").unwrap().as_bool().unwrap();
+                let is_fulltext = row.get("Index_type").unwrap().as_str().unwrap() == "FULLTEXT";
                 let item = ModelIndexItem::new(column_name, order, None);
                 indices.push(ModelIndex::new(
-                    if index_name == "PRIMARY" { ModelIndexType::Primary } else if is_unique { ModelIndexType::Unique } else { ModelIndexType::Index },
+                    if index_name == "PRIMARY" { ModelIndexType::Primary } else if is_fulltext { ModelIndexType::FullText } else if is_unique { ModelIndexType::Unique } else { ModelIndexType::Index },
                     Some(index_name),
                     vec![item],
                 ))
             }
         }
-        indices.into_iter().collect()
+        Ok(indices.into_iter().collect())
     }
 
-    async fn psql_db_indices(conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
-        let table_name = model.table_name();
+    async fn psql_db_indices(conn: &PooledConnection, table_name: &str) -> Result<HashSet<ModelIndex>, String> {
         let sql = format!(r#"SELECT     irel.relname                           AS index_name,
            a.attname                              AS column_name,
            i.indisunique                          AS is_unique,
@@ -364,7 +703,7 @@ ON         c.ordinality = o.ordinality
 join       pg_attribute AS a
 ON         trel.oid = a.attrelid
 AND        a.attnum = c.colnum
-WHERE      tnsp.nspname='public'
+WHERE      tnsp.nspname=current_schema()
 AND        trel.relname='{table_name}'
 GROUP BY   tnsp.nspname,
            trel.relname,
@@ -375,7 +714,7 @@ GROUP BY   tnsp.nspname,
            array_position(i.indkey, a.attnum),
            o.OPTION ORDER BY column_position
 "#);
-        let result_set = conn.query(Query::from(sql)).await.unwrap();
+        let result_set = Self::introspect(conn, sql).await?;
         let mut indices = vec![];
         for row in result_set {
             let index_name = row.get("index_name").unwrap().as_str().unwrap();
@@ -396,11 +735,20 @@ GROUP BY   tnsp.nspname,
                 ))
             }
         }
-        indices.into_iter().collect()
+        // full-text indices are built on expressions, which are not listed above
+        let sql = format!("SELECT indexname AS index_name, indexdef AS index_def FROM pg_indexes WHERE schemaname = current_schema() AND tablename = '{table_name}' AND indexdef LIKE '%USING gin (to_tsvector(%'");
+        let result_set = Self::introspect(conn, sql).await?;
+        let column_regex = Regex::new(r#"to_tsvector\('[^']*'::regconfig, "?([^")]+)"?\)"#).unwrap();
+        for row in result_set {
+            let index_name = row.get("index_name").unwrap().as_str().unwrap();
+            let index_def = row.get("index_def").unwrap().as_str().unwrap();
+            let items = column_regex.captures_iter(index_def).map(|c| ModelIndexItem::new(&c[1], Sort::Asc, None)).collect();
+            indices.push(ModelIndex::new(ModelIndexType::FullText, Some(index_name), items));
+        }
+        Ok(indices.into_iter().collect())
     }
 
-    async fn sqlite_db_indices(conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
-        let table_name = model.table_name();
+    async fn sqlite_db_indices(conn: &PooledConnection, table_name: &str) -> Result<HashSet<ModelIndex>, String> {
         let sql = format!(r#"SELECT
     il.name as index_name,
     ii.name as column_name,
@@ -426,7 +774,7 @@ GROUP BY
     il.seq,
     ii.`desc`
 ORDER BY 1,6"#, table_name);
-        let result_set = conn.query(Query::from(sql)).await.unwrap();
+        let result_set = Self::introspect(conn, sql).await?;
         let mut indices = vec![];
         for row in result_set {
             let index_name = row.get("index_name").unwrap().as_str().unwrap();
@@ -447,6 +795,43 @@ ORDER BY 1,6"#, table_name);
                 ))
             }
         }
-        indices.into_iter().collect()
+        // full-text indices are FTS5 tables
+        let result_set = Self::introspect(conn, sqlite_list_fts_tables_query(table_name)).await?;
+        for row in result_set {
+            let index_name = row.get("name").unwrap().as_str().unwrap();
+            let sql = row.get("sql").unwrap().as_str().unwrap();
+            let arguments = &sql[sql.find("fts5(").unwrap() + 5..sql.rfind(')').unwrap()];
+            let items = arguments.split(',').map(|a| a.trim()).filter(|a| !a.contains('=')).map(|a| {
+                ModelIndexItem::new(a.trim_matches(|c| c == '`' || c == '"'), Sort::Asc, None)
+            }).collect();
+            indices.push(ModelIndex::new(ModelIndexType::FullText, Some(index_name), items));
+        }
+        Ok(indices.into_iter().collect())
+    }
+
+    async fn mssql_db_indices(conn: &PooledConnection, table_name: &str) -> Result<HashSet<ModelIndex>, String> {
+        let result_set = Self::introspect(conn, mssql_list_indices_query(table_name)).await?;
+        let mut indices = vec![];
+        for row in result_set {
+            let index_name = row.get("index_name").unwrap().as_str().unwrap();
+            let column_name = row.get("column_name").unwrap().as_str().unwrap();
+            let order = Sort::from_desc_bool(row.get("is_descending").unwrap().as_bool().unwrap());
+            if let Some(position) = indices.iter().position(|m: &ModelIndex| m.name().unwrap() == index_name) {
+                let model_index = indices.get_mut(position).unwrap();
+                let item = ModelIndexItem::new(column_name, order, None);
+                model_index.append_item(item);
+            } else {
+                let is_unique = row.get("is_unique").unwrap().as_bool().unwrap();
+                let is_primary = row.get("is_primary").unwrap().as_bool().unwrap();
+                let item = ModelIndexItem::new(column_name, order, None);
+                indices.push(ModelIndex::new(
+                    if is_primary { ModelIndexType::Primary } else if is_unique { ModelIndexType::Unique} else { ModelIndexType::Index },
+                    Some(index_name),
+                    vec![item],
+                ))
+            }
+        }
+        Ok(indices.into_iter().collect())
     }
+
 }
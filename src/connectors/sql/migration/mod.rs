@@ -1,2 +1,3 @@
 pub(crate) mod migrate;
+pub(crate) mod history;
 pub(crate) mod sql;
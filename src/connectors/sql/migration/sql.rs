@@ -22,3 +22,70 @@ pub(crate) fn sqlite_auto_increment_query(table_name: &str) -> String {
 pub(crate) fn psql_is_auto_increment(table_name: &str, column_name: &str) -> String {
     format!("select relname from pg_class where relname = '{}_{}_seq'", table_name, column_name)
 }
+
+pub(crate) fn mssql_columns_query(table_name: &str) -> String {
+    format!("SELECT
+  c.COLUMN_NAME AS column_name,
+  c.IS_NULLABLE AS is_nullable,
+  c.DATA_TYPE AS data_type,
+  c.CHARACTER_MAXIMUM_LENGTH AS character_maximum_length,
+  c.NUMERIC_PRECISION AS numeric_precision,
+  c.NUMERIC_SCALE AS numeric_scale,
+  c.DATETIME_PRECISION AS datetime_precision,
+  COLUMNPROPERTY(OBJECT_ID(c.TABLE_SCHEMA + '.' + c.TABLE_NAME), c.COLUMN_NAME, 'IsIdentity') AS is_identity,
+  CASE WHEN EXISTS (
+    SELECT 1 FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc
+    INNER JOIN INFORMATION_SCHEMA.KEY_COLUMN_USAGE k
+      ON k.CONSTRAINT_NAME = tc.CONSTRAINT_NAME AND k.TABLE_SCHEMA = tc.TABLE_SCHEMA
+    WHERE tc.CONSTRAINT_TYPE = 'PRIMARY KEY'
+      AND tc.TABLE_SCHEMA = c.TABLE_SCHEMA
+      AND tc.TABLE_NAME = c.TABLE_NAME
+      AND k.COLUMN_NAME = c.COLUMN_NAME
+  ) THEN 1 ELSE 0 END AS is_primary
+FROM INFORMATION_SCHEMA.COLUMNS c
+WHERE c.TABLE_SCHEMA = SCHEMA_NAME() AND c.TABLE_NAME = '{}'", table_name)
+}
+
+pub(crate) fn mssql_list_indices_query(table_name: &str) -> String {
+    format!("SELECT
+  i.name AS index_name,
+  i.is_unique AS is_unique,
+  i.is_primary_key AS is_primary,
+  c.name AS column_name,
+  ic.is_descending_key AS is_descending
+FROM sys.indexes i
+INNER JOIN sys.index_columns ic ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+INNER JOIN sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+WHERE i.object_id = OBJECT_ID(SCHEMA_NAME() + '.{}') AND i.type > 0
+ORDER BY i.name, ic.key_ordinal", table_name)
+}
+
+pub(crate) fn sqlite_list_fts_tables_query(table_name: &str) -> String {
+    format!("SELECT name, sql FROM sqlite_master WHERE type = 'table' AND sql LIKE 'CREATE VIRTUAL TABLE%' AND sql LIKE '%content=''{}''%'", table_name)
+}
+
+/// A full-text index of SQLite is an external content FTS5 table, which is kept in sync with
+/// triggers on the content table.
+pub(crate) fn sqlite_fts_create_statements(fts_name: &str, table_name: &str, columns: &Vec<String>) -> Vec<String> {
+    let column_list = columns.iter().map(|c| format!("`{c}`")).collect::<Vec<String>>().join(", ");
+    let new_values = columns.iter().map(|c| format!("new.`{c}`")).collect::<Vec<String>>().join(", ");
+    let old_values = columns.iter().map(|c| format!("old.`{c}`")).collect::<Vec<String>>().join(", ");
+    let insert = format!("INSERT INTO `{fts_name}`(rowid, {column_list}) VALUES (new.rowid, {new_values});");
+    let delete = format!("INSERT INTO `{fts_name}`(`{fts_name}`, rowid, {column_list}) VALUES ('delete', old.rowid, {old_values});");
+    vec![
+        format!("CREATE VIRTUAL TABLE IF NOT EXISTS `{fts_name}` USING fts5({column_list}, content='{table_name}')"),
+        format!("CREATE TRIGGER IF NOT EXISTS `{fts_name}_ai` AFTER INSERT ON `{table_name}` BEGIN {insert} END"),
+        format!("CREATE TRIGGER IF NOT EXISTS `{fts_name}_ad` AFTER DELETE ON `{table_name}` BEGIN {delete} END"),
+        format!("CREATE TRIGGER IF NOT EXISTS `{fts_name}_au` AFTER UPDATE ON `{table_name}` BEGIN {delete} {insert} END"),
+        format!("INSERT INTO `{fts_name}`(`{fts_name}`) VALUES ('rebuild')"),
+    ]
+}
+
+pub(crate) fn sqlite_fts_drop_statements(fts_name: &str) -> Vec<String> {
+    vec![
+        format!("DROP TRIGGER IF EXISTS `{fts_name}_ai`"),
+        format!("DROP TRIGGER IF EXISTS `{fts_name}_ad`"),
+        format!("DROP TRIGGER IF EXISTS `{fts_name}_au`"),
+        format!("DROP TABLE IF EXISTS `{fts_name}`"),
+    ]
+}
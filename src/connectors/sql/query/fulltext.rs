@@ -0,0 +1,65 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::{json_string_literal, SQLEscape};
+use crate::core::error::Error;
+use crate::core::model::Model;
+use crate::core::result::Result;
+
+/// The text search configuration of PostgreSQL full-text indices. Queries should use the same
+/// configuration, otherwise the indices are not used.
+pub(crate) const PSQL_TEXT_SEARCH_CONFIG: &str = "english";
+
+/// Matches a field against a full-text query. The query is written in the query syntax of the
+/// database, PostgreSQL takes web search style queries.
+pub(crate) fn search_where_entry(model: &Model, field_name: &str, query: &str, dialect: SQLDialect, table_alias: Option<&str>) -> Result<String> {
+    let column_name = model.field(field_name).unwrap().column_name();
+    let escaped_column_name = match table_alias {
+        Some(alias) => format!("{alias}.{}", column_name.escape(dialect)),
+        None => column_name.escape(dialect),
+    };
+    let query = json_string_literal(query, dialect);
+    Ok(match dialect {
+        SQLDialect::PostgreSQL => format!("to_tsvector('{PSQL_TEXT_SEARCH_CONFIG}', {escaped_column_name}) @@ websearch_to_tsquery('{PSQL_TEXT_SEARCH_CONFIG}', {query})"),
+        SQLDialect::MySQL => format!("MATCH ({escaped_column_name}) AGAINST ({query} IN BOOLEAN MODE)"),
+        SQLDialect::SQLite => {
+            let index = model.fulltext_index_for_field(field_name).unwrap();
+            let fts_name = index.sql_name(model.table_name(), dialect);
+            let rowid = match table_alias {
+                Some(alias) => format!("{}.rowid", alias.escape(dialect)),
+                None => "rowid".to_owned(),
+            };
+            let query = sqlite_column_query(&vec![column_name], &query);
+            format!("{rowid} IN (SELECT rowid FROM `{fts_name}` WHERE `{fts_name}` MATCH {query})")
+        }
+        SQLDialect::MSSQL => return Err(Error::unsupported_filter("Full-text search is not supported for SQL Server.")),
+    })
+}
+
+/// The relevance of a record to a full-text query, bigger is more relevant. `table` is the name
+/// or alias of the queried table.
+pub(crate) fn relevance_order_entry(model: &Model, field_names: &Vec<&str>, query: &str, dialect: SQLDialect, table: &str) -> Result<String> {
+    let column_names: Vec<&str> = field_names.iter().map(|f| model.field(f).unwrap().column_name()).collect();
+    let escaped_column_names: Vec<String> = column_names.iter().map(|c| c.escape(dialect)).collect();
+    let query = json_string_literal(query, dialect);
+    Ok(match dialect {
+        SQLDialect::PostgreSQL => {
+            let vector = escaped_column_names.iter().map(|c| format!("coalesce(to_tsvector('{PSQL_TEXT_SEARCH_CONFIG}', {c}), ''::tsvector)")).collect::<Vec<String>>().join(" || ");
+            format!("ts_rank({vector}, websearch_to_tsquery('{PSQL_TEXT_SEARCH_CONFIG}', {query}))")
+        }
+        SQLDialect::MySQL => format!("MATCH ({}) AGAINST ({query} IN BOOLEAN MODE)", escaped_column_names.join(",")),
+        SQLDialect::SQLite => {
+            let index = model.fulltext_index_with_fields(field_names).unwrap();
+            let fts_name = index.sql_name(model.table_name(), dialect);
+            let query = sqlite_column_query(&column_names, &query);
+            let table = table.escape(dialect);
+            // bm25 ranks are negative, better matches are smaller
+            format!("COALESCE((SELECT -rank FROM `{fts_name}` WHERE `{fts_name}` MATCH {query} AND `{fts_name}`.rowid = {table}.rowid), 0)")
+        }
+        SQLDialect::MSSQL => return Err(Error::unsupported_filter("Full-text search is not supported for SQL Server.")),
+    })
+}
+
+/// Restricts an FTS5 query to columns. Column names are FTS5 strings inside an SQL literal.
+fn sqlite_column_query(column_names: &Vec<&str>, query: &str) -> String {
+    let columns = column_names.iter().map(|c| format!("\"{}\"", c.replace('"', "\"\"").replace('\'', "''"))).collect::<Vec<String>>().join(" ");
+    format!("'{{{columns}}} : (' || {query} || ')'")
+}
@@ -0,0 +1,147 @@
+use serde_json::Value as JsonValue;
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::{json_string_literal, json_to_sql_input, ToLike};
+use crate::connectors::sql::stmts::select::r#where::ToWrappedSQLString;
+use crate::connectors::sql::stmts::select::r#where::WhereClause::And;
+use crate::core::error::Error;
+use crate::core::result::Result;
+use crate::prelude::Value;
+
+/// Builds the where clause of a json column. `path` selects a nested value which the other
+/// filters are applied to.
+pub(crate) fn json_where_entry(column_name: &str, value: &Value, dialect: SQLDialect) -> Result<String> {
+    let map = value.as_hashmap().unwrap();
+    let path: Vec<&str> = map.get("path").map(|p| p.as_vec().unwrap().iter().map(|s| s.as_str().unwrap()).collect()).unwrap_or(vec![]);
+    let mut result: Vec<String> = vec![];
+    for (key, value) in map {
+        match key.as_str() {
+            "path" => (),
+            "equals" if path.is_empty() && value.is_null() => result.push(format!("{column_name} IS NULL")),
+            "equals" => result.push(match dialect {
+                SQLDialect::PostgreSQL => psql_equals(column_name, &path, value),
+                SQLDialect::MySQL => mysql_equals(column_name, &path, value),
+                SQLDialect::SQLite => sqlite_equals(&format!("json_type({column_name}, {})", sqlite_path(&path)), &format!("json_extract({column_name}, {})", sqlite_path(&path)), value),
+                SQLDialect::MSSQL => return Err(Error::unsupported_filter("JSON filters are not supported for SQL Server.")),
+            }),
+            "stringContains" => {
+                let like = json_string_literal(value.as_str().unwrap(), dialect).to_like(true, true);
+                result.push(match dialect {
+                    SQLDialect::PostgreSQL => {
+                        let target = psql_target(column_name, &path);
+                        format!("jsonb_typeof({target}) = 'string' AND ({target} #>> '{{}}') LIKE {like}")
+                    }
+                    SQLDialect::MySQL => {
+                        let target = format!("JSON_EXTRACT({column_name}, {})", mysql_path(&path));
+                        format!("JSON_TYPE({target}) = 'STRING' AND JSON_UNQUOTE({target}) LIKE {like}")
+                    }
+                    SQLDialect::SQLite => {
+                        let path = sqlite_path(&path);
+                        format!("json_type({column_name}, {path}) = 'text' AND json_extract({column_name}, {path}) LIKE {like}")
+                    }
+                    SQLDialect::MSSQL => return Err(Error::unsupported_filter("JSON filters are not supported for SQL Server.")),
+                });
+            }
+            "arrayContains" => {
+                // a single value is searched as an array of one element
+                let elements = match value {
+                    Value::Vec(elements) => elements.clone(),
+                    _ => vec![value.clone()],
+                };
+                result.push(match dialect {
+                    SQLDialect::PostgreSQL => {
+                        let target = psql_target(column_name, &path);
+                        format!("jsonb_typeof({target}) = 'array' AND {target} @> {}", json_to_sql_input(&Value::Vec(elements), dialect))
+                    }
+                    SQLDialect::MySQL => {
+                        let path = mysql_path(&path);
+                        let candidate: JsonValue = (&Value::Vec(elements)).into();
+                        let candidate = json_string_literal(&candidate.to_string(), dialect);
+                        format!("JSON_TYPE(JSON_EXTRACT({column_name}, {path})) = 'ARRAY' AND JSON_CONTAINS({column_name}, {candidate}, {path})")
+                    }
+                    SQLDialect::SQLite => {
+                        let path = sqlite_path(&path);
+                        let mut items = vec![format!("json_type({column_name}, {path}) = 'array'")];
+                        for element in elements.iter() {
+                            items.push(format!("EXISTS (SELECT 1 FROM json_each({column_name}, {path}) AS j WHERE {})", sqlite_equals("j.type", "j.value", element)));
+                        }
+                        items.join(" AND ")
+                    }
+                    SQLDialect::MSSQL => return Err(Error::unsupported_filter("JSON filters are not supported for SQL Server.")),
+                });
+            }
+            "hasKey" => {
+                let key = value.as_str().unwrap();
+                result.push(match dialect {
+                    SQLDialect::PostgreSQL => format!("{} ? {}", psql_target(column_name, &path), json_string_literal(key, dialect)),
+                    SQLDialect::MySQL => {
+                        let key_path = json_string_literal(&format!("{}.{}", path_string(&path), quoted_key(key)), dialect);
+                        format!("JSON_CONTAINS_PATH({column_name}, 'one', {key_path})")
+                    }
+                    SQLDialect::SQLite => {
+                        let key_path = json_string_literal(&format!("{}.{}", path_string(&path), quoted_key(key)), dialect);
+                        format!("json_type({column_name}, {key_path}) IS NOT NULL")
+                    }
+                    SQLDialect::MSSQL => return Err(Error::unsupported_filter("JSON filters are not supported for SQL Server.")),
+                });
+            }
+            _ => panic!("Unhandled key."),
+        }
+    }
+    Ok(And(result).to_wrapped_string(dialect))
+}
+
+fn psql_target(column_name: &str, path: &Vec<&str>) -> String {
+    if path.is_empty() {
+        column_name.to_owned()
+    } else {
+        let segments = path.iter().map(|s| json_string_literal(s, SQLDialect::PostgreSQL)).collect::<Vec<String>>().join(", ");
+        format!("({column_name} #> ARRAY[{segments}])")
+    }
+}
+
+fn psql_equals(column_name: &str, path: &Vec<&str>, value: &Value) -> String {
+    format!("{} = {}", psql_target(column_name, path), json_to_sql_input(value, SQLDialect::PostgreSQL))
+}
+
+fn mysql_equals(column_name: &str, path: &Vec<&str>, value: &Value) -> String {
+    format!("JSON_EXTRACT({column_name}, {}) = {}", mysql_path(path), json_to_sql_input(value, SQLDialect::MySQL))
+}
+
+/// SQLite returns scalars as SQL values and containers as JSON texts, so the type is compared
+/// together with the value.
+fn sqlite_equals(type_expr: &str, value_expr: &str, value: &Value) -> String {
+    let json_value: JsonValue = value.into();
+    match &json_value {
+        JsonValue::Null => format!("{type_expr} = 'null'"),
+        JsonValue::Bool(b) => format!("{type_expr} = '{b}'"),
+        JsonValue::Number(n) => format!("{type_expr} IN ('integer', 'real') AND {value_expr} = {n}"),
+        JsonValue::String(s) => format!("{type_expr} = 'text' AND {value_expr} = {}", json_string_literal(s, SQLDialect::SQLite)),
+        JsonValue::Array(_) => format!("{type_expr} = 'array' AND {value_expr} = json({})", json_string_literal(&json_value.to_string(), SQLDialect::SQLite)),
+        JsonValue::Object(_) => format!("{type_expr} = 'object' AND {value_expr} = json({})", json_string_literal(&json_value.to_string(), SQLDialect::SQLite)),
+    }
+}
+
+fn mysql_path(path: &Vec<&str>) -> String {
+    json_string_literal(&path_string(path), SQLDialect::MySQL)
+}
+
+fn sqlite_path(path: &Vec<&str>) -> String {
+    json_string_literal(&path_string(path), SQLDialect::SQLite)
+}
+
+/// The JSON path syntax shared by MySQL and SQLite. Numeric segments index into arrays.
+fn path_string(path: &Vec<&str>) -> String {
+    let mut result = "$".to_owned();
+    for segment in path {
+        if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+            result += &format!("[{segment}]");
+        } else {
+            result += &format!(".{}", quoted_key(segment));
+        }
+    }
+    result
+}
+
+fn quoted_key(key: &str) -> String {
+    format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -2,16 +2,23 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use maplit::{btreemap, hashmap};
 use once_cell::sync::Lazy;
+use crate::connectors::sql::query::fulltext::{relevance_order_entry, search_where_entry};
+use crate::connectors::sql::query::json::json_where_entry;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::connectors::sql::schema::value::encode::{IfIMode, SQLEscape, ToLike, ToSQLString, ToWrapped, ValueToSQLString, WrapInArray};
 use crate::connectors::sql::stmts::select::r#where::{ToWrappedSQLString, WhereClause};
 use crate::connectors::sql::stmts::select::r#where::WhereClause::{And, Not};
 use crate::connectors::sql::stmts::SQL;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::error::Error;
 use crate::core::input::Input;
 use crate::core::model::Model;
+use crate::core::result::Result;
 use crate::prelude::{Graph, Object, Value};
 
+pub(crate) mod json;
+pub(crate) mod fulltext;
+
 pub(crate) struct Query { }
 
 impl Query {
@@ -20,6 +27,17 @@ impl Query {
         format!("{} {} {}", lhs.as_ref(), op, rhs)
     }
 
+    /// Matches several columns against rows of values. Row values are not supported by every
+    /// dialect, so each row is expanded into its own condition.
+    pub(crate) fn where_rows_in(columns: &Vec<String>, rows: &Vec<Vec<String>>) -> String {
+        if rows.is_empty() {
+            return "1 = 0".to_owned();
+        }
+        rows.iter().map(|row| {
+            columns.iter().zip(row.iter()).map(|(c, v)| format!("{c} = {v}")).collect::<Vec<String>>().join(" AND ").to_wrapped()
+        }).collect::<Vec<String>>().join(" OR ").to_wrapped()
+    }
+
     pub(crate) fn where_from_identifier(object: &Object, dialect: SQLDialect) -> String {
         Self::where_from_value(object.model(), object.graph(), &object.identifier(), dialect)
     }
@@ -30,12 +48,13 @@ impl Query {
         optional: bool,
         value: &Value,
         graph: &Graph,
-        op: &str
+        op: &str,
+        dialect: SQLDialect,
     ) -> String {
         let arr_val = value.as_vec().unwrap();
         let mut arr: Vec<String> = Vec::new();
         for val in arr_val {
-            arr.push(val.to_sql_string(r#type, optional, graph));
+            arr.push(val.to_sql_string(r#type, optional, graph, dialect));
         }
         Query::where_item(column_name, op, &arr.join(", ").to_wrapped())
     }
@@ -47,51 +66,59 @@ impl Query {
         value: &Value,
         graph: &Graph,
         dialect: SQLDialect,
-    ) -> String {
-        let column_name = column_name.escape(dialect);
+    ) -> Result<String> {
         if let Some(map) = value.as_hashmap() {
             let mut result: Vec<String> = vec![];
             for (key, value) in map {
                 match key.as_str() {
+                    "equals" if value.is_null() => {
+                        result.push(format!("{column_name} IS NULL"));
+                    }
+                    "not" if value.is_null() => {
+                        result.push(format!("{column_name} IS NOT NULL"));
+                    }
                     "equals" => {
-                        result.push(Self::where_item(&column_name, "=", &value.to_sql_string(r#type, optional, graph)));
+                        result.push(Self::where_item(&column_name, "=", &value.to_sql_string(r#type, optional, graph, dialect)));
                     }
                     "not" => {
-                        result.push(Self::where_item(&column_name, "<>", &value.to_sql_string(r#type, optional, graph)));
+                        result.push(Self::where_item(&column_name, "<>", &value.to_sql_string(r#type, optional, graph, dialect)));
                     }
                     "gt" => {
-                        result.push(Self::where_item(&column_name, ">", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, ">", &value.to_sql_string(r#type, false, graph, dialect)));
                     }
                     "gte" => {
-                        result.push(Self::where_item(&column_name, ">=", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, ">=", &value.to_sql_string(r#type, false, graph, dialect)));
                     }
                     "lt" => {
-                        result.push(Self::where_item(&column_name, "<", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, "<", &value.to_sql_string(r#type, false, graph, dialect)));
                     }
                     "lte" => {
-                        result.push(Self::where_item(&column_name, "<=", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, "<=", &value.to_sql_string(r#type, false, graph, dialect)));
                     }
                     "in" => {
-                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, graph, "IN"));
+                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, graph, "IN", dialect));
                     }
                     "notIn" => {
-                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, graph, "NOT IN"));
+                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, graph, "NOT IN", dialect));
                     }
                     "contains" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph).to_like(true, true).to_i_mode(i_mode)));
+                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph, dialect).to_like(true, true).to_i_mode(i_mode)));
                     }
                     "startsWith" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph).to_like(false, true).to_i_mode(i_mode)));
+                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph, dialect).to_like(false, true).to_i_mode(i_mode)));
                     }
                     "endsWith" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph).to_like(true, false).to_i_mode(i_mode)));
+                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph, dialect).to_like(true, false).to_i_mode(i_mode)));
                     }
                     "matches" => {
+                        if dialect == SQLDialect::MSSQL {
+                            return Err(Error::unsupported_filter("Regular expression filters are not supported for SQL Server."));
+                        }
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "REGEXP", &value.to_sql_string(r#type, false, graph).to_i_mode(i_mode)));
+                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "REGEXP", &value.to_sql_string(r#type, false, graph, dialect).to_i_mode(i_mode)));
                     }
                     "mode" => { }
                     "has" => {
@@ -108,23 +135,25 @@ impl Query {
                         result.push(Self::where_item(&format!("ARRAY_LENGTH({})", &column_name), "=", "0"));
                     }
                     "length" => {
-                        result.push(Self::where_item(&format!("ARRAY_LENGTH({})", &column_name), "=", &value.to_sql_string(&FieldType::I64, false, graph)));
+                        result.push(Self::where_item(&format!("ARRAY_LENGTH({})", &column_name), "=", &value.to_sql_string(&FieldType::I64, false, graph, dialect)));
                     }
                     "_count" => {
-                        result.push(Self::where_entry_item(&format!("COUNT({})", &column_name), &FieldType::I64, false, value, graph, dialect));
+                        result.push(Self::where_entry_item(&format!("COUNT({})", &column_name), &FieldType::I64, false, value, graph, dialect)?);
                     }
                     "_avg" | "_sum" => {
-                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), &FieldType::F64, true, value, graph, dialect));
+                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), &FieldType::F64, true, value, graph, dialect)?);
                     }
                     "_min" | "_max" => {
-                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), r#type, optional, value, graph, dialect));
+                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), r#type, optional, value, graph, dialect)?);
                     }
                     _ => panic!("Unhandled key."),
                 }
             }
-            And(result).to_wrapped_string(dialect)
+            Ok(And(result).to_wrapped_string(dialect))
+        } else if value.is_null() {
+            Ok(format!("{column_name} IS NULL"))
         } else {
-            Query::where_item(column_name, "=", &value.to_sql_string(r#type, optional, graph))
+            Ok(Query::where_item(column_name, "=", &value.to_sql_string(r#type, optional, graph, dialect)))
         }
     }
 
@@ -135,7 +164,10 @@ impl Query {
         value: &Value,
         graph: &Graph,
         dialect: SQLDialect,
-    ) -> String {
+    ) -> Result<String> {
+        if field_type.is_json() {
+            return json_where_entry(column_name, value, dialect);
+        }
         Self::where_entry_item(column_name, field_type, optional, value, graph, dialect)
     }
 
@@ -149,130 +181,137 @@ impl Query {
         And(retval).to_string(dialect)
     }
 
-    pub(crate) fn r#where(model: &Model, graph: &Graph, r#where: &Value, dialect: SQLDialect, table_alias: Option<&str>) -> String {
+    pub(crate) fn r#where(model: &Model, graph: &Graph, r#where: &Value, dialect: SQLDialect, table_alias: Option<&str>) -> Result<String> {
         let r#where = r#where.as_hashmap().unwrap();
         let mut retval: Vec<String> = vec![];
         for (key, value) in r#where.iter() {
             if key == "AND" {
-                let inner = WhereClause::And(value.as_vec().unwrap().iter().map(|w| Self::r#where(model, graph, w, dialect, table_alias)).collect()).to_string(dialect);
+                let inner = WhereClause::And(value.as_vec().unwrap().iter().map(|w| Self::r#where(model, graph, w, dialect, table_alias)).collect::<Result<Vec<String>>>()?).to_string(dialect);
                 let val = "(".to_owned() + &inner + ")";
                 retval.push(val);
             } else if key == "OR" {
-                let inner = WhereClause::Or(value.as_vec().unwrap().iter().map(|_w| Self::r#where(model, graph, value, dialect, table_alias)).collect()).to_string(dialect);
+                let inner = WhereClause::Or(value.as_vec().unwrap().iter().map(|_w| Self::r#where(model, graph, value, dialect, table_alias)).collect::<Result<Vec<String>>>()?).to_string(dialect);
                 let val = "(".to_owned() + &inner + ")";
                 retval.push(val);
             } else if key == "NOT" {
-                let inner = WhereClause::Not(Self::r#where(model, graph, value, dialect, table_alias)).to_string(dialect);
+                let inner = WhereClause::Not(Self::r#where(model, graph, value, dialect, table_alias)?).to_string(dialect);
                 let val = "(".to_owned() + &inner + ")";
                 retval.push(val);
             } else {
                 if let Some(field) = model.field(key) {
+                    let mut value = Cow::Borrowed(value);
+                    if let Some(query) = value.as_hashmap().and_then(|m| m.get("search")) {
+                        retval.push(search_where_entry(model, key, query.as_str().unwrap(), dialect, table_alias)?);
+                        let mut rest = value.as_hashmap().unwrap().clone();
+                        rest.remove("search");
+                        if rest.is_empty() {
+                            continue;
+                        }
+                        value = Cow::Owned(Value::HashMap(rest));
+                    }
                     let column_name = field.column_name();
                     let optional = field.optionality.is_optional();
+                    // column names are escaped before they're qualified with the table alias
                     let entry_column_name = if let Some(alias) = table_alias {
-                        Cow::Owned(format!("{}.{}", alias, column_name))
+                        format!("{}.{}", alias, column_name.escape(dialect))
                     } else {
-                        Cow::Borrowed(column_name)
+                        column_name.escape(dialect)
                     };
-                    let where_entry = Query::where_entry(&entry_column_name, field.field_type(), optional, value, graph, dialect);
+                    let where_entry = Query::where_entry(&entry_column_name, field.field_type(), optional, value.as_ref(), graph, dialect)?;
                     retval.push(where_entry);
                 } else if let Some(relation) = model.relation(key) {
+                    // related records are matched with correlated subqueries, aliases are
+                    // prefixed with the outer alias so that nested filters don't shadow them
                     let has_join_table = relation.has_join_table();
-                    let id_columns: Vec<&str> = model.primary_index().keys().iter().map(|k| model.field(k).unwrap().column_name()).collect();
-                    let id_columns_string = id_columns.iter().map(|k| k.escape(dialect)).collect::<Vec<String>>().join(",").to_wrapped();
-                    let id_columns_prefixed_string = id_columns.iter().map(|s| format!("t.{}", s)).collect::<Vec<String>>();
-                    let id_columns_prefixed = id_columns_prefixed_string.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
-                    let _join_columns = if has_join_table {
-                        let (_m, r) = graph.through_relation(relation);
-                        Some(r.references().iter().map(|k| model.field(k).unwrap().column_name()).collect::<Vec<&str>>())
-                    } else { None };
-                    let through_columns_string = if has_join_table {
-                        let (m, r) = graph.through_relation(relation);
-                        r.fields().iter().map(|k| format!("t.{}", m.field(k).unwrap().column_name())).collect::<Vec<String>>()
-                    } else { vec![] };
-                    let through_columns = if has_join_table {
-                        through_columns_string.iter().map(|k| k.as_str()).collect::<Vec<&str>>()
-                    } else { vec![] };
+                    let outer = table_alias.unwrap_or(model.table_name());
+                    let prefix = table_alias.unwrap_or("");
+                    let through_alias = format!("{prefix}t");
+                    let related_alias = format!("{prefix}j");
+                    let opposite_model = graph.model(relation.model()).unwrap();
+                    let relation_table_name = opposite_model.table_name();
+                    let (from, on, correlation) = if has_join_table {
+                        let (through_model, through_relation) = graph.through_relation(relation);
+                        let (_, opposite_relation) = graph.opposite_relation(relation);
+                        let (join_model, join_relation) = graph.through_relation(opposite_relation.unwrap());
+                        let from = format!("{} AS {through_alias}", through_model.table_name());
+                        let on = join_relation.iter().map(|(f, r)| {
+                            let f = join_model.field(f).unwrap().column_name();
+                            let r = opposite_model.field(r).unwrap().column_name();
+                            format!("{related_alias}.{} = {through_alias}.{}", r.escape(dialect), f.escape(dialect))
+                        }).collect::<Vec<String>>().join(" AND ");
+                        let correlation = through_relation.iter().map(|(f, r)| {
+                            let f = through_model.field(f).unwrap().column_name();
+                            let r = model.field(r).unwrap().column_name();
+                            format!("{through_alias}.{} = {outer}.{}", f.escape(dialect), r.escape(dialect))
+                        }).collect::<Vec<String>>().join(" AND ");
+                        (Some(from), Some(on), correlation)
+                    } else {
+                        let correlation = relation.iter().map(|(f, r)| {
+                            let f = model.field(f).unwrap().column_name();
+                            let r = opposite_model.field(r).unwrap().column_name();
+                            format!("{related_alias}.{} = {outer}.{}", r.escape(dialect), f.escape(dialect))
+                        }).collect::<Vec<String>>().join(" AND ");
+                        (None, None, correlation)
+                    };
                     for (key, value) in value.as_hashmap().unwrap() {
-                        let from = if !has_join_table {
-                            format!("{} AS t", model.table_name())
-                        } else {
-                            let through_table_name = graph.model(relation.through().unwrap()).unwrap().table_name();
-                            format!("{} AS t", through_table_name)
-                        };
-                        let opposite_model = graph.model(relation.model()).unwrap();
-                        let relation_table_name = opposite_model.table_name();
-                        let on = if has_join_table {
-                            let (_, opposite_relation) = graph.opposite_relation(relation);
-                            let opposite_relation = opposite_relation.unwrap();
-                            let (join_model, join_relation) = graph.through_relation(opposite_relation);
-                            join_relation.iter().map(|(f, r)| {
-                                let f = join_model.field(f).unwrap().column_name();
-                                let r = opposite_model.field(r).unwrap().column_name();
-                                format!("j.{} = t.{}", r.escape(dialect), f.escape(dialect))
-                            }).collect::<Vec<String>>().join(",")
-                        } else {
-                            relation.iter().map(|(f, r)| {
-                                let f = model.field(f).unwrap().column_name();
-                                let r = opposite_model.field(r).unwrap().column_name();
-                                format!("j.{} = t.{}", r.escape(dialect), f.escape(dialect))
-                            }).collect::<Vec<String>>().join(",")
-                        };
-                        let addition_where = if has_join_table {
-                            let (m, r) = graph.through_relation(relation);
-                            r.iter().map(|(f, _r)| {
-                                let f = m.field(f).unwrap().column_name();
-                                format!("t.{} IS NOT NULL", f.escape(dialect))
-                            }).collect::<Vec<String>>().join(" AND ")
-                        } else {
-                            relation.iter().map(|(f, _r)| {
-                                let f = model.field(f).unwrap().column_name();
-                                format!("t.{} IS NOT NULL", f.escape(dialect))
-                            }).collect::<Vec<String>>().join(" AND ")
-                        };
-                        let mut inner_where = Query::r#where(opposite_model, graph, value, dialect, Some("j"));
+                        let mut inner_where = Query::r#where(opposite_model, graph, value, dialect, Some(&related_alias))?;
                         if key.as_str() == "every" {
                             inner_where = Not(inner_where.to_wrapped()).to_string(dialect).to_wrapped();
                         }
                         if &inner_where == "" {
-                            inner_where = addition_where
+                            inner_where = correlation.clone()
                         } else {
-                            inner_where = And(vec![inner_where, addition_where]).to_string(dialect);
+                            inner_where = And(vec![inner_where, correlation.clone()]).to_string(dialect);
                         }
-                        let inner_stmt = SQL::select(Some(if has_join_table { &through_columns } else { &id_columns_prefixed }), &from)
-                            .inner_join(format!("{} AS j ON {}", relation_table_name.escape(dialect), on))
-                            .r#where(inner_where).to_string(dialect).to_wrapped();
+                        let related_table = format!("{} AS {related_alias}", relation_table_name);
+                        let columns = vec!["1"];
+                        let inner_stmt = if let (Some(from), Some(on)) = (&from, &on) {
+                            SQL::select(Some(&columns), from)
+                                .inner_join(format!("{} ON {}", related_table, on))
+                                .r#where(inner_where).to_string(dialect).to_wrapped()
+                        } else {
+                            SQL::select(Some(&columns), &related_table)
+                                .r#where(inner_where).to_string(dialect).to_wrapped()
+                        };
                         match key.as_str() {
                             "some" | "is" => {
-                                retval.push(format!("{} IN {}", id_columns_string, inner_stmt))
+                                retval.push(format!("EXISTS {}", inner_stmt))
                             }
                             "none" | "isNot" | "every" => {
-                                retval.push(format!("{} NOT IN {}", id_columns_string, inner_stmt))
+                                retval.push(format!("NOT EXISTS {}", inner_stmt))
                             }
                             _ => panic!("Unhandled key.")
                         }
                     }
-
                 }
             }
         }
-        And(retval).to_string(dialect)
+        Ok(And(retval).to_string(dialect))
     }
 
     pub(crate) fn order_by(
         model: &Model,
         _graph: &Graph,
         order_by: &Value,
-        _dialect: SQLDialect,
+        dialect: SQLDialect,
         negative_take: bool,
-    ) -> String {
+        table: &str,
+    ) -> Result<String> {
         let asc = if negative_take { "DESC" } else { "ASC" };
         let desc = if negative_take { "ASC" } else { "DESC" };
         let order_by = order_by.as_vec().unwrap();
         let mut retval: Vec<String> = vec![];
         for item in order_by.iter() {
             let (key, value) = Input::key_value(item.as_hashmap().unwrap());
-            if let Some(field) = model.field(key) {
+            if key == "_relevance" {
+                let fields: Vec<&str> = value.get("fields").unwrap().as_vec().unwrap().iter().map(|f| f.as_str().unwrap()).collect();
+                let relevance = relevance_order_entry(model, &fields, value.get("search").unwrap().as_str().unwrap(), dialect, table)?;
+                match value.get("sort").unwrap().as_str().unwrap() {
+                    "asc" => retval.push(format!("{} {}", relevance, asc)),
+                    "desc" => retval.push(format!("{} {}", relevance, desc)),
+                    _ => panic!("Unhandled."),
+                }
+            } else if let Some(field) = model.field(key) {
                 let column_name = field.column_name();
                 if let Some(str) = value.as_str() {
                     match str {
@@ -283,7 +322,7 @@ impl Query {
                 }
             }
         }
-        retval.join(",")
+        Ok(retval.join(","))
     }
 
     pub(crate) fn build_for_count(
@@ -295,8 +334,8 @@ impl Query {
         additional_left_join: Option<String>,
         join_table_results: Option<Vec<String>>,
         force_negative_take: bool,
-    ) -> String {
-        format!("SELECT COUNT(*) FROM ({}) AS _", Self::build(model, graph, value, dialect, additional_where, additional_left_join, join_table_results, force_negative_take))
+    ) -> Result<String> {
+        Ok(format!("SELECT COUNT(*) FROM ({}) AS _", Self::build(model, graph, value, dialect, additional_where, additional_left_join, join_table_results, force_negative_take)?))
     }
 
     pub(crate) fn build_for_group_by(
@@ -304,20 +343,20 @@ impl Query {
         graph: &Graph,
         value: &Value,
         dialect: SQLDialect,
-    ) -> String {
-        let aggregate = Self::build_for_aggregate(model, graph, value, dialect);
+    ) -> Result<String> {
+        let aggregate = Self::build_for_aggregate(model, graph, value, dialect)?;
         let map = value.as_hashmap().unwrap();
         let by = map.get("by").unwrap().as_vec().unwrap().iter().map(|v| {
             let field_name = v.as_str().unwrap();
             model.field(field_name).unwrap().column_name()
         }).collect::<Vec<&str>>().join(",");
         let having = if let Some(having) = map.get("having") {
-            let inner = Query::r#where(model, graph, having, dialect, None);
+            let inner = Query::r#where(model, graph, having, dialect, None)?;
             " HAVING (".to_owned() + &inner + ")"
         } else {
             "".to_owned()
         };
-        format!("{} GROUP BY {}{}", aggregate, by, having)
+        Ok(format!("{} GROUP BY {}{}", aggregate, by, having))
     }
 
     pub(crate) fn build_for_aggregate(
@@ -325,7 +364,7 @@ impl Query {
         graph: &Graph,
         value: &Value,
         dialect: SQLDialect,
-    ) -> String {
+    ) -> Result<String> {
         let map = value.as_hashmap().unwrap();
         let mut results: Vec<String> = vec![];
         for (key, value) in map {
@@ -335,17 +374,21 @@ impl Query {
                         let k = k.as_str();
                         if v.as_bool().unwrap() {
                             match k {
-                                "_all" => results.push("COUNT(*) as `_count._all`".to_owned()),
+                                "_all" => results.push(format!("COUNT(*) as {}", "_count._all".escape(dialect))),
                                 _ => {
                                     let column_name = model.field(k).unwrap().column_name();
                                     let func = SQL_AGGREGATE_MAP.get(key.as_str()).unwrap();
                                     // CAST(AVG(id) as DOUBLE)
                                     let mut left = format!("{}({})", func, column_name);
                                     match key.as_str() {
-                                        "_avg" | "_sum" => left = format!("CAST({} AS DOUBLE)", left),
+                                        "_avg" | "_sum" => left = if dialect == SQLDialect::MSSQL {
+                                            format!("CAST({} AS FLOAT)", left)
+                                        } else {
+                                            format!("CAST({} AS DOUBLE)", left)
+                                        },
                                         _ => ()
                                     }
-                                    results.push(format!("{} as `{}.{}`", left, key, k));
+                                    results.push(format!("{} as {}", left, format!("{}.{}", key, k).as_str().escape(dialect)));
                                 }
                             }
                         }
@@ -360,7 +403,7 @@ impl Query {
                 results.push(model.field(field_name).unwrap().column_name().to_string());
             }
         }
-        format!("SELECT {} FROM ({}) AS _", results.join(","), Self::build(model, graph, value, dialect, None, None, None, false))
+        Ok(format!("SELECT {} FROM ({}) AS _", results.join(","), Self::build(model, graph, value, dialect, None, None, None, false)?))
     }
 
     pub(crate) fn build(
@@ -372,7 +415,7 @@ impl Query {
         additional_left_join: Option<String>,
         join_table_results: Option<Vec<String>>,
         force_negative_take: bool,
-    ) -> String {
+    ) -> Result<String> {
         let r#where = value.get("where");
         let order_by = value.get("orderBy");
         let page_size = value.get("pageSize");
@@ -392,6 +435,7 @@ impl Query {
         } else {
             model.table_name().to_string()
         };
+        let table_ref = if additional_left_join.is_some() { "t" } else { model.table_name() };
         let mut columns: Vec<String> = vec![];
         if additional_left_join.is_some() {
             columns = model.save_keys().iter().map(|k| format!("t.{} AS {}", k.as_str().escape(dialect), k.as_str().escape(dialect))).collect::<Vec<String>>();
@@ -407,14 +451,10 @@ impl Query {
             let key = order_by.keys().next().unwrap();
             let column_key = model.field(key).unwrap().column_name();
             let columns = cursor.as_hashmap().unwrap().keys().map(|_k| {
-                if dialect == SQLDialect::PostgreSQL {
-                    format!("{} AS \"c.{}\"", column_key, column_key)
-                } else {
-                    format!("{} AS `c.{}`", column_key, column_key)
-                }
+                format!("{} AS {}", column_key, format!("c.{}", column_key).as_str().escape(dialect))
             }).collect::<Vec<String>>();
             let column_refs: Vec<&str> = columns.iter().map(|k| k.as_str()).collect();
-            let sub_where = Query::r#where(model, graph, cursor, dialect, None);
+            let sub_where = Query::r#where(model, graph, cursor, dialect, None)?;
             let mut query = SQL::select(Some(&column_refs), &table_name);
             query.r#where(sub_where);
             Cow::Owned(format!("{}, ({}) AS c", &table_name, &query.to_string(dialect)))
@@ -424,7 +464,8 @@ impl Query {
         let mut stmt = SQL::select(if columns.is_empty() { None } else { Some(&column_refs) }, from.as_ref());
         if let Some(r#where) = r#where {
             if !r#where.as_hashmap().unwrap().is_empty() {
-                stmt.r#where(Query::r#where(model, graph, r#where, dialect, None));
+                let table_alias = if additional_left_join.is_some() { Some(table_ref) } else { None };
+                stmt.r#where(Query::r#where(model, graph, r#where, dialect, table_alias)?);
             }
         }
         if let Some(additional_where) = additional_where {
@@ -439,7 +480,7 @@ impl Query {
             let key = order_by.keys().next().unwrap();
             let order = if order_by.values().next().unwrap().as_str().unwrap() == if negative_take { "desc" } else { "asc" }
                 { ">=" } else { "<=" };
-            let cursor_where = Query::where_item(&key, order, &format!("`c.{}`", key));
+            let cursor_where = Query::where_item(&key, order, &format!("c.{}", key).as_str().escape(dialect));
             if stmt.r#where.is_some() {
                 stmt.r#where(And(vec![stmt.r#where.as_ref().unwrap().clone(), cursor_where]).to_string(dialect));
             } else {
@@ -450,10 +491,10 @@ impl Query {
             stmt.left_join(additional_left_join);
         }
         if let Some(order_bys) = order_by {
-            stmt.order_by(Query::order_by(model, graph, order_bys, dialect, negative_take));
+            stmt.order_by(Query::order_by(model, graph, order_bys, dialect, negative_take, table_ref)?);
         } else if negative_take {
             let val = Self::default_desc_order(model);
-            stmt.order_by(Query::order_by(model, graph, &val, dialect, false));
+            stmt.order_by(Query::order_by(model, graph, &val, dialect, false, table_ref)?);
         }
         if page_size.is_some() && page_number.is_some() {
             let skip: u64 = ((page_number.unwrap().as_i64().unwrap() - 1) * page_size.unwrap().as_i64().unwrap()) as u64;
@@ -469,8 +510,7 @@ impl Query {
                 stmt.limit(limit, skip);
             }
         }
-        let result = stmt.to_string(dialect);
-        result
+        Ok(stmt.to_string(dialect))
     }
 
     fn default_desc_order(model: &Model) -> Value {
@@ -60,13 +60,14 @@ impl ColumnDecoder {
     pub(crate) fn manipulations<'a>(db_columns: &'a HashSet<SQLColumn>, model_columns: &'a HashSet<SQLColumn>, db_indices: &'a HashSet<ModelIndex>, model_indices: &'a HashSet<ModelIndex>, model: &Model) -> Vec<ColumnManipulation<'a>> {
         let mut to_create: Vec<&ModelIndex> = vec![];
         let mut to_drop: Vec<&ModelIndex> = vec![];
+        // primary indices are created along with tables
         for index in db_indices {
-            if !model_indices.contains(index) {
+            if !model_indices.contains(index) && !index.r#type().is_primary() {
                 to_drop.push(index);
             }
         }
         for index in model_indices {
-            if !db_indices.contains(index) {
+            if !db_indices.contains(index) && !index.r#type().is_primary() {
                 to_create.push(index);
             }
         }
@@ -177,7 +178,8 @@ impl ColumnDecoder {
             let name = column.get("name").unwrap().as_str().unwrap();
             let r#type = column.get("type").unwrap().as_str().unwrap();
             let not_null = column.get("notnull").unwrap().as_bool().unwrap();
-            let pk = column.get("pk").unwrap().as_bool().unwrap();
+            // columns of a compound primary key are numbered from 1
+            let pk = column.get("pk").unwrap().as_integer().unwrap() > 0;
             let unique_row = indices_iter.iter().find(|i| {
                 i.get("column_name").unwrap().as_str().unwrap() == name
             });
@@ -255,6 +257,8 @@ AND    i.indisprimary", table_name);
             if data_type.as_str() == "ARRAY" {
                 udt_name.remove(0);
                 data_type = data_type + "|" + udt_name.as_str()
+            } else if data_type.as_str() == "USER-DEFINED" {
+                data_type = "enum|".to_owned() + udt_name.as_str()
             }
             SQLColumn {
                 name: column_name.clone(),
@@ -264,6 +268,29 @@ AND    i.indisprimary", table_name);
                 primary_key: primary_names.contains(&column_name),
                 auto_increment: Self::psql_is_auto_increment(conn, table_name, &column_name).await,
             }
+        } else if dialect == SQLDialect::MSSQL {
+            let column_name: String = row.get("column_name").unwrap().to_string().unwrap();
+            let nullable_text: String = row.get("is_nullable").unwrap().to_string().unwrap();
+            let data_type: String = row.get("data_type").unwrap().to_string().unwrap();
+            let int = |key: &str| row.get(key).map(|v| v.as_integer()).flatten();
+            let full_type = match data_type.to_lowercase().as_str() {
+                // -1 stands for MAX
+                "nvarchar" | "varchar" => match int("character_maximum_length").unwrap() {
+                    -1 => format!("{}(max)", data_type),
+                    length => format!("{}({})", data_type, length),
+                },
+                "decimal" | "numeric" => format!("{}({},{})", data_type, int("numeric_precision").unwrap(), int("numeric_scale").unwrap()),
+                "datetime2" => format!("{}({})", data_type, int("datetime_precision").unwrap()),
+                _ => data_type,
+            };
+            SQLColumn {
+                name: column_name,
+                r#type: SQLTypeDecoder::decode(&full_type, dialect),
+                not_null: nullable_text != "YES",
+                default: None,
+                primary_key: int("is_primary") == Some(1),
+                auto_increment: int("is_identity") == Some(1),
+            }
         } else {
             unreachable!()
         }
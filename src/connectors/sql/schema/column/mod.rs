@@ -50,6 +50,10 @@ impl SQLColumn {
         self.primary_key
     }
 
+    pub(crate) fn set_primary_key(&mut self, primary_key: bool) {
+        self.primary_key = primary_key;
+    }
+
     pub(crate) fn set_default(&mut self, default: Option<String>) {
         self.default = default;
     }
@@ -76,6 +80,9 @@ impl ToSQLString for SQLColumn {
                 t
             };
             format!("\"{name}\" {t_with_auto_inc}{default}{not_null}{primary}")
+        } else if dialect == SQLDialect::MSSQL {
+            let identity = if self.auto_increment { " IDENTITY(1,1)" } else { "" };
+            format!("\"{name}\" {t}{identity}{default}{not_null}{primary}")
         } else {
             format!("`{name}` {t}{default}{not_null}{primary}{auto_inc}")
         }
@@ -9,7 +9,8 @@ pub enum SQLDialect {
 impl SQLDialect {
     pub(crate) fn escape(&self) -> &str {
         match self {
-            SQLDialect::PostgreSQL => "\"",
+            // SQL Server connections are opened with QUOTED_IDENTIFIER on
+            SQLDialect::PostgreSQL | SQLDialect::MSSQL => "\"",
             _ => "`",
         }
     }
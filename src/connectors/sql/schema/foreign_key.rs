@@ -0,0 +1,88 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::ToSQLString;
+use crate::core::model::Model;
+use crate::core::relation::delete_rule::DeleteRule;
+use crate::core::relation::Relation;
+use crate::core::relation::update_rule::UpdateRule;
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub(crate) struct SQLForeignKey {
+    pub(self) name: String,
+    pub(self) columns: Vec<String>,
+    pub(self) referenced_table: String,
+    pub(self) referenced_columns: Vec<String>,
+    pub(self) on_delete: &'static str,
+    pub(self) on_update: &'static str,
+}
+
+impl SQLForeignKey {
+
+    /// Returns the foreign key constraint of a relation. Only relations which hold the foreign key
+    /// and declare a delete or update rule have a constraint. SQLite tables don't get constraints,
+    /// the rules are enforced by the engine alone there.
+    pub(crate) fn from_relation(model: &Model, relation: &Relation, models: &Vec<Model>) -> Option<Self> {
+        if !relation.has_foreign_key() || relation.has_join_table() {
+            return None;
+        }
+        if relation.delete_rule() == DeleteRule::Default && relation.update_rule() == UpdateRule::Default {
+            return None;
+        }
+        let referenced_model = models.iter().find(|m| m.name() == relation.model())?;
+        Some(Self {
+            name: format!("{}_{}_fkey", model.table_name(), relation.name()),
+            columns: relation.fields().iter().map(|f| model.field(f).unwrap().column_name().to_owned()).collect(),
+            referenced_table: referenced_model.table_name().to_owned(),
+            referenced_columns: relation.references().iter().map(|r| referenced_model.field(r).unwrap().column_name().to_owned()).collect(),
+            on_delete: match relation.delete_rule() {
+                DeleteRule::Default => "NO ACTION",
+                DeleteRule::Nullify => "SET NULL",
+                DeleteRule::Cascade => "CASCADE",
+                DeleteRule::Deny => "RESTRICT",
+            },
+            on_update: match relation.update_rule() {
+                UpdateRule::Default => "NO ACTION",
+                UpdateRule::Nullify => "SET NULL",
+                UpdateRule::Cascade => "CASCADE",
+                UpdateRule::Deny => "RESTRICT",
+            },
+        })
+    }
+
+    pub(crate) fn from_model(model: &Model, models: &Vec<Model>) -> Vec<Self> {
+        model.relations().iter().filter_map(|r| Self::from_relation(model, r, models)).collect()
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn on_delete(&self, dialect: SQLDialect) -> &str {
+        Self::rule(self.on_delete, dialect)
+    }
+
+    pub(crate) fn on_update(&self, dialect: SQLDialect) -> &str {
+        Self::rule(self.on_update, dialect)
+    }
+
+    /// SQL Server has no `RESTRICT`, its `NO ACTION` rejects the change as well.
+    fn rule(rule: &'static str, dialect: SQLDialect) -> &'static str {
+        if dialect == SQLDialect::MSSQL && rule == "RESTRICT" {
+            "NO ACTION"
+        } else {
+            rule
+        }
+    }
+}
+
+impl ToSQLString for SQLForeignKey {
+    fn to_string(&self, dialect: SQLDialect) -> String {
+        let escape = dialect.escape();
+        let name = &self.name;
+        let columns = self.columns.iter().map(|c| format!("{escape}{c}{escape}")).collect::<Vec<String>>().join(", ");
+        let referenced_table = &self.referenced_table;
+        let referenced_columns = self.referenced_columns.iter().map(|c| format!("{escape}{c}{escape}")).collect::<Vec<String>>().join(", ");
+        let on_delete = self.on_delete(dialect);
+        let on_update = self.on_update(dialect);
+        format!("CONSTRAINT {escape}{name}{escape} FOREIGN KEY ({columns}) REFERENCES {escape}{referenced_table}{escape}({referenced_columns}) ON DELETE {on_delete} ON UPDATE {on_update}")
+    }
+}
@@ -17,6 +17,10 @@ impl SQLTypeDecoder {
 }
 
 fn mysql_type_to_database_type(r#type: &str) -> DatabaseType {
+    // enum members keep their case
+    if r#type.to_lowercase().starts_with("enum(") {
+        return DatabaseType::Enum { choices: mysql_enum_choices(&r#type[5..r#type.len() - 1]) };
+    }
     let r#type_string = r#type.to_lowercase();
     let r#type: &str = r#type_string.as_str();
     let regex = Regex::new("([^ \\(\\)]+)( (.+))?(\\((.+)\\))?").unwrap();
@@ -38,6 +42,7 @@ fn mysql_type_to_database_type(r#type: &str) -> DatabaseType {
                 "char" => DatabaseType::Char { m: arg.map(|a| u8::from_str(a).unwrap()), n: None, c: None },
                 "varchar" => DatabaseType::VarChar { m: arg.map(|a| u16::from_str(a).unwrap()).unwrap(), n: None, c: None },
                 "date" => DatabaseType::Date,
+                "json" => DatabaseType::Json,
                 "datetime" => DatabaseType::DateTime(u8::from_str(arg.unwrap()).unwrap()),
                 "decimal" => {
                     if let Some(args) = arg {
@@ -53,7 +58,35 @@ fn mysql_type_to_database_type(r#type: &str) -> DatabaseType {
     }
 }
 
+/// Parses the member list of a MySQL enum column, e.g. `'a','b'`.
+fn mysql_enum_choices(list: &str) -> Vec<String> {
+    let mut choices = vec![];
+    let mut current = String::new();
+    let mut chars = list.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            if quoted && chars.peek() == Some(&'\'') {
+                current.push('\'');
+                chars.next();
+            } else {
+                quoted = !quoted;
+            }
+        } else if c == ',' && !quoted {
+            choices.push(std::mem::take(&mut current));
+        } else if quoted {
+            current.push(c);
+        }
+    }
+    choices.push(current);
+    choices
+}
+
 fn postgresql_type_to_database_type(r#type: &str) -> DatabaseType {
+    // enum type names keep their case
+    if r#type.starts_with("enum|") {
+        return DatabaseType::NamedEnum { name: r#type[5..].to_owned() };
+    }
     let lower = r#type.to_lowercase();
     let lower_str = lower.as_str();
     match lower_str {
@@ -66,6 +99,8 @@ fn postgresql_type_to_database_type(r#type: &str) -> DatabaseType {
         "real" | "float4" => DatabaseType::Real,
         "date" => DatabaseType::Date,
         "numeric" => DatabaseType::Decimal { m: Some(65), d: Some(30) },
+        "json" => DatabaseType::Json,
+        "jsonb" => DatabaseType::JsonB,
         _ => if lower_str.starts_with("array|") {
             let inner = &lower_str[6..];
             DatabaseType::Vec(Box::new(postgresql_type_to_database_type(inner)))
@@ -105,7 +140,34 @@ fn sqlite_type_to_database_type(r#type: &str) -> DatabaseType {
 }
 
 fn mssql_type_to_database_type(r#type: &str) -> DatabaseType {
-    match r#type.to_lowercase().as_str() {
-        _ => panic!("Unhandled database type.")
+    let r#type_string = r#type.to_lowercase();
+    let r#type: &str = r#type_string.as_str();
+    let regex = Regex::new("([^ \\(\\)]+)(\\((.+)\\))?").unwrap();
+    match regex.captures(r#type) {
+        None => panic!("Unhandled database type '{}' '{}'.", r#type, regex),
+        Some(captures) => {
+            let name = captures.get(1).unwrap().as_str();
+            let arg = captures.get(3).map(|m| m.as_str());
+            match name {
+                "bit" => DatabaseType::Bool,
+                "int" => DatabaseType::Int { m: None, u: false },
+                "bigint" => DatabaseType::BigInt { m: None, u: false },
+                "real" => DatabaseType::Real,
+                "float" => DatabaseType::Double { m: None, d: None },
+                "nvarchar" if arg == Some("max") => DatabaseType::Json,
+                "nvarchar" | "varchar" => DatabaseType::VarChar { m: arg.map(|a| u16::from_str(a).unwrap()).unwrap(), n: None, c: None },
+                "date" => DatabaseType::Date,
+                "datetime2" => DatabaseType::DateTime(arg.map(|a| u8::from_str(a).unwrap()).unwrap_or(7)),
+                "decimal" | "numeric" => {
+                    if let Some(args) = arg {
+                        let args = args.split(",").into_iter().collect::<Vec<&str>>();
+                        DatabaseType::Decimal { m: Some(args.get(0).unwrap().trim().parse().unwrap()), d: Some(args.get(1).unwrap().trim().parse().unwrap()) }
+                    } else {
+                        DatabaseType::Decimal { m: None, d: None }
+                    }
+                }
+                _ => panic!("Unhandled type '{}' '{:?}'.", name, arg)
+            }
+        }
     }
 }
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use itertools::Itertools;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::connectors::sql::schema::value::encode::ToSQLString;
 use crate::core::database::r#type::DatabaseType;
@@ -57,6 +58,8 @@ impl ToSQLString for DatabaseType {
             DatabaseType::Double { m: _m, d: _d } => {
                 if dialect == SQLDialect::PostgreSQL {
                     "DOUBLE PRECISION".to_string()
+                } else if dialect == SQLDialect::MSSQL {
+                    "FLOAT(53)".to_string()
                 } else {
                     "DOUBLE".to_string()
                 }
@@ -69,7 +72,11 @@ impl ToSQLString for DatabaseType {
                 }
             }
             DatabaseType::Date => "DATE".to_string(),
-            DatabaseType::DateTime(fsp) => format!("DATETIME({fsp})"),
+            DatabaseType::DateTime(fsp) => if dialect == SQLDialect::MSSQL {
+                format!("DATETIME2({fsp})")
+            } else {
+                format!("DATETIME({fsp})")
+            },
             DatabaseType::Timestamp { p, z } => {
                 if dialect == SQLDialect::PostgreSQL {
                     let tzinfo = if *z { " WITH TIME ZONE" } else { "" };
@@ -96,6 +103,10 @@ impl ToSQLString for DatabaseType {
             }
             DatabaseType::VarChar { m, n, c } => {
                 let arg = format!("({})", m);
+                if dialect == SQLDialect::MSSQL {
+                    // always store unicode, collations are set on the database
+                    return format!("NVARCHAR{arg}");
+                }
                 let charset = if let Some(v) = n {
                     Cow::Owned(format!(" CHARACTER SET {v}"))
                 } else { Cow::Borrowed("") };
@@ -154,6 +165,27 @@ impl ToSQLString for DatabaseType {
             DatabaseType::Int32 => panic!("SQL databases don't support Int32."),
             DatabaseType::Int64 => panic!("SQL databases don't support Int64."),
             DatabaseType::String => panic!("SQL databases don't support String."),
+            DatabaseType::Enum { choices } => if dialect == SQLDialect::MySQL {
+                let choices = choices.iter().map(|c| format!("'{}'", c.replace("'", "''"))).join(", ");
+                format!("ENUM({choices})")
+            } else {
+                panic!("Inline enum is only supported for MySQL.")
+            },
+            DatabaseType::NamedEnum { name } => if dialect == SQLDialect::PostgreSQL {
+                format!("\"{name}\"")
+            } else {
+                panic!("Named enum is only supported for PostgreSQL.")
+            },
+            DatabaseType::Json => match dialect {
+                SQLDialect::MSSQL => "NVARCHAR(MAX)".to_string(),
+                SQLDialect::SQLite => "TEXT".to_string(),
+                _ => "JSON".to_string(),
+            },
+            DatabaseType::JsonB => if dialect == SQLDialect::PostgreSQL {
+                "JSONB".to_string()
+            } else {
+                panic!("JSONB is only supported for PostgreSQL.")
+            },
             DatabaseType::Vec(inner) => if dialect == SQLDialect::PostgreSQL {
                 inner.to_string(dialect) + "[]"
             } else {
@@ -1,16 +1,18 @@
+use std::collections::HashMap;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::core::database::r#type::DatabaseType;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::r#enum::Enum;
 
 pub trait ToDatabaseType {
-    fn to_database_type(&self, dialect: SQLDialect) -> DatabaseType;
+    fn to_database_type(&self, dialect: SQLDialect, enums: &HashMap<String, Enum>) -> DatabaseType;
 }
 
 impl ToDatabaseType for FieldType {
-    fn to_database_type(&self, dialect: SQLDialect) -> DatabaseType {
+    fn to_database_type(&self, dialect: SQLDialect, enums: &HashMap<String, Enum>) -> DatabaseType {
         match dialect {
             SQLDialect::SQLite => default_database_type_sqlite(self),
-            SQLDialect::MySQL => default_database_type_mysql(self),
+            SQLDialect::MySQL => default_database_type_mysql(self, enums),
             SQLDialect::PostgreSQL => default_database_type_postgresql(self),
             SQLDialect::MSSQL => default_database_type_mssql(self),
         }
@@ -19,11 +21,26 @@ impl ToDatabaseType for FieldType {
 
 fn default_database_type_mssql(field_type: &FieldType) -> DatabaseType {
     match field_type {
-        _ => panic!("Unhandled."),
+        FieldType::Bool => DatabaseType::Bool,
+        FieldType::I32 => DatabaseType::Int { m: None, u: false },
+        FieldType::I64 => DatabaseType::BigInt { m: None, u: false },
+        FieldType::F32 => DatabaseType::Real,
+        FieldType::F64 => DatabaseType::Double { m: None, d: None },
+        FieldType::String => DatabaseType::VarChar { m: 450, n: None, c: None },
+        FieldType::Date => DatabaseType::Date,
+        FieldType::DateTime => DatabaseType::DateTime(3),
+        FieldType::Decimal => DatabaseType::Decimal { m: Some(32), d: Some(16) },
+        FieldType::Enum(_) => DatabaseType::VarChar { m: 450, n: None, c: None },
+        FieldType::Json => DatabaseType::Json,
+        FieldType::Vec(_) => panic!(),
+        FieldType::HashMap(_) => panic!(),
+        FieldType::BTreeMap(_) => panic!(),
+        FieldType::Object(_) => panic!(),
+        _ => panic!(),
     }
 }
 
-fn default_database_type_mysql(field_type: &FieldType) -> DatabaseType {
+fn default_database_type_mysql(field_type: &FieldType, enums: &HashMap<String, Enum>) -> DatabaseType {
     match field_type {
         FieldType::Bool => DatabaseType::TinyInt { m: Some(1), u: false },
         FieldType::I32 => DatabaseType::Int { m: None, u: false },
@@ -33,8 +50,9 @@ fn default_database_type_mysql(field_type: &FieldType) -> DatabaseType {
         FieldType::String => DatabaseType::VarChar { m: 191, n: None, c: None },
         FieldType::Date => DatabaseType::Date,
         FieldType::DateTime => DatabaseType::DateTime(3),
-        FieldType::Enum(_) => DatabaseType::String,
+        FieldType::Enum(name) => DatabaseType::Enum { choices: enums.get(name).unwrap().values().clone() },
         FieldType::Decimal => DatabaseType::Decimal { m: Some(65), d: Some(30) },
+        FieldType::Json => DatabaseType::Json,
         FieldType::Vec(_) => panic!(),
         FieldType::HashMap(_) => panic!(),
         FieldType::BTreeMap(_) => panic!(),
@@ -54,8 +72,13 @@ fn default_database_type_postgresql(field_type: &FieldType) -> DatabaseType {
         FieldType::Date => DatabaseType::Date,
         FieldType::DateTime => DatabaseType::Timestamp { p: 3, z: false },
         FieldType::Decimal => DatabaseType::Decimal { m: Some(65), d: Some(30) },
-        FieldType::Enum(_) => DatabaseType::String,
-        FieldType::Vec(inner) => DatabaseType::Vec(Box::new(default_database_type_postgresql(inner.field_type()))),
+        FieldType::Enum(name) => DatabaseType::NamedEnum { name: name.clone() },
+        FieldType::Json => DatabaseType::JsonB,
+        // arrays of enums are stored as text arrays, array literals are not typed
+        FieldType::Vec(inner) => DatabaseType::Vec(Box::new(match inner.field_type() {
+            FieldType::Enum(_) => DatabaseType::Text { m: None, n: None, c: None },
+            field_type => default_database_type_postgresql(field_type),
+        })),
         FieldType::HashMap(_) => panic!(),
         FieldType::BTreeMap(_) => panic!(),
         FieldType::Object(_) => panic!(),
@@ -74,7 +97,8 @@ fn default_database_type_sqlite(field_type: &FieldType) -> DatabaseType {
         FieldType::Date => DatabaseType::Text { m: None, n: None, c: None },
         FieldType::DateTime => DatabaseType::Text { m: None, n: None, c: None },
         FieldType::Decimal => DatabaseType::Decimal { m: None, d: None },
-        FieldType::Enum(_) => DatabaseType::String,
+        FieldType::Enum(_) => DatabaseType::Text { m: None, n: None, c: None },
+        FieldType::Json => DatabaseType::Text { m: None, n: None, c: None },
         FieldType::Vec(_) => panic!(),
         FieldType::HashMap(_) => panic!(),
         FieldType::BTreeMap(_) => panic!(),
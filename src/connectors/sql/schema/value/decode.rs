@@ -29,6 +29,12 @@ impl RowDecoder {
                     None => Value::Null,
                 }
             }
+            quaint_forked::Value::Enum(e) => {
+                match e {
+                    Some(e) => Value::String(e.as_ref().to_owned()),
+                    None => Value::Null,
+                }
+            }
             quaint_forked::Value::Boolean(b) => {
                 match b {
                     Some(d) => Value::Bool(*d),
@@ -65,6 +71,12 @@ impl RowDecoder {
                     None => Value::Null,
                 }
             }
+            quaint_forked::Value::Json(j) => {
+                match j {
+                    Some(j) => j.into(),
+                    None => Value::Null,
+                }
+            }
             _ => unreachable!()
         }
     }
@@ -121,6 +133,29 @@ impl RowDecoder {
                 return Value::Null;
             }
         }
+        if r#type.is_enum() {
+            // native enum columns are read as enum values, others as text
+            return match value {
+                quaint_forked::Value::Enum(Some(v)) => Value::String(v.as_ref().to_owned()),
+                _ => match value.as_str() {
+                    Some(v) => Value::String(v.to_owned()),
+                    None => Value::Null,
+                }
+            };
+        }
+        if r#type.is_json() {
+            // JSONB columns are read as json, JSON and text columns as text
+            return match value {
+                quaint_forked::Value::Json(Some(v)) => v.into(),
+                _ => match value.as_str() {
+                    Some(v) => match serde_json::from_str::<serde_json::Value>(v) {
+                        Ok(v) => v.into(),
+                        Err(_) => Value::Null,
+                    },
+                    None => Value::Null,
+                }
+            };
+        }
         if r#type.is_int32() {
             if let Some(v) = value.as_i32() {
                 return Value::I32(v);
@@ -129,7 +164,8 @@ impl RowDecoder {
             }
         }
         if r#type.is_int64() {
-            if let Some(v) = value.as_i64() {
+            // SQLite reads integer columns as 32 bit integers when they fit
+            if let Some(v) = value.as_i64().or(value.as_i32().map(|v| v as i64)) {
                 return Value::I64(v);
             } else {
                 return Value::Null;
@@ -1,6 +1,7 @@
 use bigdecimal::BigDecimal;
 use chrono::{NaiveDate, Utc, DateTime, SecondsFormat};
 use itertools::Itertools;
+use serde_json::Value as JsonValue;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
 use crate::prelude::{Graph, Value};
@@ -20,12 +21,12 @@ impl TypeOrNull for &str {
 }
 
 pub(crate) trait ValueToSQLString {
-    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String;
+    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph, dialect: SQLDialect) -> String;
     fn to_sql_string_array_arg<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String;
 }
 
 impl ValueToSQLString for Value {
-    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
+    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph, dialect: SQLDialect) -> String {
         if optional {
             if self.is_null() {
                 return "NULL".to_owned()
@@ -34,8 +35,8 @@ impl ValueToSQLString for Value {
         match r#type {
             #[cfg(feature = "data-source-mongodb")]
             FieldType::ObjectId => panic!("SQL doesn't support `ObjectId`."),
-            FieldType::String => ToSQLInput::to_sql_input(&self.as_str().unwrap()),
-            FieldType::Bool => self.as_bool().unwrap().to_sql_input(),
+            FieldType::String => string_to_sql_input(self.as_str().unwrap(), dialect),
+            FieldType::Bool => bool_to_sql_input(self.as_bool().unwrap(), dialect),
             FieldType::I32 | FieldType::I64 |
             FieldType::F32 | FieldType::F64 => if let Some(val) = self.as_f64() {
                 val.to_string()
@@ -44,17 +45,22 @@ impl ValueToSQLString for Value {
             } else {
                 panic!("Uncoded number.")
             }
-            FieldType::Enum(_) => ToSQLInput::to_sql_input(&self.as_str().unwrap()),
+            FieldType::Enum(_) => string_to_sql_input(self.as_str().unwrap(), dialect),
+            FieldType::Json => json_to_sql_input(self, dialect),
             FieldType::Vec(element_field) => {
                 let val = self.as_vec().unwrap();
                 let mut result: Vec<String> = vec![];
                 for (_i, v) in val.iter().enumerate() {
-                    result.push(v.to_sql_string(element_field.field_type(), element_field.is_optional(), graph));
+                    result.push(v.to_sql_string(element_field.field_type(), element_field.is_optional(), graph, dialect));
                 }
                 result.join(", ").wrap_in_array()
             }
             FieldType::Date => self.as_date().unwrap().to_string().to_sql_input(),
-            FieldType::DateTime => self.as_datetime().unwrap().to_string().to_sql_input(),
+            FieldType::DateTime => if dialect == SQLDialect::MSSQL {
+                self.as_datetime().unwrap().to_sql_input(dialect)
+            } else {
+                self.as_datetime().unwrap().to_string().to_sql_input()
+            },
             FieldType::Decimal => self.as_decimal().unwrap().to_string().to_sql_input(),
             _ => { panic!() }
         }
@@ -97,8 +103,8 @@ impl ValueToSQLString for Value {
 }
 
 impl ValueToSQLString for &Value {
-    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
-        (*self).to_sql_string(r#type, optional, graph)
+    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph, dialect: SQLDialect) -> String {
+        (*self).to_sql_string(r#type, optional, graph, dialect)
     }
 
     fn to_sql_string_array_arg<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
@@ -110,12 +116,12 @@ impl ToSQLString for Value {
     fn to_string(&self, dialect: SQLDialect) -> String {
         match self {
             Value::Null => "NULL".to_owned(),
-            Value::String(string) => string.to_sql_input(),
+            Value::String(string) => string_to_sql_input(string, dialect),
             Value::I32(i) => i.to_string(),
             Value::I64(i) => i.to_string(),
             Value::F32(i) => i.to_string(),
             Value::F64(i) => i.to_string(),
-            Value::Bool(b) => b.to_sql_input(),
+            Value::Bool(b) => bool_to_sql_input(*b, dialect),
             Value::Date(d) => d.to_sql_input(dialect),
             Value::DateTime(d) => d.to_sql_input(dialect),
             Value::Decimal(d) => d.to_sql_input(dialect),
@@ -144,6 +150,9 @@ fn field_type_to_psql(field_type: &FieldType) -> &'static str {
 
 impl PSQLArrayToSQLString for Value {
     fn to_string_with_ft(&self, dialect: SQLDialect, field_type: &FieldType) -> String {
+        if field_type.is_json() && !self.is_null() {
+            return json_to_sql_input(self, dialect);
+        }
         match self {
             Value::Vec(values) => if values.is_empty() {
                 format!("array[]::{}[]", field_type_to_psql(field_type.element_field().unwrap().field_type()))
@@ -220,6 +229,42 @@ impl ToSQLInputDialect for &str {
     }
 }
 
+/// SQL Server doesn't treat backslashes as escapes and needs `N` for unicode literals.
+fn string_to_sql_input(value: &str, dialect: SQLDialect) -> String {
+    if dialect == SQLDialect::MSSQL {
+        format!("N'{}'", value.replace('\'', "''"))
+    } else {
+        ToSQLInput::to_sql_input(&value)
+    }
+}
+
+/// Quotes a string without treating backslashes as escapes, which JSON texts are full of.
+pub(crate) fn json_string_literal(value: &str, dialect: SQLDialect) -> String {
+    match dialect {
+        SQLDialect::MySQL => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+        SQLDialect::MSSQL => format!("N'{}'", value.replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+/// Encodes a value as a JSON document of the dialect.
+pub(crate) fn json_to_sql_input(value: &Value, dialect: SQLDialect) -> String {
+    let json_value: JsonValue = value.into();
+    let literal = json_string_literal(&json_value.to_string(), dialect);
+    match dialect {
+        SQLDialect::PostgreSQL => literal + "::jsonb",
+        SQLDialect::MySQL => format!("CAST({literal} AS JSON)"),
+        _ => literal,
+    }
+}
+
+fn bool_to_sql_input(value: bool, dialect: SQLDialect) -> String {
+    if dialect == SQLDialect::MSSQL {
+        (if value { "1" } else { "0" }).to_owned()
+    } else {
+        value.to_sql_input()
+    }
+}
 
 impl ToSQLInput for bool {
     fn to_sql_input(&self) -> String {
@@ -290,12 +335,13 @@ pub trait ToLike {
 
 impl ToLike for &str {
     fn to_like(&self, left: bool, right: bool) -> String {
-        let mut retval = "".to_owned();
-        retval.push(self.chars().nth(0).unwrap());
+        // keep prefixes like SQL Server's `N'`
+        let start = self.find('\'').map(|i| i + 1).unwrap_or(1);
+        let mut retval = self[..start].to_owned();
         if left {
             retval.push('%');
         }
-        retval += &self[1..self.len() - 1];
+        retval += &self[start..self.len() - 1];
         if right {
             retval.push('%');
         }
@@ -334,7 +380,7 @@ impl SQLEscape for &str {
     fn escape(&self, dialect: SQLDialect) -> String {
         match dialect {
             SQLDialect::MySQL => format!("`{}`", self),
-            SQLDialect::PostgreSQL => format!("\"{}\"", self),
+            SQLDialect::PostgreSQL | SQLDialect::MSSQL => format!("\"{}\"", self),
             _ => format!("`{}`", self),
         }
     }
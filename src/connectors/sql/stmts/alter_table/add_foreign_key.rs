@@ -0,0 +1,17 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::foreign_key::SQLForeignKey;
+use crate::connectors::sql::schema::value::encode::ToSQLString;
+
+pub struct SQLAlterTableAddForeignKeyStatement {
+    pub(crate) table: String,
+    pub(crate) foreign_key: SQLForeignKey,
+}
+
+impl ToSQLString for SQLAlterTableAddForeignKeyStatement {
+    fn to_string(&self, dialect: SQLDialect) -> String {
+        let table = &self.table;
+        let def = self.foreign_key.to_string(dialect);
+        let escape = dialect.escape();
+        format!("ALTER TABLE {escape}{table}{escape} ADD {def}")
+    }
+}
@@ -0,0 +1,20 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::ToSQLString;
+
+pub struct SQLAlterTableDropForeignKeyStatement {
+    pub(crate) table: String,
+    pub(crate) name: String,
+}
+
+impl ToSQLString for SQLAlterTableDropForeignKeyStatement {
+    fn to_string(&self, dialect: SQLDialect) -> String {
+        let table = &self.table;
+        let name = &self.name;
+        let escape = dialect.escape();
+        if dialect == SQLDialect::MySQL {
+            format!("ALTER TABLE {escape}{table}{escape} DROP FOREIGN KEY {escape}{name}{escape}")
+        } else {
+            format!("ALTER TABLE {escape}{table}{escape} DROP CONSTRAINT {escape}{name}{escape}")
+        }
+    }
+}
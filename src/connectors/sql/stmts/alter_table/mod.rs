@@ -1,11 +1,16 @@
 use crate::connectors::sql::stmts::alter_table::add::SQLAlterTableAddStatement;
+use crate::connectors::sql::stmts::alter_table::add_foreign_key::SQLAlterTableAddForeignKeyStatement;
 use crate::connectors::sql::stmts::alter_table::drop_column::SQLAlterTableDropColumnStatement;
+use crate::connectors::sql::stmts::alter_table::drop_foreign_key::SQLAlterTableDropForeignKeyStatement;
 use crate::connectors::sql::stmts::alter_table::modify::SQLAlterTableModifyStatement;
 use crate::connectors::sql::schema::column::SQLColumn;
+use crate::connectors::sql::schema::foreign_key::SQLForeignKey;
 
 pub mod add;
 pub mod drop_column;
 pub mod modify;
+pub mod add_foreign_key;
+pub mod drop_foreign_key;
 
 pub struct SQLAlterTableStatement {
     pub(crate) table: String
@@ -23,4 +28,12 @@ impl SQLAlterTableStatement {
     pub(crate) fn add(&self, column_def: SQLColumn) -> SQLAlterTableAddStatement {
         SQLAlterTableAddStatement { table: self.table.clone(), column_def }
     }
+
+    pub(crate) fn add_foreign_key(&self, foreign_key: SQLForeignKey) -> SQLAlterTableAddForeignKeyStatement {
+        SQLAlterTableAddForeignKeyStatement { table: self.table.clone(), foreign_key }
+    }
+
+    pub(crate) fn drop_foreign_key(&self, name: impl Into<String>) -> SQLAlterTableDropForeignKeyStatement {
+        SQLAlterTableDropForeignKeyStatement { table: self.table.clone(), name: name.into() }
+    }
 }
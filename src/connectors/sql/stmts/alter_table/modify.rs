@@ -17,6 +17,11 @@ impl ToSQLString for SQLAlterTableModifyStatement {
         } else if dialect == SQLDialect::PostgreSQL {
             let c_name = self.column.name();
             format!("ALTER TABLE {escape}{table}{escape} ALTER COLUMN {escape}{c_name}{escape} TYPE column_definition;")
+        } else if dialect == SQLDialect::MSSQL {
+            let c_name = self.column.name();
+            let t = self.column.r#type().to_string(dialect);
+            let null = if self.column.not_null() { "NOT NULL" } else { "NULL" };
+            format!("ALTER TABLE {escape}{table}{escape} ALTER COLUMN {escape}{c_name}{escape} {t} {null}")
         } else {
             format!("ALTER TABLE {escape}{table}{escape} MODIFY {def}")
         }
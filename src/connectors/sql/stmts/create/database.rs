@@ -20,7 +20,8 @@ impl ToSQLString for SQLCreateDatabaseStatement {
         if dialect == SQLDialect::PostgreSQL {
             format!("CREATE DATABASE{if_not_exists} {database};")
         } else {
-            format!("CREATE DATABASE{if_not_exists} `{database}`;")
+            let escape = dialect.escape();
+            format!("CREATE DATABASE{if_not_exists} {escape}{database}{escape};")
         }
     }
 }
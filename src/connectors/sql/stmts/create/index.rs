@@ -27,7 +27,8 @@ impl ToSQLString for SQLCreateIndexOnStatement {
         let index = &self.index;
         let table = &self.table;
         let def = self.columns.iter().map(|c| ModelIndex::sql_format_item(dialect, c)).collect::<Vec<String>>().join(", ");
-        format!("CREATE{unique} INDEX `{index}` ON `{table}`({def})")
+        let escape = dialect.escape();
+        format!("CREATE{unique} INDEX {escape}{index}{escape} ON {escape}{table}{escape}({def})")
     }
 }
 
@@ -30,13 +30,24 @@ impl ToSQLString for SQLCreateTableStatement {
     fn to_string(&self, dialect: SQLDialect) -> String {
         let if_not_exists = if self.if_not_exists { " IF NOT EXISTS" } else { "" };
         let table_name = &self.table;
-        let columns = self.columns.iter().map(|c| {
-            c.to_string(dialect)
-        }).collect::<Vec<String>>().join(", ");
-        if dialect == SQLDialect::PostgreSQL {
-            format!("CREATE TABLE{if_not_exists} \"{table_name}\"( {columns} );")
+        let escape = dialect.escape();
+        // a compound primary key is declared as a table constraint
+        let primary_columns: Vec<&SQLColumn> = self.columns.iter().filter(|c| c.primary_key()).collect();
+        let compound_primary = primary_columns.len() > 1;
+        let primary_constraint = if compound_primary {
+            Some(format!("PRIMARY KEY ({})", primary_columns.iter().map(|c| format!("{escape}{}{escape}", c.name())).collect::<Vec<String>>().join(", ")))
         } else {
-            format!("CREATE TABLE{if_not_exists} `{table_name}`( {columns} );")
-        }
+            None
+        };
+        let columns = self.columns.iter().map(|c| {
+            if compound_primary && c.primary_key() {
+                let mut c = c.clone();
+                c.set_primary_key(false);
+                c.to_string(dialect)
+            } else {
+                c.to_string(dialect)
+            }
+        }).chain(primary_constraint.into_iter()).collect::<Vec<String>>().join(", ");
+        format!("CREATE TABLE{if_not_exists} {escape}{table_name}{escape}( {columns} );")
     }
 }
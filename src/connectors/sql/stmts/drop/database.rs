@@ -14,9 +14,10 @@ impl SQLDropDatabaseStatement {
 }
 
 impl ToSQLString for SQLDropDatabaseStatement {
-    fn to_string(&self, _dialect: SQLDialect) -> String {
+    fn to_string(&self, dialect: SQLDialect) -> String {
         let database = &self.database;
         let if_exists = if self.if_exists { " IF EXISTS" } else { "" };
-        format!("DROP DATABASE{if_exists} `{database}`;")
+        let escape = dialect.escape();
+        format!("DROP DATABASE{if_exists} {escape}{database}{escape};")
     }
 }
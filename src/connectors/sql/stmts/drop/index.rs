@@ -7,10 +7,11 @@ pub(crate) struct SQLDropIndexOnStatement {
 }
 
 impl ToSQLString for SQLDropIndexOnStatement {
-    fn to_string(&self, _dialect: SQLDialect) -> String {
+    fn to_string(&self, dialect: SQLDialect) -> String {
         let index = &self.index;
         let table = &self.table;
-        format!("DROP INDEX `{index}` on `{table}`")
+        let escape = dialect.escape();
+        format!("DROP INDEX {escape}{index}{escape} on {escape}{table}{escape}")
     }
 }
 
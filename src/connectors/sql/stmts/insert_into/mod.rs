@@ -38,6 +38,17 @@ impl<'a> ToSQLString for SQLInsertIntoStatement<'a> {
             } else {
                 "  RETURNING ".to_owned() + &self.returning.join(",")
             })
+        } else if dialect == SQLDialect::MSSQL {
+            let output = if self.returning.is_empty() {
+                "".to_owned()
+            } else {
+                " OUTPUT ".to_owned() + &self.returning.iter().map(|k| format!("INSERTED.\"{k}\"")).collect::<Vec<String>>().join(",")
+            };
+            if keys.is_empty() {
+                format!("INSERT INTO \"{}\"{} DEFAULT VALUES;", self.table, output)
+            } else {
+                format!("INSERT INTO \"{}\"({}){} VALUES({});", self.table, keys.iter().map(|k| format!("\"{k}\"")).collect::<Vec<String>>().join(","), output, values.join(","))
+            }
         } else {
             format!("INSERT INTO `{}`({}) VALUES({});", self.table, keys.iter().map(|k| format!("`{k}`")).collect::<Vec<String>>().join(","), values.join(","))
         }
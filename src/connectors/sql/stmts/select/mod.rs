@@ -61,15 +61,23 @@ impl<'a> ToSQLString for SQLSelectStatement<'a> {
         };
         let order_by = if let Some(order_by) = &self.order_by {
             " ORDER BY ".to_owned() + order_by
+        } else if dialect == SQLDialect::MSSQL && self.limit.is_some() {
+            // SQL Server only pages ordered results
+            " ORDER BY (SELECT NULL)".to_owned()
         } else {
             "".to_owned()
         };
         let limit = if let Some(limit) = &self.limit {
-            if dialect == SQLDialect::PostgreSQL {
+            if dialect == SQLDialect::MSSQL {
+                format!(" OFFSET {} ROWS FETCH NEXT {} ROWS ONLY", limit.1, limit.0)
+            } else if dialect == SQLDialect::PostgreSQL {
                 format!(" LIMIT {} OFFSET {}", limit.0, limit.1)
             } else {
                 format!(" LIMIT {},{}", limit.1, limit.0)
             }
+        } else if dialect == SQLDialect::MSSQL && self.order_by.is_some() {
+            // ordered subqueries are rejected without an offset
+            " OFFSET 0 ROWS".to_owned()
         } else {
             "".to_owned()
         };
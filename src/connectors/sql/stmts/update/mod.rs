@@ -26,23 +26,16 @@ impl<'a> SQLUpdateStatement<'a> {
 
 impl<'a> ToSQLString for SQLUpdateStatement<'a> {
     fn to_string(&self, dialect: SQLDialect) -> String {
+        let escape = dialect.escape();
         let mut exprs: Vec<String> = vec![];
         for (k, v) in self.values.iter() {
-            if dialect == SQLDialect::PostgreSQL {
-                exprs.push(format!("\"{}\" = {}", k, v));
-            } else {
-                exprs.push(format!("`{}` = {}", k, v));
-            }
+            exprs.push(format!("{escape}{}{escape} = {}", k, v));
         }
         let r#where = if self.r#where.is_empty() {
             "".to_owned()
         } else {
             " WHERE ".to_owned() + self.r#where
         };
-        if dialect == SQLDialect::PostgreSQL {
-            format!("UPDATE \"{}\" SET {}{};", self.table, exprs.join(","), r#where)
-        } else {
-            format!("UPDATE `{}` SET {}{};", self.table, exprs.join(","), r#where)
-        }
+        format!("UPDATE {escape}{}{escape} SET {}{};", self.table, exprs.join(","), r#where)
     }
 }
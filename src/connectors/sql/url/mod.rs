@@ -63,6 +63,33 @@ pub(crate) mod url_utils {
         url
     }
 
+    /// SQL Server URLs are JDBC style, `sqlserver://host:1433;database=db;user=sa;password=pw`.
+    /// The `mssql://` scheme is accepted as an alias.
+    pub(crate) fn mssql_normalized_url(url: &str) -> String {
+        if url.starts_with("mssql://") {
+            format!("sqlserver://{}", &url[8..])
+        } else {
+            url.to_owned()
+        }
+    }
+
+    pub(crate) fn mssql_database_name(url: &str) -> Option<String> {
+        url.split(';').skip(1).find_map(|property| {
+            let (key, value) = property.split_once('=')?;
+            if key.trim().eq_ignore_ascii_case("database") {
+                Some(value.trim().to_owned())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub(crate) fn mssql_remove_database(url: &str) -> String {
+        url.split(';').enumerate().filter(|(i, property)| {
+            *i == 0 || !property.split_once('=').map_or(false, |(key, _)| key.trim().eq_ignore_ascii_case("database"))
+        }).map(|(_, property)| property).collect::<Vec<&str>>().join(";")
+    }
+
     pub(crate) fn remove_db_path(dialect: SQLDialect, url: &Url) -> Url {
         let mut retval = url.clone();
         if dialect == SQLDialect::PostgreSQL {
@@ -35,7 +35,12 @@ pub(crate) const SINGLE: u32 = 1 << 18;
 pub(crate) const MANY: u32 = 1 << 19;
 pub(crate) const INTERNAL_AMOUNT: u32 = 1 << 20;
 
-const ALL_NAMES: u32 = CREATE | UPDATE | UPSERT | DELETE | FIND | FIND_FIRST | CONNECT | CONNECT_OR_CREATE | DISCONNECT | SET | JOIN_CREATE | JOIN_DELETE | IDENTITY | SIGN_IN | COUNT | AGGREGATE | GROUP_BY;
+pub(crate) const REFRESH_TOKEN: u32 = 1 << 21;
+pub(crate) const SIGN_OUT: u32 = 1 << 22;
+pub(crate) const RESTORE: u32 = 1 << 23;
+pub(crate) const HISTORY: u32 = 1 << 24;
+
+const ALL_NAMES: u32 = CREATE | UPDATE | UPSERT | DELETE | FIND | FIND_FIRST | CONNECT | CONNECT_OR_CREATE | DISCONNECT | SET | JOIN_CREATE | JOIN_DELETE | IDENTITY | SIGN_IN | REFRESH_TOKEN | SIGN_OUT | RESTORE | HISTORY | COUNT | AGGREGATE | GROUP_BY;
 const ALL_POSITIONS: u32 = ENTRY | NESTED | INTERNAL_POSITION;
 const ALL_AMOUNTS: u32 = SINGLE | MANY | INTERNAL_AMOUNT;
 
@@ -58,6 +63,10 @@ pub(crate) const AGGREGATE_HANDLER: u32 = AGGREGATE | ENTRY;
 pub(crate) const GROUP_BY_HANDLER: u32 = GROUP_BY | ENTRY;
 pub(crate) const SIGN_IN_HANDLER: u32 = SIGN_IN | ENTRY;
 pub(crate) const IDENTITY_HANDLER: u32 = IDENTITY | ENTRY;
+pub(crate) const REFRESH_TOKEN_HANDLER: u32 = REFRESH_TOKEN | ENTRY;
+pub(crate) const SIGN_OUT_HANDLER: u32 = SIGN_OUT | ENTRY;
+pub(crate) const RESTORE_HANDLER: u32 = RESTORE | ENTRY | SINGLE;
+pub(crate) const HISTORY_HANDLER: u32 = HISTORY | ENTRY | MANY;
 
 pub(crate) const NESTED_CREATE_ACTION: u32 = CREATE | NESTED | SINGLE;
 pub(crate) const NESTED_UPDATE_ACTION: u32 = UPDATE | NESTED | SINGLE;
@@ -86,8 +95,8 @@ impl Action {
         self.value == 0
     }
 
-    pub(crate) fn from_name(name: &str) -> Self {
-        Action {
+    pub(crate) fn from_name(name: &str) -> Result<Self, String> {
+        Ok(Action {
             value: match name {
                 "create" => CREATE,
                 "update" => UPDATE,
@@ -109,9 +118,13 @@ impl Action {
                 "internalAmount" => INTERNAL_AMOUNT,
                 "programCode" => PROGRAM_CODE,
                 "identity" => IDENTITY,
-                _ => panic!("Unrecognized action option name '{}'.", name)
+                "refreshToken" => REFRESH_TOKEN,
+                "signOut" => SIGN_OUT,
+                "restore" => RESTORE,
+                "history" => HISTORY,
+                _ => return Err(format!("Unrecognized action option name '{}'.", name))
             }
-        }
+        })
     }
 
     pub(crate) const fn from_u32(value: u32) -> Self {
@@ -227,6 +240,10 @@ impl Action {
             GROUP_BY_HANDLER => &GROUP_BY_INPUT_JSON_KEYS,
             SIGN_IN_HANDLER => &SIGN_IN_INPUT_JSON_KEYS,
             IDENTITY_HANDLER => &IDENTITY_INPUT_JSON_KEYS,
+            REFRESH_TOKEN_HANDLER => &REFRESH_TOKEN_INPUT_JSON_KEYS,
+            SIGN_OUT_HANDLER => &SIGN_OUT_INPUT_JSON_KEYS,
+            RESTORE_HANDLER => &RESTORE_INPUT_JSON_KEYS,
+            HISTORY_HANDLER => &HISTORY_INPUT_JSON_KEYS,
             _ => unreachable!()
         }
     }
@@ -243,6 +260,17 @@ impl Action {
         self.value == SIGN_IN_HANDLER
     }
 
+    pub(crate) fn handler_requires_refresh_token(&self) -> bool {
+        self.value == REFRESH_TOKEN_HANDLER
+    }
+
+
+    pub(crate) fn handler_accepts_deleted_filters(&self) -> bool {
+        match self.value {
+            FIND_UNIQUE_HANDLER | FIND_FIRST_HANDLER | FIND_MANY_HANDLER | COUNT_HANDLER | AGGREGATE_HANDLER | GROUP_BY_HANDLER => true,
+            _ => false,
+        }
+    }
 
     pub(crate) fn handler_requires_update(&self) -> bool {
         match self.value {
@@ -261,7 +289,7 @@ impl Action {
 
     pub(crate) fn handler_requires_where_unique(&self) -> bool {
         match self.value {
-            FIND_UNIQUE_HANDLER | UPDATE_HANDLER | UPSERT_HANDLER | DELETE_HANDLER => true,
+            FIND_UNIQUE_HANDLER | UPDATE_HANDLER | UPSERT_HANDLER | DELETE_HANDLER | RESTORE_HANDLER | HISTORY_HANDLER => true,
             _ => false,
         }
     }
@@ -290,6 +318,10 @@ impl Action {
             GROUP_BY_HANDLER => ResMeta::NoMeta,
             SIGN_IN_HANDLER => ResMeta::TokenInfo,
             IDENTITY_HANDLER => ResMeta::NoMeta,
+            REFRESH_TOKEN_HANDLER => ResMeta::TokenInfo,
+            SIGN_OUT_HANDLER => ResMeta::NoMeta,
+            RESTORE_HANDLER => ResMeta::NoMeta,
+            HISTORY_HANDLER => ResMeta::PagingInfo,
             _ => unreachable!()
         }
     }
@@ -311,6 +343,10 @@ impl Action {
             GROUP_BY_HANDLER => ResData::Other,
             SIGN_IN_HANDLER => ResData::Single,
             IDENTITY_HANDLER => ResData::Single,
+            REFRESH_TOKEN_HANDLER => ResData::Single,
+            SIGN_OUT_HANDLER => ResData::Single,
+            RESTORE_HANDLER => ResData::Single,
+            HISTORY_HANDLER => ResData::Vec,
             _ => unreachable!()
         }
     }
@@ -332,6 +368,10 @@ impl Action {
             GROUP_BY_HANDLER => "groupBy",
             SIGN_IN_HANDLER => "signIn",
             IDENTITY_HANDLER => "identity",
+            REFRESH_TOKEN_HANDLER => "refreshToken",
+            SIGN_OUT_HANDLER => "signOut",
+            RESTORE_HANDLER => "restore",
+            HISTORY_HANDLER => "history",
             _ => unreachable!()
         }
     }
@@ -354,13 +394,17 @@ impl Action {
                 "groupBy" => GROUP_BY_HANDLER,
                 "signIn" => SIGN_IN_HANDLER,
                 "identity" => IDENTITY_HANDLER,
+                "refreshToken" => REFRESH_TOKEN_HANDLER,
+                "signOut" => SIGN_OUT_HANDLER,
+                "restore" => RESTORE_HANDLER,
+                "history" => HISTORY_HANDLER,
                 _ => None?
             }
         })
     }
 
     pub(crate) fn handlers_iter() -> Iter<'static, Action> {
-        static HANDLER_TYPES: [Action; 15] = [
+        static HANDLER_TYPES: [Action; 19] = [
             Action::from_u32(FIND_UNIQUE_HANDLER),
             Action::from_u32(FIND_FIRST_HANDLER),
             Action::from_u32(FIND_MANY_HANDLER),
@@ -376,6 +420,10 @@ impl Action {
             Action::from_u32(GROUP_BY_HANDLER),
             Action::from_u32(SIGN_IN_HANDLER),
             Action::from_u32(IDENTITY_HANDLER),
+            Action::from_u32(REFRESH_TOKEN_HANDLER),
+            Action::from_u32(SIGN_OUT_HANDLER),
+            Action::from_u32(RESTORE_HANDLER),
+            Action::from_u32(HISTORY_HANDLER),
         ];
         HANDLER_TYPES.iter()
     }
@@ -437,13 +485,13 @@ pub enum ResData {
 }
 
 static FIND_UNIQUE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"include", "select", "where"}
+    hashset! {"include", "select", "where", "withDeleted", "onlyDeleted"}
 });
 static FIND_FIRST_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"include", "select", "where", "orderBy", "skip", "cursor", "distinct"}
+    hashset! {"include", "select", "where", "orderBy", "skip", "cursor", "distinct", "withDeleted", "onlyDeleted"}
 });
 static FIND_MANY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"include", "select", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct"}
+    hashset! {"include", "select", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct", "withDeleted", "onlyDeleted"}
 });
 static CREATE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"include", "select", "create"}
@@ -467,13 +515,13 @@ static DELETE_MANY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"select", "where"}
 });
 static COUNT_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct"}
+    hashset! {"where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct", "withDeleted", "onlyDeleted"}
 });
 static AGGREGATE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"_avg", "_count", "_sum", "_min", "_max", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor"}
+    hashset! {"_avg", "_count", "_sum", "_min", "_max", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "withDeleted", "onlyDeleted"}
 });
 static GROUP_BY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"_avg", "_count", "_sum", "_min", "_max", "by", "having", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor"}
+    hashset! {"_avg", "_count", "_sum", "_min", "_max", "by", "having", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "withDeleted", "onlyDeleted"}
 });
 static SIGN_IN_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"include", "select", "credentials"}
@@ -481,3 +529,15 @@ static SIGN_IN_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
 static IDENTITY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"include", "select"}
 });
+static REFRESH_TOKEN_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"include", "select", "refreshToken"}
+});
+static SIGN_OUT_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"refreshToken"}
+});
+static RESTORE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"include", "select", "where"}
+});
+static HISTORY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"where", "skip", "take", "pageSize", "pageNumber"}
+});
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::env;
 use std::ffi::{OsString};
@@ -6,19 +7,26 @@ use std::sync::{Arc, Mutex};
 use to_mut_proc_macro::ToMut;
 use to_mut::ToMut;
 use clap::{Arg, ArgAction, Command as ClapCommand};
-use dotenvy::dotenv;
 use crate::connectors::mongodb::connector::MongoDBConnector;
 use crate::connectors::sql::connector::SQLConnector;
 use crate::connectors::sql::schema::dialect::SQLDialect;
-use crate::core::app::command::{CLI, CLICommand, GenerateClientCommand, GenerateCommand, GenerateEntityCommand, MigrateCommand, ServeCommand};
-use crate::core::app::conf::{ClientGeneratorConf, EntityGeneratorConf, ServerConf};
+use crate::core::app::dotenv::load_env_files;
+use crate::core::app::command::{CLI, CLICommand, FmtCommand, GenerateClientCommand, GenerateCommand, GenerateEntityCommand, MigrateCommand, MigrateGenerateCommand, MigrateSubcommand, ServeCommand};
+use crate::core::app::conf::{ClientGeneratorConf, EntityGeneratorConf, JwtAlgorithm, LogFormat, LogLevel, ServerConf};
 use crate::core::app::entrance::Entrance;
 use crate::core::app::environment::EnvironmentVersion;
+use crate::core::app::fmt::run_formatter;
+use crate::core::app::lsp::run_language_server;
+use crate::core::app::middleware::Middleware;
+use crate::core::app::route::{RouteHandler, RouteTable};
+use crate::core::action::Action;
 use crate::core::connector::Connector;
 use crate::core::field::Field;
 use crate::core::database::name::DatabaseName;
 use crate::core::field::r#type::FieldType;
 use crate::core::graph::builder::GraphBuilder;
+use crate::core::audit::{AUDIT_LOG_MODEL, install_audit_log_model};
+use crate::core::app::serve::jwt_token::{REVOKED_TOKEN_MODEL, install_revoked_token_model};
 use crate::parser::ast::field::FieldClass;
 use crate::prelude::{App, Value};
 use crate::core::pipeline::item::Item;
@@ -28,7 +36,11 @@ use crate::core::pipeline::items::function::transform::{TransformResult, Transfo
 use crate::core::pipeline::items::function::validate::{ValidateArgument, ValidateItem, ValidateResult};
 use crate::core::property::Property;
 use crate::core::relation::Relation;
+use crate::parser::ast::decorator::Decorator;
 use crate::parser::ast::r#type::Arity;
+use crate::parser::ast::source::Source;
+use crate::parser::diagnostics::Diagnostics;
+use crate::parser::diagnostics::printer::{print_diagnostics, print_summary};
 use crate::parser::parser::Parser;
 
 #[derive(Debug)]
@@ -47,12 +59,15 @@ impl CallbackLookupTable {
 
 #[derive(ToMut)]
 pub struct AppBuilder {
-    pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) connectors: Vec<(String, Arc<dyn Connector>)>,
+    pub(crate) default_connector: Option<String>,
     pub(crate) graph_builder: GraphBuilder,
     pub(crate) server_conf: Option<ServerConf>,
     pub(crate) entity_generator_confs: Vec<EntityGeneratorConf>,
     pub(crate) client_generator_confs: Vec<ClientGeneratorConf>,
     pub(crate) callback_lookup_table: Arc<Mutex<CallbackLookupTable>>,
+    pub(crate) route_table: RouteTable,
+    pub(crate) middlewares: Vec<(String, Arc<dyn Middleware>)>,
     pub(crate) environment_version: EnvironmentVersion,
     pub(crate) entrance: Entrance,
     pub(crate) args: Arc<CLI>,
@@ -73,17 +88,21 @@ impl AppBuilder {
     }
 
     pub fn new_with_environment_version_and_entrance(environment_version: EnvironmentVersion, entrance: Entrance) -> Self {
-        let _ = dotenv(); // load dotenv file if exist. If the file does not exist, do nothing.
+        let args = Self::parse_cli_args(environment_version.clone(), entrance.clone());
+        load_env_files(args.env.as_deref());
         Self {
-            connector: None,
+            connectors: vec![],
+            default_connector: None,
             graph_builder: GraphBuilder::new(),
             server_conf: None,
             entity_generator_confs: vec![],
             client_generator_confs: vec![],
             callback_lookup_table: Arc::new(Mutex::new(CallbackLookupTable::new())),
+            route_table: RouteTable::new(),
+            middlewares: vec![],
             environment_version: environment_version.clone(),
             entrance,
-            args: Arc::new(Self::parse_cli_args(environment_version.clone(), entrance.clone())),
+            args: Arc::new(args),
         }
     }
 
@@ -106,6 +125,14 @@ impl AppBuilder {
                 .help("The schema file to load").action(ArgAction::Set)
                 .required(false)
                 .num_args(1))
+            .arg(Arg::new("ENV")
+                .short('e')
+                .long("env")
+                .help("The environment whose .env.<ENV> file is loaded, defaults to $TEO_ENV")
+                .action(ArgAction::Set)
+                .required(false)
+                .num_args(1)
+                .global(true))
             .arg(Arg::new("version")
                 .short('v')
                 .long("version")
@@ -155,6 +182,34 @@ impl AppBuilder {
                     .short('d')
                     .long("dry")
                     .help("Dry run")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("connector")
+                    .short('c')
+                    .long("connector")
+                    .help("Only migrate the models of this connector")
+                    .num_args(1)
+                    .global(true))
+                .subcommand(ClapCommand::new("generate")
+                    .about("Generate a migration file from schema changes")
+                    .arg(Arg::new("NAME")
+                        .help("Migration name")
+                        .required(false)
+                        .num_args(1)))
+                .subcommand(ClapCommand::new("up")
+                    .about("Apply pending migrations"))
+                .subcommand(ClapCommand::new("down")
+                    .about("Revert the last applied migration with its hand-written down.sql"))
+                .subcommand(ClapCommand::new("status")
+                    .about("Show migration status")))
+            .subcommand(ClapCommand::new("check")
+                .about("Check the schema for errors"))
+            .subcommand(ClapCommand::new("lsp")
+                .about("Start the language server"))
+            .subcommand(ClapCommand::new("fmt")
+                .about("Format the schema files")
+                .arg(Arg::new("check")
+                    .long("check")
+                    .help("Check whether the schema files are formatted without writing them")
                     .action(ArgAction::SetTrue)))
             .get_matches_from(match environment_version {
                 EnvironmentVersion::Python(_) | EnvironmentVersion::NodeJS(_) => env::args_os().enumerate().filter(|(i, _x)| *i != 1).map(|(_i, x)| x).collect::<Vec<OsString>>(),
@@ -164,6 +219,7 @@ impl AppBuilder {
                 _ => env::args_os().collect::<Vec<OsString>>(),
             });
         let schema: Option<&String> = matches.get_one("SCHEMA_FILE");
+        let env: Option<&String> = matches.get_one("ENV");
         let command = match matches.subcommand() {
             Some(("serve", submatches)) => {
                 CLICommand::Serve(ServeCommand { no_migration: submatches.get_flag("no-migration") })
@@ -182,11 +238,25 @@ impl AppBuilder {
                 }
             }
             Some(("migrate", submatches)) => {
-                CLICommand::Migrate(MigrateCommand { dry: submatches.get_flag("dry") })
+                let subcommand = match submatches.subcommand() {
+                    Some(("generate", submatches)) => {
+                        let name: Option<&String> = submatches.get_one("NAME");
+                        Some(MigrateSubcommand::Generate(MigrateGenerateCommand { name: name.map(|n| n.to_string()) }))
+                    }
+                    Some(("up", _)) => Some(MigrateSubcommand::Up),
+                    Some(("down", _)) => Some(MigrateSubcommand::Down),
+                    Some(("status", _)) => Some(MigrateSubcommand::Status),
+                    _ => None,
+                };
+                let connector: Option<&String> = submatches.subcommand().and_then(|(_, m)| m.get_one("connector")).or(submatches.get_one("connector"));
+                CLICommand::Migrate(MigrateCommand { dry: submatches.get_flag("dry"), connector: connector.map(|c| c.to_string()), subcommand })
             }
+            Some(("check", _)) => CLICommand::Check,
+            Some(("lsp", _)) => CLICommand::Lsp,
+            Some(("fmt", submatches)) => CLICommand::Fmt(FmtCommand { check: submatches.get_flag("check") }),
             _ => unreachable!()
         };
-        CLI { command, schema: schema.map(|s| s.to_string()) }
+        CLI { command, schema: schema.map(|s| s.to_string()), env: env.map(|e| e.to_string()) }
     }
 
     fn rust_environment_version() -> EnvironmentVersion {
@@ -225,23 +295,88 @@ impl AppBuilder {
         self
     }
 
+    /// Registers a custom HTTP route. Path segments starting with `:` are path parameters.
+    pub fn route<F>(&mut self, method: &str, path: &str, f: F) -> &mut Self where
+        F: RouteHandler + 'static {
+        self.route_table.add_route(method, path, Arc::new(f));
+        self
+    }
+
+    /// Registers a custom action served at `/<model url segment>/action/<name>`.
+    pub fn model_action<F>(&mut self, model: &str, name: &str, f: F) -> &mut Self where
+        F: RouteHandler + 'static {
+        self.route_table.add_model_action(model, name, Arc::new(f));
+        self
+    }
+
+    /// Registers a middleware which wraps every model action. When the server block declares
+    /// `middlewares`, only the listed ones run and in that order.
+    pub fn middleware<F>(&mut self, name: impl Into<String>, f: F) -> &mut Self where
+        F: Middleware + 'static {
+        self.middlewares.push((name.into(), Arc::new(f)));
+        self
+    }
+
     async fn load(&mut self) {
-        let mut parser = Parser::new(self.callback_lookup_table.clone());
+        if matches!(self.args.command, CLICommand::Lsp) {
+            if let Err(err) = run_language_server(self.callback_lookup_table.clone()) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+            std::process::exit(0);
+        }
         let main = match self.args.schema.as_ref() {
             Some(s) => Some(s.as_str()),
             None => None
         };
+        if let CLICommand::Fmt(command) = &self.args.command {
+            let formatted = run_formatter(self.callback_lookup_table.clone(), main, command.check);
+            std::process::exit(if formatted { 0 } else { 1 });
+        }
+        let mut parser = Parser::new(self.callback_lookup_table.clone());
         parser.parse(main);
+        if !parser.diagnostics.has_errors() {
+            Self::load_models_from_parser(&mut self.graph_builder, &mut parser);
+        }
+        print_diagnostics(&parser.diagnostics);
+        if parser.diagnostics.has_errors() || matches!(self.args.command, CLICommand::Check) {
+            print_summary(&parser.diagnostics);
+            std::process::exit(if parser.diagnostics.has_errors() { 1 } else { 0 });
+        }
         self.load_config_from_parser(&parser).await;
     }
 
     pub async fn build(&mut self) -> App {
         self.load().await;
+        let graph = match self.graph_builder.build(self.connectors.clone(), self.default_connector.as_ref().unwrap()).await {
+            Ok(graph) => graph,
+            Err(err) => {
+                eprintln!("{}", err.message);
+                std::process::exit(1);
+            }
+        };
+        for (model, name) in self.route_table.model_actions() {
+            if graph.model(model).is_none() {
+                panic!("Model '{}' of custom action '{}' is not found.", model, name);
+            }
+            if Action::handler_from_name(name).is_some() {
+                panic!("Custom action '{}' of model '{}' conflicts with a builtin action.", name, model);
+            }
+        }
+        let middlewares = match &self.server_conf.as_ref().unwrap().middlewares {
+            Some(names) => names.iter().map(|name| match self.middlewares.iter().find(|(n, _)| n == name) {
+                Some((_, middleware)) => middleware.clone(),
+                None => panic!("Middleware '{}' is not found.", name),
+            }).collect(),
+            None => self.middlewares.iter().map(|(_, middleware)| middleware.clone()).collect(),
+        };
         App {
             server_conf: self.server_conf.clone().unwrap(),
+            middlewares,
             entity_generator_confs: self.entity_generator_confs.clone(),
             client_generator_confs: self.client_generator_confs.clone(),
-            graph: self.graph_builder.build(self.connector.as_ref().unwrap().clone()).await,
+            route_table: self.route_table.clone(),
+            graph,
             environment_version: self.environment_version.clone(),
             entrance: self.entrance.clone(),
             args: self.args.clone(),
@@ -249,39 +384,42 @@ impl AppBuilder {
     }
 
     async fn load_config_from_parser(&mut self, parser: &Parser) {
-        // connector
-        let connector_ref = parser.connector.unwrap();
-        let source = parser.get_source(connector_ref.0);
-        let connector_declaration = source.get_connector(connector_ref.1);
-        let url = connector_declaration.url.as_ref().unwrap();
-        if connector_declaration.debug {
-            env::set_var("_TEO_LOG_DB_OPERATION", "true");
-        }
-        let connector: Arc<dyn Connector> = match connector_declaration.provider.unwrap() {
-            DatabaseName::MySQL => {
-                #[cfg(feature = "data-source-mysql")]
-                Arc::new(SQLConnector::new(SQLDialect::MySQL, url, false).await)
-            },
-            DatabaseName::PostgreSQL => {
-                #[cfg(feature = "data-source-postgres")]
-                Arc::new(SQLConnector::new(SQLDialect::PostgreSQL, url, false).await)
-            },
-            #[cfg(feature = "data-source-sqlite")]
-            DatabaseName::SQLite => {
+        // connectors
+        for connector_declaration in parser.connectors() {
+            let url = connector_declaration.url.as_ref().unwrap();
+            let connector: Arc<dyn Connector> = match connector_declaration.provider.unwrap() {
+                DatabaseName::MySQL => {
+                    #[cfg(feature = "data-source-mysql")]
+                    Arc::new(SQLConnector::new(SQLDialect::MySQL, url, &connector_declaration.pool, false).await)
+                },
+                DatabaseName::PostgreSQL => {
+                    #[cfg(feature = "data-source-postgres")]
+                    Arc::new(SQLConnector::new(SQLDialect::PostgreSQL, url, &connector_declaration.pool, false).await)
+                },
                 #[cfg(feature = "data-source-sqlite")]
-                Arc::new(SQLConnector::new(SQLDialect::SQLite, url, false).await)
-            },
-            DatabaseName::MongoDB => {
-                #[cfg(feature = "data-source-mongodb")]
-                Arc::new(MongoDBConnector::new(url.clone()).await)
-            },
-        };
-        self.connector = Some(connector.clone());
+                DatabaseName::SQLite => {
+                    #[cfg(feature = "data-source-sqlite")]
+                    Arc::new(SQLConnector::new(SQLDialect::SQLite, url, &connector_declaration.pool, false).await)
+                },
+                DatabaseName::MongoDB => {
+                    #[cfg(feature = "data-source-mongodb")]
+                    Arc::new(MongoDBConnector::new(url.clone()).await)
+                },
+                #[cfg(feature = "data-source-mssql")]
+                DatabaseName::MSSQL => {
+                    Arc::new(SQLConnector::new(SQLDialect::MSSQL, url, &connector_declaration.pool, false).await)
+                },
+            };
+            self.connectors.push((connector_declaration.name().to_owned(), connector));
+        }
+        self.default_connector = parser.default_connector().map(|c| c.name().to_owned());
         // server config
         let config_ref = parser.config.unwrap();
         let source = parser.get_source(config_ref.0);
         let config = source.get_server_config(config_ref.1);
         let bind = config.bind.as_ref().unwrap();
+        // the connectors' `debug` flags predate `logQueries`
+        let log_queries = config.log_queries.unwrap_or(parser.connectors().iter().any(|c| c.debug));
         self.server_conf = Some(ServerConf {
             bind: bind.clone(),
             path_prefix: if let Some(path_prefix) = &config.path_prefix {
@@ -289,11 +427,36 @@ impl AppBuilder {
             } else {
                 None
             },
+            jwt_algorithm: config.jwt_algorithm.unwrap_or(JwtAlgorithm::HS256),
             jwt_secret: if let Some(jwt_secret) = &config.jwt_secret {
                 Some(jwt_secret.clone())
             } else {
                 None
-            }
+            },
+            jwt_private_key: config.jwt_private_key.clone(),
+            jwt_public_key: config.jwt_public_key.clone(),
+            jwt_expires_in: config.jwt_expires_in.unwrap_or(365 * 24 * 60 * 60),
+            refresh_token_expires_in: config.refresh_token_expires_in,
+            allow_origins: config.allow_origins.clone().unwrap_or(vec!["*".to_owned()]),
+            // by default, every method which the server routes is allowed, methods are matched in
+            // uppercase like route methods
+            allow_methods: config.allow_methods.as_ref().map(|methods| {
+                methods.iter().map(|m| m.to_uppercase()).collect()
+            }).unwrap_or_else(|| {
+                let mut methods = vec!["OPTIONS".to_owned(), "POST".to_owned(), "GET".to_owned()];
+                for method in self.route_table.methods() {
+                    if !methods.iter().any(|m| m == method) { methods.push(method.to_owned()); }
+                }
+                methods
+            }),
+            allow_credentials: config.allow_credentials.unwrap_or(false),
+            max_body_size: config.max_body_size.unwrap_or(262_144),
+            headers: config.headers.clone().unwrap_or(vec![]),
+            middlewares: config.middlewares.clone(),
+            // queries are logged at debug level, which is the default level when they're logged
+            log_level: config.log_level.unwrap_or(if log_queries { LogLevel::Debug } else { LogLevel::Info }),
+            log_format: config.log_format.unwrap_or(LogFormat::Text),
+            log_queries,
         });
         // entity generators
         for entity_generator_ref in parser.generators.iter() {
@@ -319,13 +482,19 @@ impl AppBuilder {
                 git_commit: client.git_commit,
             })
         }
+    }
+
+    pub(crate) fn load_models_from_parser(graph_builder: &mut GraphBuilder, parser: &mut Parser) {
+        let diagnostics = RefCell::new(Diagnostics::new());
         // load enums
         for enum_ref in parser.enums.clone() {
             let source = parser.get_source(enum_ref.0);
             let r#enum = source.get_enum(enum_ref.1);
-            self.graph_builder.r#enum(&r#enum.identifier.name, |enum_builder| {
+            graph_builder.r#enum(&r#enum.identifier.name, |enum_builder| {
                for choice in r#enum.choices.iter() {
-                    enum_builder.choice(&choice.identifier.name, |_| {});
+                    enum_builder.choice(&choice.identifier.name, |choice_builder| {
+                        choice_builder.renamed(choice.renamed.clone());
+                    });
                }
             });
         }
@@ -333,7 +502,7 @@ impl AppBuilder {
         for model_ref in parser.models.clone() {
             let source = parser.get_source(model_ref.0);
             let model = source.get_model(model_ref.1);
-            self.graph_builder.model(&model.identifier.name, |model_builder| {
+            graph_builder.model(&model.identifier.name, |model_builder| {
                 if let Some(comment) = &model.comment_block {
                     if let Some(name) = comment.name.as_ref() {
                         model_builder.localized_name(name);
@@ -343,8 +512,8 @@ impl AppBuilder {
                     }
                 }
                 for decorator in model.decorators.iter() {
-                   let model_decorator = decorator.accessible.as_ref().unwrap().as_model_decorator().unwrap();
-                    model_decorator(decorator.get_argument_list(), model_builder);
+                    let model_decorator = decorator.accessible.as_ref().unwrap().as_model_decorator().unwrap();
+                    Self::install_decorator(&diagnostics, source, decorator, || model_decorator(decorator.get_argument_list(), model_builder));
                 }
                 for field in model.fields.iter() {
                     match &field.field_class {
@@ -406,7 +575,7 @@ impl AppBuilder {
                             // decorators
                             for decorator in field.decorators.iter() {
                                 let field_decorator = decorator.accessible.as_ref().unwrap().as_field_decorator().unwrap();
-                                field_decorator(decorator.get_argument_list(), &mut model_field);
+                                Self::install_decorator(&diagnostics, source, decorator, || field_decorator(decorator.get_argument_list(), &mut model_field));
                             }
                             match &field.field_class {
                                 FieldClass::DroppedField => {
@@ -439,17 +608,21 @@ impl AppBuilder {
                                 }
                                 Arity::Array => {
                                     if !field.r#type.item_required {
-                                        panic!("Relation cannot have optional items.")
+                                        diagnostics.borrow_mut().insert_error(&source.path, field.r#type.identifier.span, "Relation cannot have optional items.");
+                                        continue;
                                     }
                                     model_relation.set_is_vec(true);
                                     model_relation.set_model(field.r#type.identifier.name.clone());
                                 }
-                                Arity::Dictionary => panic!("Relations cannot be dictionary.")
+                                Arity::Dictionary => {
+                                    diagnostics.borrow_mut().insert_error(&source.path, field.r#type.identifier.span, "Relations cannot be dictionary.");
+                                    continue;
+                                }
                             }
                             // handle decorators
                             for decorator in field.decorators.iter() {
                                 let relation_decorator = decorator.accessible.as_ref().unwrap().as_relation_decorator().unwrap();
-                                relation_decorator(decorator.get_argument_list(), &mut model_relation);
+                                Self::install_decorator(&diagnostics, source, decorator, || relation_decorator(decorator.get_argument_list(), &mut model_relation));
                             }
                             model_builder.relation(model_relation);
                         }
@@ -510,15 +683,35 @@ impl AppBuilder {
                             }
                             for decorator in field.decorators.iter() {
                                 let property_decorator = decorator.accessible.as_ref().unwrap().as_property_decorator().unwrap();
-                                property_decorator(decorator.get_argument_list(), &mut model_property);
+                                Self::install_decorator(&diagnostics, source, decorator, || property_decorator(decorator.get_argument_list(), &mut model_property));
                             }
                             model_builder.property(model_property);
                         }
-                        FieldClass::Unresolved => panic!()
+                        FieldClass::Unresolved => {
+                            diagnostics.borrow_mut().insert_error(&source.path, field.span, "Field type is unresolved.");
+                        }
                     }
                 }
             });
         }
+        // audited models record into a builtin model
+        if graph_builder.model_builders.iter().any(|m| m.audit) {
+            let provider = parser.default_connector().unwrap().provider.unwrap();
+            graph_builder.model(AUDIT_LOG_MODEL, |model_builder| install_audit_log_model(model_builder, provider));
+        }
+        // signed out tokens are stored by a builtin model
+        if graph_builder.model_builders.iter().any(|m| m.identity) {
+            let provider = parser.default_connector().unwrap().provider.unwrap();
+            graph_builder.model(REVOKED_TOKEN_MODEL, |model_builder| install_revoked_token_model(model_builder, provider));
+        }
+        parser.diagnostics.extend(diagnostics.into_inner());
+    }
+
+    /// Bad decorator arguments are reported at the decorator.
+    fn install_decorator(diagnostics: &RefCell<Diagnostics>, source: &Source, decorator: &Decorator, install: impl FnOnce() -> Result<(), String>) {
+        if let Err(message) = install() {
+            diagnostics.borrow_mut().insert_error(&source.path, decorator.span, message);
+        }
     }
 
     fn install_types_to_field_builder(name: &str, field: &mut Field) {
@@ -532,6 +725,7 @@ impl AppBuilder {
             "Date" => field.field_type = Some(FieldType::Date),
             "DateTime" => field.field_type = Some(FieldType::DateTime),
             "Decimal" => field.field_type = Some(FieldType::Decimal),
+            "Json" => field.field_type = Some(FieldType::Json),
             #[cfg(feature = "data-source-mongodb")]
             "ObjectId" => field.field_type = Some(FieldType::ObjectId),
             // _ => panic!("Unrecognized type: '{}'.", name)
@@ -550,6 +744,7 @@ impl AppBuilder {
             "Date" =>  property.field_type = Some(FieldType::Date),
             "DateTime" =>  property.field_type = Some(FieldType::DateTime),
             "Decimal" => property.field_type = Some(FieldType::Decimal),
+            "Json" => property.field_type = Some(FieldType::Json),
             #[cfg(feature = "data-source-mongodb")]
             "ObjectId" =>  property.field_type = Some(FieldType::ObjectId),
             _ => property.field_type = Some(FieldType::Enum(name.to_string())),
@@ -2,6 +2,7 @@
 pub(crate) struct CLI {
     pub(crate) command: CLICommand,
     pub(crate) schema: Option<String>,
+    pub(crate) env: Option<String>,
 }
 
 #[derive(Debug)]
@@ -9,6 +10,9 @@ pub(crate) enum CLICommand {
     Serve(ServeCommand),
     Generate(GenerateCommand),
     Migrate(MigrateCommand),
+    Check,
+    Lsp,
+    Fmt(FmtCommand),
 }
 
 #[derive(Debug)]
@@ -37,4 +41,24 @@ pub(crate) struct GenerateEntityCommand {
 #[derive(Debug)]
 pub(crate) struct MigrateCommand {
     pub(crate) dry: bool,
+    pub(crate) connector: Option<String>,
+    pub(crate) subcommand: Option<MigrateSubcommand>,
+}
+
+#[derive(Debug)]
+pub(crate) enum MigrateSubcommand {
+    Generate(MigrateGenerateCommand),
+    Up,
+    Down,
+    Status,
+}
+
+#[derive(Debug)]
+pub(crate) struct MigrateGenerateCommand {
+    pub(crate) name: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct FmtCommand {
+    pub(crate) check: bool,
 }
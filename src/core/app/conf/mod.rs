@@ -2,11 +2,57 @@ use std::path::PathBuf;
 use crate::core::app::environment::Environment;
 use crate::parser::ast::client::ClientLanguage;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    HS256,
+    RS256,
+    ES256,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
 #[derive(Clone)]
 pub struct ServerConf {
     pub(crate) bind: (String, u16),
+    pub(crate) jwt_algorithm: JwtAlgorithm,
     pub(crate) jwt_secret: Option<String>,
+    pub(crate) jwt_private_key: Option<String>,
+    pub(crate) jwt_public_key: Option<String>,
+    pub(crate) jwt_expires_in: i64,
+    pub(crate) refresh_token_expires_in: Option<i64>,
     pub(crate) path_prefix: Option<String>,
+    pub(crate) allow_origins: Vec<String>,
+    pub(crate) allow_methods: Vec<String>,
+    pub(crate) allow_credentials: bool,
+    pub(crate) max_body_size: usize,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) middlewares: Option<Vec<String>>,
+    pub(crate) log_level: LogLevel,
+    pub(crate) log_format: LogFormat,
+    pub(crate) log_queries: bool,
+}
+
+/// Connection pool settings of SQL connectors. Durations are in seconds, unset values keep the
+/// pool defaults.
+#[derive(Clone, Debug, Default)]
+pub struct PoolConf {
+    pub(crate) size: Option<usize>,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) max_idle: Option<u64>,
+    pub(crate) idle_timeout: Option<u64>,
+    pub(crate) max_lifetime: Option<u64>,
 }
 
 #[derive(Clone)]
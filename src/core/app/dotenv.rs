@@ -0,0 +1,26 @@
+use std::collections::HashSet;
+use std::env;
+use dotenvy::{dotenv, from_filename_iter};
+
+/// Names the environment whose override file is loaded when `--env` is not given.
+pub(crate) const ENVIRONMENT_VARIABLE: &str = "TEO_ENV";
+
+/// Loads `.env`, and then `.env.<environment>` over it. Variables which are already set in the
+/// process environment are never overwritten by either file.
+pub(crate) fn load_env_files(environment: Option<&str>) {
+    let preset: HashSet<String> = env::vars_os().map(|(key, _)| key.to_string_lossy().to_string()).collect();
+    let _ = dotenv(); // load dotenv file if exist. If the file does not exist, do nothing.
+    let environment = match environment {
+        Some(environment) => environment.to_owned(),
+        None => match env::var(ENVIRONMENT_VARIABLE) {
+            Ok(environment) if !environment.is_empty() => environment,
+            _ => return,
+        }
+    };
+    let Ok(items) = from_filename_iter(format!(".env.{}", environment)) else { return };
+    for (key, value) in items.flatten() {
+        if !preset.contains(&key) {
+            env::set_var(key, value);
+        }
+    }
+}
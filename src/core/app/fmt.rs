@@ -0,0 +1,48 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+use colored::Colorize;
+use crate::core::app::builder::CallbackLookupTable;
+use crate::parser::diagnostics::printer::{display_path, print_diagnostics};
+use crate::parser::formatter::format_schema;
+use crate::parser::parser::Parser;
+
+/// Formats the schema file and the files it imports in place. When checking, the files are
+/// listed instead of written. Returns whether all files are formatted.
+pub(crate) fn run_formatter(callback_lookup_table: Arc<Mutex<CallbackLookupTable>>, main: Option<&str>, check: bool) -> bool {
+    let mut parser = Parser::new(callback_lookup_table);
+    parser.parse_sources(main);
+    if parser.diagnostics.has_errors() {
+        print_diagnostics(&parser.diagnostics);
+        eprintln!("{}: could not format schema due to syntax errors", "error".red().bold());
+        return false;
+    }
+    let mut unformatted = 0;
+    for source in parser.sources.values() {
+        let content = match fs::read_to_string(&source.path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("{}: cannot read '{}': {}", "error".red().bold(), display_path(&source.path), err);
+                return false;
+            }
+        };
+        let formatted = match format_schema(&content) {
+            Ok(formatted) => formatted,
+            Err(err) => {
+                eprintln!("{}: cannot format '{}': {}", "error".red().bold(), display_path(&source.path), err);
+                return false;
+            }
+        };
+        if formatted == content { continue }
+        if check {
+            eprintln!("{}: {}", "unformatted".yellow().bold(), display_path(&source.path));
+            unformatted += 1;
+        } else if let Err(err) = fs::write(&source.path, formatted) {
+            eprintln!("{}: cannot write '{}': {}", "error".red().bold(), display_path(&source.path), err);
+            return false;
+        }
+    }
+    if unformatted > 0 {
+        eprintln!("{}: {} schema file{} would be reformatted", "error".red().bold(), unformatted, if unformatted == 1 { "" } else { "s" });
+    }
+    unformatted == 0
+}
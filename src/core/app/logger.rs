@@ -0,0 +1,177 @@
+use std::time::Instant;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use colored::Colorize;
+use once_cell::sync::OnceCell;
+use serde_json::{json, Map, Value as JsonValue};
+use crate::core::app::conf::{LogFormat, LogLevel, ServerConf};
+
+struct Logger {
+    level: LogLevel,
+    format: LogFormat,
+    queries: bool,
+}
+
+static LOGGER: OnceCell<Logger> = OnceCell::new();
+
+static DEFAULT_LOGGER: Logger = Logger { level: LogLevel::Info, format: LogFormat::Text, queries: false };
+
+fn logger() -> &'static Logger {
+    LOGGER.get().unwrap_or(&DEFAULT_LOGGER)
+}
+
+/// Configures the process wide logger. Only the first call takes effect.
+pub(crate) fn setup_logger(conf: &ServerConf) {
+    let _ = LOGGER.set(Logger { level: conf.log_level, format: conf.log_format, queries: conf.log_queries });
+}
+
+/// Whether queries are logged, they're logged at debug level.
+pub(crate) fn log_queries() -> bool {
+    let logger = logger();
+    logger.queries && LogLevel::Debug >= logger.level
+}
+
+pub(crate) fn is_json() -> bool {
+    logger().format == LogFormat::Json
+}
+
+fn level_str(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Debug => "debug",
+        LogLevel::Info => "info",
+        LogLevel::Warn => "warn",
+        LogLevel::Error => "error",
+    }
+}
+
+fn write_json(level: LogLevel, r#type: &str, mut fields: Map<String, JsonValue>) {
+    fields.insert("timestamp".to_owned(), json!(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)));
+    fields.insert("level".to_owned(), json!(level_str(level)));
+    fields.insert("type".to_owned(), json!(r#type));
+    println!("{}", JsonValue::Object(fields));
+}
+
+fn text_timestamp() -> String {
+    let local: DateTime<Local> = Local::now();
+    format!("{local}").dimmed().to_string()
+}
+
+/// A record of an HTTP request, written once the response is ready.
+pub(crate) struct RequestLog {
+    pub(crate) request_id: String,
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) start: Instant,
+    pub(crate) handled: bool,
+    pub(crate) model: Option<String>,
+    pub(crate) action: Option<String>,
+    pub(crate) identity: Option<JsonValue>,
+    pub(crate) status: u16,
+    pub(crate) error_type: Option<String>,
+}
+
+impl RequestLog {
+
+    pub(crate) fn new(request_id: String, method: &str, path: &str) -> Self {
+        Self {
+            request_id,
+            method: method.to_owned(),
+            path: path.to_owned(),
+            start: Instant::now(),
+            handled: false,
+            model: None,
+            action: None,
+            identity: None,
+            status: 0,
+            error_type: None,
+        }
+    }
+
+    fn level(&self) -> LogLevel {
+        match self.status {
+            500..=599 => LogLevel::Error,
+            400..=499 => LogLevel::Warn,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
+pub(crate) fn log_request(log: &RequestLog) {
+    let logger = logger();
+    let level = log.level();
+    if level < logger.level {
+        return;
+    }
+    let ms = log.start.elapsed().as_millis();
+    match logger.format {
+        LogFormat::Json => {
+            let mut fields = Map::new();
+            fields.insert("requestId".to_owned(), json!(log.request_id));
+            fields.insert("method".to_owned(), json!(log.method));
+            fields.insert("path".to_owned(), json!(log.path));
+            fields.insert("model".to_owned(), json!(log.model));
+            fields.insert("action".to_owned(), json!(log.action));
+            fields.insert("identity".to_owned(), log.identity.clone().unwrap_or(JsonValue::Null));
+            fields.insert("status".to_owned(), json!(log.status));
+            fields.insert("durationMs".to_owned(), json!(ms as u64));
+            fields.insert("errorType".to_owned(), json!(log.error_type));
+            write_json(level, "request", fields);
+        }
+        LogFormat::Text => {
+            let code = log.status;
+            let code_string = match code {
+                0..=199 => code.to_string().purple().bold(),
+                200..=299 => code.to_string().green().bold(),
+                300..=399 => code.to_string().yellow().bold(),
+                _ => code.to_string().red().bold(),
+            };
+            let ms_str = format!("{ms}ms").dimmed();
+            let local_formatted = text_timestamp();
+            if !log.handled {
+                let unhandled = "Unhandled".red();
+                println!("{} {} {} on {} - {} {}", local_formatted, unhandled, log.method.bold(), log.path, code_string, ms_str);
+            } else if let (Some(model), Some(action)) = (&log.model, &log.action) {
+                println!("{} {} on {} - {} {}", local_formatted, action.bold(), model, code_string, ms_str);
+            } else {
+                println!("{} {} {} - {} {}", local_formatted, log.method.bold(), log.path, code_string, ms_str);
+            }
+        }
+    }
+}
+
+/// Logs a database statement. `connector` is the database family, e.g. `sql` or `mongodb`.
+pub(crate) fn log_query(connector: &str, statement: &str, start: Instant, success: bool) {
+    if !log_queries() {
+        return;
+    }
+    let logger = logger();
+    let ms = start.elapsed().as_millis();
+    match logger.format {
+        LogFormat::Json => {
+            let mut fields = Map::new();
+            fields.insert("connector".to_owned(), json!(connector));
+            fields.insert("statement".to_owned(), json!(statement));
+            fields.insert("durationMs".to_owned(), json!(ms as u64));
+            fields.insert("success".to_owned(), json!(success));
+            write_json(LogLevel::Debug, "query", fields);
+        }
+        LogFormat::Text => {
+            let label = if success { "Query".cyan() } else { "Query".red() };
+            println!("{} {} {} {}", text_timestamp(), label, statement, format!("{ms}ms").dimmed());
+        }
+    }
+}
+
+pub(crate) fn log_message(level: LogLevel, message: &str) {
+    let logger = logger();
+    if level < logger.level {
+        return;
+    }
+    match logger.format {
+        LogFormat::Json => {
+            let mut fields = Map::new();
+            fields.insert("message".to_owned(), json!(message));
+            write_json(level, "message", fields);
+        }
+        LogFormat::Text => println!("{} {}", text_timestamp(), message),
+    }
+}
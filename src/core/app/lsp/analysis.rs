@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::core::app::builder::{AppBuilder, CallbackLookupTable};
+use crate::core::graph::builder::GraphBuilder;
+use crate::parser::ast::comment_block::CommentBlock;
+use crate::parser::ast::r#type::{Arity, Type};
+use crate::parser::ast::source::Source;
+use crate::parser::ast::span::Span;
+use crate::parser::ast::top::Top;
+use crate::parser::parser::Parser;
+
+/// Parses and checks a schema the same way as loading the app, without connecting to the
+/// database.
+pub(super) fn analyze(main: &PathBuf, documents: &HashMap<PathBuf, String>, callback_lookup_table: Arc<Mutex<CallbackLookupTable>>) -> Parser {
+    let mut parser = Parser::new(callback_lookup_table);
+    parser.contents = documents.clone();
+    parser.parse(main.to_str());
+    if !parser.diagnostics.has_errors() {
+        AppBuilder::load_models_from_parser(&mut GraphBuilder::new(), &mut parser);
+    }
+    parser
+}
+
+fn source_at<'a>(parser: &'a Parser, path: &PathBuf) -> Option<&'a Source> {
+    parser.sources.values().find(|source| &source.path == path)
+}
+
+/// Finds the declaration of the model, enum or import at the offset.
+pub(super) fn definition(parser: &Parser, path: &PathBuf, offset: usize) -> Option<(PathBuf, Span)> {
+    for top in source_at(parser, path)?.tops.values() {
+        match top {
+            Top::Import(import) => {
+                for identifier in import.identifiers.iter() {
+                    if identifier.span.contains(offset) {
+                        let from_source = parser.get_source(import.from_id?);
+                        let top = declaration_in_source(from_source, &identifier.name)?;
+                        return Some((from_source.path.clone(), declaration_span(top)));
+                    }
+                }
+                if import.source.span.contains(offset) {
+                    return Some((import.path.clone(), Span::empty()));
+                }
+            }
+            Top::Model(model) => {
+                for field in model.fields.iter() {
+                    if field.r#type.identifier.span.contains(offset) {
+                        let (source, top) = type_declaration(parser, &field.r#type.identifier.name)?;
+                        return Some((source.path.clone(), declaration_span(top)));
+                    }
+                }
+            }
+            _ => ()
+        }
+    }
+    None
+}
+
+/// Returns the documentation of the item at the offset, and the span it's shown for.
+pub(super) fn hover(parser: &Parser, path: &PathBuf, offset: usize) -> Option<(String, Span)> {
+    for top in source_at(parser, path)?.tops.values() {
+        match top {
+            Top::Import(import) => {
+                for identifier in import.identifiers.iter() {
+                    if identifier.span.contains(offset) {
+                        let top = declaration_in_source(parser.get_source(import.from_id?), &identifier.name)?;
+                        return Some((declaration_documentation(top)?, identifier.span));
+                    }
+                }
+            }
+            Top::Model(model) => {
+                if model.identifier.span.contains(offset) {
+                    return Some((declaration_documentation(top)?, model.identifier.span));
+                }
+                for field in model.fields.iter() {
+                    if field.identifier.span.contains(offset) {
+                        let header = format!("{}: {}", field.identifier.name, type_description(&field.r#type));
+                        return Some((documentation(header, field.comment_block.as_ref()), field.identifier.span));
+                    }
+                    if field.r#type.identifier.span.contains(offset) {
+                        let (_, top) = type_declaration(parser, &field.r#type.identifier.name)?;
+                        return Some((declaration_documentation(top)?, field.r#type.identifier.span));
+                    }
+                }
+            }
+            Top::Enum(r#enum) => {
+                if r#enum.identifier.span.contains(offset) {
+                    return Some((declaration_documentation(top)?, r#enum.identifier.span));
+                }
+            }
+            _ => ()
+        }
+    }
+    None
+}
+
+fn type_declaration<'a>(parser: &'a Parser, name: &str) -> Option<(&'a Source, &'a Top)> {
+    parser.models.iter().chain(parser.enums.iter()).find_map(|(source_id, item_id)| {
+        let source = parser.get_source(*source_id);
+        let top = source.tops.get(item_id)?;
+        (declaration_name(top)? == name).then_some((source, top))
+    })
+}
+
+fn declaration_in_source<'a>(source: &'a Source, name: &str) -> Option<&'a Top> {
+    source.tops.values().find(|top| declaration_name(top) == Some(name))
+}
+
+fn declaration_name(top: &Top) -> Option<&str> {
+    match top {
+        Top::Model(model) => Some(model.identifier.name.as_str()),
+        Top::Enum(r#enum) => Some(r#enum.identifier.name.as_str()),
+        Top::Constant(constant) => Some(constant.identifier.name.as_str()),
+        _ => None,
+    }
+}
+
+fn declaration_span(top: &Top) -> Span {
+    match top {
+        Top::Model(model) => model.identifier.span,
+        Top::Enum(r#enum) => r#enum.identifier.span,
+        Top::Constant(constant) => constant.identifier.span,
+        _ => unreachable!(),
+    }
+}
+
+fn declaration_documentation(top: &Top) -> Option<String> {
+    match top {
+        Top::Model(model) => Some(documentation(format!("model {}", model.identifier.name), model.comment_block.as_ref())),
+        Top::Enum(r#enum) => {
+            let choices = r#enum.choices.iter().map(|c| format!("  {}", c.identifier.name)).collect::<Vec<String>>().join("\n");
+            Some(documentation(format!("enum {} {{\n{}\n}}", r#enum.identifier.name, choices), r#enum.comment_block.as_ref()))
+        }
+        Top::Constant(constant) => Some(documentation(format!("let {} = {}", constant.identifier.name, constant.expression.kind), None)),
+        _ => None,
+    }
+}
+
+fn documentation(header: String, comment_block: Option<&CommentBlock>) -> String {
+    let mut result = format!("```teo\n{}\n```", header);
+    if let Some(comment_block) = comment_block {
+        if let Some(name) = &comment_block.name {
+            result += &format!("\n\n**{}**", name);
+        }
+        if let Some(desc) = &comment_block.desc {
+            result += &format!("\n\n{}", desc);
+        }
+    }
+    result
+}
+
+fn type_description(r#type: &Type) -> String {
+    let item = format!("{}{}", r#type.identifier.name, if r#type.item_required { "" } else { "?" });
+    let collection = if r#type.collection_required { "" } else { "?" };
+    match r#type.arity {
+        Arity::Scalar => item,
+        Arity::Array => format!("{}[]{}", item, collection),
+        Arity::Dictionary => format!("{}{{}}{}", item, collection),
+    }
+}
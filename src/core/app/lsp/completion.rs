@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, BTreeSet};
+use lsp_types::{CompletionItem, CompletionItemKind};
+use regex::Regex;
+use crate::parser::parser::Parser;
+use crate::parser::std::decorators::field::GlobalFieldDecorators;
+use crate::parser::std::decorators::model::GlobalModelDecorators;
+use crate::parser::std::decorators::property::GlobalPropertyDecorators;
+use crate::parser::std::decorators::relation::GlobalRelationDecorators;
+use crate::parser::std::pipeline::global::{GlobalFunctionInstallers, GlobalPipelineInstallers};
+
+/// Completes decorators after `@` and `@@`, pipeline items after `$` and enum members after `.`.
+pub(super) fn completion(parser: &Parser, enum_members: &BTreeMap<String, Vec<String>>, content: &str, offset: usize) -> Vec<CompletionItem> {
+    let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = &content[line_start..offset];
+    let before_word = line.trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+    if before_word.ends_with("@@") {
+        let decorators = match &parser.global_model_decorators {
+            Some(decorators) => owned(decorators.names()),
+            None => owned(GlobalModelDecorators::new().names()),
+        };
+        items(decorators, CompletionItemKind::FUNCTION, None)
+    } else if before_word.ends_with("@db.") {
+        db_decorators(parser)
+    } else if before_word.ends_with('@') {
+        field_decorators(parser)
+    } else if before_word.ends_with('$') || (before_word.ends_with('.') && in_pipeline(&before_word[..before_word.len() - 1])) {
+        let mut names = owned(GlobalPipelineInstallers::new().names());
+        names.extend(owned(GlobalFunctionInstallers::new().names()));
+        items(names, CompletionItemKind::FUNCTION, None)
+    } else if before_word.ends_with('.') {
+        enum_choices(enum_members, &content[offset..])
+    } else {
+        vec![]
+    }
+}
+
+fn field_decorators(parser: &Parser) -> Vec<CompletionItem> {
+    let mut names = BTreeSet::new();
+    match &parser.global_field_decorators {
+        Some(decorators) => names.extend(owned(decorators.names())),
+        None => names.extend(owned(GlobalFieldDecorators::new(None).names())),
+    }
+    names.extend(owned(GlobalRelationDecorators::new().names()));
+    names.extend(owned(GlobalPropertyDecorators::new().names()));
+    let mut result = vec![];
+    for name in names {
+        let is_container = parser.global_field_decorators.as_ref().and_then(|d| d.get(&name)).map_or(false, |a| a.is_container());
+        let kind = if is_container { CompletionItemKind::MODULE } else { CompletionItemKind::FUNCTION };
+        result.extend(items(vec![name], kind, None));
+    }
+    result
+}
+
+fn db_decorators(parser: &Parser) -> Vec<CompletionItem> {
+    let Some(container) = parser.global_field_decorators.as_ref().and_then(|d| d.get("db")).and_then(|a| a.as_container()) else {
+        return vec![];
+    };
+    items(container.objects.keys().cloned().collect(), CompletionItemKind::FUNCTION, None)
+}
+
+/// Offers the choices of the enum which the field below the cursor is typed with, or the
+/// choices of all enums if it can't be told.
+fn enum_choices(enum_members: &BTreeMap<String, Vec<String>>, following: &str) -> Vec<CompletionItem> {
+    let field = Regex::new(r"([A-Za-z_][A-Za-z0-9_]*)\s*:\s*([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    if let Some(captures) = field.captures(following) {
+        if let Some(choices) = enum_members.get(&captures[2]) {
+            return items(choices.clone(), CompletionItemKind::ENUM_MEMBER, Some(&captures[2]));
+        }
+    }
+    enum_members.iter().flat_map(|(name, choices)| {
+        items(choices.clone(), CompletionItemKind::ENUM_MEMBER, Some(name))
+    }).collect()
+}
+
+/// Whether the text ends inside a `$a.b(…).c` pipeline chain.
+fn in_pipeline(text: &str) -> bool {
+    let mut depth = 0;
+    for c in text.chars().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth == 0 => return false,
+            '(' => depth -= 1,
+            '$' if depth == 0 => return true,
+            c if depth == 0 && !(c.is_ascii_alphanumeric() || c == '_' || c == '.') => return false,
+            _ => (),
+        }
+    }
+    false
+}
+
+fn items(names: Vec<String>, kind: CompletionItemKind, detail: Option<&str>) -> Vec<CompletionItem> {
+    names.into_iter().map(|name| CompletionItem {
+        label: name,
+        kind: Some(kind),
+        detail: detail.map(|d| d.to_owned()),
+        ..Default::default()
+    }).collect()
+}
+
+fn owned(names: Vec<&str>) -> Vec<String> {
+    names.into_iter().map(|n| n.to_owned()).collect()
+}
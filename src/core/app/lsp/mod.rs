@@ -0,0 +1,237 @@
+mod analysis;
+mod completion;
+mod position;
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{CompletionOptions, CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, InitializeParams, Location, MarkupContent, MarkupKind, OneOf, Position, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url};
+use lsp_types::notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as LspNotification, PublishDiagnostics};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as LspRequest};
+use crate::core::app::builder::CallbackLookupTable;
+use crate::core::app::lsp::analysis::analyze;
+use crate::core::app::lsp::completion::completion;
+use crate::core::app::lsp::position::{offset_of, range_of};
+use crate::parser::diagnostics::DiagnosticsLevel;
+use crate::parser::parser::Parser;
+
+type LspResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+/// Serves the language server protocol over stdio until the client asks to exit.
+pub(crate) fn run_language_server(callback_lookup_table: Arc<Mutex<CallbackLookupTable>>) -> LspResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["@".to_owned(), "$".to_owned(), ".".to_owned()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let params: InitializeParams = serde_json::from_value(connection.initialize(serde_json::to_value(capabilities)?)?)?;
+    let root = params.workspace_folders.as_ref()
+        .and_then(|folders| folders.first())
+        .and_then(|folder| folder.uri.to_file_path().ok());
+    let server = LanguageServer {
+        connection,
+        callback_lookup_table,
+        root,
+        documents: HashMap::new(),
+        analyses: HashMap::new(),
+        enum_members: BTreeMap::new(),
+    };
+    server.run()?;
+    io_threads.join()?;
+    Ok(())
+}
+
+struct LanguageServer {
+    connection: Connection,
+    callback_lookup_table: Arc<Mutex<CallbackLookupTable>>,
+    root: Option<PathBuf>,
+    documents: HashMap<PathBuf, String>,
+    analyses: HashMap<PathBuf, Rc<Parser>>,
+    // kept from the last analysis which had any, so that completion works while typing
+    enum_members: BTreeMap<String, Vec<String>>,
+}
+
+impl LanguageServer {
+
+    fn run(mut self) -> LspResult<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            GotoDefinition::METHOD => request.extract(GotoDefinition::METHOD).map(|(_, params)| serde_json::to_value(self.definition(params))),
+            HoverRequest::METHOD => request.extract(HoverRequest::METHOD).map(|(_, params)| serde_json::to_value(self.hover(params))),
+            Completion::METHOD => request.extract(Completion::METHOD).map(|(_, params)| serde_json::to_value(self.completion(params))),
+            _ => return Response::new_err(id, ErrorCode::MethodNotFound as i32, format!("Unhandled method '{}'.", request.method)),
+        };
+        match result {
+            Ok(Ok(value)) => Response { id, result: Some(value), error: None },
+            Ok(Err(err)) => Response::new_err(id, ErrorCode::InternalError as i32, err.to_string()),
+            Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> LspResult<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                if let Some(path) = file_path(&params.text_document.uri) {
+                    self.documents.insert(path.clone(), params.text_document.text);
+                    self.analyze(&path)?;
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                if let (Some(path), Some(change)) = (file_path(&params.text_document.uri), params.content_changes.into_iter().last()) {
+                    self.documents.insert(path.clone(), change.text);
+                    self.analyze(&path)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                if let Some(path) = file_path(&params.text_document.uri) {
+                    self.documents.remove(&path);
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Analyzes the schema which the document belongs to, and publishes the diagnostics of all
+    /// of its files.
+    fn analyze(&mut self, path: &PathBuf) -> LspResult<()> {
+        let workspace_main = self.workspace_main();
+        let mut parser = None;
+        if let Some(main) = &workspace_main {
+            let workspace_parser = analyze(main, &self.documents, self.callback_lookup_table.clone());
+            if workspace_parser.sources.values().any(|s| &s.path == path) {
+                parser = Some(workspace_parser);
+            }
+        }
+        let main = if parser.is_some() { workspace_main.unwrap() } else { path.clone() };
+        let parser = parser.unwrap_or_else(|| analyze(path, &self.documents, self.callback_lookup_table.clone()));
+        let mut diagnostics: BTreeMap<PathBuf, Vec<Diagnostic>> = BTreeMap::new();
+        diagnostics.insert(main.clone(), vec![]);
+        for source in parser.sources.values() {
+            diagnostics.insert(source.path.clone(), vec![]);
+        }
+        for item in parser.diagnostics.items() {
+            let (path, range) = match (&item.source_path, &item.span) {
+                (Some(path), Some(span)) => (path.clone(), range_of(&self.content(path), *span)),
+                _ => (main.clone(), Default::default()),
+            };
+            let severity = match item.level {
+                DiagnosticsLevel::Error => DiagnosticSeverity::ERROR,
+                DiagnosticsLevel::Warning => DiagnosticSeverity::WARNING,
+            };
+            diagnostics.entry(path).or_default().push(Diagnostic {
+                range,
+                severity: Some(severity),
+                source: Some("teo".to_owned()),
+                message: item.message.clone(),
+                ..Default::default()
+            });
+        }
+        for (path, diagnostics) in diagnostics {
+            let Ok(uri) = Url::from_file_path(&path) else { continue };
+            let params = PublishDiagnosticsParams { uri, diagnostics, version: None };
+            self.connection.sender.send(Message::Notification(Notification::new(PublishDiagnostics::METHOD.to_owned(), params)))?;
+        }
+        if !parser.enums.is_empty() {
+            self.enum_members = parser.enums.iter().filter_map(|(source_id, item_id)| {
+                let r#enum = parser.get_source(*source_id).tops.get(item_id)?.as_enum()?;
+                Some((r#enum.identifier.name.clone(), r#enum.choices.iter().map(|c| c.identifier.name.clone()).collect()))
+            }).collect();
+        }
+        for source in parser.sources.values() {
+            self.analyses.remove(&source.path);
+        }
+        let parser = Rc::new(parser);
+        let paths: Vec<PathBuf> = parser.sources.values().map(|s| s.path.clone()).collect();
+        for path in paths {
+            self.analyses.insert(path, parser.clone());
+        }
+        Ok(())
+    }
+
+    fn workspace_main(&self) -> Option<PathBuf> {
+        let root = self.root.as_ref()?;
+        ["schema.teo", "src/schema.teo"].iter().find_map(|name| fs::canonicalize(root.join(name)).ok())
+    }
+
+    fn content(&self, path: &PathBuf) -> String {
+        self.documents.get(path).cloned().unwrap_or_else(|| fs::read_to_string(path).unwrap_or_default())
+    }
+
+    fn location(&self, uri: &Url, position: Position) -> Option<(PathBuf, &Parser, String, usize)> {
+        let path = file_path(uri)?;
+        let parser = self.analyses.get(&path)?.as_ref();
+        let content = self.content(&path);
+        let offset = offset_of(&content, position);
+        Some((path, parser, content, offset))
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let document = params.text_document_position_params;
+        let (path, parser, _, offset) = self.location(&document.text_document.uri, document.position)?;
+        let (target, span) = analysis::definition(parser, &path, offset)?;
+        let range = range_of(&self.content(&target), span);
+        Some(GotoDefinitionResponse::Scalar(Location::new(Url::from_file_path(&target).ok()?, range)))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let document = params.text_document_position_params;
+        let (path, parser, content, offset) = self.location(&document.text_document.uri, document.position)?;
+        let (value, span) = analysis::hover(parser, &path, offset)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }),
+            range: Some(range_of(&content, span)),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let document = params.text_document_position;
+        let path = file_path(&document.text_document.uri)?;
+        let content = self.content(&path);
+        let offset = offset_of(&content, document.position);
+        let empty_parser;
+        let parser = match self.analyses.get(&path) {
+            Some(parser) => parser.as_ref(),
+            None => {
+                empty_parser = Parser::new(self.callback_lookup_table.clone());
+                &empty_parser
+            }
+        };
+        Some(CompletionResponse::Array(completion(parser, &self.enum_members, &content, offset)))
+    }
+}
+
+fn file_path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+    Some(fs::canonicalize(&path).unwrap_or(path))
+}
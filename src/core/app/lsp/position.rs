@@ -0,0 +1,40 @@
+use lsp_types::{Position, Range};
+use crate::parser::ast::span::Span;
+
+// Spans are byte offsets while LSP positions count UTF-16 code units.
+
+pub(super) fn position_of(content: &str, offset: usize) -> Position {
+    let mut line = 0;
+    let mut character = 0;
+    for (index, c) in content.char_indices() {
+        if index >= offset { break }
+        if c == '\n' {
+            line += 1;
+            character = 0;
+        } else {
+            character += c.len_utf16() as u32;
+        }
+    }
+    Position::new(line, character)
+}
+
+pub(super) fn offset_of(content: &str, position: Position) -> usize {
+    let mut line = 0;
+    let mut character = 0;
+    for (index, c) in content.char_indices() {
+        if line == position.line && (character >= position.character || c == '\n') {
+            return index
+        }
+        if c == '\n' {
+            line += 1;
+            character = 0;
+        } else {
+            character += c.len_utf16() as u32;
+        }
+    }
+    content.len()
+}
+
+pub(super) fn range_of(content: &str, span: Span) -> Range {
+    Range::new(position_of(content, span.start), position_of(content, span.end))
+}
@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use actix_web::{HttpRequest, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+use crate::core::action::Action;
+use crate::core::model::Model;
+use crate::core::object::Object;
+use crate::core::teon::Value;
+
+/// The action request seen by a middleware.
+pub struct MiddlewareContext {
+    request: HttpRequest,
+    model: Model,
+    action: Action,
+    identity: Option<Object>,
+    body: Value,
+}
+
+impl MiddlewareContext {
+
+    pub(crate) fn new(request: HttpRequest, model: Model, action: Action, identity: Option<Object>, body: Value) -> Self {
+        Self { request, model, action, identity, body }
+    }
+
+    pub fn request(&self) -> &HttpRequest {
+        &self.request
+    }
+
+    pub fn model_name(&self) -> &str {
+        self.model.name()
+    }
+
+    /// The action name as it appears in the URL, e.g. `findMany`.
+    pub fn action_name(&self) -> &'static str {
+        self.action.as_handler_str()
+    }
+
+    pub fn identity(&self) -> Option<&Object> {
+        self.identity.as_ref()
+    }
+
+    /// The decoded request body.
+    pub fn body(&self) -> &Value {
+        &self.body
+    }
+
+    /// The decoded request body. Changes are seen by later middlewares and the action.
+    pub fn body_mut(&mut self) -> &mut Value {
+        &mut self.body
+    }
+
+    pub(crate) fn into_body(self) -> Value {
+        self.body
+    }
+}
+
+pub trait Middleware: Send + Sync {
+    fn call(&self, ctx: MiddlewareContext, next: Next) -> LocalBoxFuture<'static, HttpResponse>;
+}
+
+impl<F, Fut> Middleware for F where
+F: Fn(MiddlewareContext, Next) -> Fut + Sync + Send,
+Fut: Future<Output = HttpResponse> + 'static {
+    fn call(&self, ctx: MiddlewareContext, next: Next) -> LocalBoxFuture<'static, HttpResponse> {
+        Box::pin(self(ctx, next))
+    }
+}
+
+/// The rest of the middleware chain, ending with the action itself.
+pub struct Next {
+    middlewares: &'static [Arc<dyn Middleware>],
+    handler: Rc<dyn Fn(MiddlewareContext) -> LocalBoxFuture<'static, HttpResponse>>,
+}
+
+impl Next {
+
+    pub(crate) fn new(middlewares: &'static [Arc<dyn Middleware>], handler: Rc<dyn Fn(MiddlewareContext) -> LocalBoxFuture<'static, HttpResponse>>) -> Self {
+        Self { middlewares, handler }
+    }
+
+    pub async fn run(self, ctx: MiddlewareContext) -> HttpResponse {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => {
+                middleware.call(ctx, Next::new(rest, self.handler)).await
+            }
+            None => (self.handler)(ctx).await,
+        }
+    }
+}
@@ -1,8 +1,82 @@
+pub(crate) mod versioned;
+
+use itertools::Itertools;
+use crate::core::app::command::{MigrateCommand, MigrateSubcommand};
+use crate::core::app::migrate::versioned::{generate_migration, has_versioned_migrations, migrate_down, migrate_status, migrate_up};
+use crate::core::error::Error;
+use crate::core::result::Result;
 use crate::prelude::{Graph};
 
-pub(crate) async fn migrate(graph: &mut Graph, _dry_run: bool) {
-    let result = graph.connector_mut().migrate(graph.models(), false).await;
-    if result.is_err() {
-        panic!("Migration error");
+/// Migrates the database of every connector to the models it stores.
+pub(crate) async fn migrate(graph: &Graph, dry_run: bool) -> Result<()> {
+    let names: Vec<String> = graph.connector_names().iter().map(|n| n.to_string()).collect();
+    for name in names.iter() {
+        if dry_run && names.len() > 1 {
+            println!("-- connector '{}'", name);
+        }
+        migrate_connector(graph, name, dry_run).await?;
+    }
+    Ok(())
+}
+
+/// Migrates on server start. Connectors with versioned migrations are left to `migrate up'.
+pub(crate) async fn auto_migrate(graph: &Graph) -> Result<()> {
+    let names: Vec<String> = graph.connector_names().iter().map(|n| n.to_string()).collect();
+    for name in names.iter() {
+        if has_versioned_migrations(graph, name) { continue }
+        migrate_connector(graph, name, false).await?;
+    }
+    Ok(())
+}
+
+async fn migrate_connector(graph: &Graph, connector: &str, dry_run: bool) -> Result<()> {
+    let models = graph.models_on(connector);
+    let result = graph.connector_named(connector)?.migrate(&models, graph.enums(), false, dry_run).await;
+    match result {
+        Ok(statements) => {
+            if dry_run {
+                for statement in statements {
+                    println!("{}", statement);
+                }
+            }
+            Ok(())
+        }
+        Err(err) => {
+            print_migration_error(&err);
+            Err(err)
+        }
+    }
+}
+
+pub(crate) async fn run_migrate_command(graph: &Graph, command: &MigrateCommand) -> Result<()> {
+    if let Some(connector) = &command.connector {
+        if !graph.connector_names().contains(&connector.as_str()) {
+            let err = Error::migration_failed(format!("Connector `{}' is not defined.", connector));
+            print_migration_error(&err);
+            return Err(err);
+        }
+    }
+    // versioned migrations are run on one connector at a time
+    let connector = command.connector.clone().unwrap_or(graph.default_connector_name().to_owned());
+    let result = match &command.subcommand {
+        None if command.connector.is_some() => return migrate_connector(graph, &connector, command.dry).await,
+        None => return migrate(graph, command.dry).await,
+        Some(MigrateSubcommand::Generate(generate_command)) => generate_migration(graph, &connector, generate_command.name.as_deref()).await,
+        Some(MigrateSubcommand::Up) => migrate_up(graph, &connector).await,
+        Some(MigrateSubcommand::Down) => migrate_down(graph, &connector).await,
+        Some(MigrateSubcommand::Status) => migrate_status(graph, &connector).await,
+    };
+    if let Err(err) = &result {
+        print_migration_error(err);
+    }
+    result
+}
+
+fn print_migration_error(err: &Error) {
+    eprintln!("{}", err.message);
+    if let Some(errors) = &err.errors {
+        for (name, reason) in errors.iter().sorted() {
+            eprintln!("  {}: {}", name, reason);
+        }
     }
 }
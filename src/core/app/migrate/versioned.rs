@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use inflector::Inflector;
+use itertools::Itertools;
+use crate::core::error::Error;
+use crate::core::model::Model;
+use crate::core::result::Result;
+use crate::prelude::Graph;
+
+const MIGRATIONS_DIR: &str = "migrations";
+const UP_FILE: &str = "up.sql";
+/// Down migrations are not generated, `migrate down' runs what is written into this file by hand.
+const DOWN_FILE: &str = "down.sql";
+
+/// Migrations of the default connector are kept in the migrations directory, the ones of other
+/// connectors in subdirectories named after them.
+fn migrations_dir(graph: &Graph, connector: &str) -> PathBuf {
+    let dir = std::env::current_dir().unwrap().join(MIGRATIONS_DIR);
+    if connector == graph.default_connector_name() { dir } else { dir.join(connector) }
+}
+
+pub(crate) fn has_versioned_migrations(graph: &Graph, connector: &str) -> bool {
+    migration_names(&migrations_dir(graph, connector)).map_or(true, |names| !names.is_empty())
+}
+
+/// Migration names on disk, sorted by their timestamp prefixes.
+fn migration_names(dir: &Path) -> Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let entries = fs::read_dir(dir).map_err(|err| Error::migration_failed(err.to_string()))?;
+    let mut names: Vec<String> = entries.filter_map(|entry| entry.ok()).filter(|entry| {
+        entry.path().join(UP_FILE).exists()
+    }).map(|entry| entry.file_name().to_string_lossy().to_string()).collect();
+    names.sort();
+    Ok(names)
+}
+
+fn read_migration_file(dir: &Path, name: &str, file: &str) -> Result<String> {
+    let path = dir.join(name).join(file);
+    fs::read_to_string(&path).map_err(|err| Error::migration_failed(format!("Cannot read `{}': {}", path.display(), err)))
+}
+
+fn has_statements(sql: &str) -> bool {
+    sql.lines().any(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with("--")
+    })
+}
+
+/// Versions declared with `@migration(version:)` which no migration on disk is named after yet.
+fn declared_versions(models: &Vec<Model>, names: &Vec<String>) -> Vec<String> {
+    let model_versions = models.iter().filter_map(|m| m.migration().and_then(|m| m.version.as_ref()));
+    let field_versions = models.iter().flat_map(|m| m.fields().iter().filter_map(|f| f.migration().and_then(|m| m.version.as_ref())));
+    model_versions.chain(field_versions)
+        .map(|v| v.to_snake_case())
+        .filter(|v| !names.iter().any(|n| n.ends_with(&format!("_{}", v))))
+        .unique()
+        .sorted()
+        .collect()
+}
+
+fn write_migration_file(dir: &Path, file: &str, content: String) -> Result<()> {
+    fs::write(dir.join(file), content).map_err(|err| Error::migration_failed(err.to_string()))
+}
+
+pub(crate) async fn generate_migration(graph: &Graph, connector: &str, name: Option<&str>) -> Result<()> {
+    let migrations_dir = migrations_dir(graph, connector);
+    let applied = graph.connector_named(connector)?.applied_migrations().await?;
+    let names = migration_names(&migrations_dir)?;
+    if names.iter().any(|n| !applied.contains(n)) {
+        return Err(Error::migration_failed("There are pending migrations. Run `migrate up' before generating a new one."));
+    }
+    let models = graph.models_on(connector);
+    let statements = graph.connector_named(connector)?.migrate(&models, graph.enums(), false, true).await?;
+    if statements.is_empty() {
+        println!("No schema changes.");
+        return Ok(());
+    }
+    let timestamp = Utc::now().format("%Y%m%d%H%M%S").to_string();
+    let versions = declared_versions(&models, &names);
+    let migration_name = match name {
+        Some(name) => format!("{}_{}", timestamp, name.to_snake_case()),
+        None if !versions.is_empty() => format!("{}_{}", timestamp, versions.join("_")),
+        None => timestamp,
+    };
+    let dir = migrations_dir.join(&migration_name);
+    fs::create_dir_all(&dir).map_err(|err| Error::migration_failed(err.to_string()))?;
+    let up = statements.iter().map(|s| if s.ends_with(';') { s.clone() } else { format!("{};", s) }).collect::<Vec<String>>().join("\n");
+    write_migration_file(&dir, UP_FILE, format!("{}\n", up))?;
+    write_migration_file(&dir, DOWN_FILE, format!("-- Down migrations are not generated. Write statements which revert `{}' here,\n-- `migrate down' runs them.\n", migration_name))?;
+    println!("Created migration `{}'.", migration_name);
+    Ok(())
+}
+
+pub(crate) async fn migrate_up(graph: &Graph, connector: &str) -> Result<()> {
+    let migrations_dir = migrations_dir(graph, connector);
+    let applied = graph.connector_named(connector)?.applied_migrations().await?;
+    let mut count = 0;
+    for name in migration_names(&migrations_dir)? {
+        if applied.contains(&name) { continue }
+        let sql = read_migration_file(&migrations_dir, &name, UP_FILE)?;
+        graph.connector_named(connector)?.apply_migration(&name, &sql).await?;
+        println!("Applied `{}'.", name);
+        count += 1;
+    }
+    if count == 0 {
+        println!("No pending migrations.");
+    }
+    Ok(())
+}
+
+pub(crate) async fn migrate_down(graph: &Graph, connector: &str) -> Result<()> {
+    let migrations_dir = migrations_dir(graph, connector);
+    let applied = graph.connector_named(connector)?.applied_migrations().await?;
+    let name = match applied.last() {
+        Some(name) => name,
+        None => {
+            println!("No applied migrations.");
+            return Ok(());
+        }
+    };
+    let sql = read_migration_file(&migrations_dir, name, DOWN_FILE)?;
+    if !has_statements(&sql) {
+        return Err(Error::migration_failed(format!("Migration `{}' has no down statements. Write them into its {} by hand.", name, DOWN_FILE)));
+    }
+    graph.connector_named(connector)?.revert_migration(name, &sql).await?;
+    println!("Reverted `{}'.", name);
+    Ok(())
+}
+
+pub(crate) async fn migrate_status(graph: &Graph, connector: &str) -> Result<()> {
+    let applied = graph.connector_named(connector)?.applied_migrations().await?;
+    let names = migration_names(&migrations_dir(graph, connector))?;
+    for name in &names {
+        if applied.contains(name) {
+            println!("[applied] {}", name);
+        } else {
+            println!("[pending] {}", name);
+        }
+    }
+    for name in &applied {
+        if !names.contains(name) {
+            println!("[missing] {}", name);
+        }
+    }
+    Ok(())
+}
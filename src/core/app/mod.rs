@@ -1,18 +1,25 @@
 pub mod builder;
 pub mod environment;
 pub mod entrance;
+pub mod middleware;
+pub mod route;
 pub(crate) mod conf;
 pub(crate) mod serve;
 pub(crate) mod command;
 pub(crate) mod migrate;
+pub(crate) mod logger;
+pub(crate) mod lsp;
+pub(crate) mod fmt;
+pub(crate) mod dotenv;
 
 use std::sync::Arc;
-use to_mut::ToMut;
 use crate::core::app::command::{CLI, CLICommand, GenerateCommand};
 use crate::core::app::conf::{ClientGeneratorConf, EntityGeneratorConf, ServerConf};
 use crate::core::app::entrance::Entrance;
 use crate::core::app::environment::EnvironmentVersion;
-use crate::core::app::migrate::migrate;
+use crate::core::app::migrate::run_migrate_command;
+use crate::core::app::middleware::Middleware;
+use crate::core::app::route::RouteTable;
 use crate::core::app::serve::serve;
 use crate::core::graph::Graph;
 use crate::generator::client::generate_client;
@@ -23,6 +30,8 @@ pub struct App {
     server_conf: ServerConf,
     entity_generator_confs: Vec<EntityGeneratorConf>,
     client_generator_confs: Vec<ClientGeneratorConf>,
+    route_table: RouteTable,
+    middlewares: Vec<Arc<dyn Middleware>>,
     environment_version: EnvironmentVersion,
     entrance: Entrance,
     args: Arc<CLI>,
@@ -35,6 +44,8 @@ impl App {
                 serve(
                     self.graph.clone(),
                     self.server_conf.clone(),
+                    self.route_table.clone(),
+                    self.middlewares.clone(),
                     self.environment_version.clone(),
                     self.entrance.clone(),
                     serve_command.no_migration,
@@ -83,8 +94,12 @@ impl App {
                 }
             }
             CLICommand::Migrate(migrate_command) => {
-                migrate(self.graph.to_mut(), migrate_command.dry).await;
+                if run_migrate_command(&self.graph, migrate_command).await.is_err() {
+                    std::process::exit(1);
+                }
             }
+            // these are handled while the app is being built
+            CLICommand::Check | CLICommand::Lsp | CLICommand::Fmt(_) => (),
         }
         Ok(())
     }
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use futures_util::future::BoxFuture;
+use itertools::Itertools;
+use serde_json::{Value as JsonValue};
+use crate::core::graph::Graph;
+use crate::core::object::Object;
+use crate::core::result::Result;
+use crate::core::teon::Value;
+
+/// The request passed to a custom route or a custom model action.
+pub struct RouteContext {
+    graph: Graph,
+    identity: Option<Object>,
+    body: JsonValue,
+    params: HashMap<String, String>,
+}
+
+impl RouteContext {
+
+    pub(crate) fn new(graph: Graph, identity: Option<Object>, body: JsonValue, params: HashMap<String, String>) -> Self {
+        Self { graph, identity, body, params }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn identity(&self) -> Option<&Object> {
+        self.identity.as_ref()
+    }
+
+    /// The JSON request body. This is `null` when the request has no body.
+    pub fn body(&self) -> &JsonValue {
+        &self.body
+    }
+
+    /// A path parameter declared with `:name` in the route path.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|p| p.as_str())
+    }
+}
+
+pub trait RouteHandler: Send + Sync {
+    fn call(&self, ctx: RouteContext) -> BoxFuture<'static, Result<Value>>;
+}
+
+impl<F, Fut> RouteHandler for F where
+F: Fn(RouteContext) -> Fut + Sync + Send,
+Fut: Future<Output = Result<Value>> + Send + 'static {
+    fn call(&self, ctx: RouteContext) -> BoxFuture<'static, Result<Value>> {
+        Box::pin(self(ctx))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Route {
+    method: String,
+    segments: Vec<String>,
+    pub(crate) handler: Arc<dyn RouteHandler>,
+}
+
+impl Route {
+
+    fn new(method: &str, path: &str, handler: Arc<dyn RouteHandler>) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            segments: path.split("/").filter(|s| !s.is_empty()).map(|s| s.to_owned()).collect(),
+            handler,
+        }
+    }
+
+    fn params(&self, path: &str) -> Option<HashMap<String, String>> {
+        let components: Vec<&str> = path.split("/").filter(|s| !s.is_empty()).collect();
+        if components.len() != self.segments.len() {
+            return None;
+        }
+        let mut params = HashMap::new();
+        for (segment, component) in self.segments.iter().zip(components) {
+            if let Some(name) = segment.strip_prefix(":") {
+                params.insert(name.to_owned(), component.to_owned());
+            } else if segment != component {
+                return None;
+            }
+        }
+        Some(params)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct RouteTable {
+    routes: Vec<Route>,
+    model_actions: HashMap<(String, String), Arc<dyn RouteHandler>>,
+}
+
+impl RouteTable {
+
+    pub(crate) fn new() -> Self {
+        Self { routes: vec![], model_actions: HashMap::new() }
+    }
+
+    pub(crate) fn add_route(&mut self, method: &str, path: &str, handler: Arc<dyn RouteHandler>) {
+        self.routes.push(Route::new(method, path, handler));
+    }
+
+    pub(crate) fn add_model_action(&mut self, model: &str, name: &str, handler: Arc<dyn RouteHandler>) {
+        self.model_actions.insert((model.to_owned(), name.to_owned()), handler);
+    }
+
+    pub(crate) fn model_actions(&self) -> impl Iterator<Item = &(String, String)> {
+        self.model_actions.keys()
+    }
+
+    pub(crate) fn find_route(&self, method: &str, path: &str) -> Option<(&Route, HashMap<String, String>)> {
+        self.routes.iter().filter(|r| r.method == method).find_map(|r| r.params(path).map(|p| (r, p)))
+    }
+
+    pub(crate) fn methods(&self) -> Vec<&str> {
+        self.routes.iter().map(|r| r.method.as_str()).unique().collect()
+    }
+
+    pub(crate) fn has_route_path(&self, path: &str) -> bool {
+        self.routes.iter().any(|r| r.params(path).is_some())
+    }
+
+    pub(crate) fn find_model_action(&self, model: &str, name: &str) -> Option<&Arc<dyn RouteHandler>> {
+        self.model_actions.get(&(model.to_owned(), name.to_owned()))
+    }
+}
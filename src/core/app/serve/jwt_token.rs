@@ -1,28 +1,162 @@
-use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
+use chrono::Utc;
+use jsonwebtoken::{encode, decode, Algorithm, Header, Validation, EncodingKey, DecodingKey};
+use jsonwebtoken::errors::ErrorKind;
 use serde::{Serialize, Deserialize};
 use serde_json::{Value as JsonValue};
+use uuid::Uuid;
+use crate::core::app::conf::{JwtAlgorithm, ServerConf};
+use crate::core::action::{Action, CREATE, FIND, INTERNAL_POSITION, MANY, PROGRAM_CODE, SINGLE};
+use crate::core::action::source::ActionSource;
+use crate::core::database::name::DatabaseName;
 use crate::core::error::Error;
+use crate::core::field::{Field, FieldIndex, IndexSettings};
+use crate::core::field::r#type::FieldType;
+use crate::core::graph::Graph;
+use crate::core::model::builder::ModelBuilder;
+use crate::prelude::Value;
+use crate::teon;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub id: JsonValue,
     pub model: String,
-    pub exp: usize
+    pub exp: usize,
+    #[serde(default)]
+    pub iat: usize,
+    #[serde(default)]
+    pub jti: String,
+    #[serde(default)]
+    pub refresh: bool,
 }
 
-pub fn encode_token(claims: Claims, secret: &str) -> String {
-    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_ref()));
-    token.unwrap()
+impl Claims {
+
+    pub(crate) fn new(id: JsonValue, model: impl Into<String>, expires_in: i64, refresh: bool) -> Self {
+        let now = Utc::now().timestamp();
+        Self {
+            id,
+            model: model.into(),
+            exp: (now + expires_in) as usize,
+            iat: now as usize,
+            jti: Uuid::new_v4().to_string(),
+            refresh,
+        }
+    }
+
+    // tokens issued before `jti` existed are identified by their content
+    fn revocation_key(&self) -> String {
+        if self.jti.is_empty() {
+            format!("{}:{}:{}", self.model, self.id, self.exp)
+        } else {
+            self.jti.clone()
+        }
+    }
 }
 
-pub fn decode_token(token: &String, secret: &str) -> Result<Claims, Error> {
-    let token = decode::<Claims>(&token, &DecodingKey::from_secret(secret.as_ref()), &Validation::default());
-    return match token {
-        Ok(token) => {
-            Ok(token.claims)
+/// The model which revoked tokens are stored into, so that every server process sees them.
+pub(crate) const REVOKED_TOKEN_MODEL: &str = "TeoRevokedToken";
+
+pub(crate) const REVOKED_TOKEN_TABLE: &str = "teo_revoked_tokens";
+
+pub(crate) fn install_revoked_token_model(model: &mut ModelBuilder, provider: DatabaseName) {
+    model.internal().table_name(REVOKED_TOKEN_TABLE);
+    let mut key = Field::new("key".to_owned());
+    key.field_type = Some(FieldType::String);
+    key.set_required();
+    key.primary = true;
+    key.index = Some(FieldIndex::Primary(IndexSettings::default()));
+    if matches!(provider, DatabaseName::MongoDB) {
+        key.column_name = Some("_id".to_owned());
+    }
+    model.field(key);
+    let mut expires_at = Field::new("expiresAt".to_owned());
+    expires_at.field_type = Some(FieldType::I64);
+    expires_at.set_required();
+    model.field(expires_at);
+}
+
+/// Revoked tokens are kept until they would have expired anyway.
+pub(crate) async fn revoke_token(graph: &Graph, claims: &Claims) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+    let expired = graph.find_many_internal(REVOKED_TOKEN_MODEL, &teon!({"where": {"expiresAt": {"lte": now}}}), true, Action::from_u32(PROGRAM_CODE | FIND | MANY), ActionSource::ProgramCode).await?;
+    for token in expired {
+        token.delete().await?;
+    }
+    let token = graph.new_object(REVOKED_TOKEN_MODEL, Action::from_u32(PROGRAM_CODE | CREATE | SINGLE | INTERNAL_POSITION), ActionSource::ProgramCode)?;
+    token.set_value("key", Value::String(claims.revocation_key()))?;
+    token.set_value("expiresAt", Value::I64(claims.exp as i64))?;
+    token.save().await
+}
+
+async fn is_token_revoked(graph: &Graph, claims: &Claims) -> Result<bool, Error> {
+    let count = graph.count(REVOKED_TOKEN_MODEL, &teon!({"where": {"key": claims.revocation_key()}})).await?;
+    Ok(count > 0)
+}
+
+pub(crate) struct JwtStrategy {
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+}
+
+impl JwtStrategy {
+
+    /// Returns `None` when the server config doesn't enable authentication.
+    pub(crate) fn from_conf(conf: &ServerConf) -> Result<Option<Self>, Error> {
+        match conf.jwt_algorithm {
+            JwtAlgorithm::HS256 => {
+                match &conf.jwt_secret {
+                    Some(secret) => Ok(Some(Self {
+                        algorithm: Algorithm::HS256,
+                        encoding_key: EncodingKey::from_secret(secret.as_ref()),
+                        decoding_key: DecodingKey::from_secret(secret.as_ref()),
+                    })),
+                    None => Ok(None),
+                }
+            }
+            JwtAlgorithm::RS256 | JwtAlgorithm::ES256 => {
+                let private_key = match &conf.jwt_private_key {
+                    Some(key) => key.as_bytes(),
+                    None => return Err(Error::internal_server_error("Missing JWT private key.")),
+                };
+                let public_key = match &conf.jwt_public_key {
+                    Some(key) => key.as_bytes(),
+                    None => return Err(Error::internal_server_error("Missing JWT public key.")),
+                };
+                let (algorithm, encoding_key, decoding_key) = if conf.jwt_algorithm == JwtAlgorithm::RS256 {
+                    (Algorithm::RS256, EncodingKey::from_rsa_pem(private_key), DecodingKey::from_rsa_pem(public_key))
+                } else {
+                    (Algorithm::ES256, EncodingKey::from_ec_pem(private_key), DecodingKey::from_ec_pem(public_key))
+                };
+                let encoding_key = match encoding_key {
+                    Ok(key) => key,
+                    Err(_) => return Err(Error::internal_server_error("Invalid JWT private key.")),
+                };
+                let decoding_key = match decoding_key {
+                    Ok(key) => key,
+                    Err(_) => return Err(Error::internal_server_error("Invalid JWT public key.")),
+                };
+                Ok(Some(Self { algorithm, encoding_key, decoding_key }))
+            }
         }
-        Err(_) => {
-            Err(Error::invalid_auth_token())
+    }
+
+    pub(crate) fn encode_token(&self, claims: &Claims) -> String {
+        encode(&Header::new(self.algorithm), claims, &self.encoding_key).unwrap()
+    }
+
+    pub(crate) async fn decode_token(&self, graph: &Graph, token: &str) -> Result<Claims, Error> {
+        match decode::<Claims>(token, &self.decoding_key, &Validation::new(self.algorithm)) {
+            Ok(data) => if is_token_revoked(graph, &data.claims).await? {
+                Err(Error::revoked_auth_token())
+            } else {
+                Ok(data.claims)
+            },
+            Err(err) => Err(match err.kind() {
+                ErrorKind::ExpiredSignature => Error::expired_auth_token(),
+                ErrorKind::InvalidToken | ErrorKind::Base64(_) | ErrorKind::Json(_) | ErrorKind::Utf8(_) => Error::malformed_auth_token(),
+                _ => Error::invalid_auth_token(),
+            })
         }
     }
 }
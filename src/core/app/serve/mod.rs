@@ -1,35 +1,42 @@
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::Arc;
 use futures_util::future;
-use std::time::SystemTime;
+use uuid::Uuid;
 use actix_http::body::BoxBody;
 use actix_http::{Method};
+use actix_http::header::{self, HeaderName, HeaderValue};
 use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
-use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::dev::{Service, ServiceFactory, ServiceRequest, ServiceResponse};
 use actix_web::middleware::DefaultHeaders;
-use chrono::{DateTime, Duration, Local, Utc};
+use chrono::{DateTime, Local};
 use colored::Colorize;
 use futures_util::StreamExt;
 use key_path::{KeyPath, path};
 use serde_json::{json, Value as JsonValue};
-use to_mut::ToMut;
 use crate::core::action::{
-    Action, CREATE, DELETE, ENTRY, FIND, IDENTITY, MANY, SINGLE, UPDATE, UPSERT,
+    Action, CREATE, DELETE, ENTRY, FIND, IDENTITY, MANY, SINGLE, UPDATE, UPSERT, RESTORE, HISTORY, PROGRAM_CODE,
     FIND_UNIQUE_HANDLER, FIND_FIRST_HANDLER, FIND_MANY_HANDLER, CREATE_HANDLER, UPDATE_HANDLER,
     UPSERT_HANDLER, DELETE_HANDLER, CREATE_MANY_HANDLER, UPDATE_MANY_HANDLER, DELETE_MANY_HANDLER,
     COUNT_HANDLER, AGGREGATE_HANDLER, GROUP_BY_HANDLER, SIGN_IN_HANDLER, IDENTITY_HANDLER,
+    REFRESH_TOKEN_HANDLER, SIGN_OUT_HANDLER, RESTORE_HANDLER, HISTORY_HANDLER,
 };
 use crate::core::action::source::ActionSource;
-use crate::core::app::conf::ServerConf;
+use crate::core::audit::{AUDIT_LOG_MODEL, object_record_id, record_id};
+use crate::core::app::conf::{LogLevel, ServerConf};
+use crate::core::app::logger::{self, log_message, log_request, setup_logger, RequestLog};
 use crate::core::app::entrance::Entrance;
 use crate::core::app::environment::EnvironmentVersion;
-use crate::core::app::migrate::migrate;
+use crate::core::app::migrate::auto_migrate;
+use crate::core::app::middleware::{Middleware, MiddlewareContext, Next};
+use crate::core::app::route::{RouteContext, RouteHandler, RouteTable};
 use crate::core::connector::SaveSession;
-use self::jwt_token::{Claims, decode_token, encode_token};
+use self::jwt_token::{Claims, JwtStrategy, revoke_token};
 use crate::core::graph::Graph;
 use crate::core::model::Model;
 use crate::core::object::Object;
 use crate::core::pipeline::ctx::{Ctx};
-use crate::core::error::Error;
+use crate::core::error::{Error, ErrorType};
 use crate::core::teon::decoder::Decoder;
 use crate::prelude::Value;
 use crate::teon;
@@ -55,68 +62,112 @@ fn path_components(path: &str) -> Vec<&str> {
     retval
 }
 
-fn log_unhandled(start: SystemTime, method: &str, path: &str, code: u16) {
-    let now = SystemTime::now();
-    let local: DateTime<Local> = Local::now();
-    let code_string = match code {
-        0..=199 => code.to_string().purple().bold(),
-        200..=299 => code.to_string().green().bold(),
-        300..=399 => code.to_string().yellow().bold(),
-        _ => code.to_string().red().bold(),
+async fn read_body(r: &HttpRequest, payload: &mut web::Payload, conf: &ServerConf) -> Result<web::BytesMut, Error> {
+    let content_length = r.headers().get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if content_length.unwrap_or(0) > conf.max_body_size {
+        return Err(Error::payload_too_large(conf.max_body_size));
+    }
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.unwrap();
+        // limit max size of in-memory payload
+        if (body.len() + chunk.len()) > conf.max_body_size {
+            return Err(Error::payload_too_large(conf.max_body_size));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+async fn handle_custom(handler: &Arc<dyn RouteHandler>, r: &HttpRequest, payload: &mut web::Payload, graph: &Graph, conf: &ServerConf, jwt: Option<&JwtStrategy>, params: HashMap<String, String>, log: &mut RequestLog) -> HttpResponse {
+    let body = match read_body(r, payload, conf).await {
+        Ok(body) => body,
+        Err(err) => return err.into(),
     };
-    let elapsed = now.duration_since(start).unwrap();
-    let ms = elapsed.as_millis();
-    let ms_str = format!("{ms}ms").dimmed();
-    let local_formatted = format!("{local}").dimmed();
-    let unhandled = "Unhandled".red();
-    println!("{} {} {} on {} - {} {}", local_formatted, unhandled, method.bold(), path, code_string, ms_str);
-}
-
-fn log_request(start: SystemTime, action: &str, model: &str, code: u16) {
-    let now = SystemTime::now();
-    let local: DateTime<Local> = Local::now();
-    let code_string = match code {
-        0..=199 => code.to_string().purple().bold(),
-        200..=299 => code.to_string().green().bold(),
-        300..=399 => code.to_string().yellow().bold(),
-        _ => code.to_string().red().bold(),
+    let parsed_body = if body.is_empty() {
+        JsonValue::Null
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(b) => b,
+            Err(_) => return Error::incorrect_json_format().into(),
+        }
     };
-    let elapsed = now.duration_since(start).unwrap();
-    let ms = elapsed.as_millis();
-    let ms_str = format!("{ms}ms").normal().clear();
-    let local_formatted = format!("{local}").dimmed();
-    println!("{} {} on {} - {} {}", local_formatted, action.bold(), model, code_string, ms_str.dimmed());
+    let identity = match get_identity(r, graph, jwt).await {
+        Ok(identity) => identity.map(|(identity, claims)| {
+            log.identity = Some(claims.id);
+            identity
+        }),
+        Err(err) => return err.into(),
+    };
+    let ctx = RouteContext::new(graph.clone(), identity, parsed_body, params);
+    match handler.call(ctx).await {
+        Ok(value) => HttpResponse::Ok().json(json!({
+            "data": j(value)
+        })),
+        Err(err) => err.into(),
+    }
 }
 
-async fn get_identity(r: &HttpRequest, graph: &Graph, conf: &ServerConf) -> Result<Option<Object>, Error> {
+async fn get_identity(r: &HttpRequest, graph: &Graph, jwt: Option<&JwtStrategy>) -> Result<Option<(Object, Claims)>, Error> {
     let header_value = r.headers().get("authorization");
     if let None = header_value {
         return Ok(None);
     }
-    let auth_str = header_value.unwrap().to_str().unwrap();
-    if auth_str.len() < 7 {
-        return Err(Error::invalid_auth_token());
-    }
-    let token_str = &auth_str[7..];
-    let claims_result = decode_token(&token_str.to_string(), &conf.jwt_secret.as_ref().unwrap());
-    if let Err(_) = claims_result {
+    let auth_str = match header_value.unwrap().to_str() {
+        Ok(auth_str) => auth_str,
+        Err(_) => return Err(Error::malformed_auth_token()),
+    };
+    let token_str = match auth_str.strip_prefix("Bearer ") {
+        Some(token_str) => token_str.trim(),
+        None => return Err(Error::malformed_auth_token()),
+    };
+    let jwt = match jwt {
+        Some(jwt) => jwt,
+        None => return Err(Error::internal_server_error("Missing JWT secret.")),
+    };
+    let claims = jwt.decode_token(graph, token_str).await?;
+    if claims.refresh {
         return Err(Error::invalid_auth_token());
     }
-    let claims = claims_result.unwrap();
-    let json_identifier = claims.id;
-    let tson_identifier = Decoder::decode_object(graph.model(&claims.model).unwrap(), graph, &json_identifier)?;
-    // Decoder::
-    let _model = graph.model(claims.model.as_str()).unwrap();
+    let identity = find_identity(graph, &claims).await?;
+    return Ok(Some((identity, claims)));
+}
+
+async fn find_identity(graph: &Graph, claims: &Claims) -> Result<Object, Error> {
+    let model = match graph.model(&claims.model) {
+        Some(model) => model,
+        None => return Err(Error::invalid_auth_token()),
+    };
+    let tson_identifier = Decoder::decode_object(model, graph, &claims.id)?;
     let identity = graph.find_unique_internal(
-        graph.model(claims.model.as_str()).unwrap().name(),
+        model.name(),
         &teon!({
             "where": tson_identifier
         }),
         true, Action::from_u32(IDENTITY | FIND | SINGLE | ENTRY), ActionSource::ProgramCode).await;
-    if let Err(_) = identity {
-        return Err(Error::invalid_auth_token())
+    match identity {
+        Ok(identity) => Ok(identity),
+        Err(_) => Err(Error::invalid_auth_token()),
+    }
+}
+
+fn token_meta(obj: &Object, conf: &ServerConf, jwt: &JwtStrategy) -> JsonValue {
+    let json_identifier: JsonValue = obj.identifier().into();
+    let model_name = obj.model().name();
+    let token = jwt.encode_token(&Claims::new(json_identifier.clone(), model_name, conf.jwt_expires_in, false));
+    if let Some(refresh_token_expires_in) = conf.refresh_token_expires_in {
+        let refresh_token = jwt.encode_token(&Claims::new(json_identifier, model_name, refresh_token_expires_in, true));
+        json!({
+            "token": token,
+            "refreshToken": refresh_token
+        })
+    } else {
+        json!({
+            "token": token
+        })
     }
-    return Ok(Some(identity.unwrap()));
 }
 
 async fn handle_find_unique(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
@@ -218,8 +269,9 @@ async fn handle_create(graph: &Graph, input: &Value, model: &Model, source: Acti
     let create = input.get("create");
     let include = input.get("include");
     let select = input.get("select");
-    let session = graph.connector().new_save_session();
-    let result = handle_create_internal(graph, create, include, select, model, &path!["create"], action, source, session).await;
+    let result = graph.transaction_on(model.connector_name(), |graph| async move {
+        handle_create_internal(&graph, create, include, select, model, &path!["create"], action, source, graph.new_save_session(model)).await
+    }).await;
     match result {
         Ok(val) => {
             let json_val: JsonValue = val.into();
@@ -240,95 +292,67 @@ async fn handle_update_internal(_graph: &Graph, object: Object, update: Option<&
 
 async fn handle_update(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
     let action = Action::from_u32(UPDATE | ENTRY | SINGLE);
-    let result = graph.find_unique_internal(model.name(), input, true, action, source).await;
-    if result.is_err() {
-        return HttpResponse::NotFound().json(json!({"error": result.err()}));
-    }
-    let result = result.unwrap();
     let update = input.get("update");
     let include = input.get("include");
     let select = input.get("select");
     let r#where = input.get("where");
-    let update_result = handle_update_internal(graph, result.clone(), update, include, select, r#where, model).await;
+    let update_result = graph.transaction_on(model.connector_name(), |graph| async move {
+        let object = graph.find_unique_internal(model.name(), input, true, action, source).await?;
+        handle_update_internal(&graph, object, update, include, select, r#where, model).await
+    }).await;
     match update_result {
         Ok(value) => {
             let json_val: JsonValue = value.into();
             HttpResponse::Ok().json(json!({"data": json_val}))
         }
+        Err(err) if err.r#type == ErrorType::ObjectNotFound => {
+            HttpResponse::NotFound().json(json!({"error": err}))
+        }
+        Err(err) if err.r#type == ErrorType::VersionConflict => err.into(),
         Err(err) => {
             HttpResponse::BadRequest().json(json!({"error": err}))
         }
     }
 }
 
-async fn handle_upsert(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+async fn handle_upsert_internal(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> Result<Value, Error> {
     let action = Action::from_u32(UPSERT | UPDATE | ENTRY | SINGLE);
-    let result = graph.find_unique_internal(model.name(), input, true, action, source.clone()).await;
     let include = input.get("include");
     let select = input.get("select");
-    return match result {
+    let empty = teon!({});
+    let obj = match graph.find_unique_internal(model.name(), input, true, action, source.clone()).await {
         Ok(obj) => {
             // find the object here
-            let update = input.get("update");
-            let set_json_result = match update {
-                Some(update) => {
-                    obj.set_teon_with_path(update, &path!["update"]).await
-                }
-                None => {
-                    let empty = teon!({});
-                    obj.set_teon_with_path(&empty, &path!["update"]).await
-                }
-            };
-            match set_json_result {
-                Ok(_) => {
-                    match obj.save().await {
-                        Ok(_) => {
-                            // refetch here
-                            let refetched = obj.refreshed(include, select).await.unwrap();
-                            let json_val: JsonValue = refetched.to_json_internal(&path!["data"]).await.unwrap().into();
-                            HttpResponse::Ok().json(json!({"data": json_val}))
-                        }
-                        Err(err) => {
-                            HttpResponse::BadRequest().json(json!({"error": err}))
-                        }
-                    }
-                }
-                Err(err) => {
-                    HttpResponse::BadRequest().json(json!({"error": err}))
-                }
-            }
+            let update = input.get("update").unwrap_or(&empty);
+            obj.set_teon_with_path(update, &path!["update"]).await?;
+            obj
         }
         Err(_) => {
-            let create = input.get("create");
             let action = Action::from_u32(UPSERT | CREATE | ENTRY | SINGLE);
-            let obj = graph.new_object(model.name(), action, source).unwrap();
-            let set_json_result = match create {
-                Some(create) => {
-                    obj.set_teon_with_path(create, &path!["create"]).await
-                }
-                None => {
-                    let empty = teon!({});
-                    obj.set_teon_with_path(&empty, &path!["create"]).await
-                }
-            };
-            match set_json_result {
-                Ok(_) => {
-                    match obj.save().await {
-                        Ok(_) => {
-                            // refetch here
-                            let refetched = obj.refreshed(include, select).await.unwrap();
-                            let json_data: JsonValue = refetched.to_json_internal(&path!["data"]).await.unwrap().into();
-                            return HttpResponse::Ok().json(json!({"data": json_data}));
-                        }
-                        Err(err) => {
-                            HttpResponse::BadRequest().json(json!({"error": err}))
-                        }
-                    }
-                }
-                Err(err) => {
-                    HttpResponse::BadRequest().json(json!({"error": err}))
-                }
-            }
+            let obj = graph.new_object(model.name(), action, source)?;
+            let create = input.get("create").unwrap_or(&empty);
+            obj.set_teon_with_path(create, &path!["create"]).await?;
+            obj
+        }
+    };
+    obj.save().await?;
+    // refetch here
+    let refetched = obj.refreshed(include, select).await?;
+    refetched.to_json_internal(&path!["data"]).await
+}
+
+async fn handle_upsert(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+    let result = graph.transaction_on(model.connector_name(), |graph| async move {
+        handle_upsert_internal(&graph, input, model, source).await
+    }).await;
+    match result {
+        Ok(value) => {
+            let json_val: JsonValue = value.into();
+            HttpResponse::Ok().json(json!({"data": json_val}))
+        }
+        Err(err) if err.r#type == ErrorType::VersionConflict => err.into(),
+        Err(err) => {
+            HttpResponse::BadRequest().json(json!({"error": err}))
         }
     }
 }
@@ -352,6 +376,79 @@ async fn handle_delete(graph: &Graph, input: &Value, model: &Model, source: Acti
     }
 }
 
+async fn handle_restore(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+    let action = Action::from_u32(RESTORE | SINGLE | ENTRY);
+    let include = input.get("include");
+    let select = input.get("select");
+    let mut input = input.as_hashmap().unwrap().clone();
+    input.insert("onlyDeleted".to_owned(), Value::Bool(true));
+    let result = graph.find_unique_internal(model.name(), &Value::HashMap(input), true, action, source).await;
+    if result.is_err() {
+        return HttpResponse::NotFound().json(json!({"error": result.err()}));
+    }
+    let result = result.unwrap();
+    if let Err(err) = result.restore_internal(path!["restore"]).await {
+        return err.into();
+    }
+    let refetched = match result.refreshed(include, select).await {
+        Ok(refetched) => refetched,
+        Err(err) => return err.into(),
+    };
+    match refetched.to_json_internal(&path!["data"]).await {
+        Ok(value) => {
+            let json_data: JsonValue = value.into();
+            HttpResponse::Ok().json(json!({"data": json_data}))
+        }
+        Err(err) => err.into(),
+    }
+}
+
+async fn handle_history(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+    let action = Action::from_u32(HISTORY | MANY | ENTRY);
+    let r#where = input.get("where").unwrap();
+    let mut finder = teon!({"where": r#where});
+    if model.soft_delete_field().is_some() {
+        finder.as_hashmap_mut().unwrap().insert("withDeleted".to_owned(), Value::Bool(true));
+    }
+    let record_id = match graph.find_unique_internal(model.name(), &finder, false, action, source).await {
+        Ok(object) => object_record_id(&object),
+        // the history of a deleted record is found by its primary key
+        Err(err) => if model.primary_index().keys().iter().all(|k| r#where.as_hashmap().unwrap().contains_key(k)) {
+            record_id(model, |k| r#where.get(k).unwrap().clone())
+        } else {
+            return HttpResponse::NotFound().json(json!({"error": err}));
+        }
+    };
+    let log_where = teon!({"model": model.name(), "recordId": record_id});
+    let mut log_finder = teon!({"where": log_where.clone(), "orderBy": [{"createdAt": "desc"}, {"id": "desc"}]});
+    for key in ["skip", "take", "pageSize", "pageNumber"] {
+        if let Some(value) = input.get(key) {
+            log_finder.as_hashmap_mut().unwrap().insert(key.to_owned(), value.clone());
+        }
+    }
+    let logs = match graph.find_many_internal(AUDIT_LOG_MODEL, &log_finder, false, Action::from_u32(PROGRAM_CODE | FIND | MANY), ActionSource::ProgramCode).await {
+        Ok(logs) => logs,
+        Err(err) => return err.into(),
+    };
+    let count = match graph.count(AUDIT_LOG_MODEL, &teon!({"where": log_where})).await {
+        Ok(count) => count,
+        Err(err) => return err.into(),
+    };
+    let mut meta = json!({"count": count});
+    if let Some(page_size) = input.get("pageSize") {
+        let page_size = page_size.as_i64().unwrap() as usize;
+        meta.as_object_mut().unwrap().insert("numberOfPages".to_string(), ((count + page_size - 1) / page_size).into());
+    }
+    let mut data: Vec<JsonValue> = vec![];
+    for (index, log) in logs.iter().enumerate() {
+        match log.to_json_internal(&path!["data", index]).await {
+            Ok(value) => data.push(value.into()),
+            Err(err) => return err.into(),
+        }
+    }
+    HttpResponse::Ok().json(json!({"meta": meta, "data": data}))
+}
+
 async fn handle_create_many(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
     let action = Action::from_u32(CREATE | MANY | ENTRY);
     let input = input.as_hashmap().unwrap();
@@ -368,26 +465,28 @@ async fn handle_create_many(graph: &Graph, input: &Value, model: &Model, source:
         return HttpResponse::BadRequest().json(json!({"error": err}));
     }
     let create = create.as_vec().unwrap();
-    let mut count = 0;
-    let mut ret_data: Vec<Value> = vec![];
-    let session = graph.connector().new_save_session();
-    for (index, val) in create.iter().enumerate() {
-        let result = handle_create_internal(graph, Some(val), include, select, model, &path!["create", index], action, source.clone(), session.clone()).await;
-        match result {
-            Err(err) => {
-                println!("{:?}", err.errors);
-            },
-            Ok(val) => {
-                count += 1;
-                ret_data.push(val);
-            }
+    let result = graph.transaction_on(model.connector_name(), |graph| async move {
+        let mut ret_data: Vec<Value> = vec![];
+        let session = graph.new_save_session(model);
+        for (index, val) in create.iter().enumerate() {
+            let val = handle_create_internal(&graph, Some(val), include, select, model, &path!["create", index], action, source.clone(), session.clone()).await?;
+            ret_data.push(val);
+        }
+        Ok(ret_data)
+    }).await;
+    match result {
+        Ok(ret_data) => {
+            let count = ret_data.len();
+            let json_ret_data: JsonValue = Value::Vec(ret_data).into();
+            HttpResponse::Ok().json(json!({
+                "meta": {"count": count},
+                "data": json_ret_data
+            }))
+        }
+        Err(err) => {
+            HttpResponse::BadRequest().json(json!({"error": err}))
         }
     }
-    let json_ret_data: JsonValue = Value::Vec(ret_data).into();
-    HttpResponse::Ok().json(json!({
-        "meta": {"count": count},
-        "data": json_ret_data
-    }))
 }
 
 async fn handle_update_many(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
@@ -486,7 +585,11 @@ async fn handle_group_by(graph: &Graph, input: &Value, model: &Model, _source: A
     }
 }
 
-async fn handle_sign_in(graph: &Graph, input: &Value, model: &Model, conf: &ServerConf) -> HttpResponse {
+async fn handle_sign_in(graph: &Graph, input: &Value, model: &Model, conf: &ServerConf, jwt: Option<&JwtStrategy>) -> HttpResponse {
+    let jwt = match jwt {
+        Some(jwt) => jwt,
+        None => return Error::internal_server_error("Missing JWT secret.").into(),
+    };
     let input = input.as_hashmap().unwrap();
     let credentials = input.get("credentials");
     if let None = credentials {
@@ -549,28 +652,80 @@ async fn handle_sign_in(graph: &Graph, input: &Value, model: &Model, conf: &Serv
             let select = input.get("select");
             let obj = obj.refreshed(include, select).await.unwrap();
             let json_data = obj.to_json_internal(&path!["data"]).await;
-            let exp: usize = (Utc::now() + Duration::days(365)).timestamp() as usize;
-            let tson_identifier = obj.identifier();
-            let json_identifier: JsonValue = tson_identifier.into();
-            let claims = Claims {
-                id: json_identifier,
-                model: obj.model().name().to_string(),
-                exp
-            };
-            if conf.jwt_secret.as_ref().is_none() {
-                return super::super::error::Error::internal_server_error("Missing JWT secret.").into();
-            }
-            let token = encode_token(claims, &conf.jwt_secret.as_ref().unwrap());
             HttpResponse::Ok().json(json!({
-            "meta": {
-                "token": token
-            },
+            "meta": token_meta(&obj, conf, jwt),
             "data": j(json_data.unwrap())
         }))
         }
     }
 }
 
+async fn handle_refresh_token(graph: &Graph, input: &Value, model: &Model, conf: &ServerConf, jwt: Option<&JwtStrategy>) -> HttpResponse {
+    let jwt = match jwt {
+        Some(jwt) => jwt,
+        None => return Error::internal_server_error("Missing JWT secret.").into(),
+    };
+    let input = input.as_hashmap().unwrap();
+    let refresh_token = match input.get("refreshToken") {
+        Some(refresh_token) => refresh_token.as_str().unwrap(),
+        None => return Error::missing_required_input_with_type("string", path!["refreshToken"]).into(),
+    };
+    let claims = match jwt.decode_token(graph, refresh_token).await {
+        Ok(claims) => claims,
+        Err(err) => return err.into(),
+    };
+    if !claims.refresh {
+        return Error::invalid_auth_token().into();
+    }
+    if claims.model != model.name() {
+        return Error::wrong_identity_model().into();
+    }
+    let obj = match find_identity(graph, &claims).await {
+        Ok(obj) => obj,
+        Err(err) => return err.into(),
+    };
+    if let Err(err) = revoke_token(graph, &claims).await {
+        return err.into();
+    }
+    let include = input.get("include");
+    let select = input.get("select");
+    let obj = obj.refreshed(include, select).await.unwrap();
+    let json_data = obj.to_json_internal(&path!["data"]).await;
+    HttpResponse::Ok().json(json!({
+        "meta": token_meta(&obj, conf, jwt),
+        "data": j(json_data.unwrap())
+    }))
+}
+
+async fn handle_sign_out(graph: &Graph, input: &Value, model: &Model, jwt: Option<&JwtStrategy>, identity: Option<(Object, Claims)>) -> HttpResponse {
+    let (identity, claims) = match identity {
+        Some(identity) => identity,
+        None => return Error::invalid_auth_token().into(),
+    };
+    if identity.model() != model {
+        return Error::wrong_identity_model().into();
+    }
+    if let Some(refresh_token) = input.as_hashmap().unwrap().get("refreshToken") {
+        let refresh_claims = match jwt.unwrap().decode_token(graph, refresh_token.as_str().unwrap()).await {
+            Ok(refresh_claims) => refresh_claims,
+            Err(err) => return err.into(),
+        };
+        if !refresh_claims.refresh || refresh_claims.model != claims.model || refresh_claims.id != claims.id {
+            return Error::invalid_auth_token().into();
+        }
+        if let Err(err) = revoke_token(graph, &refresh_claims).await {
+            return err.into();
+        }
+    }
+    if let Err(err) = revoke_token(graph, &claims).await {
+        return err.into();
+    }
+    let json_data = identity.to_json_internal(&path!["data"]).await;
+    HttpResponse::Ok().json(json!({
+        "data": j(json_data.unwrap())
+    }))
+}
+
 async fn handle_identity(_graph: &Graph, input: &Value, model: &Model, _conf: &ServerConf, source: ActionSource) -> HttpResponse {
     let identity = source.as_identity();
     if let Some(identity) = identity {
@@ -591,7 +746,62 @@ async fn handle_identity(_graph: &Graph, input: &Value, model: &Model, _conf: &S
     }
 }
 
-pub fn make_app(graph: Graph, conf: ServerConf) ->  App<impl ServiceFactory<
+async fn handle_action(graph: &Graph, conf: &ServerConf, jwt: Option<&JwtStrategy>, model_def: &Model, action: Action, parsed_body: Value, identity: Option<(Object, Claims)>) -> HttpResponse {
+    let (transformed_body, transformed_action) = if model_def.has_action_transformers() || parsed_body.as_hashmap().unwrap().get("include").is_some() {
+        if ((action.to_u32() == CREATE_MANY_HANDLER) || (action.to_u32() == CREATE_HANDLER)) && (parsed_body.get("create").unwrap().is_vec()) {
+            // create with many items
+            let entries = parsed_body.get("create").unwrap().as_vec().unwrap();
+            let mut transformed_entries: Vec<Value> = vec![];
+            let mut new_action = action;
+            for (_index, entry) in entries.iter().enumerate() {
+                let ctx = Ctx::initial_state_with_value(teon!({"create": entry})).with_action(action);
+                match model_def.transformed_action(ctx).await {
+                    Ok(result) => {
+                        transformed_entries.push(result.0.get("create").unwrap().clone());
+                        new_action = result.1;
+                    },
+                    Err(err) => return err.into(),
+                }
+            }
+            let mut new_val = parsed_body.clone();
+            new_val.as_hashmap_mut().unwrap().insert("create".to_owned(), Value::Vec(transformed_entries));
+            (new_val, new_action)
+        } else {
+            let ctx = Ctx::initial_state_with_value(parsed_body).with_action(action);
+            match model_def.transformed_action(ctx).await {
+                Ok(result) => result,
+                Err(err) => return err.into(),
+            }
+        }
+    } else {
+        (parsed_body, action)
+    };
+    let source = ActionSource::Identity(identity.as_ref().map(|(identity, _)| identity.clone()));
+    match transformed_action.to_u32() {
+        FIND_UNIQUE_HANDLER => handle_find_unique(graph, &transformed_body, model_def, source.clone()).await,
+        FIND_FIRST_HANDLER => handle_find_first(graph, &transformed_body, model_def, source.clone()).await,
+        FIND_MANY_HANDLER => handle_find_many(graph, &transformed_body, model_def, source.clone()).await,
+        CREATE_HANDLER => handle_create(graph, &transformed_body, model_def, source.clone()).await,
+        UPDATE_HANDLER => handle_update(graph, &transformed_body, model_def, source.clone()).await,
+        UPSERT_HANDLER => handle_upsert(graph, &transformed_body, model_def, source.clone()).await,
+        DELETE_HANDLER => handle_delete(graph, &transformed_body, model_def, source.clone()).await,
+        CREATE_MANY_HANDLER => handle_create_many(graph, &transformed_body, model_def, source.clone()).await,
+        UPDATE_MANY_HANDLER => handle_update_many(graph, &transformed_body, model_def, source.clone()).await,
+        DELETE_MANY_HANDLER => handle_delete_many(graph, &transformed_body, model_def, source.clone()).await,
+        COUNT_HANDLER => handle_count(graph, &transformed_body, model_def, source.clone()).await,
+        AGGREGATE_HANDLER => handle_aggregate(graph, &transformed_body, model_def, source.clone()).await,
+        GROUP_BY_HANDLER => handle_group_by(graph, &transformed_body, model_def, source.clone()).await,
+        SIGN_IN_HANDLER => handle_sign_in(graph, &transformed_body, model_def, conf, jwt).await,
+        IDENTITY_HANDLER => handle_identity(graph, &transformed_body, model_def, conf, source.clone()).await,
+        REFRESH_TOKEN_HANDLER => handle_refresh_token(graph, &transformed_body, model_def, conf, jwt).await,
+        SIGN_OUT_HANDLER => handle_sign_out(graph, &transformed_body, model_def, jwt, identity).await,
+        RESTORE_HANDLER => handle_restore(graph, &transformed_body, model_def, source.clone()).await,
+        HISTORY_HANDLER => handle_history(graph, &transformed_body, model_def, source.clone()).await,
+        _ => unreachable!()
+    }
+}
+
+pub fn make_app(graph: Graph, conf: ServerConf, routes: RouteTable, middlewares: Vec<Arc<dyn Middleware>>) ->  App<impl ServiceFactory<
     ServiceRequest,
     Response = ServiceResponse<BoxBody>,
     Config = (),
@@ -599,219 +809,229 @@ pub fn make_app(graph: Graph, conf: ServerConf) ->  App<impl ServiceFactory<
     Error = actix_web::Error,
 > + 'static> {
     let leaked_graph = Box::leak(Box::new(graph));
+    let jwt = match JwtStrategy::from_conf(&conf) {
+        Ok(jwt) => jwt,
+        Err(err) => panic!("{}", err.message),
+    };
     let leaked_conf = Box::leak(Box::new(conf));
+    let leaked_jwt = Box::leak(Box::new(jwt));
+    let leaked_routes = Box::leak(Box::new(routes));
+    let leaked_middlewares = Box::leak(Box::new(middlewares));
     Graph::set_current(leaked_graph);
-    make_app_inner(leaked_graph, leaked_conf)
+    make_app_inner(leaked_graph, leaked_conf, leaked_jwt.as_ref(), leaked_routes, leaked_middlewares.as_slice())
 }
 
-fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf) -> App<impl ServiceFactory<
+fn cors_headers(conf: &ServerConf, r: &HttpRequest) -> Vec<(HeaderName, HeaderValue)> {
+    let mut headers = vec![];
+    let origin = r.headers().get(header::ORIGIN).and_then(|o| o.to_str().ok());
+    let any_origin = conf.allow_origins.iter().any(|o| o == "*");
+    // browsers reject a wildcard origin on credentialed requests, echo the origin instead
+    let allowed_origin = if any_origin && !conf.allow_credentials {
+        Some("*")
+    } else {
+        origin.filter(|o| any_origin || conf.allow_origins.iter().any(|a| a == o))
+    };
+    if let Some(allowed_origin) = allowed_origin {
+        headers.push((header::ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_str(allowed_origin).unwrap()));
+        if allowed_origin != "*" {
+            headers.push((header::VARY, HeaderValue::from_static("Origin")));
+        }
+    }
+    headers.push((header::ACCESS_CONTROL_ALLOW_METHODS, HeaderValue::from_str(&conf.allow_methods.join(", ")).unwrap()));
+    let requested_headers = r.headers().get(header::ACCESS_CONTROL_REQUEST_HEADERS);
+    headers.push((header::ACCESS_CONTROL_ALLOW_HEADERS, match requested_headers {
+        Some(requested_headers) if conf.allow_credentials => requested_headers.clone(),
+        _ => HeaderValue::from_static("*"),
+    }));
+    if conf.allow_credentials {
+        headers.push((header::ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true")));
+    }
+    headers.push((header::ACCESS_CONTROL_MAX_AGE, HeaderValue::from_static("86400")));
+    headers
+}
+
+fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf, jwt: Option<&'static JwtStrategy>, routes: &'static RouteTable, middlewares: &'static [Arc<dyn Middleware>]) -> App<impl ServiceFactory<
     ServiceRequest,
     Response = ServiceResponse<BoxBody>,
     Config = (),
     InitError = (),
     Error = actix_web::Error,
 > + 'static> {
+    let mut default_headers = DefaultHeaders::new();
+    for (name, value) in conf.headers.iter() {
+        default_headers = default_headers.add((name.as_str(), value.as_str()));
+    }
     let app = App::new()
-        .wrap(DefaultHeaders::new()
-            .add(("Access-Control-Allow-Origin", "*"))
-            .add(("Access-Control-Allow-Methods", "OPTIONS, POST, GET"))
-            .add(("Access-Control-Allow-Headers", "*"))
-            .add(("Access-Control-Max-Age", "86400")))
-        .default_service(web::route().to(move |r: HttpRequest, mut payload: web::Payload| async move {
-            let start = SystemTime::now();
-            let mut path = r.path().to_string();
-            if let Some(prefix) = &conf.path_prefix {
-                if !path.starts_with(prefix) {
-                    log_unhandled(start, r.method().as_str(), &path, 404);
-                    return Error::destination_not_found().into();
-                }
-                path = path.strip_prefix(prefix).unwrap().to_string();
-            }
-            let path = if path.len() > 1 && path.ends_with("/") {
-                path[0..path.len() - 1].to_string()
-            } else {
-                path
-            };
-            if (r.method() != Method::POST) && (r.method() != Method::OPTIONS) {
-                log_unhandled(start, r.method().as_str(), &path, 404);
-                return Error::destination_not_found().into();
-            }
-            let path_components = path_components(&path);
-            let first_component = path_components.get(1).unwrap();
-            if !(path_components.len() == 3 && first_component == &"action") {
-                log_unhandled(start, r.method().as_str(), &path, 404);
-                return Error::destination_not_found().into();
-            }
-            let model_url_segment_name = path_components[0];
-            let action_segment_name = path_components[2];
-            let action = Action::handler_from_name(action_segment_name);
-            let action = match action {
-                Some(a) => a,
-                None => {
-                    log_unhandled(start, r.method().as_str(), &path, 404);
-                    return Error::destination_not_found().into();
-                }
-            };
-            let model_def = match graph.model_with_url_segment_name(model_url_segment_name) {
-                Some(name) => name,
-                None => {
-                    log_unhandled(start, r.method().as_str(), &path, 404);
-                    return Error::destination_not_found().into();
+        .wrap(default_headers)
+        .wrap_fn(move |req, srv| {
+            let cors_headers = cors_headers(conf, req.request());
+            let fut = srv.call(req);
+            async move {
+                let mut res = fut.await?;
+                for (name, value) in cors_headers {
+                    res.headers_mut().insert(name, value);
                 }
-            };
-            if !model_def.has_action(action) {
-                log_unhandled(start, r.method().as_str(), &path, 400);
-                return Error::destination_not_found().into();
+                Ok(res)
             }
-            if r.method() == Method::OPTIONS {
-                return HttpResponse::Ok().json(json!({}));
-            }
-            // read body
-            let mut body = web::BytesMut::new();
-            while let Some(chunk) = payload.next().await {
-                let chunk = chunk.unwrap();
-                // limit max size of in-memory payload
-                if (body.len() + chunk.len()) > 262_144usize {
-                    return HttpResponse::InternalServerError()
-                        .json(json!({"error": Error::internal_server_error("Memory overflow.".to_string())}));
-                }
-                body.extend_from_slice(&chunk);
+        })
+        .default_service(web::route().to(move |r: HttpRequest, mut payload: web::Payload| async move {
+            let request_id = r.headers().get(REQUEST_ID)
+                .and_then(|v| v.to_str().ok())
+                .filter(|v| !v.is_empty() && v.len() <= 128)
+                .map(|v| v.to_owned())
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            let mut log = RequestLog::new(request_id, r.method().as_str(), r.path());
+            let mut res = dispatch(&r, &mut payload, &mut log, graph, conf, jwt, routes, middlewares).await;
+            log.status = res.status().as_u16();
+            log.error_type = res.extensions().get::<ErrorType>().map(|t| format!("{:?}", t));
+            // successful preflight requests are not worth a log line
+            if !(r.method() == Method::OPTIONS && log.status == 200) {
+                log_request(&log);
             }
-            let parsed_body: Result<JsonValue, serde_json::Error> = serde_json::from_slice(&body);
-            let parsed_body = match parsed_body {
-                Ok(b) => b,
-                Err(_) => {
-                    log_unhandled(start, r.method().as_str(), &path, 400);
-                    return HttpResponse::BadRequest().json(json!({"error": Error::incorrect_json_format()}));
-                }
-            };
+            res.headers_mut().insert(HeaderName::from_static(REQUEST_ID), HeaderValue::from_str(&log.request_id).unwrap());
+            res
+        }));
+    app
+}
 
-            if !parsed_body.is_object() {
-                log_unhandled(start, r.method().as_str(), &path, 400);
-                return HttpResponse::BadRequest().json(json!({"error": Error::unexpected_input_root_type("object")}));
-            }
-            let identity = match get_identity(&r, &graph, conf).await {
-                Ok(identity) => { identity },
-                Err(err) => return HttpResponse::Unauthorized().json(json!({"error": err }))
-            };
-
-            let parsed_body = match Decoder::decode_action_arg(model_def, graph, action, &parsed_body) {
-                Ok(body) => body,
-                Err(err) => return err.into()
-            };
-            let (transformed_body, transformed_action) = if model_def.has_action_transformers() || parsed_body.as_hashmap().unwrap().get("include").is_some() {
-                if ((action.to_u32() == CREATE_MANY_HANDLER) || (action.to_u32() == CREATE_HANDLER)) && (parsed_body.get("create").unwrap().is_vec()) {
-                    // create with many items
-                    let entries = parsed_body.get("create").unwrap().as_vec().unwrap();
-                    let mut transformed_entries: Vec<Value> = vec![];
-                    let mut new_action = action;
-                    for (_index, entry) in entries.iter().enumerate() {
-                        let ctx = Ctx::initial_state_with_value(teon!({"create": entry})).with_action(action);
-                        match model_def.transformed_action(ctx).await {
-                            Ok(result) => {
-                                transformed_entries.push(result.0.get("create").unwrap().clone());
-                                new_action = result.1;
-                            },
-                            Err(err) => return err.into(),
-                        }
-                    }
-                    let mut new_val = parsed_body.clone();
-                    new_val.as_hashmap_mut().unwrap().insert("create".to_owned(), Value::Vec(transformed_entries));
-                    (new_val, new_action)
-                } else {
-                    let ctx = Ctx::initial_state_with_value(parsed_body).with_action(action);
-                    match model_def.transformed_action(ctx).await {
-                        Ok(result) => result,
-                        Err(err) => return err.into(),
-                    }
-                }
-            } else {
-                (parsed_body, action)
-            };
-            let source = ActionSource::Identity(identity);
-            match transformed_action.to_u32() {
-                FIND_UNIQUE_HANDLER => {
-                    let result = handle_find_unique(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    return result;
-                }
-                FIND_FIRST_HANDLER => {
-                    let result = handle_find_first(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                FIND_MANY_HANDLER => {
-                    let result = handle_find_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                CREATE_HANDLER => {
-                    let result = handle_create(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                UPDATE_HANDLER => {
-                    let result = handle_update(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                UPSERT_HANDLER => {
-                    let result = handle_upsert(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                DELETE_HANDLER => {
-                    let result = handle_delete(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                CREATE_MANY_HANDLER => {
-                    let result = handle_create_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                UPDATE_MANY_HANDLER => {
-                    let result = handle_update_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                DELETE_MANY_HANDLER => {
-                    let result = handle_delete_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                COUNT_HANDLER => {
-                    let result = handle_count(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                AGGREGATE_HANDLER => {
-                    let result = handle_aggregate(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                GROUP_BY_HANDLER => {
-                    let result = handle_group_by(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                SIGN_IN_HANDLER => {
-                    let result = handle_sign_in(&graph, &transformed_body, model_def, conf).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
+const REQUEST_ID: &str = "x-request-id";
+
+async fn dispatch(
+    r: &HttpRequest,
+    payload: &mut web::Payload,
+    log: &mut RequestLog,
+    graph: &'static Graph,
+    conf: &'static ServerConf,
+    jwt: Option<&'static JwtStrategy>,
+    routes: &'static RouteTable,
+    middlewares: &'static [Arc<dyn Middleware>],
+) -> HttpResponse {
+    // preflight requests are always answered, the other methods must be allowed
+    if r.method() != Method::OPTIONS && !conf.allow_methods.iter().any(|m| m == r.method().as_str()) {
+        return Error::method_not_allowed(r.method().as_str()).into();
+    }
+    let mut path = r.path().to_string();
+    if let Some(prefix) = &conf.path_prefix {
+        if !path.starts_with(prefix) {
+            return Error::destination_not_found().into();
+        }
+        path = path.strip_prefix(prefix).unwrap().to_string();
+    }
+    let path = if path.len() > 1 && path.ends_with("/") {
+        path[0..path.len() - 1].to_string()
+    } else {
+        path
+    };
+    if let Some((route, params)) = routes.find_route(r.method().as_str(), &path) {
+        log.handled = true;
+        // the connection is pinned on the default connector. queries of models on other
+        // connectors check out their own connections
+        let graph = match graph.with_connection(graph.default_connector_name()).await {
+            Ok(graph) => graph,
+            Err(err) => return err.into(),
+        };
+        let response = handle_custom(&route.handler, r, payload, &graph, conf, jwt, params, log).await;
+        graph.release_connection();
+        return response;
+    }
+    if r.method() == Method::OPTIONS && routes.has_route_path(&path) {
+        return HttpResponse::Ok().json(json!({}));
+    }
+    let path_components = path_components(&path);
+    let first_component = path_components.get(1).unwrap_or(&"");
+    if !(path_components.len() == 3 && first_component == &"action") {
+        return Error::destination_not_found().into();
+    }
+    let model_url_segment_name = path_components[0];
+    let action_segment_name = path_components[2];
+    let model_def = match graph.model_with_url_segment_name(model_url_segment_name) {
+        Some(name) => name,
+        None => return Error::destination_not_found().into(),
+    };
+    let action = match Action::handler_from_name(action_segment_name) {
+        Some(a) => a,
+        None => {
+            if let Some(handler) = routes.find_model_action(model_def.name(), action_segment_name) {
+                if r.method() == Method::OPTIONS {
+                    return HttpResponse::Ok().json(json!({}));
                 }
-                IDENTITY_HANDLER => {
-                    let result = handle_identity(&graph, &transformed_body, model_def, conf, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
+                if r.method() != Method::POST {
+                    return Error::method_not_allowed(r.method().as_str()).into();
                 }
-                _ => unreachable!()
+                log.handled = true;
+                log.model = Some(model_def.name().to_owned());
+                log.action = Some(action_segment_name.to_owned());
+                let graph = match graph.with_connection(model_def.connector_name()).await {
+                    Ok(graph) => graph,
+                    Err(err) => return err.into(),
+                };
+                let response = handle_custom(handler, r, payload, &graph, conf, jwt, HashMap::new(), log).await;
+                graph.release_connection();
+                return response;
             }
-        }));
-    app
+            return Error::destination_not_found().into();
+        }
+    };
+    if !model_def.has_action(action) {
+        return Error::destination_not_found().into();
+    }
+    log.model = Some(model_def.name().to_owned());
+    log.action = Some(action.as_handler_str().to_owned());
+    if r.method() == Method::OPTIONS {
+        return HttpResponse::Ok().json(json!({}));
+    }
+    if r.method() != Method::POST {
+        return Error::method_not_allowed(r.method().as_str()).into();
+    }
+    // read body
+    let body = match read_body(r, payload, conf).await {
+        Ok(body) => body,
+        Err(err) => return err.into(),
+    };
+    let parsed_body: Result<JsonValue, serde_json::Error> = serde_json::from_slice(&body);
+    let parsed_body = match parsed_body {
+        Ok(b) => b,
+        Err(_) => return Error::incorrect_json_format().into(),
+    };
+    if !parsed_body.is_object() {
+        return Error::unexpected_input_root_type("object").into();
+    }
+    // one connection serves every query of this request
+    let graph = match graph.with_connection(model_def.connector_name()).await {
+        Ok(graph) => graph,
+        Err(err) => return err.into(),
+    };
+    let identity = match get_identity(r, &graph, jwt).await {
+        Ok(identity) => identity,
+        Err(err) => return err.into(),
+    };
+    log.handled = true;
+    log.identity = identity.as_ref().map(|(_, claims)| claims.id.clone());
+    let parsed_body = match Decoder::decode_action_arg(model_def, &graph, action, &parsed_body) {
+        Ok(body) => body,
+        Err(err) => return err.into()
+    };
+    let claims = identity.as_ref().map(|(_, claims)| claims.clone());
+    let ctx = MiddlewareContext::new(r.clone(), model_def.clone(), action, identity.map(|(identity, _)| identity), parsed_body);
+    let action_graph = graph.clone();
+    let next = Next::new(middlewares, Rc::new(move |ctx: MiddlewareContext| {
+        let identity = ctx.identity().cloned().zip(claims.clone());
+        let graph = action_graph.clone();
+        Box::pin(async move {
+            handle_action(&graph, conf, jwt, model_def, action, ctx.into_body(), identity).await
+        })
+    }));
+    let response = next.run(ctx).await;
+    graph.release_connection();
+    response
 }
 
 async fn server_start_message(port: u16, environment_version: EnvironmentVersion, entrance: Entrance) -> Result<(), std::io::Error> {
+    if logger::is_json() {
+        log_message(LogLevel::Info, &format!("Teo {} ({}, {})", env!("CARGO_PKG_VERSION"), environment_version.to_string(), entrance.to_str()));
+        log_message(LogLevel::Info, &format!("Listening on port {}", port));
+        return Ok(());
+    }
     // Introducing
     let now: DateTime<Local> = Local::now();
     let now_formatted = format!("{now}").dimmed();
@@ -830,17 +1050,26 @@ async fn server_start_message(port: u16, environment_version: EnvironmentVersion
 pub(crate) async fn serve(
     graph: Graph,
     conf: ServerConf,
+    routes: RouteTable,
+    middlewares: Vec<Arc<dyn Middleware>>,
     environment_version: EnvironmentVersion,
     entrance: Entrance,
     no_migration: bool,
 ) -> Result<(), std::io::Error> {
     if !no_migration {
-        migrate(graph.to_mut(), false).await;
+        if auto_migrate(&graph).await.is_err() {
+            std::process::exit(1);
+        }
+    }
+    setup_logger(&conf);
+    if let Err(err) = JwtStrategy::from_conf(&conf) {
+        eprintln!("{}", err.message);
+        std::process::exit(1);
     }
     let bind = conf.bind.clone();
     let port = bind.1;
     let server = HttpServer::new(move || {
-        make_app(graph.clone(), conf.clone())
+        make_app(graph.clone(), conf.clone(), routes.clone(), middlewares.clone())
     })
         .bind(bind)
         .unwrap()
@@ -5,6 +5,10 @@ use crate::core::error::Error;
 
 impl Into<HttpResponse> for Error {
     fn into(self) -> HttpResponse {
-        HttpResponseBuilder::new(StatusCode::from_u16(self.r#type.code()).unwrap()).json(json!({"error": self}))
+        let r#type = self.r#type.clone();
+        let mut res = HttpResponseBuilder::new(StatusCode::from_u16(self.r#type.code()).unwrap()).json(json!({"error": self}));
+        // picked up by the request logger
+        res.extensions_mut().insert(r#type);
+        res
     }
 }
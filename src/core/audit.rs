@@ -0,0 +1,59 @@
+use serde_json::Value as JsonValue;
+use crate::core::database::name::DatabaseName;
+use crate::core::field::{Field, FieldIndex, IndexSettings};
+use crate::core::field::r#type::FieldType;
+use crate::core::model::builder::ModelBuilder;
+use crate::core::model::Model;
+use crate::core::object::Object;
+use crate::prelude::Value;
+
+/// The model which `@@audit` models record their creates, updates and deletes into.
+pub(crate) const AUDIT_LOG_MODEL: &str = "TeoAuditLog";
+
+pub(crate) const AUDIT_LOG_TABLE: &str = "teo_audit_logs";
+
+pub(crate) fn install_audit_log_model(model: &mut ModelBuilder, provider: DatabaseName) {
+    model.internal().table_name(AUDIT_LOG_TABLE);
+    let mut id = Field::new("id".to_owned());
+    id.set_required();
+    id.primary = true;
+    id.index = Some(FieldIndex::Primary(IndexSettings::default()));
+    match provider {
+        DatabaseName::MongoDB => {
+            id.field_type = Some(FieldType::ObjectId);
+            id.column_name = Some("_id".to_owned());
+            id.auto = true;
+        }
+        _ => {
+            id.field_type = Some(FieldType::I64);
+            id.auto_increment = true;
+        }
+    }
+    model.field(id);
+    for (name, field_type, optional) in [
+        ("model", FieldType::String, false),
+        ("recordId", FieldType::String, false),
+        ("action", FieldType::String, false),
+        ("identityModel", FieldType::String, true),
+        ("identityId", FieldType::String, true),
+        ("before", FieldType::Json, true),
+        ("after", FieldType::Json, true),
+        ("createdAt", FieldType::DateTime, false),
+    ] {
+        let mut field = Field::new(name.to_owned());
+        field.field_type = Some(field_type);
+        if optional { field.set_optional() } else { field.set_required() }
+        model.field(field);
+    }
+    model.index(["model", "recordId"]);
+}
+
+/// Identifies a record by the values of its primary key, in the order of the primary index.
+pub(crate) fn record_id(model: &Model, value: impl Fn(&str) -> Value) -> String {
+    let values: Vec<JsonValue> = model.primary_index().keys().iter().map(|k| (&value(k)).into()).collect();
+    JsonValue::Array(values).to_string()
+}
+
+pub(crate) fn object_record_id(object: &Object) -> String {
+    record_id(object.model(), |k| object.get_value(k).unwrap())
+}
@@ -1,3 +1,5 @@
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 use async_trait::async_trait;
@@ -7,29 +9,50 @@ use crate::core::database::r#type::DatabaseType;
 use crate::core::field::r#type::FieldType;
 use crate::core::graph::Graph;
 use crate::core::model::Model;
+use crate::core::r#enum::Enum;
 use crate::core::object::Object;
 use crate::core::result::Result;
 use crate::prelude::Value;
 
 #[async_trait]
-pub(crate) trait SaveSession: Debug + Send + Sync { }
+pub(crate) trait SaveSession: Debug + Send + Sync {
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn in_transaction(&self) -> bool;
+
+    /// Gives back the database connection held by this session.
+    fn release(&self);
+
+    async fn commit(&self) -> Result<()>;
+
+    async fn abort(&self) -> Result<()>;
+}
 
 #[async_trait]
 pub(crate) trait Connector: Send + Sync {
 
     // Query database types
 
-    fn default_database_type(&self, field_type: &FieldType) -> DatabaseType;
+    fn default_database_type(&self, field_type: &FieldType, enums: &HashMap<String, Enum>) -> DatabaseType;
 
-    async fn load(&mut self, models: &Vec<Model>) -> Result<()>;
+    async fn load(&self, models: &Vec<Model>) -> Result<()>;
 
     // Migration
 
-    async fn migrate(&mut self, models: &Vec<Model>, reset_database: bool) -> Result<()>;
+    async fn migrate(&self, models: &Vec<Model>, enums: &HashMap<String, Enum>, reset_database: bool, dry_run: bool) -> Result<Vec<String>>;
+
+    // Versioned migration
+
+    async fn applied_migrations(&self) -> Result<Vec<String>>;
+
+    async fn apply_migration(&self, name: &str, statements: &str) -> Result<()>;
+
+    async fn revert_migration(&self, name: &str, statements: &str) -> Result<()>;
 
     // Raw query
 
-    async fn query_raw(&self, query: &Value) -> Result<Value>;
+    async fn query_raw(&self, session: Option<&Arc<dyn SaveSession>>, query: &Value) -> Result<Value>;
 
     // Object manipulation
 
@@ -50,4 +73,10 @@ pub(crate) trait Connector: Send + Sync {
     // Save session
 
     fn new_save_session(&self) -> Arc<dyn SaveSession>;
+
+    /// Creates a session which keeps one connection for its whole lifetime.
+    async fn new_connection_session(&self) -> Result<Arc<dyn SaveSession>>;
+
+    /// Starts a transaction. If `session` holds a connection, the transaction runs on it.
+    async fn new_transaction(&self, session: Option<&Arc<dyn SaveSession>>) -> Result<Arc<dyn SaveSession>>;
 }
@@ -5,4 +5,6 @@ pub enum DatabaseName {
     #[cfg(feature = "data-source-sqlite")]
     SQLite,
     MongoDB,
+    #[cfg(feature = "data-source-mssql")]
+    MSSQL,
 }
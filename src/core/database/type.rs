@@ -208,5 +208,31 @@ pub enum DatabaseType {
     // PostgreSQL only
     ByteA,
 
+    /// Enum
+    /// Represents an enum column which lists its members inline.
+    /// Arguments:
+    ///     choices: the enum members
+    /// Availability: MySQL
+    Enum { choices: Vec<String> },
+
+    /// NamedEnum
+    /// Represents a column of an enum type created with `CREATE TYPE ... AS ENUM`.
+    /// Arguments:
+    ///     name: the type name
+    /// Availability: PostgreSQL
+    NamedEnum { name: String },
+
+    /// Json
+    /// Represents a JSON document.
+    /// Note: In SQL Server, this is stored as NVARCHAR(MAX). In MongoDB, this is an embedded
+    /// document or any other BSON value.
+    /// Availability: MySQL, PostgreSQL, SQL Server, MongoDB
+    Json,
+
+    /// JsonB
+    /// Represents a JSON document stored in a decomposed binary format.
+    /// Availability: PostgreSQL
+    JsonB,
+
     Vec(Box<DatabaseType>),
 }
@@ -4,6 +4,7 @@ pub struct EnumChoiceBuilder {
     name: String,
     localized_name: String,
     description: String,
+    renamed: Vec<String>,
 }
 
 impl EnumChoiceBuilder {
@@ -11,7 +12,8 @@ impl EnumChoiceBuilder {
         EnumChoiceBuilder {
             name: name.into(),
             localized_name: "".into(),
-            description: "".into()
+            description: "".into(),
+            renamed: vec![],
         }
     }
 
@@ -24,6 +26,11 @@ impl EnumChoiceBuilder {
         self.description = description.into();
         self
     }
+
+    pub fn renamed<I, T>(&mut self, names: I) -> &mut Self where I: IntoIterator<Item = T>, T: Into<String> {
+        self.renamed = names.into_iter().map(Into::into).collect();
+        self
+    }
 }
 
 pub struct EnumBuilder {
@@ -66,7 +73,8 @@ impl Into<EnumChoice> for EnumChoiceBuilder {
         EnumChoice {
             name: self.name.clone(),
             localized_name: self.localized_name.clone(),
-            description: self.description.clone()
+            description: self.description.clone(),
+            renamed: self.renamed.clone(),
         }
     }
 }
@@ -76,7 +84,8 @@ impl Into<EnumChoice> for &EnumChoiceBuilder {
         EnumChoice {
             name: self.name.clone(),
             localized_name: self.localized_name.clone(),
-            description: self.description.clone()
+            description: self.description.clone(),
+            renamed: self.renamed.clone(),
         }
     }
 }
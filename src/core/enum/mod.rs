@@ -5,6 +5,7 @@ pub struct EnumChoice {
     pub(self) name: String,
     pub(self) localized_name: String,
     pub(self) description: String,
+    pub(self) renamed: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +30,11 @@ impl EnumChoice {
     pub(crate) fn description(&self) -> &str {
         &self.description
     }
+
+    /// Previous names of this member, used when migrating native enum types.
+    pub(crate) fn renamed(&self) -> &Vec<String> {
+        &self.renamed
+    }
 }
 
 impl Enum {
@@ -17,6 +17,7 @@ pub(crate) enum ErrorType {
     UnknownDatabaseFindError,
     UnknownDatabaseFindUniqueError,
     UnknownDatabaseCountError,
+    UnknownDatabaseTransactionError,
     WrongIdentityModel,
     PropertySetterError,
 
@@ -24,8 +25,10 @@ pub(crate) enum ErrorType {
 
     // request destination
     DestinationNotFound,
+    MethodNotAllowed,
 
     // request input
+    PayloadTooLarge,
     IncorrectJSONFormat,
     UnexpectedInputRootType,
     UnexpectedInputType,
@@ -33,13 +36,21 @@ pub(crate) enum ErrorType {
     ValidationError,
     MissingRequiredInput,
     UnexpectedObjectLength,
+    UnsupportedFilter,
 
     // request token
     InvalidAuthToken,
+    ExpiredAuthToken,
+    MalformedAuthToken,
+    RevokedAuthToken,
 
     // request permission
     PermissionError,
     DeletionDenied,
+    UpdateDenied,
+
+    // concurrency
+    VersionConflict,
 
     // response destination
     ObjectNotFound,
@@ -57,6 +68,7 @@ pub(crate) enum ErrorType {
 
     // database
     RecordDecodingError,
+    MigrationError,
 }
 
 impl ErrorType {
@@ -69,10 +81,16 @@ impl ErrorType {
             ErrorType::UnknownDatabaseFindError => { 500 }
             ErrorType::UnknownDatabaseFindUniqueError => { 500 }
             ErrorType::UnknownDatabaseCountError => { 500 }
+            ErrorType::UnknownDatabaseTransactionError => { 500 }
             ErrorType::DestinationNotFound => { 404 }
+            ErrorType::MethodNotAllowed => { 405 }
+            ErrorType::PayloadTooLarge => { 413 }
             ErrorType::InternalServerError => { 500 }
             ErrorType::ObjectNotFound => { 404 }
             ErrorType::InvalidAuthToken => { 401 }
+            ErrorType::ExpiredAuthToken => { 401 }
+            ErrorType::MalformedAuthToken => { 401 }
+            ErrorType::RevokedAuthToken => { 401 }
             ErrorType::CustomInternalServerError => { 500 }
             ErrorType::CustomValidationError => { 400 }
             ErrorType::WrongIdentityModel => { 401 }
@@ -82,12 +100,16 @@ impl ErrorType {
             ErrorType::UnexpectedInputKey => { 400 }
             ErrorType::MissingRequiredInput => { 400 }
             ErrorType::UnexpectedObjectLength => { 400 }
+            ErrorType::UnsupportedFilter => { 400 }
             ErrorType::InvalidKey => { 500 }
             ErrorType::InvalidOperation => { 500 }
             ErrorType::PermissionError => { 401 }
             ErrorType::UnexpectedOutputException => { 500 }
             ErrorType::DeletionDenied => { 400 }
+            ErrorType::UpdateDenied => { 400 }
+            ErrorType::VersionConflict => { 409 }
             ErrorType::RecordDecodingError => { 500 }
+            ErrorType::MigrationError => { 500 }
         }
     }
 }
@@ -155,6 +177,14 @@ impl Error {
         }
     }
 
+    pub(crate) fn unknown_database_transaction_error() -> Self {
+        Error {
+            r#type: ErrorType::UnknownDatabaseTransactionError,
+            message: "An unknown database transaction error occurred.".to_string(),
+            errors: None
+        }
+    }
+
     pub(crate) fn destination_not_found() -> Self {
         Error {
             r#type: ErrorType::DestinationNotFound,
@@ -163,6 +193,22 @@ impl Error {
         }
     }
 
+    pub(crate) fn method_not_allowed(method: impl AsRef<str>) -> Self {
+        Error {
+            r#type: ErrorType::MethodNotAllowed,
+            message: format!("The request method '{}' is not allowed.", method.as_ref()),
+            errors: None
+        }
+    }
+
+    pub(crate) fn payload_too_large(limit: usize) -> Self {
+        Error {
+            r#type: ErrorType::PayloadTooLarge,
+            message: format!("The request body exceeds the limit of {} bytes.", limit),
+            errors: None
+        }
+    }
+
     pub(crate) fn object_not_found() -> Self {
         Error {
             r#type: ErrorType::ObjectNotFound,
@@ -219,6 +265,30 @@ impl Error {
         }
     }
 
+    pub(crate) fn expired_auth_token() -> Self {
+        Error {
+            r#type: ErrorType::ExpiredAuthToken,
+            message: "This auth token is expired.".to_string(),
+            errors: None
+        }
+    }
+
+    pub(crate) fn malformed_auth_token() -> Self {
+        Error {
+            r#type: ErrorType::MalformedAuthToken,
+            message: "This auth token is malformed.".to_string(),
+            errors: None
+        }
+    }
+
+    pub(crate) fn revoked_auth_token() -> Self {
+        Error {
+            r#type: ErrorType::RevokedAuthToken,
+            message: "This auth token is revoked.".to_string(),
+            errors: None
+        }
+    }
+
     pub fn custom_internal_server_error(message: impl Into<String>) -> Self {
         Error {
             r#type: ErrorType::CustomInternalServerError,
@@ -325,6 +395,14 @@ impl Error {
         }
     }
 
+    pub(crate) fn unsupported_filter(reason: impl Into<String>) -> Self {
+        Error {
+            r#type: ErrorType::UnsupportedFilter,
+            message: reason.into(),
+            errors: None
+        }
+    }
+
     pub(crate) fn invalid_key(unexpected_key: impl AsRef<str>, model: &Model) -> Self {
         Error {
             r#type: ErrorType::InvalidKey,
@@ -357,6 +435,46 @@ impl Error {
         }
     }
 
+    pub(crate) fn update_denied(relation_name: impl AsRef<str>) -> Self {
+        Error {
+            r#type: ErrorType::UpdateDenied,
+            message: format!("Update denied by `{}'.", relation_name.as_ref()),
+            errors: None
+        }
+    }
+
+    pub(crate) fn version_conflict() -> Self {
+        Error {
+            r#type: ErrorType::VersionConflict,
+            message: "The object was modified by another request.".to_string(),
+            errors: None
+        }
+    }
+
+    pub(crate) fn migration_error(errors: Vec<(String, String)>) -> Self {
+        // a table can fail more than once, keep every reason
+        let mut merged: HashMap<String, String> = HashMap::with_capacity(errors.len());
+        for (name, reason) in errors {
+            match merged.get_mut(&name) {
+                Some(existing) => { existing.push_str("; "); existing.push_str(&reason); }
+                None => { merged.insert(name, reason); }
+            }
+        }
+        Error {
+            r#type: ErrorType::MigrationError,
+            message: "Migration failed.".to_string(),
+            errors: Some(merged)
+        }
+    }
+
+    pub(crate) fn migration_failed(reason: impl Into<String>) -> Self {
+        Error {
+            r#type: ErrorType::MigrationError,
+            message: reason.into(),
+            errors: None
+        }
+    }
+
     pub(crate) fn validation_error<'a>(path: impl AsRef<KeyPath<'a>>, reason: impl Into<String>) -> Self {
         Error {
             r#type: ErrorType::ValidationError,
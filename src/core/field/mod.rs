@@ -4,6 +4,7 @@ pub(crate) mod read_rule;
 pub(crate) mod write_rule;
 pub(crate) mod migration;
 
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use inflector::Inflector;
@@ -17,6 +18,7 @@ use crate::core::field::r#type::{FieldType, FieldTypeOwner};
 use crate::core::field::read_rule::ReadRule;
 use crate::core::field::write_rule::WriteRule;
 use crate::core::pipeline::Pipeline;
+use crate::core::r#enum::Enum;
 use crate::core::pipeline::ctx::Ctx;
 use crate::core::teon::Value;
 use crate::core::result::Result;
@@ -142,6 +144,7 @@ pub(crate) struct Field {
     pub(crate) foreign_key: bool,
     pub(crate) migration: Option<FieldMigration>,
     pub(crate) dropped: bool,
+    pub(crate) version: bool,
 }
 
 impl Debug for Field {
@@ -186,6 +189,7 @@ impl Field {
             foreign_key: false,
             migration: None,
             dropped: false,
+            version: false,
         }
     }
 
@@ -245,8 +249,10 @@ impl Field {
         self.on_output_pipeline.process(ctx).await
     }
 
-    pub(crate) fn finalize(&mut self, connector: Arc<dyn Connector>) {
-        self.database_type = Some(connector.default_database_type(self.field_type()));
+    pub(crate) fn finalize(&mut self, connector: Arc<dyn Connector>, enums: &HashMap<String, Enum>) {
+        if self.database_type.is_none() {
+            self.database_type = Some(connector.default_database_type(self.field_type(), enums));
+        }
     }
 
     pub(crate) fn set_required(&mut self) {
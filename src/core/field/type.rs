@@ -18,6 +18,7 @@ pub(crate) enum FieldType {
     Date,
     DateTime,
     Enum(String),
+    Json,
     Vec(Box<Field>),
     HashMap(Box<Field>),
     BTreeMap(Box<Field>),
@@ -40,6 +41,13 @@ impl FieldType {
         }
     }
 
+    pub(crate) fn is_json(&self) -> bool {
+        match self {
+            FieldType::Json => true,
+            _ => false,
+        }
+    }
+
     pub(crate) fn enum_name(&self) -> &str {
         match self {
             FieldType::Enum(n) => n,
@@ -169,6 +177,7 @@ impl FieldType {
             FieldType::Decimal => &DEFAULT_FILTERS,
             FieldType::String => &STRING_FILTERS,
             FieldType::Enum(_) => &ENUM_FILTERS,
+            FieldType::Json => &JSON_FILTERS,
             FieldType::Vec(_) => &VEC_FILTERS,
             FieldType::HashMap(_) => &MAP_FILTERS,
             FieldType::BTreeMap(_) => &MAP_FILTERS,
@@ -186,6 +195,7 @@ impl FieldType {
             FieldType::Date | FieldType::DateTime => &DEFAULT_FILTERS_WITH_AGGREGATE,
             FieldType::String => &STRING_FILTERS_WITH_AGGREGATE,
             FieldType::Enum(_) => &ENUM_FILTERS_WITH_AGGREGATE,
+            FieldType::Json => &JSON_FILTERS,
             FieldType::Vec(_) => &VEC_FILTERS,
             FieldType::HashMap(_) => &MAP_FILTERS,
             FieldType::BTreeMap(_) => &MAP_FILTERS,
@@ -212,7 +222,7 @@ static BOOL_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset!{"equals", "not"}
 });
 static STRING_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset!{"equals", "not", "gt", "gte", "lt", "lte", "in", "notIn", "contains", "startsWith", "endsWith", "matches", "mode"}
+    hashset!{"equals", "not", "gt", "gte", "lt", "lte", "in", "notIn", "contains", "startsWith", "endsWith", "matches", "search", "mode"}
 });
 static DEFAULT_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset!{"equals", "not", "gt", "gte", "lt", "lte", "in", "notIn"}
@@ -223,6 +233,9 @@ static ENUM_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
 static VEC_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"equals", "has", "hasEvery", "hasSome", "isEmpty", "length"}
 });
+static JSON_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"path", "equals", "stringContains", "arrayContains", "hasKey"}
+});
 static MAP_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"equals", "has", "hasEvery", "hasSome", "isEmpty", "length", "hasKey"}
 });
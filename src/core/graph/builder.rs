@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use crate::core::connector::Connector;
 use crate::core::r#enum::builder::EnumBuilder;
@@ -6,6 +6,8 @@ use crate::core::graph::GraphInner;
 use crate::core::model::builder::ModelBuilder;
 use crate::core::model::Model;
 use crate::core::r#enum::Enum;
+use crate::core::error::Error;
+use crate::core::result::Result;
 use crate::prelude::Graph;
 
 pub struct GraphBuilder {
@@ -52,15 +54,28 @@ impl GraphBuilder {
         retval
     }
 
-    pub(crate) async fn build(&self, connector: Arc<dyn Connector>) -> Graph {
+    /// Builds the graph. Each model is stored by the connector it names, or by the default
+    /// connector if it names none.
+    pub(crate) async fn build(&self, connectors: Vec<(String, Arc<dyn Connector>)>, default_connector: &str) -> Result<Graph> {
+        if !connectors.iter().any(|(n, _)| n == default_connector) {
+            return Err(Error::invalid_operation(format!("Default connector '{}' is not defined.", default_connector)));
+        }
         let mut graph = GraphInner {
             enums: self.build_enums(),
             models_vec: Vec::new(),
             models_map: HashMap::new(),
             url_segment_name_map: HashMap::new(),
-            connector: None,
+            referenced_fields_map: HashMap::new(),
+            connectors: Vec::new(),
+            default_connector: default_connector.to_owned(),
         };
-        graph.models_vec = self.model_builders.iter().map(|mb| { mb.build(connector.clone()) }).collect();
+        for mb in self.model_builders.iter() {
+            let name = mb.connector.as_deref().unwrap_or(default_connector);
+            let Some((_, connector)) = connectors.iter().find(|(n, _)| n == name) else {
+                return Err(Error::invalid_operation(format!("Connector '{}' of model '{}' is not defined.", name, mb.name)));
+            };
+            graph.models_vec.push(mb.build(name, connector.clone(), &graph.enums));
+        }
         let mut models_map: HashMap<String, Model> = HashMap::new();
         let mut url_segment_name_map: HashMap<String, String> = HashMap::new();
         for model in graph.models_vec.iter() {
@@ -69,14 +84,34 @@ impl GraphBuilder {
         }
         graph.models_map = models_map;
         graph.url_segment_name_map = url_segment_name_map;
-        let c = connector.as_ref();
-        let connector_mut = unsafe {
-            let d: * const dyn Connector = c;
-            let e: * mut dyn Connector = d as *mut dyn Connector;
-            &mut *e
-        };
-        let _ = connector_mut.load(&graph.models_vec).await;
-        graph.connector = Some(connector.clone());
-        Graph { inner: Arc::new(graph) }
+        graph.referenced_fields_map = graph.models_vec.iter().map(|model| {
+            (model.name().to_owned(), Self::referenced_fields(model, &graph.models_map))
+        }).collect();
+        for (name, connector) in connectors.iter() {
+            let models: Vec<Model> = graph.models_vec.iter().filter(|m| m.connector_name() == name).cloned().collect();
+            connector.load(&models).await?;
+        }
+        graph.connectors = connectors;
+        Ok(Graph { inner: Arc::new(graph), session: None })
+    }
+
+    /// Fields of the model which the opposite or through relations of its relations reference.
+    fn referenced_fields(model: &Model, models_map: &HashMap<String, Model>) -> HashSet<String> {
+        let mut fields = HashSet::new();
+        for relation in model.relations() {
+            if relation.has_join_table() {
+                let through_relation = models_map.get(relation.through().unwrap()).and_then(|m| m.relation(relation.local()));
+                if let Some(through_relation) = through_relation {
+                    fields.extend(through_relation.references().iter().cloned());
+                }
+            } else {
+                let Some(opposite_model) = models_map.get(relation.model()) else { continue };
+                let has_opposite = opposite_model.relations().iter().any(|r| r.fields() == relation.references() && r.references() == relation.fields());
+                if has_opposite {
+                    fields.extend(relation.fields().iter().cloned());
+                }
+            }
+        }
+        fields
     }
 }
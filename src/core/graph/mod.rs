@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::sync::Arc;
 use key_path::KeyPath;
@@ -6,7 +6,7 @@ use to_mut_proc_macro::ToMut;
 use to_mut::ToMut;
 use crate::core::action::{Action, CREATE, INTERNAL_AMOUNT, INTERNAL_POSITION, PROGRAM_CODE, SINGLE};
 use crate::core::action::source::ActionSource;
-use crate::core::connector::Connector;
+use crate::core::connector::{Connector, SaveSession};
 use crate::core::model::Model;
 use crate::core::object::Object;
 use crate::core::r#enum::Enum;
@@ -19,7 +19,9 @@ pub mod builder;
 
 #[derive(Clone, ToMut)]
 pub struct Graph {
-    inner: Arc<GraphInner>
+    inner: Arc<GraphInner>,
+    // the session, and the name of the connector it's opened on
+    session: Option<(String, Arc<dyn SaveSession>)>,
 }
 
 pub(crate) struct GraphInner {
@@ -27,7 +29,9 @@ pub(crate) struct GraphInner {
     pub(crate) models_vec: Vec<Model>,
     pub(crate) models_map: HashMap<String, Model>,
     pub(crate) url_segment_name_map: HashMap<String, String>,
-    pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) referenced_fields_map: HashMap<String, HashSet<String>>,
+    pub(crate) connectors: Vec<(String, Arc<dyn Connector>)>,
+    pub(crate) default_connector: String,
 }
 
 static mut CURRENT: Option<&'static Graph> = None;
@@ -49,8 +53,101 @@ impl Graph {
         }
     }
 
+    // MARK: - Transaction
+
+    /// Runs the closure inside a transaction of the default connector.
+    ///
+    /// The closure receives a graph bound to the transaction. Objects fetched or created through
+    /// it are saved and deleted inside the transaction. The transaction is committed if the
+    /// closure returns `Ok`, and rolled back if it returns `Err`. Calling this on a graph which
+    /// is already inside a transaction reuses that transaction.
+    pub async fn transaction<F, Fut, R>(&self, f: F) -> Result<R> where
+        F: FnOnce(Graph) -> Fut,
+        Fut: Future<Output = Result<R>> {
+        let connector = self.inner.default_connector.clone();
+        self.transaction_on(&connector, f).await
+    }
+
+    /// Runs the closure inside a transaction of the named connector. Only the models of this
+    /// connector are saved inside the transaction. A transaction cannot be opened inside a
+    /// transaction of another connector.
+    pub async fn transaction_on<F, Fut, R>(&self, connector: &str, f: F) -> Result<R> where
+        F: FnOnce(Graph) -> Fut,
+        Fut: Future<Output = Result<R>> {
+        let target = self.connector_named(connector)?;
+        if let Some((name, session)) = &self.session {
+            if name != connector && session.in_transaction() {
+                return Err(Error::invalid_operation(format!("Cannot open a transaction on connector '{connector}' inside a transaction on connector '{name}'.")));
+            }
+        }
+        let current = self.session_on(connector);
+        if current.map_or(false, |s| s.in_transaction()) {
+            return f(self.clone()).await;
+        }
+        let session = target.new_transaction(current).await?;
+        let graph = Graph { inner: self.inner.clone(), session: Some((connector.to_owned(), session.clone())) };
+        match f(graph).await {
+            Ok(result) => {
+                session.commit().await?;
+                Ok(result)
+            }
+            Err(err) => {
+                session.abort().await?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns a graph which runs every query of the connector's models on the same database
+    /// connection. A graph which is already bound to a session is returned as is.
+    pub(crate) async fn with_connection(&self, connector: &str) -> Result<Graph> {
+        if self.session.is_some() {
+            return Ok(self.clone());
+        }
+        let session = self.connector_named(connector)?.new_connection_session().await?;
+        Ok(Graph { inner: self.inner.clone(), session: Some((connector.to_owned(), session)) })
+    }
+
+    /// Gives back the connection held by this graph's session. Queries made through the graph
+    /// afterwards check out connections from the pool again.
+    pub(crate) fn release_connection(&self) {
+        if let Some((_, session)) = &self.session {
+            session.release();
+        }
+    }
+
+    pub(crate) fn session(&self) -> Option<&Arc<dyn SaveSession>> {
+        self.session.as_ref().map(|(_, session)| session)
+    }
+
+    fn session_on(&self, connector: &str) -> Option<&Arc<dyn SaveSession>> {
+        self.session.as_ref().filter(|(name, _)| name == connector).map(|(_, session)| session)
+    }
+
+    pub(crate) fn new_save_session(&self, model: &Model) -> Arc<dyn SaveSession> {
+        match self.session_on(model.connector_name()) {
+            Some(session) => session.clone(),
+            None => self.connector_for(model).new_save_session(),
+        }
+    }
+
+    /// Returns the graph which queries of the model are made through. A session of another
+    /// connector cannot be used by the model's connector, so it's left out.
+    fn routed(&self, model: &Model) -> Graph {
+        match &self.session {
+            Some((name, _)) if name != model.connector_name() => Graph { inner: self.inner.clone(), session: None },
+            _ => self.clone(),
+        }
+    }
+
     // MARK: - Queries
 
+    /// Runs a raw query on the connector, inside this graph's session when it belongs to the
+    /// connector.
+    pub(crate) async fn query_raw(&self, connector: &str, query: &Value) -> Result<Value> {
+        self.connector_named(connector)?.query_raw(self.session_on(connector), query).await
+    }
+
     pub async fn find_unique<T: From<Object>>(&self, model: &str, finder: &Value) -> Result<T> {
         match self.find_unique_internal(model, finder, false, Action::from_u32(PROGRAM_CODE | INTERNAL_AMOUNT | INTERNAL_POSITION), ActionSource::ProgramCode).await {
             Ok(result) => Ok(result.into()),
@@ -74,15 +171,17 @@ impl Graph {
 
     pub(crate) async fn find_unique_internal(&self, model: &str, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
         let model = self.model(model).unwrap();
-        self.connector().find_unique(self, model, finder, mutation_mode, action, action_source).await
+        let finder = &self.exclude_soft_deleted(model, finder);
+        self.connector_for(model).find_unique(&self.routed(model), model, finder, mutation_mode, action, action_source).await
     }
 
     pub(crate) async fn find_first_internal(&self, model: &str, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
         let model = self.model(model).unwrap();
+        let finder = self.exclude_soft_deleted(model, finder);
         let mut finder = finder.as_hashmap().clone().unwrap().clone();
         finder.insert("take".to_string(), 1.into());
         let finder = Value::HashMap(finder);
-        let result = self.connector().find_many(self, model, &finder, mutation_mode, action, action_source).await;
+        let result = self.connector_for(model).find_many(&self.routed(model), model, &finder, mutation_mode, action, action_source).await;
         match result {
             Err(err) => Err(err),
             Ok(retval) => {
@@ -97,7 +196,8 @@ impl Graph {
 
     pub(crate) async fn find_many_internal(&self, model: &str, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
         let model = self.model(model).unwrap();
-        self.connector().find_many(self, model, finder, mutation_mode, action, action_source).await
+        let finder = &self.exclude_soft_deleted(model, finder);
+        self.connector_for(model).find_many(&self.routed(model), model, finder, mutation_mode, action, action_source).await
     }
 
     pub(crate) async fn batch<F, Fut>(&self, model: &str, finder: &Value, action: Action, action_source: ActionSource, f: F) -> Result<()> where
@@ -122,17 +222,63 @@ impl Graph {
 
     pub(crate) async fn count(&self, model: &str, finder: &Value) -> Result<usize> {
         let model = self.model(model).unwrap();
-        self.connector().count(self, model, finder).await
+        let finder = &self.exclude_soft_deleted(model, finder);
+        self.connector_for(model).count(&self.routed(model), model, finder).await
     }
 
     pub(crate) async fn aggregate(&self, model: &str, finder: &Value) -> Result<Value> {
         let model = self.model(model).unwrap();
-        self.connector().aggregate(self, model, finder).await
+        let finder = &self.exclude_soft_deleted(model, finder);
+        self.connector_for(model).aggregate(&self.routed(model), model, finder).await
     }
 
     pub(crate) async fn group_by(&self, model: &str, finder: &Value) -> Result<Value> {
         let model = self.model(model).unwrap();
-        self.connector().group_by(self, model, finder).await
+        let finder = &self.exclude_soft_deleted(model, finder);
+        self.connector_for(model).group_by(&self.routed(model), model, finder).await
+    }
+
+    /// Adds the soft delete conditions of `withDeleted` and `onlyDeleted` to the where inputs of
+    /// a finder and of its includes. Soft deleted records are excluded if neither is given.
+    fn exclude_soft_deleted(&self, model: &Model, finder: &Value) -> Value {
+        if !self.models().iter().any(|m| m.soft_delete_field().is_some()) {
+            return finder.clone();
+        }
+        let mut finder = match finder.as_hashmap() {
+            Some(map) => map.clone(),
+            None => return finder.clone(),
+        };
+        let with_deleted = finder.remove("withDeleted").map_or(false, |v| v.as_bool().unwrap());
+        let only_deleted = finder.remove("onlyDeleted").map_or(false, |v| v.as_bool().unwrap());
+        if let Some(field) = model.soft_delete_field() {
+            if only_deleted || !with_deleted {
+                let condition = if only_deleted { "not" } else { "equals" };
+                let condition = Value::HashMap(HashMap::from([(field.to_owned(), Value::HashMap(HashMap::from([(condition.to_owned(), Value::Null)])))]));
+                let r#where = match finder.remove("where") {
+                    Some(Value::HashMap(map)) if !map.contains_key(field) => {
+                        let mut map = map;
+                        map.extend(condition.as_hashmap().unwrap().clone());
+                        Value::HashMap(map)
+                    }
+                    Some(r#where) => Value::HashMap(HashMap::from([("AND".to_owned(), Value::Vec(vec![r#where, condition]))])),
+                    None => condition,
+                };
+                finder.insert("where".to_owned(), r#where);
+            }
+        }
+        if let Some(Value::HashMap(include)) = finder.get("include") {
+            let include = include.iter().map(|(name, value)| {
+                let relation_model = model.relation(name).map(|r| self.model(r.model()).unwrap());
+                let value = match (relation_model, value) {
+                    (Some(relation_model), Value::HashMap(_)) => self.exclude_soft_deleted(relation_model, value),
+                    (Some(relation_model), Value::Bool(true)) if relation_model.soft_delete_field().is_some() => self.exclude_soft_deleted(relation_model, &Value::HashMap(HashMap::new())),
+                    _ => value.clone(),
+                };
+                (name.clone(), value)
+            }).collect();
+            finder.insert("include".to_owned(), Value::HashMap(include));
+        }
+        Value::HashMap(finder)
     }
 
     // MARK: - Create an object
@@ -158,28 +304,40 @@ impl Graph {
 
     // MARK: - Getting the connector
 
+    /// The connector of models without `@@connector`.
     pub(crate) fn connector(&self) -> &dyn Connector {
-        match &self.inner.connector {
-            Some(c) => { c.as_ref() }
-            None => { panic!() }
-        }
+        self.built_connector(&self.inner.default_connector)
     }
 
-    pub(crate) fn connector_mut(&self) -> &mut dyn Connector {
-        match &self.inner.connector {
-            Some(c) => {
-                let r = c.as_ref();
-                let result = unsafe {
-                    let d: * const dyn Connector = r;
-                    let e: * mut dyn Connector = d as *mut dyn Connector;
-                    &mut *e
-                };
-                result
-            }
-            None => { panic!() }
+    pub(crate) fn connector_for(&self, model: &Model) -> &dyn Connector {
+        self.built_connector(model.connector_name())
+    }
+
+    pub(crate) fn connector_named(&self, name: &str) -> Result<&dyn Connector> {
+        match self.inner.connectors.iter().find(|(n, _)| n == name) {
+            Some((_, c)) => Ok(c.as_ref()),
+            None => Err(Error::invalid_operation(format!("Connector with name '{name}' is not defined."))),
         }
     }
 
+    // The default connector and the connectors of models are checked when the graph is built.
+    fn built_connector(&self, name: &str) -> &dyn Connector {
+        self.inner.connectors.iter().find(|(n, _)| n == name).map(|(_, c)| c.as_ref()).unwrap()
+    }
+
+    /// Names of the connectors in the order they are declared.
+    pub(crate) fn connector_names(&self) -> Vec<&str> {
+        self.inner.connectors.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub(crate) fn default_connector_name(&self) -> &str {
+        &self.inner.default_connector
+    }
+
+    pub(crate) fn models_on(&self, connector: &str) -> Vec<Model> {
+        self.models().iter().filter(|m| m.connector_name() == connector).cloned().collect()
+    }
+
     pub(crate) fn model(&self, name: &str) -> Option<&Model> {
         self.inner.models_map.get(name)
     }
@@ -193,6 +351,11 @@ impl Graph {
 
     pub(crate) fn models(&self) -> &Vec<Model> { &self.inner.models_vec }
 
+    /// Whether a field of the model is referenced by a relation of another model.
+    pub(crate) fn is_referenced_field(&self, model: &Model, name: &str) -> bool {
+        self.inner.referenced_fields_map.get(model.name()).map_or(false, |fields| fields.contains(name))
+    }
+
     pub(crate) fn r#enum(&self, name: &str) -> Option<&Enum> {
         self.inner.enums.get(name)
     }
@@ -14,3 +14,4 @@ pub(crate) mod relation;
 pub(crate) mod property;
 pub(crate) mod input;
 pub(crate) mod action;
+pub(crate) mod audit;
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use inflector::Inflector;
 use to_mut::ToMut;
-use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, IDENTITY_HANDLER, SIGN_IN_HANDLER};
+use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, IDENTITY_HANDLER, REFRESH_TOKEN_HANDLER, HISTORY_HANDLER, RESTORE_HANDLER, SIGN_IN_HANDLER, SIGN_OUT_HANDLER};
 use crate::core::connector::Connector;
 use crate::core::field::*;
 use crate::core::field::Field;
@@ -15,6 +15,7 @@ use crate::core::model::index::builder::{ModelIndexBuilder};
 use crate::core::model::{Model, ModelInner};
 use crate::core::model::migration::ModelMigration;
 use crate::core::pipeline::Pipeline;
+use crate::core::r#enum::Enum;
 
 pub struct ModelBuilder {
     pub(crate) name: String,
@@ -40,6 +41,9 @@ pub struct ModelBuilder {
     pub(crate) disabled_actions: Option<Vec<Action>>,
     pub(crate) action_transformers: Vec<Pipeline>,
     pub(crate) migration: Option<ModelMigration>,
+    pub(crate) soft_delete: Option<String>,
+    pub(crate) audit: bool,
+    pub(crate) connector: Option<String>,
 }
 
 impl ModelBuilder {
@@ -69,6 +73,9 @@ impl ModelBuilder {
             disabled_actions: None,
             action_transformers: vec![],
             migration: None,
+            soft_delete: None,
+            audit: false,
+            connector: None,
         }
     }
 
@@ -127,6 +134,21 @@ impl ModelBuilder {
         self
     }
 
+    pub fn soft_delete(&mut self, field: impl Into<String>) -> &mut Self {
+        self.soft_delete = Some(field.into());
+        self
+    }
+
+    pub fn audit(&mut self) -> &mut Self {
+        self.audit = true;
+        self
+    }
+
+    pub fn connector(&mut self, name: impl Into<String>) -> &mut Self {
+        self.connector = Some(name.into());
+        self
+    }
+
     pub fn primary<I, T>(&mut self, keys: I) -> &mut Self where I: IntoIterator<Item = T>, T: Into<String> {
         let string_keys: Vec<String> = keys.into_iter().map(Into::into).collect();
         let name = string_keys.join("_");
@@ -182,10 +204,20 @@ impl ModelBuilder {
         self
     }
 
-    pub(crate) fn build(&self, connector: Arc<dyn Connector>) -> Model {
-        let fields_vec: Vec<Arc<Field>> = self.fields.clone().iter_mut().map(|fb| { Arc::new({ fb.finalize(connector.clone()); fb.clone()}) }).collect();
-        let dropped_fields_vec: Vec<Arc<Field>> = self.dropped_fields.clone().iter_mut().map(|fb| { Arc::new({ fb.finalize(connector.clone()); fb.clone()}) }).collect();
-        let properties_vec: Vec<Arc<Property>> = self.properties.clone().iter_mut().map(|pb| { Arc::new({ pb.finalize(connector.clone()); pb.clone() }) }).collect();
+    pub fn fulltext<I, T>(&mut self, keys: I) -> &mut Self where I: IntoIterator<Item = T>, T: Into<String> {
+        let string_keys: Vec<String> = keys.into_iter().map(Into::into).collect();
+        let items: Vec<ModelIndexItem> = string_keys.iter().map(|k| {
+            ModelIndexItem::new(k, Sort::Asc, None)
+        }).collect();
+        let index = ModelIndex::new(ModelIndexType::FullText, None::<String>, items);
+        self.indices.push(index);
+        self
+    }
+
+    pub(crate) fn build(&self, connector_name: &str, connector: Arc<dyn Connector>, enums: &HashMap<String, Enum>) -> Model {
+        let fields_vec: Vec<Arc<Field>> = self.fields.clone().iter_mut().map(|fb| { Arc::new({ fb.finalize(connector.clone(), enums); fb.clone()}) }).collect();
+        let dropped_fields_vec: Vec<Arc<Field>> = self.dropped_fields.clone().iter_mut().map(|fb| { Arc::new({ fb.finalize(connector.clone(), enums); fb.clone()}) }).collect();
+        let properties_vec: Vec<Arc<Property>> = self.properties.clone().iter_mut().map(|pb| { Arc::new({ pb.finalize(connector.clone(), enums); pb.clone() }) }).collect();
         let mut fields_map: HashMap<String, Arc<Field>> = HashMap::new();
         let mut dropped_fields_map: HashMap<String, Arc<Field>> = HashMap::new();
         let mut properties_map: HashMap<String, Arc<Property>> = HashMap::new();
@@ -233,10 +265,36 @@ impl ModelBuilder {
         if primary.is_none() && !self.r#virtual {
             panic!("Model '{}' must has a primary field.", self.name);
         }
-        // install recordPrevious for primary
+        for index in indices.iter().filter(|i| i.r#type().is_fulltext()) {
+            for key in index.keys() {
+                match fields_map.get(key) {
+                    Some(field) if field.field_type().is_string() => (),
+                    _ => panic!("Full-text index of model '{}' can only contain string fields, '{}' is not.", self.name, key),
+                }
+            }
+        }
+        if let Some(soft_delete) = &self.soft_delete {
+            match fields_map.get(soft_delete) {
+                Some(field) if field.field_type().is_datetime() && field.is_optional() => (),
+                _ => panic!("Soft delete field of model '{}' should be an optional DateTime field, '{}' is not.", self.name, soft_delete),
+            }
+        }
+        let version_fields: Vec<&Arc<Field>> = fields_vec.iter().filter(|f| f.version).collect();
+        if version_fields.len() > 1 {
+            panic!("Model '{}' can only have one version field.", self.name);
+        }
+        let version = version_fields.first().map(|field| {
+            let field_type = field.field_type();
+            if !(field_type.is_int() || field_type.is_datetime()) || field.is_optional() {
+                panic!("Version field of model '{}' should be a required Int, Int64 or DateTime field, '{}' is not.", self.name, field.name());
+            }
+            field.name().to_owned()
+        });
+        // install recordPrevious for primary, fields of a compound primary key are primary, too
         for key in primary.as_ref().unwrap().keys() {
             let field = fields_map.get(key).unwrap();
             field.as_ref().to_mut().previous_value_rule = PreviousValueRule::Keep;
+            field.as_ref().to_mut().primary = true;
         }
         let unique_query_keys = Self::unique_query_keys(self, &indices, primary.as_ref());
         let inner = ModelInner {
@@ -282,6 +340,10 @@ impl ModelBuilder {
             disabled_actions: self.disabled_actions.clone(),
             action_transformers: self.action_transformers.clone(),
             migration: self.migration.clone(),
+            soft_delete: self.soft_delete.clone(),
+            version,
+            audit: self.audit,
+            connector: connector_name.to_owned(),
         };
         Model::new_with_inner(Arc::new(inner))
     }
@@ -387,6 +449,7 @@ impl ModelBuilder {
     pub(crate) fn unique_query_keys(&self, indices: &Vec<ModelIndex>, primary: Option<&ModelIndex>) -> Vec<HashSet<String>> {
         let mut result: Vec<HashSet<String>> = Vec::new();
         for index in indices {
+            if index.r#type().is_fulltext() { continue }
             let set = HashSet::from_iter(index.items().iter().map(|i| {
                 i.field_name().to_string()
             }));
@@ -455,9 +518,17 @@ impl ModelBuilder {
         } else {
             Action::handlers_default()
         };
+        if self.soft_delete.is_some() && !self.internal && !self.r#virtual {
+            default.insert(Action::from_u32(RESTORE_HANDLER));
+        }
+        if self.audit && !self.internal && !self.r#virtual {
+            default.insert(Action::from_u32(HISTORY_HANDLER));
+        }
         if self.identity {
             default.insert(Action::from_u32(SIGN_IN_HANDLER));
             default.insert(Action::from_u32(IDENTITY_HANDLER));
+            default.insert(Action::from_u32(REFRESH_TOKEN_HANDLER));
+            default.insert(Action::from_u32(SIGN_OUT_HANDLER));
         }
         if let Some(disabled) = &self.disabled_actions {
             default.iter().filter(|a| {
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use array_tool::vec::Join;
+use crate::connectors::sql::query::fulltext::PSQL_TEXT_SEARCH_CONFIG;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::core::field::Sort;
 
@@ -10,6 +11,7 @@ pub enum ModelIndexType {
     Primary,
     Index,
     Unique,
+    FullText,
 }
 
 impl ModelIndexType {
@@ -26,6 +28,13 @@ impl ModelIndexType {
             _ => false,
         }
     }
+
+    pub(crate) fn is_fulltext(&self) -> bool {
+        match self {
+            ModelIndexType::FullText => true,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
@@ -104,10 +113,6 @@ impl ModelIndex {
         }
     }
 
-    pub(crate) fn set_name(&mut self, new_name: String) {
-        self.name = Some(new_name);
-    }
-
     pub(crate) fn items(&self) -> &Vec<ModelIndexItem> {
         &self.items
     }
@@ -120,13 +125,19 @@ impl ModelIndex {
         let escape = dialect.escape();
         let index_name_cow = self.sql_name(table_name, dialect);
         let index_name = index_name_cow.as_ref();
-        format!("DROP INDEX {escape}{index_name}{escape} ON {escape}{table_name}{escape}")
+        match dialect {
+            SQLDialect::PostgreSQL | SQLDialect::SQLite => format!("DROP INDEX {escape}{index_name}{escape}"),
+            _ => format!("DROP INDEX {escape}{index_name}{escape} ON {escape}{table_name}{escape}"),
+        }
     }
 
     pub(crate) fn to_sql_create(&self, dialect: SQLDialect, table_name: &str) -> String {
         let escape = dialect.escape();
         let index_name_cow = self.sql_name(table_name, dialect);
         let index_name = index_name_cow.as_ref();
+        if self.r#type().is_fulltext() {
+            return self.to_sql_create_fulltext(dialect, table_name, index_name);
+        }
         let unique = if self.r#type().is_unique() { "UNIQUE " } else { "" };
         let fields: Vec<String> = self.items.iter().map(|item| {
             Self::sql_format_item(dialect, item)
@@ -134,6 +145,24 @@ impl ModelIndex {
         format!("CREATE {unique}INDEX {escape}{index_name}{escape} ON {escape}{table_name}{escape}({})", fields.join(","))
     }
 
+    /// SQLite full-text indices are virtual tables, they are created by the migration instead.
+    /// Keys are column names here.
+    fn to_sql_create_fulltext(&self, dialect: SQLDialect, table_name: &str, index_name: &str) -> String {
+        let escape = dialect.escape();
+        match dialect {
+            SQLDialect::MySQL => {
+                let fields = self.keys.iter().map(|k| format!("`{k}`")).collect::<Vec<String>>().join(",");
+                format!("CREATE FULLTEXT INDEX `{index_name}` ON `{table_name}`({fields})")
+            }
+            SQLDialect::PostgreSQL => {
+                let fields = self.keys.iter().map(|k| format!("to_tsvector('{PSQL_TEXT_SEARCH_CONFIG}', {escape}{k}{escape})")).collect::<Vec<String>>().join(",");
+                format!("CREATE INDEX {escape}{index_name}{escape} ON {escape}{table_name}{escape} USING GIN ({fields})")
+            }
+            SQLDialect::SQLite => panic!("SQLite full-text indices are FTS5 tables."),
+            SQLDialect::MSSQL => unreachable!("Full-text indices of SQL Server are rejected by the schema."),
+        }
+    }
+
     pub(crate) fn sql_format_item(dialect: SQLDialect, item: &ModelIndexItem) -> String {
         let escape = dialect.escape();
         let name = item.field_name();
@@ -156,8 +185,9 @@ impl ModelIndex {
                 SQLDialect::MySQL => "PRIMARY".to_owned(),
                 SQLDialect::SQLite => format!("sqlite_autoindex_{}_1", table_name),
                 SQLDialect::PostgreSQL => self.normalize_name_psql(table_name),
-                _ => unreachable!()
+                SQLDialect::MSSQL => format!("PK_{}", table_name),
             },
+            ModelIndexType::FullText if dialect == SQLDialect::SQLite => format!("{}_fts", self.normalize_name_normal(table_name)),
             _ => match dialect {
                 SQLDialect::PostgreSQL => self.normalize_name_psql(table_name),
                 _ => self.normalize_name_normal(table_name),
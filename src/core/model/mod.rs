@@ -63,6 +63,10 @@ pub struct ModelInner {
     pub(crate) disabled_actions: Option<Vec<Action>>,
     pub(crate) action_transformers: Vec<Pipeline>,
     pub(crate) migration: Option<ModelMigration>,
+    pub(crate) soft_delete: Option<String>,
+    pub(crate) version: Option<String>,
+    pub(crate) audit: bool,
+    pub(crate) connector: String,
 }
 
 #[derive(Clone)]
@@ -108,6 +112,26 @@ impl Model {
         self.inner.r#virtual
     }
 
+    /// The field which records when a record was soft deleted.
+    pub(crate) fn soft_delete_field(&self) -> Option<&str> {
+        self.inner.soft_delete.as_deref()
+    }
+
+    /// Whether creates, updates and deletes are recorded into the audit log.
+    pub(crate) fn audited(&self) -> bool {
+        self.inner.audit
+    }
+
+    /// The name of the connector which stores this model's records.
+    pub(crate) fn connector_name(&self) -> &str {
+        &self.inner.connector
+    }
+
+    /// The field which is checked and bumped on every update.
+    pub(crate) fn version_field(&self) -> Option<&Field> {
+        self.inner.version.as_ref().map(|name| self.field(name).unwrap())
+    }
+
     pub(crate) fn actions(&self) -> &HashSet<Action> {
         &self.inner.handler_actions
     }
@@ -128,6 +152,10 @@ impl Model {
         return &self.inner.deny_relation_keys
     }
 
+    pub(crate) fn dropped_fields(&self) -> &Vec<Arc<Field>> {
+        &self.inner.dropped_fields
+    }
+
     pub(crate) fn dropped_field(&self, name: &str) -> Option<&Field> {
         match self.inner.dropped_fields_map.get(name) {
             Some(f) => Some(f.as_ref()),
@@ -229,6 +257,10 @@ impl Model {
     }
 
     pub(crate) fn has_action(&self, action: Action) -> bool {
+        // internal models and disabled handlers are not served
+        if Action::handlers_iter().any(|a| *a == action) && !self.inner.handler_actions.contains(&action) {
+            return false;
+        }
         if let Some(disabled_actions) = self.disabled_actions() {
             if action.passes(disabled_actions) {
                 return false;
@@ -252,6 +284,17 @@ impl Model {
         &self.inner.indices
     }
 
+    pub(crate) fn fulltext_index_for_field(&self, name: &str) -> Option<&ModelIndex> {
+        self.inner.indices.iter().find(|i| i.r#type().is_fulltext() && i.keys().iter().any(|k| k == name))
+    }
+
+    /// The full-text index which covers exactly these fields.
+    pub(crate) fn fulltext_index_with_fields(&self, names: &Vec<&str>) -> Option<&ModelIndex> {
+        self.inner.indices.iter().find(|i| {
+            i.r#type().is_fulltext() && i.keys().len() == names.len() && names.iter().all(|n| i.keys().iter().any(|k| k == n))
+        })
+    }
+
     pub(crate) fn primary_index(&self) -> &ModelIndex {
         self.inner.primary.as_ref().unwrap()
     }
@@ -8,13 +8,16 @@ use tokio::sync::Mutex as TokioMutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use key_path::{KeyPath, path};
 use async_recursion::async_recursion;
+use chrono::{TimeZone, Utc};
 use maplit::hashmap;
 use indexmap::IndexMap;
 use to_mut::ToMut;
 use to_mut_proc_macro::ToMut;
 use crate::core::action::{Action, CONNECT, CONNECT_OR_CREATE, CREATE, PROGRAM_CODE, DELETE, DISCONNECT, FIND, JOIN_CREATE, JOIN_DELETE, MANY, NESTED, SINGLE, UPDATE, UPSERT, NESTED_CREATE_ACTION, NESTED_DISCONNECT_ACTION, NESTED_SET_ACTION, NESTED_CONNECT_ACTION, NESTED_DELETE_MANY_ACTION, NESTED_UPDATE_MANY_ACTION, NESTED_UPDATE_ACTION, NESTED_DELETE_ACTION, NESTED_CONNECT_OR_CREATE_ACTION, NESTED_UPSERT_ACTION, INTERNAL_POSITION, SET};
 use crate::core::action::source::ActionSource;
+use crate::core::audit::{AUDIT_LOG_MODEL, object_record_id};
 use crate::core::field::{Field, PreviousValueRule};
+use crate::core::field::r#type::FieldTypeOwner;
 use crate::core::field::optionality::Optionality;
 use crate::core::input::Input;
 use crate::core::input::Input::{AtomicUpdator, SetValue};
@@ -28,6 +31,7 @@ use crate::core::error::{Error, ErrorType};
 use crate::core::field::write_rule::WriteRule;
 use crate::core::relation::delete_rule::DeleteRule;
 use crate::core::relation::delete_rule::DeleteRule::Deny;
+use crate::core::relation::update_rule::UpdateRule;
 use crate::core::result::Result;
 use crate::teon;
 
@@ -173,7 +177,10 @@ impl Object {
                     // set_value_to_value_map
                     let value = value_map.get(key).unwrap();
                     match Input::decode_field(value) {
-                        AtomicUpdator(updator) => self.set_value_to_atomic_updator_map(key, updator),
+                        AtomicUpdator(updator) => {
+                            self.record_previous_value_for_field_if_needed(field);
+                            self.set_value_to_atomic_updator_map(key, updator)
+                        }
                         SetValue(value) => {
                             // record previous value if needed
                             self.record_previous_value_for_field_if_needed(field);
@@ -236,7 +243,7 @@ impl Object {
     }
 
     fn record_previous_value_for_field_if_needed(&self, field: &Field) {
-        if !self.is_new() && field.previous_value_rule == PreviousValueRule::Keep {
+        if !self.is_new() && (field.previous_value_rule == PreviousValueRule::Keep || self.graph().is_referenced_field(self.model(), field.name()) || self.model().audited()) {
             if self.inner.previous_value_map.lock().unwrap().get(field.name()).is_none() {
                 self.inner.previous_value_map.lock().unwrap().insert(field.name().to_string(), self.get_value(field.name()).unwrap());
             }
@@ -291,6 +298,9 @@ impl Object {
         if !model_keys.contains(&key.as_ref().to_string()) {
             return Err(Error::invalid_key(key, self.model()));
         }
+        if let Some(field) = self.model().field(key.as_ref()) {
+            self.record_previous_value_for_field_if_needed(field);
+        }
         self.set_value_to_value_map(key.as_ref(), value);
         Ok(())
     }
@@ -534,6 +544,7 @@ impl Object {
                         return Err(Error::unexpected_input_value_with_reason(err.message, &(path + key)));
                     }
                     Ok(value) => {
+                        self.record_previous_value_for_field_if_needed(field);
                         self.inner.value_map.lock().unwrap().insert(key.to_string(), value);
                         if !self.inner.is_new.load(Ordering::SeqCst) {
                             self.inner.is_modified.store(true, Ordering::SeqCst);
@@ -546,7 +557,7 @@ impl Object {
         // validate required fields
         for key in model_keys {
             if let Some(field) = self.model().field(key) {
-                if field.auto || field.auto_increment || field.foreign_key {
+                if field.auto || field.auto_increment || field.foreign_key || field.version {
                     continue
                 }
                 match &field.optionality {
@@ -661,75 +672,222 @@ impl Object {
         self.inner.is_new.store(false, Ordering::SeqCst);
         self.inner.is_modified.store(false, Ordering::SeqCst);
         *self.inner.modified_fields.lock().unwrap() = HashSet::new();
+        // previous values of referenced keys are only needed until saved
+        let model = self.model();
+        self.inner.previous_value_map.lock().unwrap().retain(|k, _| {
+            model.field(k).map_or(false, |f| f.previous_value_rule == PreviousValueRule::Keep)
+        });
+    }
+
+    /// Returns the relations of other models which reference this object, together with this
+    /// model's relation and the other model. For a relation with a join table, the join model's
+    /// local relation is returned.
+    fn referencing_relations(&self) -> Vec<(&Relation, &Model, &Relation)> {
+        let graph = self.graph();
+        self.model().relations().iter().filter_map(|relation| {
+            if relation.has_join_table() {
+                let (through_model, through_relation) = graph.through_relation(relation);
+                Some((relation.as_ref(), through_model, through_relation))
+            } else {
+                let (opposite_model, opposite_relation) = graph.opposite_relation(relation);
+                opposite_relation.map(|opposite_relation| (relation.as_ref(), opposite_model, opposite_relation))
+            }
+        }).collect()
+    }
+
+    fn where_for_referencing_relation(&self, referencing: &Relation, previous: bool) -> Value {
+        Value::HashMap(referencing.iter().map(|(f, r)| {
+            let value = if previous { self.get_previous_value(r).unwrap() } else { self.get_value(r).unwrap() };
+            (f.to_owned(), value)
+        }).collect())
+    }
+
+    fn delete_rule_for(relation: &Relation, referencing: &Relation) -> DeleteRule {
+        // join records cannot outlive the objects they join
+        if relation.has_join_table() && referencing.delete_rule() == DeleteRule::Default {
+            DeleteRule::Cascade
+        } else {
+            referencing.delete_rule()
+        }
+    }
+
+    fn update_rule_for(relation: &Relation, referencing: &Relation) -> UpdateRule {
+        if relation.has_join_table() && referencing.update_rule() == UpdateRule::Default {
+            UpdateRule::Cascade
+        } else {
+            referencing.update_rule()
+        }
     }
 
     #[async_recursion]
     pub(crate) async fn delete_from_database(&self, session: Arc<dyn SaveSession>) -> Result<()> {
-        let model = self.model();
+        // soft deleted records are kept, so are the records referencing them
+        if let Some(field) = self.model().soft_delete_field() {
+            self.set_value(field, Value::DateTime(Utc::now()))?;
+            return self.save_to_database(session).await;
+        }
         let graph = self.graph();
+        let referencing_relations = self.referencing_relations();
         // check deny first
-        for relation in model.relations() {
-            if relation.through().is_some() {
-                continue
-            }
-            let (opposite_model, opposite_relation) = graph.opposite_relation(relation);
-            if let Some(opposite_relation) = opposite_relation {
-                if opposite_relation.delete_rule() == Deny {
-                    let finder = self.intrinsic_where_unique_for_relation(relation);
-                    let count = graph.count(opposite_model.name(), &finder).await.unwrap();
-                    if count > 0 {
-                        return Err(Error::deletion_denied(relation.name()));
-                    }
+        for (relation, referencing_model, referencing) in referencing_relations.iter() {
+            if Self::delete_rule_for(relation, referencing) == Deny {
+                let finder = teon!({ "where": self.where_for_referencing_relation(referencing, false) });
+                let count = graph.count(referencing_model.name(), &finder).await?;
+                if count > 0 {
+                    return Err(Error::deletion_denied(relation.name()));
                 }
             }
         }
         // real delete
-        let connector = self.graph().connector();
+        let connector = self.graph().connector_for(self.model());
         connector.delete_object(self, session.clone()).await?;
+        if self.model().audited() {
+            let keys = self.model().fields().iter().filter(|f| !f.read_rule.is_no_read()).map(|f| f.name().to_owned()).collect();
+            self.write_audit_log("delete", Some(self.audit_values(&keys, false)), None, session.clone()).await?;
+        }
         // nullify and cascade
-        for relation in model.relations() {
-            if relation.through().is_some() {
-                continue
-            }
-            let (opposite_model, opposite_relation) = graph.opposite_relation(relation);
-            if let Some(opposite_relation) = opposite_relation {
-                match opposite_relation.delete_rule() {
-                    DeleteRule::Default => {}, // do nothing
-                    DeleteRule::Deny => {}, // done before
-                    DeleteRule::Nullify => {
-                        if !opposite_relation.has_foreign_key() {
-                            continue
-                        }
-                        let finder = self.intrinsic_where_unique_for_relation(relation);
-                        graph.batch(opposite_model.name(), &finder, Action::from_u32(PROGRAM_CODE | DISCONNECT | (if relation.is_vec() { MANY } else { SINGLE })), ActionSource::ProgramCode, |object| async move {
-                            for key in opposite_relation.fields() {
-                                object.set_value(key, Value::Null)?;
-                            }
-                            object.save_with_session_and_path(self.graph().connector().new_save_session(), &path![]).await?;
-                            Ok(())
-                        }).await?;
-                    },
-                    DeleteRule::Cascade => {
-                        let finder = self.intrinsic_where_unique_for_relation(relation);
-                        graph.batch(opposite_model.name(), &finder, Action::from_u32(PROGRAM_CODE | DELETE | (if relation.is_vec() { MANY } else { SINGLE })), ActionSource::ProgramCode, |object| async move {
-                            object.delete_from_database(self.graph().connector().new_save_session()).await?;
-                            Ok(())
-                        }).await?;
+        for (relation, referencing_model, referencing) in referencing_relations.iter() {
+            let finder = teon!({ "where": self.where_for_referencing_relation(referencing, false) });
+            match Self::delete_rule_for(relation, referencing) {
+                DeleteRule::Default => {}, // do nothing
+                DeleteRule::Deny => {}, // done before
+                DeleteRule::Nullify => {
+                    if !referencing.has_foreign_key() {
+                        continue
                     }
+                    graph.batch(referencing_model.name(), &finder, Action::from_u32(PROGRAM_CODE | DISCONNECT | (if relation.is_vec() { MANY } else { SINGLE })), ActionSource::ProgramCode, |object| async move {
+                        for key in referencing.fields() {
+                            object.set_value(key, Value::Null)?;
+                        }
+                        object.save_with_session_and_path(self.graph().new_save_session(object.model()), &path![]).await?;
+                        Ok(())
+                    }).await?;
+                },
+                DeleteRule::Cascade => {
+                    graph.batch(referencing_model.name(), &finder, Action::from_u32(PROGRAM_CODE | DELETE | (if relation.is_vec() { MANY } else { SINGLE })), ActionSource::ProgramCode, |object| async move {
+                        object.delete_from_database(self.graph().new_save_session(object.model())).await?;
+                        Ok(())
+                    }).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn updated_referencing_relations(&self) -> Vec<(&Relation, &Model, &Relation)> {
+        let modified_fields = self.inner.modified_fields.lock().unwrap().clone();
+        self.referencing_relations().into_iter().filter(|(_, _, referencing)| {
+            referencing.references().iter().any(|r| {
+                modified_fields.contains(r) && self.get_previous_value(r).unwrap() != self.get_value(r).unwrap()
+            })
+        }).collect()
+    }
+
+    async fn check_update_deny_rules(&self) -> Result<()> {
+        let graph = self.graph();
+        for (relation, referencing_model, referencing) in self.updated_referencing_relations() {
+            if Self::update_rule_for(relation, referencing) == UpdateRule::Deny {
+                let finder = teon!({ "where": self.where_for_referencing_relation(referencing, true) });
+                let count = graph.count(referencing_model.name(), &finder).await?;
+                if count > 0 {
+                    return Err(Error::update_denied(relation.name()));
                 }
             }
         }
         Ok(())
     }
 
+    async fn perform_update_rules(&self, session: Arc<dyn SaveSession>) -> Result<()> {
+        let graph = self.graph();
+        for (relation, referencing_model, referencing) in self.updated_referencing_relations() {
+            if !referencing.has_foreign_key() {
+                continue
+            }
+            let finder = teon!({ "where": self.where_for_referencing_relation(referencing, true) });
+            let nullify = match Self::update_rule_for(relation, referencing) {
+                UpdateRule::Default | UpdateRule::Deny => continue,
+                UpdateRule::Nullify => true,
+                UpdateRule::Cascade => false,
+            };
+            let session = &session;
+            graph.batch(referencing_model.name(), &finder, Action::from_u32(PROGRAM_CODE | UPDATE | MANY), ActionSource::ProgramCode, |object| async move {
+                for (field, reference) in referencing.iter() {
+                    object.set_value(field, if nullify { Value::Null } else { self.get_value(reference)? })?;
+                }
+                object.save_with_session_and_path(session.clone(), &path![]).await?;
+                Ok(())
+            }).await?;
+        }
+        Ok(())
+    }
+
     #[async_recursion]
     async fn save_to_database(&self, session: Arc<dyn SaveSession>) -> Result<()> {
-        let connector = self.graph().connector();
-        connector.save_object(self, session).await?;
+        if self.is_new() {
+            if let Some(field) = self.model().version_field() {
+                self.set_value_to_value_map(field.name(), Self::next_version(field, &Value::Null));
+            }
+        }
+        let is_new = self.is_new();
+        let mut audited_keys = self.audited_keys();
+        let mut before = if is_new { None } else { Some(self.audit_values(&audited_keys, true)) };
+        let connector = self.graph().connector_for(self.model());
+        connector.save_object(self, session.clone()).await?;
+        let mut after = self.audit_values(&audited_keys, false);
+        if let Some(before) = before.as_mut() {
+            // fields set to their current values are not changes
+            audited_keys.retain(|k| before.get(k) != after.get(k));
+            before.retain(|k, _| audited_keys.contains(k));
+            after.retain(|k, _| audited_keys.contains(k));
+        }
+        if !audited_keys.is_empty() {
+            let action = match self.model().soft_delete_field() {
+                _ if is_new => "create",
+                Some(field) if audited_keys.iter().any(|k| k == field) => if after.get(field).unwrap().is_null() { "restore" } else { "delete" },
+                _ => "update",
+            };
+            self.write_audit_log(action, before, Some(after), session).await?;
+        }
         self.clear_new_state();
         Ok(())
     }
 
+    /// The changed fields which are recorded into the audit log. Write only fields are left out.
+    fn audited_keys(&self) -> Vec<String> {
+        if !self.model().audited() {
+            return vec![];
+        }
+        self.keys_for_save().iter().filter(|k| {
+            self.model().field(k).map_or(false, |f| !f.read_rule.is_no_read())
+        }).map(|k| k.to_string()).collect()
+    }
+
+    fn audit_values(&self, keys: &Vec<String>, previous: bool) -> HashMap<String, Value> {
+        keys.iter().map(|k| {
+            let value = if previous { self.get_previous_value(k) } else { self.get_value(k) };
+            (k.clone(), value.unwrap())
+        }).collect()
+    }
+
+    async fn write_audit_log(&self, action: &str, before: Option<HashMap<String, Value>>, after: Option<HashMap<String, Value>>, session: Arc<dyn SaveSession>) -> Result<()> {
+        let log = self.graph().new_object(AUDIT_LOG_MODEL, Action::from_u32(PROGRAM_CODE | CREATE | SINGLE | INTERNAL_POSITION), ActionSource::ProgramCode)?;
+        log.set_value("model", Value::String(self.model().name().to_owned()))?;
+        log.set_value("recordId", Value::String(object_record_id(self)))?;
+        log.set_value("action", Value::String(action.to_owned()))?;
+        if let Some(identity) = self.action_source().as_identity() {
+            log.set_value("identityModel", Value::String(identity.model().name().to_owned()))?;
+            log.set_value("identityId", Value::String(object_record_id(identity)))?;
+        }
+        log.set_value("before", before.map_or(Value::Null, |v| Value::HashMap(v)))?;
+        log.set_value("after", after.map_or(Value::Null, |v| Value::HashMap(v)))?;
+        log.set_value("createdAt", Value::DateTime(Utc::now()))?;
+        // the log is written by the default connector, which may not be the one of this object.
+        // then it's saved outside of the object's transaction, and is kept if that transaction
+        // is rolled back
+        let session = if log.model().connector_name() == self.model().connector_name() { session } else { self.graph().new_save_session(log.model()) };
+        log.save_to_database(session).await
+    }
+
     fn before_save_callback_check(&self) -> Result<()> {
         let inside_before_callback = self.inner.inside_before_save_callback.load(Ordering::SeqCst);
         if inside_before_callback {
@@ -752,7 +910,13 @@ impl Object {
             // perform relation manipulations (has foreign key)
             self.perform_relation_manipulations(|r| r.has_foreign_key(), session.clone(), path).await?;
             if !self.model().r#virtual() {
+                if !is_new {
+                    self.check_update_deny_rules().await?;
+                }
                 self.save_to_database(session.clone()).await?;
+                if !is_new {
+                    self.perform_update_rules(session.clone()).await?;
+                }
             }
         } else {
             // perform relation manipulations (has foreign key)
@@ -769,7 +933,7 @@ impl Object {
     }
 
     pub async fn save(&self) -> Result<()> {
-        let session = self.graph().connector().new_save_session();
+        let session = self.graph().new_save_session(self.model());
         self.save_with_session_and_path(session, &path![]).await
     }
 
@@ -810,16 +974,23 @@ impl Object {
 
     pub async fn delete(&self) -> Result<()> {
         self.trigger_before_delete_callbacks(path![]).await?;
-        self.delete_from_database(self.graph().connector().new_save_session()).await
+        self.delete_from_database(self.graph().new_save_session(self.model())).await
     }
 
     pub(crate) async fn delete_internal<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
         self.check_model_write_permission(path.as_ref()).await?;
         self.trigger_before_delete_callbacks(path.as_ref()).await?;
-        self.delete_from_database(self.graph().connector().new_save_session()).await?;
+        self.delete_from_database(self.graph().new_save_session(self.model())).await?;
         self.trigger_after_delete_callbacks(path.as_ref()).await
     }
 
+    pub(crate) async fn restore_internal<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
+        self.check_model_write_permission(path.as_ref()).await?;
+        let field = self.model().soft_delete_field().unwrap();
+        self.set_value(field, Value::Null)?;
+        self.save_to_database(self.graph().new_save_session(self.model())).await
+    }
+
     #[async_recursion]
     pub(crate) async fn to_json_internal<'a>(&self, path: &KeyPath<'a>) -> Result<Value> {
         // check read permission
@@ -1005,15 +1176,16 @@ impl Object {
         let foreign = opposite_relation.local();
         let join_local_relation = join_model.relation(local).unwrap();
         let join_foreign_relation = join_model.relation(foreign).unwrap();
-        let local_join_relation = self.graph().opposite_relation(join_local_relation).1.unwrap();
-        let foreign_join_relation = self.graph().opposite_relation(join_foreign_relation).1.unwrap();
-        let where_local = self.intrinsic_where_unique_for_relation(local_join_relation);
-        let where_foreign = object.intrinsic_where_unique_for_relation(foreign_join_relation);
+        // the join record is found by the columns linking to both sides
         let mut r#where_map: HashMap<String, Value> = hashmap!{};
-        r#where_map.extend(where_local.as_hashmap().cloned().unwrap());
-        r#where_map.extend(where_foreign.as_hashmap().cloned().unwrap());
+        for (field, reference) in join_local_relation.iter() {
+            r#where_map.insert(field.to_owned(), self.get_value(reference).unwrap());
+        }
+        for (field, reference) in join_foreign_relation.iter() {
+            r#where_map.insert(field.to_owned(), object.get_value(reference).unwrap());
+        }
         let r#where = Value::HashMap(r#where_map);
-        let object = match self.graph().find_unique_internal(relation.model(), &teon!({ "where": r#where }), true, action, self.action_source().clone()).await {
+        let object = match self.graph().find_unique_internal(join_model.name(), &teon!({ "where": r#where }), true, action, self.action_source().clone()).await {
             Ok(object) => object,
             Err(_) => return Err(Error::unexpected_input_value_with_reason("Join object is not found.", path)),
         };
@@ -1546,6 +1718,35 @@ impl Object {
         self.inner.action
     }
 
+    /// The version field with the value an update expects to find in the database and the value
+    /// it writes. A version given in the update input is expected instead of the fetched one.
+    pub(crate) fn version_update(&self) -> Option<(&Field, Value, Value)> {
+        if self.is_new() {
+            return None;
+        }
+        let field = self.model().version_field()?;
+        let expected = self.get_value(field.name()).unwrap();
+        let next = Self::next_version(field, &expected);
+        Some((field, expected, next))
+    }
+
+    fn next_version(field: &Field, current: &Value) -> Value {
+        let field_type = field.field_type();
+        if field_type.is_datetime() {
+            // MongoDB keeps milliseconds, a finer version never matches the stored one
+            let now = Utc::now().timestamp_millis();
+            let next = match current.as_datetime() {
+                Some(current) if current.timestamp_millis() >= now => current.timestamp_millis() + 1,
+                _ => now,
+            };
+            Value::DateTime(Utc.timestamp_millis_opt(next).unwrap())
+        } else if field_type.is_int32() {
+            Value::I32(current.as_i32().unwrap_or(0) + 1)
+        } else {
+            Value::I64(current.as_i64().unwrap_or(0) + 1)
+        }
+    }
+
     pub(crate) fn action_source(&self) -> &ActionSource {
         &self.inner.action_source
     }
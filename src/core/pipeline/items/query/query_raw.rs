@@ -18,7 +18,14 @@ impl QueryRawItem {
 #[async_trait]
 impl Item for QueryRawItem {
     async fn call<'a>(&self, ctx: Ctx<'a>) -> Result<Ctx<'a>> {
-        let result = Graph::current().connector().query_raw(&self.query).await;
+        // a query made for an object goes to the connector of its model, in the object's session
+        let result = match &ctx.object {
+            Some(object) => object.graph().query_raw(object.model().connector_name(), &self.query).await,
+            None => {
+                let graph = Graph::current();
+                graph.query_raw(graph.default_connector_name(), &self.query).await
+            }
+        };
         match result {
             Err(err) => Err(err),
             Ok(val) => Ok(ctx.with_value(val)),
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use inflector::Inflector;
 use crate::core::connector::Connector;
@@ -5,6 +6,7 @@ use crate::core::database::r#type::DatabaseType;
 use crate::core::field::optionality::Optionality;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
 use crate::core::pipeline::Pipeline;
+use crate::core::r#enum::Enum;
 
 #[derive(Clone)]
 pub struct Property {
@@ -59,8 +61,8 @@ impl Property {
         self.optionality.is_required()
     }
 
-    pub(crate) fn finalize(&mut self, connector: Arc<dyn Connector>) {
-        self.database_type = Some(connector.default_database_type(self.field_type()));
+    pub(crate) fn finalize(&mut self, connector: Arc<dyn Connector>, enums: &HashMap<String, Enum>) {
+        self.database_type = Some(connector.default_database_type(self.field_type(), enums));
     }
 
     pub(crate) fn set_required(&mut self) {
@@ -10,6 +10,7 @@ use once_cell::sync::Lazy;
 use crate::core::field::Field;
 use crate::core::field::optionality::Optionality;
 use crate::core::relation::delete_rule::DeleteRule;
+use crate::core::relation::update_rule::UpdateRule;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Relation {
@@ -23,6 +24,7 @@ pub(crate) struct Relation {
     pub(self) fields: Vec<String>,
     pub(self) references: Vec<String>,
     pub(self) delete_rule: DeleteRule,
+    pub(self) update_rule: UpdateRule,
     pub(self) has_foreign_key: bool,
 }
 
@@ -40,6 +42,7 @@ impl Relation {
             fields: Vec::new(),
             references: Vec::new(),
             delete_rule: DeleteRule::Default,
+            update_rule: UpdateRule::Default,
             has_foreign_key: false,
         }
     }
@@ -118,10 +121,22 @@ impl Relation {
         self.references.get(0).unwrap()
     }
 
+    pub(crate) fn set_delete_rule(&mut self, delete_rule: DeleteRule) {
+        self.delete_rule = delete_rule;
+    }
+
     pub(crate) fn delete_rule(&self) -> DeleteRule {
         self.delete_rule
     }
 
+    pub(crate) fn set_update_rule(&mut self, update_rule: UpdateRule) {
+        self.update_rule = update_rule;
+    }
+
+    pub(crate) fn update_rule(&self) -> UpdateRule {
+        self.update_rule
+    }
+
     pub(crate) fn has_foreign_key(&self) -> bool {
         self.has_foreign_key
     }
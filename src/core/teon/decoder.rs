@@ -87,6 +87,12 @@ impl Decoder {
                 "create" => { retval.insert(key.to_owned(), if action.to_u32() == CREATE_MANY_HANDLER { Self::decode_enumerate(value, path, |v, p: &KeyPath| Self::decode_create(model, graph, v, p))? } else { Self::decode_create(model, graph, value, path)? } ); }
                 "update" => { retval.insert(key.to_owned(), Self::decode_update(model, graph, value, path)?); }
                 "credentials" => { retval.insert(key.to_owned(), Self::decode_credentials(model, graph, value, path)?); }
+                "refreshToken" => { retval.insert(key.to_owned(), Self::decode_string(value, path)?); }
+                "withDeleted" | "onlyDeleted" => if model.soft_delete_field().is_some() {
+                    retval.insert(key.to_owned(), Self::decode_bool(value, path)?);
+                } else {
+                    return Err(Error::unexpected_input_key(key, path));
+                },
                 _ => unreachable!()
             }
         }
@@ -648,6 +654,15 @@ impl Decoder {
         }
     }
 
+    fn decode_string<'a>(json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+        let path = path.as_ref();
+        if let Some(s) = json_value.as_str() {
+            Ok(Value::String(s.to_owned()))
+        } else {
+            Err(Error::unexpected_input_type("string", path))
+        }
+    }
+
     fn decode_bool<'a>(json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         let path = path.as_ref();
         if let Some(b) = json_value.as_bool() {
@@ -695,10 +710,13 @@ impl Decoder {
         }
     }
 
-    fn decode_order_by_item<'a>(_model: &Model, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+    fn decode_order_by_item<'a>(model: &Model, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         let path = path.as_ref();
         if let Some(_json_map) = json_value.as_object() {
             let (key, value) = Self::check_length_1(json_value, path)?;
+            if key == "_relevance" {
+                return Ok(Value::HashMap(hashmap!{key.to_owned() => Self::decode_relevance(model, value, path + key)?}));
+            }
             match value.as_str() {
                 Some(s) => match s {
                     "asc" | "desc" => Ok(Value::HashMap(hashmap!{key.to_owned() => Value::String(s.to_owned())})),
@@ -711,6 +729,43 @@ impl Decoder {
         }
     }
 
+    fn decode_relevance<'a>(model: &Model, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+        let path = path.as_ref();
+        let json_map = if let Some(json_map) = json_value.as_object() {
+            json_map
+        } else {
+            return Err(Error::unexpected_input_type("object", path));
+        };
+        Self::check_json_keys(json_map, &hashset!{"fields", "search", "sort"}, path)?;
+        let fields_path = path + "fields";
+        let fields = match json_map.get("fields") {
+            Some(JsonValue::String(s)) => vec![s.as_str()],
+            Some(JsonValue::Array(a)) => a.iter().enumerate().map(|(i, v)| match v.as_str() {
+                Some(s) => Ok(s),
+                None => Err(Error::unexpected_input_type("string", &fields_path + i)),
+            }).collect::<Result<Vec<&str>>>()?,
+            Some(_) => return Err(Error::unexpected_input_type("string or array", fields_path)),
+            None => return Err(Error::missing_required_input_with_type("string or array", fields_path)),
+        };
+        if model.fulltext_index_with_fields(&fields).is_none() {
+            return Err(Error::unexpected_input_value_with_reason("Fields should be covered by a full-text index.", fields_path));
+        }
+        let search = match json_map.get("search") {
+            Some(search) => Self::decode_string(search, path + "search")?,
+            None => return Err(Error::missing_required_input_with_type("string", path + "search")),
+        };
+        let sort = match json_map.get("sort").map(|s| s.as_str()) {
+            Some(Some(s)) if s == "asc" || s == "desc" => Value::String(s.to_owned()),
+            Some(_) => return Err(Error::unexpected_input_value("'asc' or 'desc'", path + "sort")),
+            None => return Err(Error::missing_required_input_with_type("string", path + "sort")),
+        };
+        Ok(Value::HashMap(hashmap!{
+            "fields".to_owned() => Value::Vec(fields.iter().map(|f| Value::String(f.to_string())).collect()),
+            "search".to_owned() => search,
+            "sort".to_owned() => sort,
+        }))
+    }
+
     fn decode_where<'a>(model: &Model, graph: &Graph, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         let path = path.as_ref();
         let json_map = if let Some(json_map) = json_value.as_object() {
@@ -756,6 +811,9 @@ impl Decoder {
                     }
                     if let Some(field) = model.field(key) {
                         let optional = field.optionality.is_optional();
+                        if field.field_type().is_string() && value.get("search").is_some() && model.fulltext_index_for_field(key).is_none() {
+                            return Err(Error::unexpected_input_value_with_reason("Field is not full-text indexed.", &path + "search"));
+                        }
                         retval.insert(key.to_owned(), Self::decode_where_for_field(graph, field.field_type(), optional, value, path)?);
                     } else if let Some(relation) = model.relation(key) {
                         retval.insert(key.to_owned(), Self::decode_where_for_relation(graph, relation, value, path)?);
@@ -776,15 +834,36 @@ impl Decoder {
         if json_map.len() == 0 {
             return Err(Error::unexpected_input_value_with_reason("Unique where can't be empty.", path));
         }
+        // compound unique keys are accepted both flat and nested under the joined key name,
+        // e.g. `{ a: 1, b: 2 }` and `{ a_b: { a: 1, b: 2 } }`
+        let (json_map, path) = if json_map.len() == 1 {
+            let (key, value) = json_map.iter().next().unwrap();
+            let is_compound = model.indices().iter().any(|i| !i.r#type().is_fulltext() && i.keys().len() > 1 && &i.keys().join("_") == key);
+            if is_compound {
+                if let Some(inner) = value.as_object() {
+                    (inner, path + key)
+                } else {
+                    return Err(Error::unexpected_input_type("object", path + key));
+                }
+            } else {
+                (json_map, path.clone())
+            }
+        } else {
+            (json_map, path.clone())
+        };
+        let path = &path;
+        let json_keys: HashSet<&str> = json_map.keys().map(|k| k.as_str()).collect();
         for index in model.indices() {
-            if index.keys() == &json_map.keys().into_iter().map(|k| k.to_owned()).collect::<Vec<String>>() {
+            if index.r#type().is_fulltext() { continue }
+            let index_keys: HashSet<&str> = index.keys().iter().map(|k| k.as_str()).collect();
+            if index_keys == json_keys {
                 let mut retval: HashMap<String, Value> = HashMap::new();
                 for (key, value) in json_map {
                     let field = model.field(key).unwrap();
                     let path = path + key;
                     retval.insert(key.to_owned(), Self::decode_value_for_field_type(graph, field.field_type(), field.is_optional(), value, path)?);
-                    return Ok(Value::HashMap(retval));
                 }
+                return Ok(Value::HashMap(retval));
             }
         }
         Err(Error::unexpected_input_key(json_map.keys().next().unwrap(), path))
@@ -792,6 +871,9 @@ impl Decoder {
 
     fn decode_where_for_field_internal<'a>(graph: &Graph, r#type: &FieldType, optional: bool, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>, aggregate: bool) -> Result<Value> {
         let path = path.as_ref();
+        if r#type.is_json() {
+            return Self::decode_where_for_json_field(json_value, path);
+        }
         if json_value.is_object() {
             let json_map = json_value.as_object().unwrap();
             Self::check_json_keys(json_map, if aggregate { r#type.filters_with_aggregates() } else { r#type.filters() }, path)?;
@@ -806,7 +888,7 @@ impl Decoder {
                     "not" => {
                         retval.insert(key.to_owned(), Self::decode_where_for_field(graph, r#type, optional, value, path)?);
                     }
-                    "gt" | "gte" | "lt" | "lte" | "contains" | "startsWith" | "endsWith" | "matches" => {
+                    "gt" | "gte" | "lt" | "lte" | "contains" | "startsWith" | "endsWith" | "matches" | "search" => {
                         retval.insert(key.to_owned(), Self::decode_value_for_field_type(graph, r#type, false, value, path)?);
                     }
                     "in" | "notIn" => {
@@ -852,6 +934,41 @@ impl Decoder {
         }
     }
 
+    // json values are objects themselves, so json filters are never abbreviated
+    fn decode_where_for_json_field<'a>(json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+        let path = path.as_ref();
+        let json_map = if let Some(json_map) = json_value.as_object() {
+            json_map
+        } else {
+            return Err(Error::unexpected_input_type("object", path));
+        };
+        Self::check_json_keys(json_map, FieldType::Json.filters(), path)?;
+        let mut retval: HashMap<String, Value> = hashmap!{};
+        for (key, value) in json_map {
+            let key = key.as_str();
+            let path = path + key;
+            match key {
+                "path" => match value.as_array() {
+                    Some(array) => {
+                        let path = &path;
+                        retval.insert(key.to_owned(), Value::Vec(array.iter().enumerate().map(|(i, v)| {
+                            Self::decode_string(v, path + i)
+                        }).collect::<Result<Vec<Value>>>()?));
+                    }
+                    None => return Err(Error::unexpected_input_type("array", path)),
+                }
+                "equals" | "arrayContains" => {
+                    retval.insert(key.to_owned(), value.into());
+                }
+                "stringContains" | "hasKey" => {
+                    retval.insert(key.to_owned(), Self::decode_string(value, path)?);
+                }
+                _ => return Err(Error::unexpected_input_key(key, path))
+            }
+        }
+        Ok(Value::HashMap(retval))
+    }
+
     fn decode_where_with_aggregates_for_field<'a>(graph: &Graph, r#type: &FieldType, optional: bool, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         Self::decode_where_for_field_internal(graph, r#type, optional, json_value, path, true)
     }
@@ -890,6 +1007,9 @@ impl Decoder {
 
     fn decode_value_or_updator_for_field_type<'a>(graph: &Graph, r#type: &FieldType, optional: bool, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>, set_only: bool) -> Result<Value> {
         let path = path.as_ref();
+        if r#type.is_json() {
+            return Self::decode_value_for_field_type(graph, r#type, optional, json_value, path);
+        }
         if let Some(json_map) = json_value.as_object() {
             Self::check_length_1(json_value, path)?;
             Self::check_json_keys(json_map, if set_only { r#type.default_updators() } else { r#type.updators() }, path)?;
@@ -981,6 +1101,11 @@ impl Decoder {
                 },
                 None => Err(Error::unexpected_input_type(format!("string represents enum {enum_name}"), path))
             }
+            FieldType::Json => if json_value.is_null() {
+                Err(Error::unexpected_input_type("json value", path))
+            } else {
+                Ok(json_value.into())
+            },
             FieldType::Vec(inner_field) => match json_value.as_array() {
                 Some(a) => {
                     Ok(Value::Vec(a.iter().enumerate().map(|(i, v)| {
@@ -46,6 +46,7 @@ impl Index for str {
         match v {
             Value::HashMap(map) => map.get(self),
             Value::BTreeMap(map) => map.get(self),
+            Value::IndexMap(map) => map.get(self),
             _ => None,
         }
     }
@@ -53,6 +54,7 @@ impl Index for str {
         match v {
             Value::HashMap(map) => map.get_mut(self),
             Value::BTreeMap(map) => map.get_mut(self),
+            Value::IndexMap(map) => map.get_mut(self),
             _ => None,
         }
     }
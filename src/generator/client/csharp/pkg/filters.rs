@@ -501,6 +501,50 @@ namespace Teo {{
             Length = length;
         }}
     }}
+
+    public class JsonFilter {{
+        public string[]? Path {{ get; set; }}
+        public new object? Equals {{ get; set; }}
+        public string? StringContains {{ get; set; }}
+        public object? ArrayContains {{ get; set; }}
+        public string? HasKey {{ get; set; }}
+
+        public JsonFilter(
+            string[]? path = null,
+            object? equals = null,
+            string? stringContains = null,
+            object? arrayContains = null,
+            string? hasKey = null
+        ) {{
+            Path = path;
+            Equals = equals;
+            StringContains = stringContains;
+            ArrayContains = arrayContains;
+            HasKey = hasKey;
+        }}
+    }}
+
+    public class JsonNullableFilter {{
+        public string[]? Path {{ get; set; }}
+        public new Optional<object>? Equals {{ get; set; }}
+        public string? StringContains {{ get; set; }}
+        public object? ArrayContains {{ get; set; }}
+        public string? HasKey {{ get; set; }}
+
+        public JsonNullableFilter(
+            string[]? path = null,
+            Optional<object>? equals = null,
+            string? stringContains = null,
+            object? arrayContains = null,
+            string? hasKey = null
+        ) {{
+            Path = path;
+            Equals = equals;
+            StringContains = stringContains;
+            ArrayContains = arrayContains;
+            HasKey = hasKey;
+        }}
+    }}
 }}
 "#)
 }
@@ -1,5 +1,5 @@
 use inflector::Inflector;
-use crate::core::action::{Action, FIND_MANY_HANDLER, FIND_FIRST_HANDLER, FIND_UNIQUE_HANDLER, ResData, UPSERT_HANDLER, AGGREGATE_HANDLER};
+use crate::core::action::{Action, FIND_MANY_HANDLER, FIND_FIRST_HANDLER, FIND_UNIQUE_HANDLER, ResData, UPSERT_HANDLER, AGGREGATE_HANDLER, REFRESH_TOKEN_HANDLER, HISTORY_HANDLER};
 use crate::generator::lib::cases::{field_description, field_localized_name, model_api_object_description, model_localized_name, model_localized_name_word_case, relation_description, relation_localized_name};
 use crate::core::field::Field;
 use crate::core::graph::Graph;
@@ -61,9 +61,12 @@ pub(crate) fn action_and_model(r#type: Action, model: &Model) -> String {
         FIND_FIRST_HANDLER | FIND_UNIQUE_HANDLER => "find".to_owned(),
         UPSERT_HANDLER => "create or update".to_owned(),
         AGGREGATE_HANDLER => "aggregate on".to_owned(),
+        REFRESH_TOKEN_HANDLER => "refresh the token of".to_owned(),
+        HISTORY_HANDLER => "fetch the change history of".to_owned(),
         _ => r#type.as_handler_str().to_word_case(),
     };
     let object = match r#type.handler_res_data() {
+        ResData::Vec if r#type.to_u32() == HISTORY_HANDLER => localized_name.articlize(),
         ResData::Vec | ResData::Number | ResData::Other => localized_name.to_plural(),
         ResData::Single => {
             match r#type.to_u32() {
@@ -122,6 +125,13 @@ pub(crate) fn credentials_doc(model: &Model, action: Action) -> String {
  */"#)
 }
 
+pub(crate) fn refresh_token_doc(model: &Model, action: Action) -> String {
+    let verb_and_object = action_and_model(action, model);
+    format!(r#"/**
+ * The refresh token issued at sign in, needed to {verb_and_object}.
+ */"#)
+}
+
 pub(crate) fn unique_connect_doc(model: &Model) -> String {
     let object = model_localized_name_word_case(model);
     format!(r#"/**
@@ -199,6 +209,20 @@ pub(crate) fn page_number_doc(model: &Model) -> String {
  */"#)
 }
 
+pub(crate) fn with_deleted_doc(model: &Model) -> String {
+    let object = model_localized_name_word_case(model).to_plural();
+    format!(r#"/**
+ * Whether soft deleted {object} are included.
+ */"#)
+}
+
+pub(crate) fn only_deleted_doc(model: &Model) -> String {
+    let object = model_localized_name_word_case(model).to_plural();
+    format!(r#"/**
+ * Whether only soft deleted {object} are returned.
+ */"#)
+}
+
 pub(crate) fn field_doc(field: &Field) -> String {
     let name = field_localized_name(field);
     let desc = field_description(field);
@@ -1,8 +1,9 @@
 use inflector::Inflector;
-use crate::core::action::{Action, CREATE_HANDLER, FIND_FIRST_HANDLER, ResData, ResMeta, UPDATE_HANDLER, UPDATE_MANY_HANDLER, UPSERT_HANDLER};
+use crate::core::action::{Action, CREATE_HANDLER, FIND_FIRST_HANDLER, ResData, ResMeta, UPDATE_HANDLER, UPDATE_MANY_HANDLER, UPSERT_HANDLER, HISTORY_HANDLER};
+use crate::core::audit::AUDIT_LOG_MODEL;
 use crate::core::app::conf::ClientGeneratorConf;
 use crate::core::field::r#type::FieldTypeOwner;
-use crate::generator::client::csharp::pkg::index::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, field_doc, include_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first};
+use crate::generator::client::csharp::pkg::index::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, field_doc, include_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, refresh_token_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_deleted_doc, only_deleted_doc};
 use crate::generator::client::csharp::r#type::ToCSharpType;
 
 use crate::core::graph::Graph;
@@ -733,6 +734,37 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                         });
                     }
                 });
+                // compound unique keys
+                m.indices().iter().for_each(|index| {
+                    if index.r#type().is_unique() && index.items().len() > 1 {
+                        let compound_name = index.keys().join("_");
+                        let compound_class_name = format!("{model_name}{}CompoundUniqueInput", compound_name.to_pascal_case());
+                        let compound_fields = index.items().iter().map(|item| {
+                            let field = m.field(item.field_name()).unwrap();
+                            CSharpClassField {
+                                n: item.field_name().to_pascal_case(),
+                                t: field.field_type().to_csharp_type(false),
+                                o: false,
+                                d: Some(field_doc(field)),
+                                j: None
+                            }
+                        }).collect();
+                        let builder = CSharpClassBuilder {
+                            name: compound_class_name.clone(),
+                            fields: compound_fields,
+                            indent_spaces: 4,
+                            indent_level: 0
+                        };
+                        c.indented(builder.build());
+                        where_unique_fields.push(CSharpClassField {
+                            n: compound_name.to_pascal_case(),
+                            t: compound_class_name,
+                            o: true,
+                            d: None,
+                            j: Some(compound_name),
+                        });
+                    }
+                });
                 let builder = CSharpClassBuilder {
                     name: format!("{model_name}WhereUniqueInput"),
                     fields: where_unique_fields,
@@ -889,20 +921,22 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                             j: None,
                         });
                     }
-                    fields.push(CSharpClassField {
-                        n: "Select".to_owned(),
-                        t: format!("{model_name}Select"),
-                        o: true,
-                        d: Some(select_doc(m)),
-                        j: None,
-                    });
-                    fields.push(CSharpClassField {
-                        n: "Include".to_owned(),
-                        t: format!("{model_name}Include"),
-                        o: true,
-                        d: Some(include_doc(m)),
-                        j: None,
-                    });
+                    if a.handler_allowed_input_json_keys().contains("select") {
+                        fields.push(CSharpClassField {
+                            n: "Select".to_owned(),
+                            t: format!("{model_name}Select"),
+                            o: true,
+                            d: Some(select_doc(m)),
+                            j: None,
+                        });
+                        fields.push(CSharpClassField {
+                            n: "Include".to_owned(),
+                            t: format!("{model_name}Include"),
+                            o: true,
+                            d: Some(include_doc(m)),
+                            j: None,
+                        });
+                    }
                     if a.handler_requires_where() {
                         fields.push(CSharpClassField {
                             n: "OrderBy".to_owned(),
@@ -918,6 +952,8 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                             d: Some(cursor_doc(m)),
                             j: None,
                         });
+                    }
+                    if a.handler_requires_where() || *a == Action::from_u32(HISTORY_HANDLER) {
                         fields.push(CSharpClassField {
                             n: "Take".to_owned(),
                             t: "uint".to_owned(),
@@ -947,6 +983,22 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                             j: None,
                         });
                     }
+                    if a.handler_accepts_deleted_filters() && m.soft_delete_field().is_some() {
+                        fields.push(CSharpClassField {
+                            n: "WithDeleted".to_owned(),
+                            t: "bool".to_owned(),
+                            o: true,
+                            d: Some(with_deleted_doc(m)),
+                            j: None,
+                        });
+                        fields.push(CSharpClassField {
+                            n: "OnlyDeleted".to_owned(),
+                            t: "bool".to_owned(),
+                            o: true,
+                            d: Some(only_deleted_doc(m)),
+                            j: None,
+                        });
+                    }
                     if a.handler_requires_create() {
                         fields.push(CSharpClassField {
                             n: "Create".to_owned(),
@@ -974,6 +1026,15 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                             j: None,
                         });
                     }
+                    if a.handler_requires_refresh_token() {
+                        fields.push(CSharpClassField {
+                            n: "RefreshToken".to_owned(),
+                            t: "string".to_owned(),
+                            o: true,
+                            d: Some(refresh_token_doc(m, *a)),
+                            j: None,
+                        });
+                    }
                     let builder = CSharpClassBuilder {
                         name: format!("{model_name}{action_name}Args"),
                         fields,
@@ -1011,6 +1072,7 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                                     ResMeta::Other => "",
                                 };
                                 let res_data = match a.handler_res_data() {
+                                    ResData::Vec if *a == Action::from_u32(HISTORY_HANDLER) => AUDIT_LOG_MODEL.to_string() + "[]",
                                     ResData::Single => model_name.to_string(),
                                     ResData::Vec => model_name.to_string() + "[]",
                                     ResData::Other => "short".to_string(),
@@ -32,6 +32,7 @@ namespace Teo {{
 
     public struct TokenInfo {{
         public string Token {{ get; set; }}
+        public string? RefreshToken {{ get; set; }}
     }}
 
     public class TeoException : Exception {{
@@ -49,6 +50,11 @@ namespace Teo {{
         }}
     }}
 
+    public class TeoConflictException : TeoException {{
+
+        public TeoConflictException(ResponseError responseError) : base(responseError) {{ }}
+    }}
+
     public class Delegate {{
 
         protected static readonly string HOST = "{url}";
@@ -63,6 +69,9 @@ namespace Teo {{
             var content = JSJsonSerializer.Serialize(args)!;
             var response = await client.PostAsync(uri, new HttpStringContent(content));
             var httpResponseBody = await response.Content.ReadAsStringAsync();
+            if (((int)response.StatusCode) == 409) {{
+                throw new TeoConflictException(JSJsonSerializer.Deserialize<ResponseError>(httpResponseBody)!);
+            }}
             if (((int)response.StatusCode) >= 400) {{
                 throw new TeoException(JSJsonSerializer.Deserialize<ResponseError>(httpResponseBody)!);
             }}
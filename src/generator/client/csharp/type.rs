@@ -56,6 +56,7 @@ impl ToCSharpType for FieldType {
             FieldType::Date => "DateOnly".to_string(),
             FieldType::DateTime => "DateTime".to_string(),
             FieldType::Enum(name) => name.to_string(),
+            FieldType::Json => "object".to_string(),
             FieldType::Vec(internal) => internal.field_type().to_csharp_type(internal.optionality.is_optional()) + "[]",
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
@@ -86,6 +87,7 @@ impl ToCSharpType for FieldType {
                 let enum_type = self.to_csharp_type(false);
                 one_of(base_type, format!("Enum{nullable}Filter<{enum_type}>"))
             },
+            FieldType::Json => format!("Json{nullable}Filter"),
             FieldType::Vec(internal) => {
                 let internal_type = internal.field_type().to_csharp_type(false);
                 let prefix = array_prefix(&internal_type);
@@ -110,6 +112,9 @@ impl ToCSharpType for FieldType {
 
     fn to_csharp_update_input_type(&self, optional: bool, no_question_mark: bool) -> String {
         let create_input = self.to_csharp_create_input_type(optional, true);
+        if self.is_json() {
+            return create_input + if no_question_mark { "" } else { "?" };
+        }
         let operation_input = self.to_csharp_update_operation_input(optional);
         one_of(create_input, operation_input) + if no_question_mark { "" } else { "?" }
     }
@@ -131,6 +136,7 @@ impl ToCSharpType for FieldType {
                 let enum_type = self.to_csharp_type(false);
                 format!("{prefix}EnumFieldUpdateOperationsInput<{enum_type}>")
             },
+            FieldType::Json => panic!("Json fields are updated with plain values."),
             FieldType::Vec(internal) => {
                 let internal_type = internal.field_type().to_csharp_type(false);
                 let arr_prefix = array_prefix(&internal_type);
@@ -91,6 +91,7 @@ export type StringFilter = {{
     startsWith?: string
     endsWith?: string
     matches?: string
+    search?: string
     not?: StringFilter | string
 }}
 
@@ -106,6 +107,7 @@ export type StringNullableFilter = {{
     startsWith?: string
     endsWith?: string
     matches?: string
+    search?: string
     not?: StringNullableFilter | string | null
 }}
 
@@ -184,5 +186,21 @@ export type ArrayNullableFilter<T> = {{
     isEmpty?: boolean
     length?: number
 }}
+
+export type JsonFilter = {{
+    path?: string[]
+    equals?: any
+    stringContains?: string
+    arrayContains?: any
+    hasKey?: string
+}}
+
+export type JsonNullableFilter = {{
+    path?: string[]
+    equals?: any | null
+    stringContains?: string
+    arrayContains?: any
+    hasKey?: string
+}}
 "#)
 }
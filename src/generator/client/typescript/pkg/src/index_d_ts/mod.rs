@@ -1,8 +1,10 @@
 use inflector::Inflector;
-use crate::core::action::{ResMeta, ResData, Action, UPDATE_HANDLER, CREATE_HANDLER, FIND_FIRST_HANDLER, UPSERT_HANDLER, UPDATE_MANY_HANDLER};
+use itertools::Itertools;
+use crate::core::action::{ResMeta, ResData, Action, UPDATE_HANDLER, CREATE_HANDLER, FIND_FIRST_HANDLER, UPSERT_HANDLER, UPDATE_MANY_HANDLER, HISTORY_HANDLER};
+use crate::core::audit::AUDIT_LOG_MODEL;
 use crate::core::app::conf::ClientGeneratorConf;
 use crate::core::field::r#type::FieldTypeOwner;
-use crate::generator::client::typescript::pkg::src::index_d_ts::docs::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, field_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_token_doc};
+use crate::generator::client::typescript::pkg::src::index_d_ts::docs::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, field_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, refresh_token_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_token_doc, with_deleted_doc, only_deleted_doc};
 use crate::generator::client::typescript::r#type::ToTypeScriptType;
 
 use crate::core::graph::Graph;
@@ -351,6 +353,13 @@ export declare class TeoError extends Error {
 
     constructor(responseError: ResponseError)
 
+    get name(): string
+}
+
+export declare class TeoConflictError extends TeoError {
+
+    constructor(responseError: ResponseError)
+
     get name(): string
 }"#);
         c.empty_line();
@@ -437,6 +446,18 @@ export declare class TeoError extends Error {
                         });
                     }
                 });
+                // compound unique keys
+                m.indices().iter().for_each(|index| {
+                    if (index.r#type() == Primary || index.r#type() == Unique) && index.items().len() > 1 {
+                        let compound_name = index.keys().join("_");
+                        let compound_type = index.items().iter().map(|item| {
+                            let field = m.field(item.field_name()).unwrap();
+                            let ts_type = field.field_type().to_typescript_type(field.optionality.is_optional());
+                            format!("{}: {ts_type}", item.field_name())
+                        }).join(", ");
+                        b.line(format!("{compound_name}?: {{ {compound_type} }}"));
+                    }
+                });
             }, "}");
             // relation filter
             c.block(format!("export type {model_name}RelationFilter = {{"), |b| {
@@ -461,8 +482,20 @@ export declare class TeoError extends Error {
                         let _relation_name = relation.name();
                         //b.line(format!("{relation_name}?: {relation_model}OrderByRelationAggregateInput"));
                     }
-                })
+                });
+                if m.indices().iter().any(|i| i.r#type().is_fulltext()) {
+                    b.line(format!("_relevance?: {model_name}OrderByRelevanceInput"));
+                }
             }, "}");
+            // relevance of full-text search
+            let fulltext_keys: Vec<String> = m.indices().iter().filter(|i| i.r#type().is_fulltext()).flat_map(|i| i.keys().iter().map(|k| format!("\"{k}\""))).unique().collect();
+            if !fulltext_keys.is_empty() {
+                c.block(format!("export type {model_name}OrderByRelevanceInput = {{"), |b| {
+                    b.line(format!("fields: Enumerable<{}>", fulltext_keys.join(" | ")));
+                    b.line("search: string");
+                    b.line("sort: SortOrder");
+                }, "}");
+            }
             // create and update inputs without anything
             c.line(generate_model_create_input(graph, m, None));
             c.line(generate_model_create_nested_input(graph, m, None, true));
@@ -515,10 +548,22 @@ export declare class TeoError extends Error {
                         b.doc(unique_where_doc(m));
                         b.line(format!(r#"where?: {model_name}WhereUniqueInput"#));
                     }
-                    b.doc(select_doc(m));
-                    b.line(format!(r#"select?: {model_name}Select"#));
-                    b.doc(include_doc(m));
-                    b.line(format!(r#"include?: {model_name}Include"#));
+                    if a.handler_allowed_input_json_keys().contains("select") {
+                        b.doc(select_doc(m));
+                        b.line(format!(r#"select?: {model_name}Select"#));
+                        b.doc(include_doc(m));
+                        b.line(format!(r#"include?: {model_name}Include"#));
+                    }
+                    if a == &Action::from_u32(HISTORY_HANDLER) {
+                        b.doc(take_doc(m));
+                        b.line(format!(r#"take?: number"#));
+                        b.doc(skip_doc(m));
+                        b.line(format!(r#"skip?: number"#));
+                        b.doc(page_size_doc(m));
+                        b.line(format!(r#"pageSize?: number"#));
+                        b.doc(page_number_doc(m));
+                        b.line(format!(r#"pageNumber?: number"#));
+                    }
                     if a.handler_requires_where() {
                         b.doc(order_by_doc(m));
                         b.line(format!(r#"orderBy?: Enumerable<{model_name}OrderByInput>"#));
@@ -534,6 +579,12 @@ export declare class TeoError extends Error {
                         b.line(format!(r#"pageNumber?: number"#));
                         //b.line(format!{r#"distinct? {model_name}ScalarFieldEnum"#})
                     }
+                    if a.handler_accepts_deleted_filters() && m.soft_delete_field().is_some() {
+                        b.doc(with_deleted_doc(m));
+                        b.line(r#"withDeleted?: boolean"#);
+                        b.doc(only_deleted_doc(m));
+                        b.line(r#"onlyDeleted?: boolean"#);
+                    }
                     if a.handler_requires_create() {
                         b.doc(create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(CREATE_HANDLER) } else { a.clone() }));
                         b.line(format!(r#"create: {model_name}CreateInput"#));
@@ -546,6 +597,10 @@ export declare class TeoError extends Error {
                         b.doc(credentials_doc(m, *a));
                         b.line(format!(r#"credentials: {model_name}CredentialsInput"#))
                     }
+                    if a.handler_requires_refresh_token() {
+                        b.doc(refresh_token_doc(m, *a));
+                        b.line(r#"refreshToken: string"#)
+                    }
                 }, "}");
             });
             // get payload is for typescript only
@@ -606,6 +661,10 @@ export declare class TeoError extends Error {
                             };
                             b.empty_line();
                             b.doc(action_doc(object_name, a.clone(), m));
+                            if a == &Action::from_u32(HISTORY_HANDLER) {
+                                b.line(format!("{action_var_name}(args?: {model_name}{action_capitalized_name}Args): Promise<Response<{res_meta}, {AUDIT_LOG_MODEL}[]>>"));
+                                return
+                            }
                             b.line(format!("{action_var_name}<T extends {model_name}{action_capitalized_name}Args>(args?: T): Promise<Response<{res_meta}, CheckSelectInclude<T, {res_data}, {model_name}GetPayload<T>{payload_array}>>>"));
                         }
                     });
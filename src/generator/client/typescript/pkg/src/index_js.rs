@@ -103,6 +103,9 @@ async function request(urlSegmentName, action, args, token = getBearerToken()) {
       return value
     }}
   }})
+  if (response.status === 409) {{
+      throw new TeoConflictError(response_json.error)
+  }}
   if (400 <= response.status) {{
       throw new TeoError(response_json.error)
   }}
@@ -123,6 +126,18 @@ class TeoError extends Error {{
   }}
 }}
 
+class TeoConflictError extends TeoError {{
+
+  constructor(responseError) {{
+      super(responseError)
+      Object.setPrototypeOf(this, TeoConflictError.prototype)
+  }}
+
+  get name() {{
+      return "TeoConflictError"
+  }}
+}}
+
 
 class Delegate {{
 
@@ -177,6 +192,7 @@ module.exports = {{
   setBearerToken,
   getBearerToken,
   TeoError,
+  TeoConflictError,
   {object_name},
 }}
 "#)
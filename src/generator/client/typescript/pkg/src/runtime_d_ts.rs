@@ -54,6 +54,7 @@ export type PagingInfo = {{
 
 export type TokenInfo = {{
     token: string
+    refreshToken?: string
 }}
 "#)
 }
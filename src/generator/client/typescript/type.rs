@@ -19,6 +19,7 @@ impl ToTypeScriptType for FieldType {
             FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 => "number".to_string(),
             FieldType::Decimal => "Decimal".to_string(),
             FieldType::Enum(name) => name.to_string(),
+            FieldType::Json => "any".to_string(),
             FieldType::Vec(internal) => internal.field_type().to_typescript_type(internal.optionality.is_optional()) + "[]",
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
@@ -42,6 +43,7 @@ impl ToTypeScriptType for FieldType {
             FieldType::Bool => "boolean | Bool".to_string(),
             FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 => "number | Number".to_string(),
             FieldType::Decimal => "string | Decimal | Decimal".to_string(),
+            FieldType::Json => "Json".to_string(),
             FieldType::Enum(name) => {
                 with_generic = true;
                 if optional {
@@ -84,6 +86,7 @@ impl ToTypeScriptType for FieldType {
             FieldType::Bool => "boolean".to_string(),
             FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 => "number".to_string(),
             FieldType::Enum(name) => name.to_string(),
+            FieldType::Json => "any".to_string(),
             FieldType::Vec(internal) => internal.field_type().to_typescript_type(internal.optionality.is_optional()) + "[]",
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
@@ -97,6 +100,9 @@ impl ToTypeScriptType for FieldType {
     }
 
     fn to_typescript_update_input_type(&self, optional: bool) -> String {
+        if self.is_json() {
+            return self.to_typescript_create_input_type(optional);
+        }
         let update_operation = self.to_typescript_update_operation_input(optional);
         let create_input = self.to_typescript_create_input_type(optional);
         return format!("{update_operation} | {create_input}");
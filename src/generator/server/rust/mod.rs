@@ -35,7 +35,7 @@ impl RustEntityGenerator {
     }
 
     fn setter_type_for_field<T: FieldTypeOwner>(&self, field: &T) -> String {
-        if field.is_optional() && !field.field_type().is_string() {
+        if field.is_optional() && !field.field_type().is_string() && !field.field_type().is_json() {
             format!("Option<{}>", self.setter_type_for_field_type(field.field_type(), false))
         } else {
             self.setter_type_for_field_type(field.field_type(), field.is_optional())
@@ -43,7 +43,8 @@ impl RustEntityGenerator {
     }
 
     fn getter_type_for_field<T: FieldTypeOwner>(&self, field: &T) -> String {
-        if field.is_optional() {
+        // a null json value is represented by `Value::Null`
+        if field.is_optional() && !field.field_type().is_json() {
             format!("Option<{}>", self.getter_type_for_field_type(field.field_type()))
         } else {
             self.getter_type_for_field_type(field.field_type())
@@ -63,6 +64,7 @@ impl RustEntityGenerator {
             FieldType::Date => "NaiveDate".to_owned(),
             FieldType::DateTime => "DateTime<Utc>".to_owned(),
             FieldType::Enum(name) => name.clone(),
+            FieldType::Json => "Value".to_owned(),
             FieldType::Vec(inner) => format!("Vec<{}>", self.getter_type_for_field(inner.as_ref())),
             FieldType::HashMap(inner) => format!("HashMap<String, {}>", self.getter_type_for_field(inner.as_ref())),
             FieldType::BTreeMap(inner) => format!("BTreemap<String, {}>", self.getter_type_for_field(inner.as_ref())),
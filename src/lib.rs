@@ -7,6 +7,9 @@ pub mod interface;
 pub mod prelude {
     pub use crate::core::app::App;
     pub use crate::core::app::builder::AppBuilder;
+    pub use crate::core::app::route::RouteContext;
+    pub use crate::core::app::middleware::{MiddlewareContext, Next};
+    pub use actix_web::{HttpRequest, HttpResponse};
     pub use crate::core::graph::Graph;
     pub use crate::core::graph::builder::GraphBuilder;
     pub use crate::core::teon::Value;
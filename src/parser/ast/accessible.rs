@@ -11,8 +11,10 @@ use crate::core::property::Property;
 use crate::core::relation::Relation;
 use crate::parser::ast::argument::Argument;
 use crate::parser::ast::entity::Entity;
+use crate::parser::ast::span::Span;
 use crate::parser::std::callables::date_constructor::date_constructor;
 use crate::parser::std::callables::datetime_constructor::datetime_constructor;
+use crate::parser::std::callables::env_function::env_function;
 use crate::parser::std::callables::float_constructor::float_constructor;
 use crate::parser::std::callables::int_constructor::int_constructor;
 #[cfg(feature = "data-source-mongodb")]
@@ -21,19 +23,19 @@ use crate::parser::std::callables::string_constructor::string_constructor;
 use crate::parser::std::constants::EnvObject;
 use crate::prelude::Value;
 
-pub(crate) type Callable = fn(args: Vec<Argument>) -> Value;
+pub(crate) type Callable = fn(args: Vec<Argument>) -> Result<Value, String>;
 
-pub(crate) type FieldDecorator = fn(args: Vec<Argument>, field: &mut Field);
+pub(crate) type FieldDecorator = fn(args: Vec<Argument>, field: &mut Field) -> Result<(), String>;
 
-pub(crate) type RelationDecorator = fn(args: Vec<Argument>, relation: &mut Relation);
+pub(crate) type RelationDecorator = fn(args: Vec<Argument>, relation: &mut Relation) -> Result<(), String>;
 
-pub(crate) type PropertyDecorator = fn(args: Vec<Argument>, property: &mut Property);
+pub(crate) type PropertyDecorator = fn(args: Vec<Argument>, property: &mut Property) -> Result<(), String>;
 
-pub(crate) type ModelDecorator = fn(args: Vec<Argument>, model: &mut ModelBuilder);
+pub(crate) type ModelDecorator = fn(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String>;
 
-pub(crate) type ASTPipelineInstaller = fn(args: Vec<Argument>) -> Arc<dyn Item>;
+pub(crate) type ASTPipelineInstaller = fn(args: Vec<Argument>) -> Result<Arc<dyn Item>, String>;
 
-pub(crate) type ASTFunctionInstaller = fn(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Arc<dyn Item>;
+pub(crate) type ASTFunctionInstaller = fn(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Result<Arc<dyn Item>, String>;
 
 #[derive(Debug, Clone)]
 pub(crate) struct ASTPipelineItem {
@@ -41,6 +43,7 @@ pub(crate) struct ASTPipelineItem {
     pub(crate) function_installer: Option<ASTFunctionInstaller>,
     pub(crate) lookup_table: Option<Arc<Mutex<CallbackLookupTable>>>,
     pub(crate) args: Vec<Argument>,
+    pub(crate) span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -49,16 +52,20 @@ pub(crate) struct ASTPipeline {
 }
 
 impl ASTPipeline {
-    pub(crate) fn to_value_pipeline(&self) -> Pipeline {
+    /// Installs the items. An installer error is returned with the span of its item.
+    pub(crate) fn to_value_pipeline(&self) -> Result<Pipeline, (Span, String)> {
         let mut modifiers = vec![];
         for item in self.items.iter() {
-            if let Some(installer) = item.installer {
-                modifiers.push((installer)(item.args.clone()));
+            let modifier = if let Some(installer) = item.installer {
+                (installer)(item.args.clone())
             } else if let Some(function_installer) = item.function_installer {
-                modifiers.push((function_installer)(item.lookup_table.as_ref().unwrap().clone(), item.args.clone()));
-            }
+                (function_installer)(item.lookup_table.as_ref().unwrap().clone(), item.args.clone())
+            } else {
+                continue
+            };
+            modifiers.push(modifier.map_err(|message| (item.span, message))?);
         }
-        Pipeline { items: modifiers }
+        Ok(Pipeline { items: modifiers })
     }
 }
 
@@ -72,6 +79,7 @@ impl Container {
         Self {
             objects: hashmap!{
                 "ENV".to_owned() => Entity::Accessible(Accessible::Env(EnvObject {})),
+                "env".to_owned() => Entity::Accessible(Accessible::Callable(env_function)),
                 #[cfg(feature = "data-source-mongodb")]
                 "ObjectId".to_owned() => Entity::Accessible(Accessible::Callable(object_id_constructor)),
                 "Int".to_owned() => Entity::Accessible(Accessible::Callable(int_constructor)),
@@ -83,8 +91,8 @@ impl Container {
         }
     }
 
-    pub(crate) fn access_property(&self, name: &str) -> &Entity {
-        self.objects.get(name).unwrap()
+    pub(crate) fn access_property(&self, name: &str) -> Option<&Entity> {
+        self.objects.get(name)
     }
 }
 
@@ -157,10 +165,7 @@ impl Accessible {
         }
     }
 
-    pub(crate) fn access_property(&self, name: &str) -> &Entity {
-        match self.as_container() {
-            Some(c) => c.access_property(name),
-            None => panic!("Cannot access property '{}'", name),
-        }
+    pub(crate) fn access_property(&self, name: &str) -> Option<&Entity> {
+        self.as_container().and_then(|c| c.access_property(name))
     }
 }
@@ -3,6 +3,7 @@ use crate::parser::ast::entity::Entity;
 use crate::parser::ast::expression::ExpressionKind;
 use crate::parser::ast::identifier::Identifier;
 use crate::parser::ast::span::Span;
+use crate::prelude::Value;
 
 #[derive(Debug, Clone)]
 pub struct Argument {
@@ -12,6 +13,17 @@ pub struct Argument {
     pub(crate) resolved: Option<Entity>,
 }
 
+impl Argument {
+
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|n| n.name.as_str())
+    }
+
+    pub(crate) fn resolved_value(&self) -> Option<&Value> {
+        self.resolved.as_ref().and_then(|r| r.as_value())
+    }
+}
+
 impl Display for Argument {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some(name) = &self.name {
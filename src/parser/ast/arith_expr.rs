@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
 use crate::parser::ast::expression::ExpressionKind;
+use crate::parser::ast::span::Span;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Op {
@@ -44,6 +45,15 @@ pub(crate) enum ArithExpr {
     },
 }
 
+impl ArithExpr {
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            ArithExpr::Expression(e) | ArithExpr::UnaryNeg(e) | ArithExpr::UnaryBitNeg(e) => e.span(),
+            ArithExpr::BinaryOp { lhs, op: _, rhs } => Span::new(lhs.span().start, rhs.span().end),
+        }
+    }
+}
+
 impl Display for ArithExpr {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1,4 +1,5 @@
 use crate::parser::ast::span::Span;
+use crate::core::app::conf::{JwtAlgorithm, LogFormat, LogLevel};
 use crate::parser::ast::item::Item;
 
 #[derive(Debug, Clone)]
@@ -8,8 +9,22 @@ pub struct ServerConfig {
     pub(crate) items: Vec<Item>,
     pub(crate) span: Span,
     pub(crate) bind: Option<(String, u16)>,
+    pub(crate) jwt_algorithm: Option<JwtAlgorithm>,
     pub(crate) jwt_secret: Option<String>,
+    pub(crate) jwt_private_key: Option<String>,
+    pub(crate) jwt_public_key: Option<String>,
+    pub(crate) jwt_expires_in: Option<i64>,
+    pub(crate) refresh_token_expires_in: Option<i64>,
     pub(crate) path_prefix: Option<String>,
+    pub(crate) allow_origins: Option<Vec<String>>,
+    pub(crate) allow_methods: Option<Vec<String>>,
+    pub(crate) allow_credentials: Option<bool>,
+    pub(crate) max_body_size: Option<usize>,
+    pub(crate) headers: Option<Vec<(String, String)>>,
+    pub(crate) middlewares: Option<Vec<String>>,
+    pub(crate) log_level: Option<LogLevel>,
+    pub(crate) log_format: Option<LogFormat>,
+    pub(crate) log_queries: Option<bool>,
 }
 
 impl ServerConfig {
@@ -20,8 +35,22 @@ impl ServerConfig {
             items,
             span,
             bind: None,
+            jwt_algorithm: None,
             jwt_secret: None,
+            jwt_private_key: None,
+            jwt_public_key: None,
+            jwt_expires_in: None,
+            refresh_token_expires_in: None,
             path_prefix: None,
+            allow_origins: None,
+            allow_methods: None,
+            allow_credentials: None,
+            max_body_size: None,
+            headers: None,
+            middlewares: None,
+            log_level: None,
+            log_format: None,
+            log_queries: None,
         }
     }
 }
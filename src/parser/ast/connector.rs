@@ -1,22 +1,36 @@
+use crate::core::app::conf::PoolConf;
 use crate::core::database::name::DatabaseName;
 use crate::parser::ast::span::Span;
 use crate::parser::ast::item::Item;
+use crate::parser::ast::identifier::Identifier;
+
+pub(crate) const DEFAULT_CONNECTOR_NAME: &str = "default";
 
 #[derive(Debug, Clone)]
 pub struct Connector {
     pub(crate) id: usize,
     pub(crate) source_id: usize,
+    pub(crate) identifier: Option<Identifier>,
     pub(crate) items: Vec<Item>,
     pub(crate) span: Span,
     pub(crate) provider: Option<DatabaseName>,
     pub(crate) url: Option<String>,
     pub(crate) debug: bool,
+    pub(crate) pool: PoolConf,
 }
 
 impl Connector {
-    pub(crate) fn new(items: Vec<Item>, span: Span, source_id: usize, item_id: usize) -> Self {
+    pub(crate) fn new(identifier: Option<Identifier>, items: Vec<Item>, span: Span, source_id: usize, item_id: usize) -> Self {
         Self {
-            id: item_id, items, span, source_id, provider: None, url: None, debug: false
+            id: item_id, identifier, items, span, source_id, provider: None, url: None, debug: false, pool: PoolConf::default()
+        }
+    }
+
+    /// The name models refer to with `@@connector`. An unnamed connector is the default one.
+    pub(crate) fn name(&self) -> &str {
+        match &self.identifier {
+            Some(identifier) => identifier.name.as_str(),
+            None => DEFAULT_CONNECTOR_NAME,
         }
     }
 }
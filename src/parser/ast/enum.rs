@@ -36,10 +36,11 @@ pub(crate) struct EnumChoice {
     pub(crate) decorators: Vec<Decorator>,
     pub(crate) span: Span,
     pub(crate) resolved: bool,
+    pub(crate) renamed: Vec<String>,
 }
 
 impl EnumChoice {
     pub(crate) fn new(identifier: Identifier, decorators: Vec<Decorator>, span: Span) -> Self {
-        Self { identifier, decorators, span, resolved: false }
+        Self { identifier, decorators, span, resolved: false, renamed: vec![] }
     }
 }
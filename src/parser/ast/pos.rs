@@ -17,3 +17,16 @@ impl fmt::Display for Pos {
         write!(f, "{}:{}", self.line, self.column)
     }
 }
+
+impl Pos {
+
+    /// The position of a byte offset in a schema file's content.
+    pub(crate) fn from_offset(content: &str, offset: usize) -> Self {
+        let offset = offset.min(content.len());
+        let before = &content[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = content[line_start..offset].chars().count() + 1;
+        Self { line, column }
+    }
+}
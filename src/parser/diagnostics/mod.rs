@@ -0,0 +1,73 @@
+pub(crate) mod printer;
+
+use std::path::PathBuf;
+use crate::parser::ast::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiagnosticsLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DiagnosticsItem {
+    pub(crate) level: DiagnosticsLevel,
+    pub(crate) message: String,
+    pub(crate) source_path: Option<PathBuf>,
+    pub(crate) span: Option<Span>,
+}
+
+/// Collects the errors and warnings found while loading a schema, so that all of them are
+/// reported at once.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Diagnostics {
+    items: Vec<DiagnosticsItem>,
+}
+
+impl Diagnostics {
+
+    pub(crate) fn new() -> Self {
+        Self { items: vec![] }
+    }
+
+    pub(crate) fn insert_error(&mut self, source_path: &PathBuf, span: Span, message: impl Into<String>) {
+        self.insert(DiagnosticsLevel::Error, Some(source_path.clone()), Some(span), message.into());
+    }
+
+    pub(crate) fn insert_warning(&mut self, source_path: &PathBuf, span: Span, message: impl Into<String>) {
+        self.insert(DiagnosticsLevel::Warning, Some(source_path.clone()), Some(span), message.into());
+    }
+
+    /// Records an error which doesn't belong to a place in a schema file.
+    pub(crate) fn insert_unlocated_error(&mut self, message: impl Into<String>) {
+        self.insert(DiagnosticsLevel::Error, None, None, message.into());
+    }
+
+    fn insert(&mut self, level: DiagnosticsLevel, source_path: Option<PathBuf>, span: Option<Span>, message: String) {
+        self.items.push(DiagnosticsItem { level, message, source_path, span });
+    }
+
+    pub(crate) fn extend(&mut self, other: Diagnostics) {
+        self.items.extend(other.items);
+    }
+
+    pub(crate) fn items(&self) -> &Vec<DiagnosticsItem> {
+        &self.items
+    }
+
+    pub(crate) fn has_errors(&self) -> bool {
+        self.items.iter().any(|i| i.level == DiagnosticsLevel::Error)
+    }
+
+    pub(crate) fn errors_count(&self) -> usize {
+        self.items.iter().filter(|i| i.level == DiagnosticsLevel::Error).count()
+    }
+
+    pub(crate) fn warnings_count(&self) -> usize {
+        self.items.iter().filter(|i| i.level == DiagnosticsLevel::Warning).count()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
@@ -0,0 +1,69 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use colored::Colorize;
+use crate::parser::ast::pos::Pos;
+use crate::parser::diagnostics::{Diagnostics, DiagnosticsItem, DiagnosticsLevel};
+
+/// Prints diagnostics with annotated source snippets, in the style of rustc.
+pub(crate) fn print_diagnostics(diagnostics: &Diagnostics) {
+    for item in diagnostics.items() {
+        eprintln!("{}", format_item(item));
+    }
+}
+
+pub(crate) fn print_summary(diagnostics: &Diagnostics) {
+    let errors = diagnostics.errors_count();
+    let warnings = diagnostics.warnings_count();
+    let warnings_str = match warnings {
+        0 => "".to_owned(),
+        1 => "; 1 warning emitted".to_owned(),
+        n => format!("; {n} warnings emitted"),
+    };
+    match errors {
+        0 => eprintln!("{}: schema is valid{}", "success".green().bold(), warnings_str),
+        1 => eprintln!("{}: could not load schema due to previous error{}", "error".red().bold(), warnings_str),
+        n => eprintln!("{}: could not load schema due to {n} previous errors{}", "error".red().bold(), warnings_str),
+    }
+}
+
+fn format_item(item: &DiagnosticsItem) -> String {
+    let level = match item.level {
+        DiagnosticsLevel::Error => "error".red().bold(),
+        DiagnosticsLevel::Warning => "warning".yellow().bold(),
+    };
+    let mut result = format!("{}{} {}\n", level, ":".bold(), item.message.bold());
+    let (Some(path), Some(span)) = (&item.source_path, &item.span) else {
+        return result;
+    };
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let start = Pos::from_offset(&content, span.start);
+    let end = Pos::from_offset(&content, span.end);
+    let line_content = content.lines().nth(start.line - 1).unwrap_or("");
+    let gutter = " ".repeat(start.line.to_string().len());
+    let underline_len = if end.line == start.line {
+        (end.column - start.column).max(1)
+    } else {
+        (line_content.chars().count() + 1 - start.column).max(1)
+    };
+    let marker = "^".repeat(underline_len);
+    let marker = match item.level {
+        DiagnosticsLevel::Error => marker.red().bold(),
+        DiagnosticsLevel::Warning => marker.yellow().bold(),
+    };
+    result += &format!("{gutter}{} {}:{}\n", "-->".blue().bold(), display_path(path), start);
+    result += &format!("{gutter} {}\n", "|".blue().bold());
+    result += &format!("{} {} {}\n", start.line.to_string().blue().bold(), "|".blue().bold(), line_content);
+    result += &format!("{gutter} {} {}{}\n", "|".blue().bold(), " ".repeat(start.column - 1), marker);
+    result
+}
+
+pub(crate) fn display_path(path: &PathBuf) -> String {
+    match env::current_dir() {
+        Ok(dir) => match path.strip_prefix(&dir) {
+            Ok(relative) => relative.to_str().unwrap().to_owned(),
+            Err(_) => path.to_str().unwrap().to_owned(),
+        },
+        Err(_) => path.to_str().unwrap().to_owned(),
+    }
+}
@@ -0,0 +1,354 @@
+use pest::Parser as PestParser;
+use crate::parser::parser::{Rule, SchemaParser};
+
+type Pair<'a> = pest::iterators::Pair<'a, Rule>;
+
+const INDENT: &str = "  ";
+
+/// Prints a schema file in the canonical style. Comments are kept, fields and config items are
+/// aligned in columns, and expressions are printed on one line without trailing commas.
+pub(crate) fn format_schema(content: &str) -> Result<String, pest::error::Error<Rule>> {
+    let schema = SchemaParser::parse(Rule::schema, content)?.next().unwrap();
+    let mut result = String::new();
+    let mut previous: Option<Rule> = None;
+    let mut line_ended = true;
+    let mut blank = false;
+    for current in schema.into_inner() {
+        match current.as_rule() {
+            Rule::EOI => break,
+            Rule::EMPTY_LINES => {
+                blank = blank || has_blank_line(&current, line_ended);
+                line_ended = true;
+                continue
+            }
+            _ => (),
+        }
+        if let Some(previous) = previous {
+            let grouped = previous == Rule::comment_block
+                || (previous == current.as_rule() && matches!(previous, Rule::import_statement | Rule::let_declaration));
+            if blank || !grouped {
+                result.push('\n');
+            }
+        }
+        previous = Some(current.as_rule());
+        line_ended = matches!(current.as_rule(), Rule::comment_block | Rule::CATCH_ALL);
+        blank = false;
+        match current.as_rule() {
+            Rule::import_statement => result += &format!("{}\n", import(current)),
+            Rule::let_declaration => result += &format!("{}\n", let_declaration(current)),
+            Rule::config_declaration | Rule::model_declaration | Rule::enum_declaration => result += &block_declaration(current),
+            Rule::comment_block => result += &comment_lines(current),
+            _ => result += &format!("{}\n", current.as_str().trim()),
+        }
+    }
+    Ok(result)
+}
+
+// The line break which ends an item is part of the item for some rules, and part of the empty
+// lines after it for others.
+fn has_blank_line(pair: &Pair<'_>, line_ended: bool) -> bool {
+    let newlines = pair.as_str().matches('\n').count();
+    newlines > if line_ended { 0 } else { 1 }
+}
+
+fn import(pair: Pair<'_>) -> String {
+    let mut identifiers = None;
+    let mut source = "";
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::import_identifier_list => identifiers = Some(current.into_inner().map(|i| i.as_str()).collect::<Vec<&str>>().join(", ")),
+            Rule::string_literal => source = current.as_str(),
+            _ => unreachable!(),
+        }
+    }
+    match identifiers {
+        Some(identifiers) => format!("import {{ {} }} from {}", identifiers, source),
+        None => format!("import {}", source),
+    }
+}
+
+fn let_declaration(pair: Pair<'_>) -> String {
+    let mut inner = pair.into_inner();
+    let identifier = inner.next().unwrap().as_str();
+    format!("let {} = {}", identifier, expression(inner.next().unwrap()))
+}
+
+enum Line {
+    Blank,
+    Text(String),
+    Column(ColumnLine),
+}
+
+/// A field, enum choice or config item. Its right side and trailing comment are aligned with
+/// the neighbouring lines.
+struct ColumnLine {
+    above: Vec<String>,
+    left: String,
+    right: Option<String>,
+    trailing_comment: Option<String>,
+}
+
+fn block_declaration(pair: Pair<'_>) -> String {
+    let mut result = String::new();
+    let mut header: Vec<String> = vec![];
+    let mut decorators: Vec<Pair<'_>> = vec![];
+    let mut lines: Vec<Line> = vec![];
+    let mut line_ended = true;
+    for current in pair.into_inner() {
+        if current.as_rule() != Rule::EMPTY_LINES {
+            line_ended = current.as_rule() != Rule::config_item;
+        }
+        match current.as_rule() {
+            Rule::triple_comment_block => result += &comment_lines(current),
+            Rule::item_decorator => decorators.push(current),
+            Rule::config_keywords | Rule::MODEL_KEYWORD | Rule::ENUM_KEYWORD | Rule::identifier => header.push(current.as_str().trim().to_owned()),
+            Rule::BLOCK_OPEN => {
+                for line in decorator_lines(decorators.drain(..)) {
+                    result += &format!("{}\n", line);
+                }
+                let rest = current.as_str()[1..].trim();
+                header.push(if rest.is_empty() { "{".to_owned() } else { format!("{{ {}", rest) });
+                result += &format!("{}\n", header.join(" "));
+            }
+            Rule::EMPTY_LINES => {
+                if has_blank_line(&current, line_ended) {
+                    lines.push(Line::Blank);
+                }
+                line_ended = true;
+            }
+            Rule::comment_block => lines.extend(comment_lines(current).lines().map(|l| Line::Text(l.to_owned()))),
+            Rule::block_decorator => lines.push(Line::Text(decorator(current))),
+            Rule::field_declaration => lines.push(Line::Column(field(current))),
+            Rule::enum_value_declaration => lines.push(Line::Column(enum_choice(current))),
+            Rule::config_item => lines.push(Line::Column(config_item(current))),
+            Rule::BLOCK_CLOSE => {
+                result += &block_lines(lines);
+                result += "}\n";
+                break
+            }
+            _ => lines.push(Line::Text(current.as_str().trim().to_owned())),
+        }
+    }
+    result
+}
+
+fn block_lines(lines: Vec<Line>) -> String {
+    lines.split(|l| matches!(l, Line::Blank))
+        .filter(|p| !p.is_empty())
+        .map(paragraph)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn paragraph(lines: &[Line]) -> String {
+    let columns: Vec<&ColumnLine> = lines.iter().filter_map(|l| match l {
+        Line::Column(column) => Some(column),
+        _ => None,
+    }).collect();
+    let left_width = columns.iter().filter(|c| c.right.is_some()).map(|c| c.left.chars().count()).max().unwrap_or(0);
+    let text = |column: &ColumnLine| match &column.right {
+        Some(right) => format!("{:width$} {}", column.left, right, width = left_width),
+        None => column.left.clone(),
+    };
+    let text_width = columns.iter().filter(|c| c.trailing_comment.is_some()).map(|c| text(c).chars().count()).max().unwrap_or(0);
+    let mut result = String::new();
+    for line in lines {
+        match line {
+            Line::Column(column) => {
+                for above in column.above.iter() {
+                    result += &format!("{}{}\n", INDENT, above);
+                }
+                match &column.trailing_comment {
+                    Some(comment) => result += &format!("{}{:width$} {}\n", INDENT, text(column), comment, width = text_width),
+                    None => result += &format!("{}{}\n", INDENT, text(column)),
+                }
+            }
+            Line::Text(text) => result += &format!("{}{}\n", INDENT, text),
+            Line::Blank => (),
+        }
+    }
+    result
+}
+
+fn field(pair: Pair<'_>) -> ColumnLine {
+    let mut above = vec![];
+    let mut decorators = vec![];
+    let mut left = String::new();
+    let mut right = String::new();
+    let mut trailing_comment = None;
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::triple_comment_block => above.extend(comment_lines(current).lines().map(|l| l.to_owned())),
+            Rule::item_decorator => decorators.push(current),
+            Rule::identifier => left = current.as_str().to_owned(),
+            Rule::COLON => left.push(':'),
+            Rule::field_type => right = current.into_inner().map(|p| p.as_str()).collect(),
+            Rule::trailing_comment => trailing_comment = Some(current.as_str().trim().to_owned()),
+            _ => unreachable!(),
+        }
+    }
+    above.extend(decorator_lines(decorators.into_iter()));
+    ColumnLine { above, left, right: Some(right), trailing_comment }
+}
+
+fn enum_choice(pair: Pair<'_>) -> ColumnLine {
+    let mut decorators = vec![];
+    let mut left = String::new();
+    let mut trailing_comment = None;
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::item_decorator => decorators.push(current),
+            Rule::identifier => left = current.as_str().to_owned(),
+            Rule::trailing_comment => trailing_comment = Some(current.as_str().trim().to_owned()),
+            _ => unreachable!(),
+        }
+    }
+    ColumnLine { above: decorator_lines(decorators.into_iter()), left, right: None, trailing_comment }
+}
+
+fn config_item(pair: Pair<'_>) -> ColumnLine {
+    let mut left = String::new();
+    let mut right = String::new();
+    let mut trailing_comment = None;
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::identifier => left = current.as_str().to_owned(),
+            Rule::expression => right = expression(current),
+            Rule::trailing_comment => trailing_comment = Some(current.as_str().trim().to_owned()),
+            _ => unreachable!(),
+        }
+    }
+    ColumnLine { above: vec![], left, right: Some(right), trailing_comment }
+}
+
+/// Decorators are put above the item they decorate. The ones written on the same line stay
+/// together.
+fn decorator_lines<'a>(decorators: impl Iterator<Item=Pair<'a>>) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+    let mut last_line = 0;
+    for current in decorators {
+        let line = current.as_span().start_pos().line_col().0;
+        let decorator = decorator(current);
+        match result.last_mut() {
+            Some(last) if line == last_line => *last += &format!(" {}", decorator),
+            _ => result.push(decorator),
+        }
+        last_line = line;
+    }
+    result
+}
+
+fn decorator(pair: Pair<'_>) -> String {
+    let prefix = if pair.as_rule() == Rule::block_decorator { "@@" } else { "@" };
+    format!("{}{}", prefix, expression(pair.into_inner().next().unwrap()))
+}
+
+fn comment_lines(pair: Pair<'_>) -> String {
+    pair.into_inner().flatten()
+        .filter(|p| matches!(p.as_rule(), Rule::triple_comment | Rule::double_comment))
+        .map(|p| format!("{}\n", p.as_str().trim()))
+        .collect()
+}
+
+fn expression(pair: Pair<'_>) -> String {
+    match pair.as_rule() {
+        Rule::expression | Rule::operand | Rule::range_end | Rule::argument => expression(pair.into_inner().next().unwrap()),
+        Rule::negation => format!("-{}", expression(pair.into_inner().next().unwrap())),
+        Rule::bitwise_negation => format!("~{}", expression(pair.into_inner().next().unwrap())),
+        Rule::arith_expr => pair.into_inner().map(|p| match p.as_rule() {
+            Rule::operand => expression(p),
+            _ => p.as_str().to_owned(),
+        }).collect::<Vec<String>>().join(" "),
+        Rule::nullish_coalescing => pair.into_inner().map(expression).collect::<Vec<String>>().join(" ?? "),
+        Rule::unit | Rule::identifier_unit | Rule::unit_without_range_literal => {
+            let mut inner = pair.into_inner();
+            let mut result = expression(inner.next().unwrap());
+            for current in inner {
+                match current.as_rule() {
+                    Rule::identifier => result += &format!(".{}", current.as_str()),
+                    _ => result += &expression(current),
+                }
+            }
+            result
+        }
+        Rule::pipeline => format!("${}", expression(pair.into_inner().next().unwrap())),
+        Rule::group => format!("({})", expression(pair.into_inner().next().unwrap())),
+        Rule::subscript => format!("[{}]", expression(pair.into_inner().next().unwrap())),
+        Rule::enum_choice_literal => {
+            pair.into_inner().map(|p| match p.as_rule() {
+                Rule::identifier => format!(".{}", p.as_str()),
+                _ => expression(p),
+            }).collect()
+        }
+        Rule::range_literal => pair.into_inner().map(|p| match p.as_rule() {
+            Rule::range_end => expression(p),
+            _ => p.as_str().to_owned(),
+        }).collect(),
+        Rule::tuple_literal => {
+            let items: Vec<String> = pair.into_inner().map(expression).collect();
+            match items.len() {
+                0 => "(,)".to_owned(),
+                1 => format!("({},)", items[0]),
+                _ => format!("({})", items.join(", ")),
+            }
+        }
+        Rule::array_literal => format!("[{}]", pair.into_inner().map(expression).collect::<Vec<String>>().join(", ")),
+        Rule::dictionary_literal => format!("{{{}}}", pair.into_inner().map(expression).collect::<Vec<String>>().join(", ")),
+        Rule::named_expression => {
+            let mut inner = pair.into_inner();
+            format!("{}: {}", expression(inner.next().unwrap()), expression(inner.next().unwrap()))
+        }
+        Rule::argument_list => {
+            let arguments: Vec<String> = pair.into_inner().filter(|p| p.as_rule() == Rule::argument).map(expression).collect();
+            format!("({})", arguments.join(", "))
+        }
+        Rule::named_argument => {
+            let mut inner = pair.into_inner();
+            format!("{}: {}", inner.next().unwrap().as_str(), expression(inner.next().unwrap()))
+        }
+        Rule::empty_argument => format!("{}:", pair.into_inner().next().unwrap().as_str()),
+        _ => pair.as_str().trim().to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_schema;
+
+    fn assert_formatted(input: &str, expected: &str) {
+        let formatted = format_schema(input).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_schema(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn keeps_comments() {
+        assert_formatted(
+            "// the server\nserver {\n  bind (\"0.0.0.0\", 5000) // public port\n  // below\n  jwtSecret \"secret\"\n}\n\nmodel User {\n  /// The name\n  name: String // display name\n}\n\nenum Sex {\n  male // m\n  female\n}\n",
+            "// the server\nserver {\n  bind      (\"0.0.0.0\", 5000) // public port\n  // below\n  jwtSecret \"secret\"\n}\n\nmodel User {\n  /// The name\n  name: String // display name\n}\n\nenum Sex {\n  male // m\n  female\n}\n",
+        );
+    }
+
+    #[test]
+    fn collapses_blank_lines() {
+        assert_formatted(
+            "\n\nmodel A {\n\n\n  a: Int\n\n\n\n  b: Int\n\n}\n\n\n\nmodel B {\n  c: Int\n}\n\n\n",
+            "model A {\n  a: Int\n\n  b: Int\n}\n\nmodel B {\n  c: Int\n}\n",
+        );
+    }
+
+    #[test]
+    fn puts_decorators_above_items() {
+        assert_formatted(
+            "@map(\"users\") model User {\n  @id @autoIncrement id: Int\n  @unique\n  email: String\n  @@index([.email])\n}\n",
+            "@map(\"users\")\nmodel User {\n  @id @autoIncrement\n  id:    Int\n  @unique\n  email: String\n  @@index([.email])\n}\n",
+        );
+    }
+
+    #[test]
+    fn is_idempotent() {
+        let input = "import { A } from \"./a\"\nlet a = 1\nconnector {\n  provider .sqlite\n  url \"sqlite::memory:\" // memory\n}\n\n/// Post\nmodel Post {\n  @id id: Int\n  tags: String[]? // tags\n}\n";
+        let formatted = format_schema(input).unwrap();
+        assert_eq!(format_schema(&formatted).unwrap(), formatted);
+    }
+}
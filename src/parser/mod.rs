@@ -1,3 +1,5 @@
 pub(crate) mod parser;
 pub(crate) mod ast;
 pub(crate) mod std;
+pub(crate) mod diagnostics;
+pub(crate) mod formatter;
@@ -2,11 +2,12 @@ pub(crate) mod resolver;
 
 use std::borrow::Borrow;
 use snailquote::unescape;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::fs;
 use std::sync::{Arc, Mutex};
 use maplit::{btreemap, btreeset};
+use pest::error::InputLocation;
 use pest::Parser as PestParser;
 use pest::pratt_parser::PrattParser;
 use to_mut::ToMut;
@@ -37,6 +38,7 @@ use crate::parser::ast::span::Span;
 use crate::parser::ast::subscript::Subscript;
 use crate::parser::ast::top::Top;
 use crate::parser::ast::unit::Unit;
+use crate::parser::diagnostics::Diagnostics;
 use crate::parser::parser::resolver::Resolver;
 use crate::parser::std::decorators::field::GlobalFieldDecorators;
 use crate::parser::std::decorators::model::GlobalModelDecorators;
@@ -70,7 +72,7 @@ pub(crate) struct Parser {
     pub(crate) sources: BTreeMap<usize, Source>,
     pub(crate) enums: Vec<(usize, usize)>,
     pub(crate) models: Vec<(usize, usize)>,
-    pub(crate) connector: Option<(usize, usize)>,
+    pub(crate) connectors: Vec<(usize, usize)>,
     pub(crate) config: Option<(usize, usize)>,
     pub(crate) generators: Vec<(usize, usize)>,
     pub(crate) clients: Vec<(usize, usize)>,
@@ -83,6 +85,9 @@ pub(crate) struct Parser {
     pub(crate) global_pipeline_installers: Option<GlobalPipelineInstallers>,
     pub(crate) global_function_installers: Option<GlobalFunctionInstallers>,
     pub(crate) callback_lookup_table: Arc<Mutex<CallbackLookupTable>>,
+    pub(crate) diagnostics: Diagnostics,
+    /// Contents which replace the schema files on disk, like unsaved editor buffers.
+    pub(crate) contents: HashMap<PathBuf, String>,
 }
 
 impl Parser {
@@ -92,7 +97,7 @@ impl Parser {
             sources: btreemap!{},
             enums: vec![],
             models: vec![],
-            connector: None,
+            connectors: vec![],
             config: None,
             generators: vec![],
             clients: vec![],
@@ -105,6 +110,8 @@ impl Parser {
             global_pipeline_installers: None,
             global_function_installers: None,
             callback_lookup_table,
+            diagnostics: Diagnostics::new(),
+            contents: HashMap::new(),
         }
     }
 
@@ -114,6 +121,14 @@ impl Parser {
     }
 
     pub(crate) fn parse(&mut self, main: Option<&str>) -> () {
+        self.parse_sources(main);
+        if !self.sources.is_empty() {
+            Resolver::resolve_parser(self);
+        }
+    }
+
+    /// Parses the main schema file and the files it imports, without resolving them.
+    pub(crate) fn parse_sources(&mut self, main: Option<&str>) {
         let main = if main.is_some() { main.unwrap() } else {
             let mut result: Option<&str> = None;
             for name in ["schema.teo", "src/schema.teo"] {
@@ -130,29 +145,45 @@ impl Parser {
             if result.is_some() {
                 result.unwrap()
             } else {
-                panic!("Cannot find a schema file.")
+                self.diagnostics.insert_unlocated_error("Cannot find a schema file.");
+                return
             }
         };
         let relative = PathBuf::from(main);
         let absolute = match fs::canonicalize(&relative) {
             Ok(path) => path,
-            Err(_) => panic!("Schema file '{}' is not found.", relative.to_str().unwrap()),
+            Err(_) => {
+                self.diagnostics.insert_unlocated_error(format!("Schema file '{}' is not found.", relative.to_str().unwrap()));
+                return
+            }
         };
         self.parse_source(&absolute);
-        Resolver::resolve_parser(self);
     }
 
     fn parse_source(&mut self, path: &PathBuf) {
         let source_id = self.next_id();
-        let content = match fs::read_to_string(&path) {
+        let content = match self.contents.get(path).cloned().map_or_else(|| fs::read_to_string(&path), Ok) {
             Ok(content) => content,
-            Err(err) => panic!("{}", err)
+            Err(err) => {
+                self.diagnostics.insert_unlocated_error(format!("Cannot read schema file '{}': {}.", path.to_str().unwrap(), err));
+                self.sources.insert(source_id, Source::new(source_id, path.clone(), btreemap!{}, btreeset!{}, btreeset!{}, btreeset!{}, btreeset!{}));
+                return
+            }
         };
         let mut pairs = match SchemaParser::parse(Rule::schema, &content) {
             Ok(pairs) => pairs,
-            Err(err) => panic!("{}", err)
+            Err(err) => {
+                let span = match err.location {
+                    InputLocation::Pos(pos) => Span::new(pos, pos),
+                    InputLocation::Span((start, end)) => Span::new(start, end),
+                };
+                self.diagnostics.insert_error(path, span, format!("Syntax error: {}.", err.variant.message()));
+                self.sources.insert(source_id, Source::new(source_id, path.clone(), btreemap!{}, btreeset!{}, btreeset!{}, btreeset!{}, btreeset!{}));
+                return
+            }
         };
         let pairs = pairs.next().unwrap();
+        self.check_unparsed_content(&pairs, path);
         let mut tops: BTreeMap<usize, Top> = btreemap![];
         let mut imports: BTreeSet<usize> = btreeset!{};
         let mut constants: BTreeSet<usize> = btreeset!{};
@@ -164,9 +195,10 @@ impl Parser {
             let item_id = self.next_id();
             match current.as_rule() {
                 Rule::import_statement => {
-                    let import = self.parse_import(current, source_id, item_id, path.clone());
-                    tops.insert(item_id, import);
-                    imports.insert(item_id);
+                    if let Some(import) = self.parse_import(current, source_id, item_id, path.clone()) {
+                        tops.insert(item_id, import);
+                        imports.insert(item_id);
+                    }
                 },
                 Rule::let_declaration => {
                     let constant = self.parse_let_declaration(current, source_id, item_id);
@@ -186,11 +218,11 @@ impl Parser {
                     self.enums.push((source_id, item_id));
                 },
                 Rule::config_declaration => {
-                    let config_block = self.parse_config_block(current, source_id, item_id);
+                    let config_block = self.parse_config_block(current, source_id, item_id, path);
                     tops.insert(item_id, config_block);
                 },
                 Rule::EOI | Rule::EMPTY_LINES => {},
-                Rule::CATCH_ALL => panic!("Catch all: {}", current.as_str()),
+                Rule::CATCH_ALL => (),
                 Rule::comment_block => (),
                 _ => panic!("Parsing panic! {}", current),
             }
@@ -207,13 +239,32 @@ impl Parser {
         self.sources.insert(source_id, result);
     }
 
-    fn parse_import(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize, path: PathBuf) -> Top {
+    /// Reports the content which the grammar doesn't recognize. The rest of the file is still
+    /// parsed.
+    fn check_unparsed_content(&mut self, pair: &Pair<'_>, path: &PathBuf) {
+        for current in pair.clone().into_inner().flatten() {
+            match current.as_rule() {
+                Rule::CATCH_ALL => {
+                    let start = current.as_span().start();
+                    let span = Span::new(start, start + current.as_str().trim_end().len());
+                    self.diagnostics.insert_error(path, span, "Unexpected content.");
+                }
+                Rule::empty_argument => {
+                    let name = current.as_str().trim_end_matches(':').trim();
+                    self.diagnostics.insert_error(path, Self::parse_span(&current), format!("Argument '{}' has no value.", name));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn parse_import(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize, path: PathBuf) -> Option<Top> {
         let mut identifiers = vec![];
         let span = Self::parse_span(&pair);
         let mut source: Option<StringLiteral> = None;
         for current in pair.into_inner() {
             match current.as_rule() {
-                Rule::string_literal => source = Some(StringLiteral { value: current.as_str().to_string(), span }),
+                Rule::string_literal => source = Some(StringLiteral { value: current.as_str().to_string(), span: Self::parse_span(&current) }),
                 Rule::import_identifier_list => identifiers = Self::parse_import_identifier_list(current),
                 _ => unreachable!(),
             }
@@ -225,9 +276,12 @@ impl Parser {
         let new = dir.join(&relative);
         let absolute = match Self::canonicalize(&new) {
             Some(path) => path,
-            None => panic!("Schema file '{}' is not found.", relative.to_str().unwrap()),
+            None => {
+                self.diagnostics.insert_error(&path, source.unwrap().span, format!("Schema file '{}' is not found.", relative.to_str().unwrap()));
+                return None
+            }
         };
-        Top::Import(Import::new(item_id, source_id, identifiers, source.unwrap(), absolute, span))
+        Some(Top::Import(Import::new(item_id, source_id, identifiers, source.unwrap(), absolute, span)))
     }
 
     fn canonicalize(path_buf: &PathBuf) -> Option<PathBuf> {
@@ -270,7 +324,7 @@ impl Parser {
                     let (token, doc) = Self::parse_comment_line(current);
                     if let Some(token) = token {
                         if &token == "@name" {
-                            name = doc;
+                            name = doc.trim().to_owned();
                         } else if &token == "@description" {
                             desc = Self::append_doc_desc(desc, doc)
                         }
@@ -323,6 +377,7 @@ impl Parser {
                 Rule::block_decorator => decorators.push(Self::parse_decorator(current)),
                 Rule::item_decorator => decorators.push(Self::parse_decorator(current)),
                 Rule::triple_comment_block => comment_block = Some(Self::parse_comment_block(current)),
+                Rule::BLOCK_LEVEL_CATCH_ALL => (),
                 _ => panic!("error. {:?}", current),
             }
         }
@@ -345,7 +400,7 @@ impl Parser {
         let span = Self::parse_span(&pair);
         for current in pair.into_inner() {
             match current.as_rule() {
-                Rule::COLON => {},
+                Rule::COLON | Rule::trailing_comment => {},
                 Rule::triple_comment_block => comment_block = Some(Self::parse_comment_block(current)),
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::field_type => r#type = Some(Self::parse_type(current)),
@@ -371,10 +426,12 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::ENUM_KEYWORD | Rule::COLON | Rule::EMPTY_LINES | Rule::BLOCK_OPEN | Rule::BLOCK_CLOSE => {},
+                Rule::triple_comment_block => comment_block = Some(Self::parse_comment_block(current)),
                 Rule::comment_block => comment_block = Some(Self::parse_comment_block(current)),
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::enum_value_declaration => choices.push(self.parse_enum_value(current)),
                 Rule::block_decorator => decorators.push(Self::parse_decorator(current)),
+                Rule::BLOCK_LEVEL_CATCH_ALL => (),
                 _ => panic!("error. {}", current),
             }
         }
@@ -395,7 +452,7 @@ impl Parser {
         let span = Self::parse_span(&pair);
         for current in pair.into_inner() {
             match current.as_rule() {
-                Rule::COLON | Rule::EMPTY_LINES | Rule::comment_block => {},
+                Rule::COLON | Rule::EMPTY_LINES | Rule::comment_block | Rule::trailing_comment => {},
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::item_decorator => decorators.push(Self::parse_decorator(current)),
                 _ => panic!("error."),
@@ -412,13 +469,14 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::expression => expression = Some(Self::parse_expression(current)),
+                Rule::trailing_comment => (),
                 _ => panic!("error."),
             }
         }
         Top::Constant(Constant::new(item_id, source_id, identifier.unwrap(), expression.unwrap(), span))
     }
 
-    fn parse_config_block(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize) -> Top {
+    fn parse_config_block(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize, path: &PathBuf) -> Top {
         let mut identifier: Option<Identifier> = None;
         let mut items: Vec<Item> = vec![];
         let mut keyword = "";
@@ -438,17 +496,15 @@ impl Parser {
         match keyword {
             "server" => {
                 if self.config.is_some() {
-                    panic!("Duplicated config found.");
+                    self.diagnostics.insert_error(path, span, "Duplicated server config found.");
+                } else {
+                    self.config = Some((source_id, item_id));
                 }
-                self.config = Some((source_id, item_id));
                 Top::ServerConfig(ServerConfig::new(item_id, source_id, items, span))
             },
             "connector" => {
-                if self.connector.is_some() {
-                    panic!("Duplicated connector found.");
-                }
-                self.connector = Some((source_id, item_id));
-                Top::Connector(Connector::new(items, span, source_id, item_id))
+                self.connectors.push((source_id, item_id));
+                Top::Connector(Connector::new(identifier, items, span, source_id, item_id))
             },
             "entity" => {
                 self.generators.push((source_id, item_id));
@@ -470,6 +526,7 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::expression => expression = Some(Self::parse_expression(current)),
+                Rule::trailing_comment => (),
                 _ => panic!("error."),
             }
         }
@@ -513,13 +570,26 @@ impl Parser {
                     return Self::parse_named_argument(current);
                 },
                 Rule::expression => value = Some(Self::parse_expression(current).kind),
-                Rule::empty_argument => panic!("Empty argument found."),
+                // reported when the file is parsed, the value is left null
+                Rule::empty_argument => return Self::parse_empty_argument(current),
                 _ => panic!(),
             }
         }
         Argument { name, value: value.unwrap(), span, resolved: None }
     }
 
+    fn parse_empty_argument(pair: Pair<'_>) -> Argument {
+        let span = Self::parse_span(&pair);
+        let mut name: Option<Identifier> = None;
+        for current in pair.into_inner() {
+            match current.as_rule() {
+                Rule::identifier => name = Some(Self::parse_identifier(&current)),
+                _ => panic!(),
+            }
+        }
+        Argument { name, value: ExpressionKind::NullLiteral(NullLiteral { value: "null".to_owned(), span }), span, resolved: None }
+    }
+
     fn parse_named_argument(pair: Pair<'_>) -> Argument {
         let span = Self::parse_span(&pair);
         let mut name: Option<Identifier> = None;
@@ -528,7 +598,6 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => name = Some(Self::parse_identifier(&current)),
                 Rule::expression => value = Some(Self::parse_expression(current).kind),
-                Rule::empty_argument => panic!("Empty argument found."),
                 _ => panic!(),
             }
         }
@@ -672,6 +741,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::argument => arguments.push(Self::parse_argument(current)),
+                Rule::TRAILING_COMMA => (),
                 _ => panic!("{}", current),
             }
         }
@@ -826,6 +896,17 @@ impl Parser {
         self.sources.get(&id).unwrap()
     }
 
+    pub(crate) fn connectors(&self) -> Vec<&Connector> {
+        self.connectors.iter().map(|(source_id, item_id)| self.get_source(*source_id).get_connector(*item_id)).collect()
+    }
+
+    /// The connector of models without `@@connector`. This is the unnamed connector, or the
+    /// first declared one if every connector is named.
+    pub(crate) fn default_connector(&self) -> Option<&Connector> {
+        let connectors = self.connectors();
+        connectors.iter().find(|c| c.identifier.is_none()).or(connectors.first()).cloned()
+    }
+
     pub(crate) fn set_global_model_decorators(&self, deco: GlobalModelDecorators) {
         self.to_mut().global_model_decorators = Some(deco);
     }
@@ -8,7 +8,7 @@ use snailquote::unescape;
 use crate::core::database::name::DatabaseName;
 use crate::core::teon::range::Range;
 use crate::parser::ast::accessible::{Accessible, ASTPipeline, ASTPipelineItem, Container};
-use crate::parser::ast::argument::ArgumentList;
+use crate::parser::ast::argument::{Argument, ArgumentList};
 use crate::parser::ast::config::ServerConfig;
 use crate::parser::ast::constant::Constant;
 use crate::parser::ast::decorator::Decorator;
@@ -18,22 +18,28 @@ use crate::parser::ast::field::{Field, FieldClass};
 use crate::parser::ast::group::Group;
 use crate::parser::ast::identifier::Identifier;
 use crate::parser::ast::import::Import;
+use crate::parser::ast::item::Item;
 use crate::parser::ast::model::Model;
 use crate::parser::ast::pipeline::Pipeline;
 use crate::parser::ast::r#enum::{Enum, EnumChoice};
+use crate::parser::ast::r#type::Arity;
 use crate::parser::ast::reference::{Reference};
 use crate::parser::ast::source::Source;
+use crate::parser::ast::span::Span;
 use crate::parser::ast::subscript::Subscript;
 use crate::parser::ast::top::Top;
 use crate::parser::ast::unit::Unit;
 use crate::parser::parser::Parser;
+use crate::parser::std::decorators::field::db::db_container;
 use crate::parser::std::decorators::field::GlobalFieldDecorators;
 use crate::parser::std::decorators::model::GlobalModelDecorators;
 use crate::parser::std::decorators::property::GlobalPropertyDecorators;
 use crate::parser::std::decorators::relation::GlobalRelationDecorators;
 use crate::prelude::Value;
+use itertools::Itertools;
 use to_mut::ToMut;
 use crate::core::action::Action;
+use crate::core::app::conf::{JwtAlgorithm, LogFormat, LogLevel};
 use crate::core::app::environment::Environment;
 use crate::parser::ast::arith_expr::{ArithExpr, Op};
 use crate::parser::ast::client::{Client, ClientLanguage};
@@ -45,7 +51,7 @@ pub(crate) struct Resolver { }
 impl Resolver {
 
     pub(crate) fn resolve_parser(parser: &Parser) {
-        let database_name = Self::resolve_connector(parser);
+        let database_name = Self::resolve_connectors(parser);
         parser.set_global_model_decorators(GlobalModelDecorators::new());
         parser.set_global_field_decorators(GlobalFieldDecorators::new(database_name));
         parser.set_global_relation_decorators(GlobalRelationDecorators::new());
@@ -58,6 +64,10 @@ impl Resolver {
             if *index == 1 { continue }
             Self::resolve_source(parser, source);
         }
+        Self::check_relation_connectors(parser);
+        if parser.config.is_none() && !parser.diagnostics.has_errors() {
+            parser.to_mut().diagnostics.insert_unlocated_error("Server config is not defined.");
+        }
         parser.to_mut().resolved = true;
     }
 
@@ -94,10 +104,12 @@ impl Resolver {
         source.to_mut().resolved = true;
     }
 
-    pub(crate) fn resolve_import(parser: &Parser, _source: &Source, import: &mut Import) {
-        let from_source = parser.sources.iter().find(|(_source_id, source)| {
+    pub(crate) fn resolve_import(parser: &Parser, source: &Source, import: &mut Import) {
+        let Some((_, from_source)) = parser.sources.iter().find(|(_source_id, source)| {
             &import.path == &source.path
-        }).unwrap().1;
+        }) else {
+            return
+        };
         import.from_id = Some(from_source.id);
         for (item_id, top) in from_source.tops.iter() {
             if top.is_model() {
@@ -116,10 +128,19 @@ impl Resolver {
                 }
             }
         }
+        for identifier in import.identifiers.iter() {
+            if import.references.contains_key(&identifier.name) { continue }
+            // enums are global, importing them is allowed
+            let is_enum = from_source.tops.values().any(|top| top.as_enum().map_or(false, |e| e.identifier.name == identifier.name));
+            if !is_enum {
+                Self::insert_error(parser, source, identifier.span, format!("Cannot find '{}' in imported file.", identifier.name));
+            }
+        }
         import.resolved = true;
     }
 
     pub(crate) fn resolve_constant(parser: &Parser, source: &Source, constant: &mut Constant) {
+        if constant.resolved { return }
         Self::resolve_expression(parser, source, &mut constant.expression);
         constant.resolved = true;
     }
@@ -131,214 +152,171 @@ impl Resolver {
         r#enum.resolved = true;
     }
 
-    pub(crate) fn resolve_enum_choice(_parser: &Parser, _source: &Source, choice: &mut EnumChoice) {
+    pub(crate) fn resolve_enum_choice(parser: &Parser, source: &Source, choice: &mut EnumChoice) {
+        for decorator in choice.decorators.iter_mut() {
+            decorator.resolved = true;
+            let (identifier, argument_list) = match &decorator.expression {
+                ExpressionKind::Unit(unit) => (unit.expressions.get(0).unwrap().as_identifier().unwrap(), unit.expressions.get(1).map(|e| e.as_argument_list()).flatten().cloned()),
+                ExpressionKind::Identifier(identifier) => (identifier, None),
+                _ => unreachable!(),
+            };
+            if identifier.name.as_str() != "migration" {
+                Self::insert_error(parser, source, identifier.span, format!("Unknown enum member decorator '{}'.", identifier.name));
+                continue
+            }
+            let Some(mut argument_list) = argument_list else {
+                Self::insert_error(parser, source, decorator.span, "@migration requires arguments.");
+                continue
+            };
+            for argument in argument_list.arguments.iter_mut() {
+                let value = Self::resolve_expression_kind_force_value(parser, source, &argument.value, false);
+                match argument.name.as_ref().map(|n| n.name.as_str()) {
+                    Some("renamed") => match &value {
+                        Value::String(s) => choice.renamed = vec![s.clone()],
+                        Value::Vec(v) if v.iter().all(|v| v.as_str().is_some()) => choice.renamed = v.iter().map(|v| v.as_str().unwrap().to_owned()).collect(),
+                        _ => Self::insert_error(parser, source, argument.value.span(), "Value of 'renamed' should be a string or an array of strings."),
+                    },
+                    _ => Self::insert_error(parser, source, argument.span, "Enum member @migration only accepts 'renamed'."),
+                }
+                argument.resolved = Some(Entity::Value(value));
+            }
+            decorator.arguments = Some(argument_list);
+        }
         choice.resolved = true;
     }
 
     pub(crate) fn resolve_model(parser: &Parser, source: &Source, model: &mut Model) {
         // decorators
         for decorator in model.decorators.iter_mut() {
-            Self::resolve_model_decorator(parser, source, decorator);
+            Self::resolve_decorator(parser, source, decorator, "model", &|name| parser.global_model_decorators().get(name).cloned());
         }
         // fields
+        let database_name = Self::resolve_model_connector(parser, source, model);
+        if matches!(database_name, Some(DatabaseName::MSSQL)) {
+            Self::check_mssql_model_decorators(parser, source, model);
+        }
         for field in model.fields.iter_mut() {
-            Self::resolve_field(parser, source, field);
+            Self::resolve_field(parser, source, field, database_name);
         }
         // cached enums
         //
         model.resolved = true;
     }
 
-    fn resolve_model_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_model_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
-            }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_model_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
-                }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let when_option = identifier.name.as_str() == "disable";
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, when_option);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
+    /// Checks the connector the model names, and returns the database its fields are stored in.
+    fn resolve_model_connector(parser: &Parser, source: &Source, model: &Model) -> Option<DatabaseName> {
+        let connector = match Self::model_connector_name(model) {
+            Some((name, span)) => match parser.connectors().into_iter().find(|c| c.name() == name) {
+                Some(connector) => Some(connector),
+                None => {
+                    Self::insert_error(parser, source, span, format!("Cannot find connector '{}'.", name));
+                    parser.default_connector()
                 }
-                decorator.arguments = arg_list;
-            }
-            _ => panic!()
-        }
-        decorator.resolved = true;
+            },
+            None => parser.default_connector(),
+        };
+        connector.and_then(|c| c.provider)
     }
 
-    fn resolve_field_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_field_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
+    fn check_mssql_model_decorators(parser: &Parser, source: &Source, model: &Model) {
+        for decorator in model.decorators.iter() {
+            let Some(unit) = decorator.expression.as_unit() else { continue };
+            if unit.expressions.first().and_then(|e| e.as_identifier()).map(|i| i.name.as_str()) == Some("fulltext") {
+                Self::insert_error(parser, source, decorator.span, "Full-text indices are not supported for SQL Server.");
             }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_field_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
-                }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, false);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
-                }
-                decorator.arguments = arg_list;
-            }
-            _ => panic!()
         }
-        decorator.resolved = true;
     }
 
-    fn resolve_property_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_property_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
+    fn model_connector_name(model: &Model) -> Option<(&str, Span)> {
+        model.decorators.iter().find_map(|decorator| {
+            let unit = decorator.expression.as_unit()?;
+            if unit.expressions.first()?.as_identifier()?.name != "connector" { return None }
+            let argument = decorator.arguments.as_ref()?.arguments.first()?;
+            Some((argument.resolved.as_ref()?.as_value()?.as_str()?, argument.span))
+        })
+    }
+
+    /// Relations are joined by the database, so both models of a relation must be stored by the
+    /// same connector.
+    fn check_relation_connectors(parser: &Parser) {
+        let connector_name = |model: &Model| -> String {
+            match Self::model_connector_name(model) {
+                Some((name, _)) => name.to_owned(),
+                None => parser.default_connector().map_or("", |c| c.name()).to_owned(),
             }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_property_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
-                }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, false);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
+        };
+        for (source_id, model_id) in parser.models.iter() {
+            let source = parser.get_source(*source_id);
+            let model = source.get_model(*model_id);
+            let connector = connector_name(model);
+            for field in model.fields.iter().filter(|f| matches!(f.field_class, FieldClass::Relation)) {
+                let Some(related) = parser.models.iter()
+                    .map(|(source_id, model_id)| parser.get_source(*source_id).get_model(*model_id))
+                    .find(|m| m.identifier.name == field.r#type.identifier.name) else { continue };
+                let related_connector = connector_name(related);
+                if related_connector != connector {
+                    Self::insert_error(parser, source, field.identifier.span, format!("Relation '{}' crosses connectors '{}' and '{}', relations cannot be joined across data sources.", field.identifier.name, connector, related_connector));
                 }
-                decorator.arguments = arg_list;
             }
-            _ => panic!()
         }
-        decorator.resolved = true;
     }
 
-    fn resolve_relation_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_relation_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
-            }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_relation_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
+    fn resolve_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator, kind: &str, lookup: &dyn Fn(&str) -> Option<Accessible>) {
+        decorator.resolved = true;
+        let (identifier, accessors) = match &decorator.expression {
+            ExpressionKind::Identifier(identifier) => (identifier, &[][..]),
+            ExpressionKind::Unit(unit) => (unit.expressions.get(0).unwrap().as_identifier().unwrap(), &unit.expressions[1..]),
+            _ => unreachable!(),
+        };
+        let Some(mut accessible) = lookup(&identifier.name) else {
+            return Self::insert_error(parser, source, identifier.span, format!("Unknown {} decorator '{}'.", kind, identifier.name));
+        };
+        let mut path = identifier.name.clone();
+        let mut arg_list: Option<ArgumentList> = None;
+        for expression in accessors {
+            match expression {
+                ExpressionKind::ArgumentList(argument_list) => {
+                    arg_list = Some(argument_list.clone());
+                }
+                ExpressionKind::Subscript(subscript) => {
+                    return Self::insert_error(parser, source, subscript.span, "Cannot access decorator object with subscript.");
                 }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, false);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
+                ExpressionKind::Identifier(identifier) => {
+                    path = format!("{}.{}", path, identifier.name);
+                    match accessible.access_property(&identifier.name).map(|e| e.as_accessible()).flatten() {
+                        Some(property) => accessible = property.clone(),
+                        None => return Self::insert_error(parser, source, identifier.span, format!("Unknown {} decorator '{}'.", kind, path)),
+                    }
                 }
-                decorator.arguments = arg_list;
+                _ => unreachable!()
             }
-            _ => panic!()
         }
-        decorator.resolved = true;
+        if accessible.is_container() {
+            return Self::insert_error(parser, source, decorator.span, format!("'{}' is not a {} decorator.", path, kind));
+        }
+        // namespaced decorators like `@db.enum` may omit the argument list
+        if let Some(arg_list) = arg_list.as_mut() {
+            let when_option = kind == "model" && identifier.name.as_str() == "disable";
+            let connector_name = kind == "model" && identifier.name.as_str() == "connector";
+            for argument in arg_list.arguments.iter_mut() {
+                let value = match &argument.value {
+                    // connectors are referred to by their names
+                    ExpressionKind::Identifier(identifier) if connector_name => Value::String(identifier.name.clone()),
+                    _ => Self::resolve_expression_kind_force_value(parser, source, &argument.value, when_option),
+                };
+                argument.resolved = Some(Entity::Value(value));
+            }
+        }
+        decorator.accessible = Some(accessible);
+        decorator.arguments = arg_list;
     }
 
     fn resolve_pipeline(parser: &Parser, source: &Source, pipeline: &Pipeline) -> Entity {
+        let errors_count = parser.diagnostics.errors_count();
         let mut items: Vec<ASTPipelineItem> = vec![];
         match pipeline.expression.as_ref() {
             ExpressionKind::Identifier(identifier) => {
-                let installer = parser.global_pipeline_installers().get(&identifier.name);
-                if let Some(installer) = installer {
-                    items.push(ASTPipelineItem {
-                        installer: Some(installer.clone()),
-                        function_installer: None,
-                        lookup_table: None,
-                        args: vec![]
-                    })
-                } else {
-                    let installer = parser.global_function_installers().get(&identifier.name);
-                    if let Some(installer) = installer {
-                        items.push(ASTPipelineItem {
-                            installer: None,
-                            function_installer: Some(installer.clone()),
-                            lookup_table: Some(parser.callback_lookup_table.clone()),
-                            args: vec![]
-                        })
-                    } else {
-                        panic!("Cannot find pipeline item named '{}'.", identifier.name);
-                    }
-                }
+                items.extend(Self::resolve_pipeline_item(parser, source, identifier, vec![]));
             }
             ExpressionKind::Unit(unit) => {
                 let mut previous_identifier: Option<&Identifier> = None;
@@ -346,73 +324,78 @@ impl Resolver {
                     match expression {
                         ExpressionKind::Identifier(identifier) => {
                             if let Some(previous_identifier) = previous_identifier {
-                                let installer = parser.global_pipeline_installers().get(&previous_identifier.name);
-                                if let Some(installer) = installer {
-                                    items.push(ASTPipelineItem { installer: Some(installer.clone()), function_installer: None, lookup_table: None, args: vec![]});
-                                } else {
-                                    panic!("Cannot find pipeline item named '{}'.", identifier.name);
-                                }
+                                items.extend(Self::resolve_pipeline_item(parser, source, previous_identifier, vec![]));
                             }
                             previous_identifier = Some(&identifier);
                         }
                         ExpressionKind::ArgumentList(argument_list) => {
+                            let identifier = previous_identifier.unwrap();
                             let mut args = argument_list.clone();
                             for (index, arg) in &mut args.arguments.iter_mut().enumerate() {
-                                let value = if ((&previous_identifier.unwrap().name == "when") || (&previous_identifier.unwrap().name == "redirect")) && index == 0 {
-                                    Self::resolve_expression_kind_force_value(parser, source, &arg.value, true)
-                                } else {
-                                    Self::resolve_expression_kind_force_value(parser, source, &arg.value, false)
-                                };
+                                let when_option = ((&identifier.name == "when") || (&identifier.name == "redirect")) && index == 0;
+                                let value = Self::resolve_expression_kind_force_value(parser, source, &arg.value, when_option);
                                 arg.resolved = Some(Entity::Value(value));
                             }
-                            let installer = parser.global_pipeline_installers().get(&previous_identifier.unwrap().name);
-                            if let Some(installer) = installer {
-                                items.push(ASTPipelineItem { installer: Some(installer.clone()), function_installer: None, lookup_table: None, args: args.arguments});
-                            } else {
-                                let installer = parser.global_function_installers().get(&previous_identifier.unwrap().name);
-                                if let Some(installer) = installer {
-                                    items.push(ASTPipelineItem { installer: None, function_installer: Some(installer.clone()), lookup_table: Some(parser.callback_lookup_table.clone()), args: args.arguments});
-                                } else {
-                                    panic!("Cannot find pipeline item named '{}'.", previous_identifier.unwrap().name);
-                                }
-                            }
+                            items.extend(Self::resolve_pipeline_item(parser, source, identifier, args.arguments));
                             previous_identifier = None;
                         }
-                        _ => panic!()
+                        ExpressionKind::Subscript(subscript) => {
+                            Self::insert_error(parser, source, subscript.span, "Cannot access pipeline item with subscript.");
+                        }
+                        _ => unreachable!()
                     }
                 }
                 if let Some(previous_identifier) = previous_identifier {
-                    let installer = parser.global_pipeline_installers().get(&previous_identifier.name);
-                    if let Some(installer) = installer {
-                        items.push(ASTPipelineItem { installer: Some(installer.clone()), function_installer: None, lookup_table: None, args: vec![]});
-                    } else {
-                        panic!("Cannot find pipeline item named '{}'.", previous_identifier.name);
-                    }
+                    items.extend(Self::resolve_pipeline_item(parser, source, previous_identifier, vec![]));
                 }
             }
-            _ => panic!()
+            _ => unreachable!()
+        }
+        if parser.diagnostics.errors_count() > errors_count {
+            return Entity::Value(Value::Null);
         }
         let ast_pipeline = ASTPipeline { items };
-        let value_pipeline = ast_pipeline.to_value_pipeline();
-        Entity::Value(Value::Pipeline(value_pipeline))
+        match ast_pipeline.to_value_pipeline() {
+            Ok(value_pipeline) => Entity::Value(Value::Pipeline(value_pipeline)),
+            Err((span, message)) => {
+                Self::insert_error(parser, source, span, message);
+                Entity::Value(Value::Null)
+            }
+        }
     }
 
-    fn resolve_field(parser: &Parser, source: &Source, field: &mut Field) {
+    fn resolve_pipeline_item(parser: &Parser, source: &Source, identifier: &Identifier, args: Vec<Argument>) -> Option<ASTPipelineItem> {
+        if let Some(installer) = parser.global_pipeline_installers().get(&identifier.name) {
+            Some(ASTPipelineItem { installer: Some(installer.clone()), function_installer: None, lookup_table: None, args, span: identifier.span })
+        } else if let Some(installer) = parser.global_function_installers().get(&identifier.name) {
+            Some(ASTPipelineItem { installer: None, function_installer: Some(installer.clone()), lookup_table: Some(parser.callback_lookup_table.clone()), args, span: identifier.span })
+        } else {
+            Self::insert_error(parser, source, identifier.span, format!("Cannot find pipeline item named '{}'.", identifier.name));
+            None
+        }
+    }
+
+    fn resolve_field(parser: &Parser, source: &Source, field: &mut Field, database_name: Option<DatabaseName>) {
         field.figure_out_class();
+        Self::resolve_field_type(parser, source, field);
         match &field.field_class {
-            FieldClass::Field => {
+            FieldClass::Field | FieldClass::DroppedField => {
                 for decorator in field.decorators.iter_mut() {
-                    Self::resolve_field_decorator(parser, source, decorator);
+                    Self::resolve_decorator(parser, source, decorator, "field", &|name| match name {
+                        // `@db` decorators are of the database the model is stored in
+                        "db" => database_name.map(|d| Accessible::Container(db_container(d))),
+                        _ => parser.global_field_decorators().get(name).cloned(),
+                    });
                 }
             }
             FieldClass::Relation => {
                 for decorator in field.decorators.iter_mut() {
-                    Self::resolve_relation_decorator(parser, source, decorator);
+                    Self::resolve_decorator(parser, source, decorator, "relation", &|name| parser.global_relation_decorators().get(name).cloned());
                 }
             }
             FieldClass::Property => {
                 for decorator in field.decorators.iter_mut() {
-                    Self::resolve_property_decorator(parser, source, decorator);
+                    Self::resolve_decorator(parser, source, decorator, "property", &|name| parser.global_property_decorators().get(name).cloned());
                 }
             }
             _ => {}
@@ -420,67 +403,155 @@ impl Resolver {
         field.resolved = true;
     }
 
-    pub(crate) fn resolve_connector(parser: &Parser) -> DatabaseName {
-        if parser.connector.is_none() {
-            panic!("Connector is not defined.");
+    fn resolve_field_type(parser: &Parser, source: &Source, field: &Field) {
+        let r#type = &field.r#type;
+        let name = r#type.identifier.name.as_str();
+        match &field.field_class {
+            FieldClass::Relation => {
+                let is_model = parser.models.iter().any(|(source_id, model_id)| parser.get_source(*source_id).get_model(*model_id).identifier.name == name);
+                if !is_model {
+                    Self::insert_error(parser, source, r#type.identifier.span, format!("Cannot find model '{}'.", name));
+                } else if r#type.arity == Arity::Dictionary {
+                    Self::insert_error(parser, source, r#type.identifier.span, "Relations cannot be dictionary.");
+                } else if r#type.arity == Arity::Array && !r#type.item_required {
+                    Self::insert_error(parser, source, r#type.identifier.span, "Relation cannot have optional items.");
+                }
+            }
+            _ => {
+                let is_enum = parser.enums.iter().any(|(source_id, enum_id)| parser.get_source(*source_id).get_enum(*enum_id).identifier.name == name);
+                if !Self::is_builtin_type(name) && !is_enum {
+                    Self::insert_error(parser, source, r#type.identifier.span, format!("Cannot find type '{}'.", name));
+                }
+            }
+        }
+    }
+
+    fn is_builtin_type(name: &str) -> bool {
+        match name {
+            "String" | "Bool" | "Int" | "Int32" | "Int64" | "Float32" | "Float" | "Float64" | "Date" | "DateTime" | "Decimal" | "Json" => true,
+            #[cfg(feature = "data-source-mongodb")]
+            "ObjectId" => true,
+            _ => false,
         }
-        let connector_ref = parser.connector.unwrap();
+    }
+
+    /// Resolves the connectors and returns the database of the default one.
+    pub(crate) fn resolve_connectors(parser: &Parser) -> Option<DatabaseName> {
+        if parser.connectors.is_empty() {
+            // a connector inside a file with syntax errors is not found
+            if !parser.diagnostics.has_errors() {
+                parser.to_mut().diagnostics.insert_unlocated_error("Connector is not defined.");
+            }
+            return None
+        }
+        let mut names: Vec<String> = vec![];
+        for connector_ref in parser.connectors.iter() {
+            Self::resolve_connector(parser, *connector_ref);
+            let connector = parser.get_source(connector_ref.0).get_connector(connector_ref.1);
+            if names.iter().any(|n| n == connector.name()) {
+                let source = parser.get_source(connector.source_id);
+                match &connector.identifier {
+                    Some(identifier) => Self::insert_error(parser, source, identifier.span, format!("Duplicated connector '{}' found.", identifier.name)),
+                    None => Self::insert_error(parser, source, connector.span, "Duplicated connector found."),
+                }
+            } else {
+                names.push(connector.name().to_owned());
+            }
+        }
+        parser.default_connector().and_then(|c| c.provider)
+    }
+
+    fn resolve_connector(parser: &Parser, connector_ref: (usize, usize)) {
         let source = parser.get_source(connector_ref.0);
         let top = source.to_mut().tops.get_mut(&connector_ref.1).unwrap();
-        let mut connector = top.as_connector_mut().unwrap();
+        let connector = top.as_connector_mut().unwrap();
         for item in connector.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "provider" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let provider_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let provider_str = provider_value.as_raw_enum_choice().unwrap();
-                    match provider_str {
+                    let Some(provider_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match provider_value.as_raw_enum_choice() {
                         #[cfg(feature = "data-source-sqlite")]
-                        "sqlite" => connector.provider = Some(DatabaseName::SQLite),
-                        "mongo" => connector.provider = Some(DatabaseName::MongoDB),
-                        "mysql" => connector.provider = Some(DatabaseName::MySQL),
-                        "postgres" => connector.provider = Some(DatabaseName::PostgreSQL),
-                        _ => panic!("Unrecognized provider. {}", provider_str)
+                        Some("sqlite") => connector.provider = Some(DatabaseName::SQLite),
+                        Some("mongo") => connector.provider = Some(DatabaseName::MongoDB),
+                        Some("mysql") => connector.provider = Some(DatabaseName::MySQL),
+                        Some("postgres") => connector.provider = Some(DatabaseName::PostgreSQL),
+                        #[cfg(feature = "data-source-mssql")]
+                        Some("mssql") => connector.provider = Some(DatabaseName::MSSQL),
+                        Some(provider_str) => Self::insert_error(parser, source, item.expression.kind.span(), format!("Unrecognized provider '{}'.", provider_str)),
+                        None => Self::insert_invalid_item_value(parser, source, item, "an enum choice"),
                     }
                 },
                 "url" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let url_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let url_str = url_value.as_str().unwrap();
-                    connector.url = Some(url_str.to_owned());
+                    let Some(url_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match url_value.as_str() {
+                        Some(url_str) => connector.url = Some(url_str.to_owned()),
+                        None => Self::insert_invalid_item_value(parser, source, item, "a string"),
+                    }
                 },
                 "debug" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let bool_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let bool = bool_value.as_bool().unwrap();
-                    connector.debug = bool;
+                    Self::insert_warning(parser, source, item.identifier.span, "'debug' in the connector block is deprecated, use 'logQueries' in the server block instead.");
+                    let Some(bool_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match bool_value.as_bool() {
+                        Some(b) => connector.debug = b,
+                        None => Self::insert_invalid_item_value(parser, source, item, "bool"),
+                    }
+                }
+                "poolSize" => {
+                    let Some(size_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match size_value.as_i64() {
+                        Some(i) if i > 0 => connector.pool.size = Some(i as usize),
+                        _ => Self::insert_invalid_item_value(parser, source, item, "a positive number"),
+                    }
+                }
+                "poolTimeout" => {
+                    let Some(timeout_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match timeout_value.as_i64() {
+                        Some(i) if i > 0 => connector.pool.timeout = Some(i as u64),
+                        _ => Self::insert_invalid_item_value(parser, source, item, "a positive number of seconds"),
+                    }
                 }
-                _ => { panic!("Undefined name '{}' in connector block.", item.identifier.name.as_str())}
+                "maxIdle" | "idleTimeout" | "maxLifetime" => {
+                    let Some(value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let value = match value.as_i64() {
+                        Some(i) if i >= 0 => i as u64,
+                        _ => {
+                            Self::insert_invalid_item_value(parser, source, item, "a non-negative number");
+                            continue
+                        }
+                    };
+                    match item.identifier.name.as_str() {
+                        "maxIdle" => connector.pool.max_idle = Some(value),
+                        "idleTimeout" => connector.pool.idle_timeout = Some(value),
+                        _ => connector.pool.max_lifetime = Some(value),
+                    }
+                }
+                _ => Self::insert_error(parser, source, item.identifier.span, format!("Undefined name '{}' in connector block.", item.identifier.name)),
             }
         }
-        connector.provider.unwrap()
+        Self::check_required_items(parser, source, &connector.items, connector.span, "connector", &["provider", "url"]);
     }
 
     pub(crate) fn resolve_client_generator(parser: &Parser, source: &Source, client: &mut Client) {
         for item in client.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "provider" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let provider_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let provider_str = provider_value.as_raw_enum_choice().unwrap();
-                    match provider_str {
-                        "javaScript" | "typeScript" => client.provider = Some(ClientLanguage::TypeScript),
-                        "swift" => client.provider = Some(ClientLanguage::Swift),
-                        "kotlin" => client.provider = Some(ClientLanguage::Kotlin),
-                        "cSharp" => client.provider = Some(ClientLanguage::CSharp),
-                        "dart" => client.provider = Some(ClientLanguage::Dart),
-                        _ => panic!("Unrecognized client generator provider. {}", provider_str)
+                    let Some(provider_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match provider_value.as_raw_enum_choice() {
+                        Some("javaScript") | Some("typeScript") => client.provider = Some(ClientLanguage::TypeScript),
+                        Some("swift") => client.provider = Some(ClientLanguage::Swift),
+                        Some("kotlin") => client.provider = Some(ClientLanguage::Kotlin),
+                        Some("cSharp") => client.provider = Some(ClientLanguage::CSharp),
+                        Some("dart") => client.provider = Some(ClientLanguage::Dart),
+                        Some(provider_str) => Self::insert_error(parser, source, item.expression.kind.span(), format!("Unrecognized client generator provider '{}'.", provider_str)),
+                        None => Self::insert_invalid_item_value(parser, source, item, "an enum choice"),
                     }
                 },
                 "dest" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let dest_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let dest_str = dest_value.as_str().unwrap();
+                    let Some(dest_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let Some(dest_str) = dest_value.as_str() else {
+                        Self::insert_invalid_item_value(parser, source, item, "a string");
+                        continue
+                    };
                     let mut dest_path = source.path.clone();
                     dest_path.pop();
                     let dest = dest_path.join(PathBuf::from(dest_str));
@@ -488,104 +559,266 @@ impl Resolver {
                     client.dest = Some(absolute.as_ref().to_owned());
                 },
                 "package" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let package_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let package_bool = package_value.as_bool().unwrap();
-                    client.package = Some(package_bool);
+                    let Some(package_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match package_value.as_bool() {
+                        Some(package_bool) => client.package = Some(package_bool),
+                        None => Self::insert_invalid_item_value(parser, source, item, "bool"),
+                    }
                 },
                 "host" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let host_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let host_str = host_value.as_str().unwrap();
-                    client.host = Some(host_str.to_owned());
+                    let Some(host_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match host_value.as_str() {
+                        Some(host_str) => client.host = Some(host_str.to_owned()),
+                        None => Self::insert_invalid_item_value(parser, source, item, "a string"),
+                    }
                 },
                 "objectName" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let object_name_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let object_name_str = object_name_value.as_str().unwrap();
-                    client.object_name = Some(object_name_str.to_owned());
+                    let Some(object_name_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match object_name_value.as_str() {
+                        Some(object_name_str) => client.object_name = Some(object_name_str.to_owned()),
+                        None => Self::insert_invalid_item_value(parser, source, item, "a string"),
+                    }
                 },
                 "gitCommit" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let git_commit_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let git_commit_bool = git_commit_value.as_bool().unwrap();
-                    client.git_commit = git_commit_bool;
+                    let Some(git_commit_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match git_commit_value.as_bool() {
+                        Some(git_commit_bool) => client.git_commit = git_commit_bool,
+                        None => Self::insert_invalid_item_value(parser, source, item, "bool"),
+                    }
                 }
-                _ => { panic!("Undefined name '{}' in client generator block.", item.identifier.name.as_str())}
+                _ => Self::insert_error(parser, source, item.identifier.span, format!("Undefined name '{}' in client generator block.", item.identifier.name)),
             }
         }
+        Self::check_required_items(parser, source, &client.items, client.span, "client generator", &["provider", "dest", "package", "host"]);
     }
 
     pub(crate) fn resolve_model_entity_generator(parser: &Parser, source: &Source, generator: &mut Generator) {
         for item in generator.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "provider" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let provider_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let provider_str = provider_value.as_raw_enum_choice().unwrap();
-                    match provider_str {
-                        "rust" => generator.provider = Some(Environment::Rust),
-                        "node" => generator.provider = Some(Environment::NodeJS),
-                        "python" => generator.provider = Some(Environment::Python),
-                        "go" => generator.provider = Some(Environment::Go),
-                        "java" => generator.provider = Some(Environment::Java),
-                        _ => panic!("Unrecognized entity generator provider. {}", provider_str)
+                    let Some(provider_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match provider_value.as_raw_enum_choice() {
+                        Some("rust") => generator.provider = Some(Environment::Rust),
+                        Some("node") => generator.provider = Some(Environment::NodeJS),
+                        Some("python") => generator.provider = Some(Environment::Python),
+                        Some("go") => generator.provider = Some(Environment::Go),
+                        Some("java") => generator.provider = Some(Environment::Java),
+                        Some(provider_str) => Self::insert_error(parser, source, item.expression.kind.span(), format!("Unrecognized entity generator provider '{}'.", provider_str)),
+                        None => Self::insert_invalid_item_value(parser, source, item, "an enum choice"),
                     }
                 },
                 "dest" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let dest_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
+                    let Some(dest_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let Some(dest_str) = dest_value.as_str() else {
+                        Self::insert_invalid_item_value(parser, source, item, "a string");
+                        continue
+                    };
                     let mut dest = source.path.clone();
                     dest.pop();
-                    dest.push(PathBuf::from(dest_value.as_str().unwrap()));
+                    dest.push(PathBuf::from(dest_str));
                     let absolute = dest.absolutize().unwrap();
                     generator.dest = Some(absolute.as_ref().to_owned());
                 },
-                _ => { panic!("Undefined name '{}' in entity generator block.", item.identifier.name.as_str())}
+                _ => Self::insert_error(parser, source, item.identifier.span, format!("Undefined name '{}' in entity generator block.", item.identifier.name)),
             }
         }
+        Self::check_required_items(parser, source, &generator.items, generator.span, "entity generator", &["provider", "dest"]);
     }
 
     pub(crate) fn resolve_config(parser: &Parser, source: &Source, config: &mut ServerConfig) {
         for item in config.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "bind" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let bind_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    match bind_value.as_tuple() {
-                        Some(tuple_vec) => {
-                            let arg1 = tuple_vec.get(0).unwrap();
-                            let arg2 = tuple_vec.get(1).unwrap();
-                            let str = arg1.as_str().unwrap().to_owned();
-                            let int = arg2.as_i32().unwrap().to_owned();
-                            config.bind = Some((str, int as u16));
-                        }
-                        None => panic!("Argument to 'bind' should be a tuple.")
+                    let Some(bind_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let bind = bind_value.as_tuple().map(|tuple_vec| {
+                        (tuple_vec.get(0).map(|v| v.as_str()).flatten(), tuple_vec.get(1).map(|v| v.as_i32()).flatten())
+                    });
+                    match bind {
+                        Some((Some(str), Some(int))) => config.bind = Some((str.to_owned(), int as u16)),
+                        _ => Self::insert_invalid_item_value(parser, source, item, "a tuple of host and port"),
                     }
                 }
                 "jwtSecret" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let jwt_secret_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
+                    let Some(jwt_secret_value) = Self::resolve_item_value(parser, source, item) else { continue };
                     match jwt_secret_value {
                         Value::Null => (),
                         Value::String(s) => config.jwt_secret = Some(s.clone()),
-                        _ => panic!("Value of 'jwtSecret' should be string.")
+                        _ => Self::insert_invalid_item_value(parser, source, item, "string"),
+                    }
+                }
+                "jwtAlgorithm" => {
+                    let Some(jwt_algorithm_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match jwt_algorithm_value.as_raw_enum_choice() {
+                        Some("hs256") => config.jwt_algorithm = Some(JwtAlgorithm::HS256),
+                        Some("rs256") => config.jwt_algorithm = Some(JwtAlgorithm::RS256),
+                        Some("es256") => config.jwt_algorithm = Some(JwtAlgorithm::ES256),
+                        Some(jwt_algorithm_str) => Self::insert_error(parser, source, item.expression.kind.span(), format!("Unrecognized JWT algorithm '{}'.", jwt_algorithm_str)),
+                        None => Self::insert_invalid_item_value(parser, source, item, "an enum choice"),
+                    }
+                }
+                "jwtPrivateKey" | "jwtPublicKey" => {
+                    let Some(key_path_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let Some(key_path_str) = key_path_value.as_str() else {
+                        Self::insert_invalid_item_value(parser, source, item, "string");
+                        continue
+                    };
+                    let mut key_path = source.path.clone();
+                    key_path.pop();
+                    key_path.push(PathBuf::from(key_path_str));
+                    let key_path = key_path.absolutize().unwrap();
+                    let key = match std::fs::read_to_string(&key_path) {
+                        Ok(key) => key,
+                        Err(_) => {
+                            Self::insert_error(parser, source, item.expression.kind.span(), format!("Cannot read '{}' at '{}'.", item.identifier.name, key_path.to_str().unwrap()));
+                            continue
+                        }
+                    };
+                    if item.identifier.name.as_str() == "jwtPrivateKey" {
+                        config.jwt_private_key = Some(key);
+                    } else {
+                        config.jwt_public_key = Some(key);
+                    }
+                }
+                "jwtExpiresIn" | "refreshTokenExpiresIn" => {
+                    let Some(expires_in_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let expires_in = match expires_in_value.as_i64() {
+                        Some(i) if i > 0 => i,
+                        _ => {
+                            Self::insert_invalid_item_value(parser, source, item, "a positive number of seconds");
+                            continue
+                        }
+                    };
+                    if item.identifier.name.as_str() == "jwtExpiresIn" {
+                        config.jwt_expires_in = Some(expires_in);
+                    } else {
+                        config.refresh_token_expires_in = Some(expires_in);
                     }
                 }
                 "pathPrefix" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let path_prefix_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
+                    let Some(path_prefix_value) = Self::resolve_item_value(parser, source, item) else { continue };
                     match path_prefix_value {
                         Value::Null => (),
                         Value::String(s) => config.path_prefix = Some(s.clone()),
-                        _ => panic!("Value of 'pathPrefix' should be string.")
+                        _ => Self::insert_invalid_item_value(parser, source, item, "string"),
+                    }
+                }
+                "middlewares" => {
+                    let Some(middlewares_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match Self::value_as_strings(&middlewares_value, false) {
+                        Some(v) => config.middlewares = Some(v),
+                        None => Self::insert_invalid_item_value(parser, source, item, "array of strings"),
+                    }
+                }
+                "logLevel" => {
+                    let Some(log_level_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match log_level_value.as_raw_enum_choice() {
+                        Some("debug") => config.log_level = Some(LogLevel::Debug),
+                        Some("info") => config.log_level = Some(LogLevel::Info),
+                        Some("warn") => config.log_level = Some(LogLevel::Warn),
+                        Some("error") => config.log_level = Some(LogLevel::Error),
+                        Some(log_level_str) => Self::insert_error(parser, source, item.expression.kind.span(), format!("Unrecognized log level '{}'.", log_level_str)),
+                        None => Self::insert_invalid_item_value(parser, source, item, "an enum choice"),
+                    }
+                }
+                "logFormat" => {
+                    let Some(log_format_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match log_format_value.as_raw_enum_choice() {
+                        Some("text") => config.log_format = Some(LogFormat::Text),
+                        Some("json") => config.log_format = Some(LogFormat::Json),
+                        Some(log_format_str) => Self::insert_error(parser, source, item.expression.kind.span(), format!("Unrecognized log format '{}'.", log_format_str)),
+                        None => Self::insert_invalid_item_value(parser, source, item, "an enum choice"),
                     }
                 }
-                _ => { panic!("Undefined name '{}' in config block.", item.identifier.name.as_str())}
+                "logQueries" => {
+                    let Some(log_queries_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match log_queries_value.as_bool() {
+                        Some(b) => config.log_queries = Some(b),
+                        None => Self::insert_invalid_item_value(parser, source, item, "bool"),
+                    }
+                }
+                "allowOrigins" | "allowMethods" => {
+                    let Some(list_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let Some(list) = Self::value_as_strings(&list_value, true) else {
+                        Self::insert_invalid_item_value(parser, source, item, "string or array of strings");
+                        continue
+                    };
+                    if item.identifier.name.as_str() == "allowOrigins" {
+                        config.allow_origins = Some(list);
+                    } else {
+                        config.allow_methods = Some(list);
+                    }
+                }
+                "allowCredentials" => {
+                    let Some(allow_credentials_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match allow_credentials_value.as_bool() {
+                        Some(b) => config.allow_credentials = Some(b),
+                        None => Self::insert_invalid_item_value(parser, source, item, "bool"),
+                    }
+                }
+                "maxBodySize" => {
+                    let Some(max_body_size_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    match max_body_size_value.as_i64() {
+                        Some(i) if i > 0 => config.max_body_size = Some(i as usize),
+                        _ => Self::insert_invalid_item_value(parser, source, item, "a positive number of bytes"),
+                    }
+                }
+                "headers" => {
+                    let Some(headers_value) = Self::resolve_item_value(parser, source, item) else { continue };
+                    let headers = headers_value.as_hashmap().map(|map| {
+                        map.iter().sorted_by_key(|(k, _)| *k).map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_owned()))).collect::<Option<Vec<(String, String)>>>()
+                    }).flatten();
+                    match headers {
+                        Some(headers) => config.headers = Some(headers),
+                        None => Self::insert_invalid_item_value(parser, source, item, "a dictionary of strings"),
+                    }
+                }
+                _ => Self::insert_error(parser, source, item.identifier.span, format!("Undefined name '{}' in config block.", item.identifier.name)),
+            }
+        }
+        Self::check_required_items(parser, source, &config.items, config.span, "config", &["bind"]);
+    }
+
+    fn value_as_strings(value: &Value, allows_single: bool) -> Option<Vec<String>> {
+        match value {
+            Value::String(s) if allows_single => Some(vec![s.clone()]),
+            Value::Vec(v) => v.iter().map(|i| i.as_str().map(|s| s.to_owned())).collect(),
+            _ => None,
+        }
+    }
+
+    // Block items
+
+    /// Resolves the value of a block item. `None` is returned if the expression has errors,
+    /// these are already reported.
+    fn resolve_item_value(parser: &Parser, source: &Source, item: &mut Item) -> Option<Value> {
+        let errors_count = parser.diagnostics.errors_count();
+        Self::resolve_expression(parser, source, &mut item.expression);
+        let value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap(), item.expression.kind.span());
+        (parser.diagnostics.errors_count() == errors_count).then_some(value)
+    }
+
+    fn insert_invalid_item_value(parser: &Parser, source: &Source, item: &Item, expected: &str) {
+        Self::insert_error(parser, source, item.expression.kind.span(), format!("Value of '{}' should be {}.", item.identifier.name, expected));
+    }
+
+    fn check_required_items(parser: &Parser, source: &Source, items: &Vec<Item>, span: Span, block: &str, names: &[&str]) {
+        for name in names {
+            if !items.iter().any(|item| item.identifier.name.as_str() == *name) {
+                Self::insert_error(parser, source, span, format!("Missing '{}' in {} block.", name, block));
             }
         }
     }
 
+    // Diagnostics
+
+    fn insert_error(parser: &Parser, source: &Source, span: Span, message: impl Into<String>) {
+        parser.to_mut().diagnostics.insert_error(&source.path, span, message);
+    }
+
+    fn insert_warning(parser: &Parser, source: &Source, span: Span, message: impl Into<String>) {
+        parser.to_mut().diagnostics.insert_warning(&source.path, span, message);
+    }
+
     // Expression
 
     pub(crate) fn resolve_expression<'a>(parser: &Parser, source: &Source, expression: &mut Expression) {
@@ -610,16 +843,16 @@ impl Resolver {
                 Self::resolve_arith_expr(parser, source, arith, when_option)
             }
             ExpressionKind::NumericLiteral(n) => {
-                Self::resolve_numeric_literal(n)
+                Self::resolve_numeric_literal(parser, source, n)
             }
             ExpressionKind::StringLiteral(s) => {
-                Self::resolve_string_literal(s)
+                Self::resolve_string_literal(parser, source, s)
             }
             ExpressionKind::RegExpLiteral(r) => {
-                Self::resolve_regexp_literal(r)
+                Self::resolve_regexp_literal(parser, source, r)
             }
             ExpressionKind::BoolLiteral(b) => {
-                Self::resolve_bool_literal(b)
+                Self::resolve_bool_literal(parser, source, b)
             }
             ExpressionKind::NullLiteral(n) => {
                 Self::resolve_null_literal(n)
@@ -642,11 +875,13 @@ impl Resolver {
             ExpressionKind::Identifier(identifier) => {
                 Self::resolve_identifier(parser, source, identifier, None)
             }
-            ExpressionKind::ArgumentList(_a) => {
-                panic!("Argument list cannot appear alone.")
+            ExpressionKind::ArgumentList(a) => {
+                Self::insert_error(parser, source, a.span, "Argument list cannot appear alone.");
+                Entity::Value(Value::Null)
             }
-            ExpressionKind::Subscript(_s) => {
-                panic!("Subscript cannot appear alone.")
+            ExpressionKind::Subscript(s) => {
+                Self::insert_error(parser, source, s.span, "Subscript cannot appear alone.");
+                Entity::Value(Value::Null)
             }
             ExpressionKind::Unit(unit) => {
                 Self::resolve_unit(parser, source, unit)
@@ -659,7 +894,15 @@ impl Resolver {
 
     fn resolve_expression_kind_force_value(parser: &Parser, source: &Source, expression_kind: &ExpressionKind, when_option: bool) -> Value {
         let entity = Self::resolve_expression_kind(parser, source, expression_kind, when_option);
-        Self::unwrap_into_value_if_needed(parser, source, &entity)
+        Self::unwrap_into_value_if_needed(parser, source, &entity, expression_kind.span())
+    }
+
+    /// Resolves an operand. `None` is returned if the operand has errors, so that the operator
+    /// doesn't report them again.
+    fn resolve_operand(parser: &Parser, source: &Source, expression_kind: &ExpressionKind, when_option: bool) -> Option<Value> {
+        let errors_count = parser.diagnostics.errors_count();
+        let value = Self::resolve_expression_kind_force_value(parser, source, expression_kind, when_option);
+        (parser.diagnostics.errors_count() == errors_count).then_some(value)
     }
 
     // identifier
@@ -669,38 +912,37 @@ impl Resolver {
     }
 
     fn resolve_identifier(parser: &Parser, source: &Source, identifier: &Identifier, parent: Option<&Entity>) -> Entity {
-        match parent {
+        let entity = match parent {
             Some(parent) => {
-                if parent.is_accessible() {
-                    let parent = parent.as_accessible().unwrap();
-                    if parent.is_container() {
-                        let container = parent.as_container().unwrap();
-                        let result = container.objects.get(&identifier.name);
-                        match result {
-                            Some(entity) => entity.clone(),
-                            None => panic!("Cannot access {}", identifier.name),
-                        }
-                    } else {
-                        panic!("Cannot access {}", identifier.name);
-                    }
-                } else {
-                    panic!("Cannot access {}", identifier.name);
+                let entity = parent.as_accessible().map(|a| a.access_property(&identifier.name)).flatten().cloned();
+                if entity.is_none() {
+                    Self::insert_error(parser, source, identifier.span, format!("Cannot access '{}'.", identifier.name));
                 }
+                entity
             }
             None => {
-                match Self::find_identifier_origin_in_source(parser, source, identifier) {
-                    Some(reference) => Entity::Reference(reference),
-                    None => Container::std_global_constants().access_property(&identifier.name).clone()
+                let entity = match Self::find_identifier_origin_in_source(parser, source, identifier) {
+                    Some(reference) => Some(Entity::Reference(reference)),
+                    None => Container::std_global_constants().access_property(&identifier.name).cloned()
+                };
+                if entity.is_none() {
+                    Self::insert_error(parser, source, identifier.span, format!("Cannot find '{}'.", identifier.name));
                 }
+                entity
             }
-        }
+        };
+        entity.unwrap_or(Entity::Value(Value::Null))
     }
 
     fn resolve_unit(parser: &Parser, source: &Source, unit: &Unit) -> Entity {
+        let errors_count = parser.diagnostics.errors_count();
         let first_expression = unit.expressions.get(0).unwrap();
         let mut entity = Self::resolve_expression_kind(parser, source, first_expression, false);
         for (index, expression) in unit.expressions.iter().enumerate() {
             if index == 0 { continue }
+            if parser.diagnostics.errors_count() > errors_count {
+                return Entity::Value(Value::Null);
+            }
             entity = Self::resolve_accessor(parser, source, expression, &entity);
         }
         return entity
@@ -717,75 +959,86 @@ impl Resolver {
                     let value = Self::resolve_expression_kind_force_value(parser, source, &arg.value, false);
                     arg.resolved = Some(Entity::Value(value));
                 }
-                match entity.as_accessible().unwrap() {
-                    Accessible::Callable(callable) => Entity::Value(callable(args.arguments.clone())),
-                    _ => unreachable!(),
+                let result = match entity.as_accessible() {
+                    Some(Accessible::Callable(callable)) => callable(args.arguments),
+                    _ => Err("This expression is not callable.".to_owned()),
+                };
+                match result {
+                    Ok(value) => Entity::Value(value),
+                    Err(message) => {
+                        Self::insert_error(parser, source, argument_list.span, message);
+                        Entity::Value(Value::Null)
+                    }
                 }
             }
             ExpressionKind::Identifier(identifier) => {
                 Self::resolve_identifier(parser, source, identifier, Some(entity))
             }
-            _ => panic!()
+            _ => unreachable!()
         }
     }
 
     fn resolve_subscript(parser: &Parser, source: &Source, subscript: &Subscript, entity: &Entity) -> Entity {
-        let index_entity = Self::resolve_expression_kind(parser, source, &subscript.expression, false);
-        let index_value = Self::unwrap_into_value_if_needed(parser, source, &index_entity);
-        if entity.is_accessible() {
+        let index_value = Self::resolve_expression_kind_force_value(parser, source, &subscript.expression, false);
+        let result = if entity.is_accessible() {
             let accessible = entity.as_accessible().unwrap();
             match accessible {
                 Accessible::Env(env) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(env.get_value(s)),
-                        None => panic!("ENV can only be subscripted with string.")
+                        Some(s) => Ok(env.get_value(s)),
+                        None => Err("ENV can only be subscripted with string.")
                     }
                 }
-                _ => panic!("Cannot access subscript"),
+                _ => Err("Cannot access subscript."),
             }
         } else {
-            let entity_value = Self::unwrap_into_value_if_needed(parser, source, entity);
+            let entity_value = Self::unwrap_into_value_if_needed(parser, source, entity, subscript.span);
             match entity_value {
                 Value::String(s) => {
                     match index_value.as_i64() {
-                        Some(i) => Entity::Value(Value::String(s.chars().nth(i as usize).unwrap().to_string())),
-                        None => panic!("String can only be subscripted with integer.")
+                        Some(i) => s.chars().nth(i as usize).map(|c| Value::String(c.to_string())).ok_or("Index out of range."),
+                        None => Err("String can only be subscripted with integer.")
                     }
                 }
                 Value::Vec(v) => {
                     match index_value.as_i64() {
-                        Some(i) => Entity::Value(v.get(i as usize).unwrap().clone()),
-                        None => panic!("Array can only be subscripted with integer.")
+                        Some(i) => v.get(i as usize).cloned().ok_or("Index out of range."),
+                        None => Err("Array can only be subscripted with integer.")
                     }
                 }
                 Value::HashMap(m) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(m.get(s).unwrap().clone()),
-                        None => panic!("Map can only be subscripted with string.")
+                        Some(s) => m.get(s).cloned().ok_or("Key is not found."),
+                        None => Err("Map can only be subscripted with string.")
                     }
                 }
                 Value::BTreeMap(m) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(m.get(s).unwrap().clone()),
-                        None => panic!("Map can only be subscripted with string.")
+                        Some(s) => m.get(s).cloned().ok_or("Key is not found."),
+                        None => Err("Map can only be subscripted with string.")
                     }
                 }
                 Value::IndexMap(m) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(m.get(s).unwrap().clone()),
-                        None => panic!("Map can only be subscripted with string.")
+                        Some(s) => m.get(s).cloned().ok_or("Key is not found."),
+                        None => Err("Map can only be subscripted with string.")
                     }
                 }
-                _ => {
-                    panic!("")
-                }
+                _ => Err("Cannot access subscript.")
+            }
+        };
+        match result {
+            Ok(value) => Entity::Value(value),
+            Err(message) => {
+                Self::insert_error(parser, source, subscript.span, message);
+                Entity::Value(Value::Null)
             }
         }
     }
 
     // literals and operators
 
-    fn resolve_numeric_literal(n: &NumericLiteral) -> Entity {
+    fn resolve_numeric_literal(parser: &Parser, source: &Source, n: &NumericLiteral) -> Entity {
         let i = i32::from_str(&n.value);
         if i.is_ok() {
             return Entity::Value(Value::I32(i.unwrap()));
@@ -798,22 +1051,38 @@ impl Resolver {
         if i.is_ok() {
             return Entity::Value(Value::F64(i.unwrap()));
         }
-        panic!("Cannot resolve numeric value: {}.", n.value.as_str())
+        Self::insert_error(parser, source, n.span, format!("Cannot resolve numeric value: {}.", n.value.as_str()));
+        Entity::Value(Value::Null)
     }
 
-    fn resolve_string_literal(s: &StringLiteral) -> Entity {
-        return Entity::Value(Value::String(unescape(s.value.as_str()).unwrap()));
+    fn resolve_string_literal(parser: &Parser, source: &Source, s: &StringLiteral) -> Entity {
+        match unescape(s.value.as_str()) {
+            Ok(value) => Entity::Value(Value::String(value)),
+            Err(_) => {
+                Self::insert_error(parser, source, s.span, "Invalid string literal.");
+                Entity::Value(Value::Null)
+            }
+        }
     }
 
-    fn resolve_regexp_literal(r: &RegExpLiteral) -> Entity {
-        return Entity::Value(Value::RegExp(Regex::new(r.value.as_str()).unwrap()));
+    fn resolve_regexp_literal(parser: &Parser, source: &Source, r: &RegExpLiteral) -> Entity {
+        match Regex::new(r.value.as_str()) {
+            Ok(regex) => Entity::Value(Value::RegExp(regex)),
+            Err(_) => {
+                Self::insert_error(parser, source, r.span, "Invalid regular expression.");
+                Entity::Value(Value::Null)
+            }
+        }
     }
 
-    fn resolve_bool_literal(b: &BoolLiteral) -> Entity {
+    fn resolve_bool_literal(parser: &Parser, source: &Source, b: &BoolLiteral) -> Entity {
         match b.value.as_str() {
             "true" => Entity::Value(Value::Bool(true)),
             "false" => Entity::Value(Value::Bool(false)),
-            _ => panic!("Cannot resolve bool value: {}", b.value.as_str())
+            _ => {
+                Self::insert_error(parser, source, b.span, format!("Cannot resolve bool value: {}", b.value.as_str()));
+                Entity::Value(Value::Null)
+            }
         }
     }
 
@@ -833,19 +1102,16 @@ impl Resolver {
         let mut result = vec![];
         for arg in arg_list.arguments.iter() {
             let name = arg.name.as_ref().map(|i| i.name.clone());
-            let resolve_result = Self::resolve_expression_kind(parser, source, &arg.value, false);
-            let value = Self::unwrap_into_value_if_needed(parser, source, &resolve_result);
+            let value = Self::resolve_expression_kind_force_value(parser, source, &arg.value, false);
             result.push((name, value));
         }
         result
     }
 
     fn resolve_range_literal(parser: &Parser, source: &Source, range_literal: &RangeLiteral) -> Entity {
-        let a = Self::resolve_expression_kind(parser, source, range_literal.expressions.get(0).unwrap(), false);
-        let a_v = Self::unwrap_into_value_if_needed(parser, source, &a);
+        let a_v = Self::resolve_expression_kind_force_value(parser, source, range_literal.expressions.get(0).unwrap(), false);
         let start = Box::new(a_v);
-        let b = Self::resolve_expression_kind(parser, source, range_literal.expressions.get(1).unwrap(), false);
-        let b_v = Self::unwrap_into_value_if_needed(parser, source, &b);
+        let b_v = Self::resolve_expression_kind_force_value(parser, source, range_literal.expressions.get(1).unwrap(), false);
         let end = Box::new(b_v);
         Entity::Value(Value::Range(Range { closed: range_literal.closed.clone(), start, end }))
     }
@@ -853,8 +1119,7 @@ impl Resolver {
     fn resolve_tuple_literal(parser: &Parser, source: &Source, tuple_literal: &TupleLiteral) -> Entity {
         let mut resolved = vec![];
         for expression in tuple_literal.expressions.iter() {
-            let e = Self::resolve_expression_kind(parser, source, expression, false);
-            let v = Self::unwrap_into_value_if_needed(parser, source, &e);
+            let v = Self::resolve_expression_kind_force_value(parser, source, expression, false);
             resolved.push(v);
         }
         Entity::Value(Value::Tuple(resolved))
@@ -863,8 +1128,7 @@ impl Resolver {
     fn resolve_array_literal(parser: &Parser, source: &Source, array_literal: &ArrayLiteral, when_option: bool) -> Entity {
         let mut resolved = vec![];
         for expression in array_literal.expressions.iter() {
-            let e = Self::resolve_expression_kind(parser, source, expression, when_option);
-            let v = Self::unwrap_into_value_if_needed(parser, source, &e);
+            let v = Self::resolve_expression_kind_force_value(parser, source, expression, when_option);
             resolved.push(v);
         }
         Entity::Value(Value::Vec(resolved))
@@ -873,11 +1137,12 @@ impl Resolver {
     fn resolve_dictionary_literal(parser: &Parser, source: &Source, dic: &DictionaryLiteral) -> Entity {
         let mut resolved: HashMap<String, Value> = HashMap::new();
         for (key, value) in dic.expressions.iter() {
-            let k = Self::resolve_expression_kind(parser, source, key, false);
-            let k = Self::unwrap_into_value_if_needed(parser, source, &k);
-            let v = Self::resolve_expression_kind(parser, source, value, false);
-            let v = Self::unwrap_into_value_if_needed(parser, source, &v);
-            resolved.insert(k.as_str().unwrap().to_string(), v);
+            let k = Self::resolve_expression_kind_force_value(parser, source, key, false);
+            let v = Self::resolve_expression_kind_force_value(parser, source, value, false);
+            match k.as_str() {
+                Some(k) => { resolved.insert(k.to_string(), v); }
+                None => Self::insert_error(parser, source, key.span(), "Dictionary key should be a string."),
+            }
         }
         Entity::Value(Value::HashMap(resolved))
     }
@@ -894,117 +1159,113 @@ impl Resolver {
     }
 
     fn resolve_negation(parser: &Parser, source: &Source, negation: &Negation) -> Entity {
-        let value = Self::resolve_expression_kind_force_value(parser, source, &negation.expression, false);
-        Entity::Value(match value {
-            Value::I32(v) => Value::I32(-v),
-            Value::I64(v) => Value::I64(-v),
-            Value::F32(v) => Value::F32(-v),
-            Value::F64(v) => Value::F64(-v),
-            _ => panic!("Cannot negate value {:?}", value)
-        })
+        match Self::resolve_operand(parser, source, &negation.expression, false) {
+            Some(value) => Self::negate(parser, source, value, negation.span),
+            None => Entity::Value(Value::Null),
+        }
     }
 
     fn resolve_bitwise_negation(parser: &Parser, source: &Source, negation: &BitwiseNegation, when_option: bool) -> Entity {
-        let value = Self::resolve_expression_kind_force_value(parser, source, &negation.expression, when_option);
-        Entity::Value(match value {
-            Value::I32(v) => Value::I32(!v),
-            Value::I64(v) => Value::I64(!v),
-            Value::RawEnumChoice(e, _) => if when_option {
-                Value::RawOptionChoice(Action::from_name(&e).neg().to_u32())
-            } else {
-                panic!("Unhandled option bitwise operation")
+        match Self::resolve_operand(parser, source, &negation.expression, when_option) {
+            Some(value) => Self::bitwise_negate(parser, source, value, when_option, negation.span),
+            None => Entity::Value(Value::Null),
+        }
+    }
+
+    fn negate(parser: &Parser, source: &Source, value: Value, span: Span) -> Entity {
+        match -value {
+            Ok(value) => Entity::Value(value),
+            Err(_) => {
+                Self::insert_error(parser, source, span, "Cannot apply operator '-' to this value.");
+                Entity::Value(Value::Null)
             }
-            Value::RawOptionChoice(o) => if when_option {
-                Value::RawOptionChoice(Action::from_u32(o).neg().to_u32())
+        }
+    }
+
+    fn bitwise_negate(parser: &Parser, source: &Source, value: Value, when_option: bool, span: Span) -> Entity {
+        let result = match &value {
+            Value::I32(v) => Ok(Value::I32(!v)),
+            Value::I64(v) => Ok(Value::I64(!v)),
+            Value::RawEnumChoice(_, _) | Value::RawOptionChoice(_) => if when_option {
+                Self::value_to_action_option(&value).map(|a| Value::RawOptionChoice(a.neg().to_u32()))
             } else {
-                panic!("Unhandled option bitwise operation")
+                Err("Unhandled option bitwise operation.".to_owned())
             },
-            _ => panic!("Cannot negate value {:?}", value)
-        })
+            _ => Err("Cannot apply operator '~' to this value.".to_owned()),
+        };
+        match result {
+            Ok(value) => Entity::Value(value),
+            Err(message) => {
+                Self::insert_error(parser, source, span, message);
+                Entity::Value(Value::Null)
+            }
+        }
     }
 
     fn resolve_arith_expr(parser: &Parser, source: &Source, arith_expr: &ArithExpr, when_option: bool) -> Entity {
         match arith_expr {
             ArithExpr::Expression(expression) => return Self::resolve_expression_kind(parser, source, &expression, when_option),
             ArithExpr::UnaryNeg(expression) => {
-                let origin = Self::resolve_expression_kind_force_value(parser, source, &expression, when_option);
-                return Entity::Value((-origin).unwrap());
+                match Self::resolve_operand(parser, source, &expression, when_option) {
+                    Some(origin) => Self::negate(parser, source, origin, arith_expr.span()),
+                    None => Entity::Value(Value::Null),
+                }
             }
             ArithExpr::UnaryBitNeg(expression) => {
-                let origin = Self::resolve_expression_kind_force_value(parser, source, &expression, when_option);
-                return Entity::Value(match origin {
-                    Value::I32(v) => Value::I32(!v),
-                    Value::I64(v) => Value::I64(!v),
-                    Value::RawEnumChoice(e, _) => if when_option {
-                        Value::RawOptionChoice(Action::from_name(&e).neg().to_u32())
-                    } else {
-                        panic!("Unhandled option bitwise operation")
-                    }
-                    Value::RawOptionChoice(o) => if when_option {
-                        Value::RawOptionChoice(Action::from_u32(o).neg().to_u32())
-                    } else {
-                        panic!("Unhandled option bitwise operation")
-                    },
-                    _ => panic!("Cannot negate value {:?}", origin)
-                });
+                match Self::resolve_operand(parser, source, &expression, when_option) {
+                    Some(origin) => Self::bitwise_negate(parser, source, origin, when_option, arith_expr.span()),
+                    None => Entity::Value(Value::Null),
+                }
             }
             ArithExpr::BinaryOp { lhs, op, rhs } => {
-                let lhs_value = Self::resolve_arith_expr(parser, source, &lhs, when_option).as_value().unwrap().clone();
-                let rhs_value = Self::resolve_arith_expr(parser, source, &rhs, when_option).as_value().unwrap().clone();
-                match op {
-                    Op::Add => {
-                        Entity::Value((lhs_value.clone() + rhs_value.clone()).unwrap())
-                    }
-                    Op::Sub => {
-                        Entity::Value((lhs_value.clone() - rhs_value.clone()).unwrap())
-                    }
-                    Op::Mul => {
-                        Entity::Value((lhs_value.clone() * rhs_value.clone()).unwrap())
-                    }
-                    Op::Div => {
-                        Entity::Value((lhs_value.clone() / rhs_value.clone()).unwrap())
-                    }
-                    Op::Mod => {
-                        Entity::Value((lhs_value.clone() % rhs_value.clone()).unwrap())
-                    }
-                    Op::BitAnd => {
-                        if when_option {
-                            let lhs_action = Self::value_to_action_option(&lhs_value);
-                            let rhs_action = Self::value_to_action_option(&rhs_value);
-                            Entity::Value(Value::RawOptionChoice(lhs_action.and(rhs_action).to_u32()))
-                        } else {
-                            Entity::Value((lhs_value.clone() & rhs_value.clone()).unwrap())
-                        }
-                    }
-                    Op::BitXor => {
-                        if when_option {
-                            let lhs_action = Self::value_to_action_option(&lhs_value);
-                            let rhs_action = Self::value_to_action_option(&rhs_value);
-                            Entity::Value(Value::RawOptionChoice(lhs_action.xor(rhs_action).to_u32()))
-                        } else {
-                            Entity::Value((lhs_value.clone() ^ rhs_value.clone()).unwrap())
-                        }
-                    }
-                    Op::BitOr => {
-                        if when_option {
-                            let lhs_action = Self::value_to_action_option(&lhs_value);
-                            let rhs_action = Self::value_to_action_option(&rhs_value);
-                            Entity::Value(Value::RawOptionChoice(lhs_action.or(rhs_action).to_u32()))
-                        } else {
-                            Entity::Value((lhs_value.clone() | rhs_value.clone()).unwrap())
+                let errors_count = parser.diagnostics.errors_count();
+                let lhs_value = Self::resolve_arith_expr(parser, source, &lhs, when_option);
+                let lhs_value = Self::unwrap_into_value_if_needed(parser, source, &lhs_value, lhs.span());
+                let rhs_value = Self::resolve_arith_expr(parser, source, &rhs, when_option);
+                let rhs_value = Self::unwrap_into_value_if_needed(parser, source, &rhs_value, rhs.span());
+                if parser.diagnostics.errors_count() > errors_count {
+                    return Entity::Value(Value::Null);
+                }
+                let result = match op {
+                    Op::Add => (lhs_value + rhs_value).map_err(|_| ()),
+                    Op::Sub => (lhs_value - rhs_value).map_err(|_| ()),
+                    Op::Mul => (lhs_value * rhs_value).map_err(|_| ()),
+                    Op::Div => (lhs_value / rhs_value).map_err(|_| ()),
+                    Op::Mod => (lhs_value % rhs_value).map_err(|_| ()),
+                    Op::BitAnd | Op::BitXor | Op::BitOr if when_option => {
+                        match (Self::value_to_action_option(&lhs_value), Self::value_to_action_option(&rhs_value)) {
+                            (Ok(lhs_action), Ok(rhs_action)) => Ok(Value::RawOptionChoice(match op {
+                                Op::BitAnd => lhs_action.and(rhs_action),
+                                Op::BitXor => lhs_action.xor(rhs_action),
+                                _ => lhs_action.or(rhs_action),
+                            }.to_u32())),
+                            (Err(message), _) | (_, Err(message)) => {
+                                Self::insert_error(parser, source, arith_expr.span(), message);
+                                return Entity::Value(Value::Null);
+                            }
                         }
                     }
+                    Op::BitAnd => (lhs_value & rhs_value).map_err(|_| ()),
+                    Op::BitXor => (lhs_value ^ rhs_value).map_err(|_| ()),
+                    Op::BitOr => (lhs_value | rhs_value).map_err(|_| ()),
                     _ => unreachable!()
+                };
+                match result {
+                    Ok(value) => Entity::Value(value),
+                    Err(_) => {
+                        Self::insert_error(parser, source, arith_expr.span(), format!("Cannot apply operator '{}' to these values.", op));
+                        Entity::Value(Value::Null)
+                    }
                 }
             }
         }
     }
 
-    fn value_to_action_option(v: &Value) -> Action {
+    fn value_to_action_option(v: &Value) -> Result<Action, String> {
         match v {
             Value::RawEnumChoice(e, _) => Action::from_name(&e),
-            Value::RawOptionChoice(u) => Action::from_u32(*u),
-            _ => unreachable!()
+            Value::RawOptionChoice(u) => Ok(Action::from_u32(*u)),
+            _ => Err("Only action options can be combined here.".to_owned())
         }
     }
 
@@ -1030,7 +1291,7 @@ impl Resolver {
             let i = source.get_import(*id);
             let found = i.identifiers.iter().find(|i| &i.name == &identifier.name);
             if found.is_some() {
-                let source_id = i.from_id.unwrap();
+                let Some(source_id) = i.from_id else { continue };
                 let origin_source = parser.get_source(source_id);
                 return Self::find_identifier_origin_in_source(parser, origin_source, identifier);
             }
@@ -1038,25 +1299,32 @@ impl Resolver {
         None
     }
 
-    fn constant_with_reference(parser: &Parser, _source: &Source, reference: (usize, usize)) -> Value {
+    fn constant_with_reference(parser: &Parser, reference: (usize, usize)) -> Value {
         let source = parser.get_source(reference.0);
+        if source.get_constant(reference.1).expression.resolved.is_none() {
+            // constants of imported files are resolved on first use, the placeholder stops cycles
+            let constant = source.to_mut().tops.get_mut(&reference.1).unwrap().as_constant_mut().unwrap();
+            constant.expression.resolved = Some(Entity::Value(Value::Null));
+            Self::resolve_constant(parser, source, constant);
+        }
         let c = source.get_constant(reference.1);
         let entity = c.expression.resolved.as_ref().unwrap();
-        Self::unwrap_into_value_if_needed(parser, source, entity)
+        Self::unwrap_into_value_if_needed(parser, source, entity, c.expression.kind.span())
     }
 
-    fn unwrap_into_value_if_needed(parser: &Parser, source: &Source, entity: &Entity) -> Value {
+    fn unwrap_into_value_if_needed(parser: &Parser, source: &Source, entity: &Entity, span: Span) -> Value {
         if entity.is_value() {
             return entity.as_value().unwrap().clone()
         } else if entity.is_reference() {
             let r = entity.as_reference().unwrap();
             return if r.is_constant_ref() {
-                Self::constant_with_reference(parser, source, r.as_constant_ref().unwrap())
+                Self::constant_with_reference(parser, r.as_constant_ref().unwrap())
             } else {
                 Value::RawEnumChoice(r.as_model_ref().unwrap().2.clone(), None)
             }
         } else {
-            panic!("Cannot unwrap accessible into value.")
+            Self::insert_error(parser, source, span, "This expression is not a value.");
+            Value::Null
         }
     }
 }
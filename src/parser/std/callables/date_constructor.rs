@@ -1,8 +1,12 @@
 use chrono::NaiveDate;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::callables::string_argument;
 use crate::prelude::Value;
 
-pub(crate) fn date_constructor(args: Vec<Argument>) -> Value {
-    let b = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    Value::Date(NaiveDate::parse_from_str(b, "%Y-%m-%d").unwrap())
+pub(crate) fn date_constructor(args: Vec<Argument>) -> Result<Value, String> {
+    let b = string_argument(&args, "Date")?;
+    match NaiveDate::parse_from_str(b, "%Y-%m-%d") {
+        Ok(date) => Ok(Value::Date(date)),
+        Err(_) => Err(format!("'{}' is not a valid date.", b)),
+    }
 }
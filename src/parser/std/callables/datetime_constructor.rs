@@ -1,8 +1,12 @@
 use chrono::{DateTime, Utc};
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::callables::string_argument;
 use crate::prelude::Value;
 
-pub(crate) fn datetime_constructor(args: Vec<Argument>) -> Value {
-    let b = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    Value::DateTime(DateTime::parse_from_rfc3339(b).unwrap().with_timezone(&Utc))
+pub(crate) fn datetime_constructor(args: Vec<Argument>) -> Result<Value, String> {
+    let b = string_argument(&args, "DateTime")?;
+    match DateTime::parse_from_rfc3339(b) {
+        Ok(datetime) => Ok(Value::DateTime(datetime.with_timezone(&Utc))),
+        Err(_) => Err(format!("'{}' is not a valid datetime.", b)),
+    }
 }
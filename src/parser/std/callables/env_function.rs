@@ -0,0 +1,60 @@
+use std::env;
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+/// Reads an environment variable. The variable is parsed into `type`, one of `.string`, `.int`,
+/// `.int64`, `.float` and `.bool`, e.g. `env("PORT", type: .int)`. Without a type, it's parsed
+/// into the type of the default, or read as a string if there's no default either. A variable
+/// without a default is required.
+pub(crate) fn env_function(args: Vec<Argument>) -> Result<Value, String> {
+    let Some(name) = args.iter().find(|a| a.name.is_none()) else {
+        return Err("Environment variable name is missing.".to_owned());
+    };
+    let Some(name) = name.resolved_value().and_then(|v| v.as_str()) else {
+        return Err("Environment variable name should be a string.".to_owned());
+    };
+    let default = args.iter().find(|a| a.name() == Some("default")).and_then(|a| a.resolved_value()).cloned();
+    let default_type = match &default {
+        Some(default) => Some(value_type(default)?),
+        None => None,
+    };
+    let r#type = match args.iter().find(|a| a.name() == Some("type")) {
+        Some(arg) => match arg.resolved_value().and_then(|v| v.as_raw_enum_choice()) {
+            Some(r#type @ ("string" | "int" | "int64" | "float" | "bool")) => r#type,
+            _ => return Err("Environment variable type should be one of .string, .int, .int64, .float and .bool.".to_owned()),
+        },
+        None => default_type.unwrap_or("string"),
+    };
+    if default_type.map_or(false, |t| t != r#type) {
+        return Err(format!("Default of environment variable '{}' is not of type {}.", name, r#type));
+    }
+    let value = match env::var(name) {
+        Ok(value) => value,
+        Err(_) => match default {
+            Some(default) => return Ok(default),
+            None => return Err(format!("Environment variable '{}' is not set.", name)),
+        }
+    };
+    let parsed = match r#type {
+        "int" => value.parse().ok().map(Value::I32),
+        "int64" => value.parse().ok().map(Value::I64),
+        "float" => value.parse().ok().map(Value::F64),
+        "bool" => value.parse().ok().map(Value::Bool),
+        _ => Some(Value::String(value.clone())),
+    };
+    match parsed {
+        Some(parsed) => Ok(parsed),
+        None => Err(format!("Environment variable '{}' has invalid value '{}'.", name, value)),
+    }
+}
+
+fn value_type(value: &Value) -> Result<&'static str, String> {
+    match value {
+        Value::String(_) => Ok("string"),
+        Value::I32(_) => Ok("int"),
+        Value::I64(_) => Ok("int64"),
+        Value::F32(_) | Value::F64(_) => Ok("float"),
+        Value::Bool(_) => Ok("bool"),
+        _ => Err("Default of an environment variable should be a string, a number or a bool.".to_owned()),
+    }
+}
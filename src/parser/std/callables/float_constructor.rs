@@ -1,7 +1,11 @@
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::callables::string_argument;
 use crate::prelude::Value;
 
-pub(crate) fn float_constructor(args: Vec<Argument>) -> Value {
-    let b = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    Value::F64(b.parse().unwrap())
+pub(crate) fn float_constructor(args: Vec<Argument>) -> Result<Value, String> {
+    let b = string_argument(&args, "Float")?;
+    match b.parse() {
+        Ok(f) => Ok(Value::F64(f)),
+        Err(_) => Err(format!("'{}' is not a valid float.", b)),
+    }
 }
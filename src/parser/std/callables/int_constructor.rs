@@ -1,7 +1,11 @@
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::callables::string_argument;
 use crate::prelude::Value;
 
-pub(crate) fn int_constructor(args: Vec<Argument>) -> Value {
-    let b = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    Value::I32(b.parse().unwrap())
+pub(crate) fn int_constructor(args: Vec<Argument>) -> Result<Value, String> {
+    let b = string_argument(&args, "Int")?;
+    match b.parse() {
+        Ok(i) => Ok(Value::I32(i)),
+        Err(_) => Err(format!("'{}' is not a valid int.", b)),
+    }
 }
@@ -4,3 +4,11 @@ pub(crate) mod float_constructor;
 pub(crate) mod date_constructor;
 pub(crate) mod datetime_constructor;
 pub(crate) mod string_constructor;
+pub(crate) mod env_function;
+
+use crate::parser::ast::argument::Argument;
+
+/// The string which a constructor like `Int("1")` is called with.
+fn string_argument<'a>(args: &'a Vec<Argument>, constructor: &str) -> Result<&'a str, String> {
+    args.get(0).and_then(|a| a.resolved_value()).and_then(|v| v.as_str()).ok_or_else(|| format!("{}() takes a string.", constructor))
+}
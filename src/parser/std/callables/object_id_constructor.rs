@@ -1,8 +1,12 @@
 use bson::oid::ObjectId;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::callables::string_argument;
 use crate::prelude::Value;
 
-pub(crate) fn object_id_constructor(args: Vec<Argument>) -> Value {
-    let b = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    Value::ObjectId(ObjectId::parse_str(b).unwrap())
+pub(crate) fn object_id_constructor(args: Vec<Argument>) -> Result<Value, String> {
+    let b = string_argument(&args, "ObjectId")?;
+    match ObjectId::parse_str(b) {
+        Ok(id) => Ok(Value::ObjectId(id)),
+        Err(_) => Err(format!("'{}' is not a valid object id.", b)),
+    }
 }
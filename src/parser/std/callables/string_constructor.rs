@@ -1,7 +1,8 @@
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::callables::string_argument;
 use crate::prelude::Value;
 
-pub(crate) fn string_constructor(args: Vec<Argument>) -> Value {
-    let b = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    Value::String(b.to_owned())
+pub(crate) fn string_constructor(args: Vec<Argument>) -> Result<Value, String> {
+    let b = string_argument(&args, "String")?;
+    Ok(Value::String(b.to_owned()))
 }
@@ -2,6 +2,7 @@ use crate::core::field::Field;
 
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn atomic_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn atomic_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.atomic = true;
+    Ok(())
 }
@@ -2,6 +2,7 @@ use crate::core::field::Field;
 
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn auth_identity_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn auth_identity_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.identity = true;
+    Ok(())
 }
@@ -1,6 +1,8 @@
 use crate::core::field::Field;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn can_mutate_decorator(args: Vec<Argument>, field: &mut Field) {
-    field.can_mutate_pipeline = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap().clone()
+pub(crate) fn can_mutate_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    field.can_mutate_pipeline = first_value(&args)?.as_pipeline().required()?.clone();
+    Ok(())
 }
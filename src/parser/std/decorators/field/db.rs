@@ -1,14 +1,25 @@
 use maplit::hashmap;
 use crate::core::database::name::DatabaseName;
-use crate::parser::ast::accessible::Container;
+use crate::core::database::r#type::DatabaseType;
+use crate::core::field::Field;
+use crate::core::field::r#type::FieldTypeOwner;
+use crate::parser::ast::accessible::{Accessible, Container};
+use crate::parser::ast::argument::Argument;
+use crate::parser::ast::entity::Entity;
 
 pub(crate) fn db_container(database_name: DatabaseName) -> Container {
     match database_name {
         DatabaseName::MySQL => {
-            Container { objects: hashmap!{} }
+            Container { objects: hashmap!{
+                "string".to_owned() => Entity::Accessible(Accessible::FieldDecorator(mysql_string_decorator)),
+                "enum".to_owned() => Entity::Accessible(Accessible::FieldDecorator(native_enum_decorator)),
+            } }
         }
         DatabaseName::PostgreSQL => {
-            Container { objects: hashmap!{} }
+            Container { objects: hashmap!{
+                "string".to_owned() => Entity::Accessible(Accessible::FieldDecorator(psql_string_decorator)),
+                "enum".to_owned() => Entity::Accessible(Accessible::FieldDecorator(native_enum_decorator)),
+            } }
         }
         #[cfg(feature = "data-source-sqlite")]
         DatabaseName::SQLite => {
@@ -17,5 +28,36 @@ pub(crate) fn db_container(database_name: DatabaseName) -> Container {
         DatabaseName::MongoDB => {
             Container { objects: hashmap!{} }
         }
+        #[cfg(feature = "data-source-mssql")]
+        DatabaseName::MSSQL => {
+            Container { objects: hashmap!{} }
+        }
+    }
+}
+
+fn ensure_enum_field(field: &Field, decorator: &str) -> Result<(), String> {
+    if !field.field_type().is_enum() {
+        return Err(format!("@db.{} can only be applied to enum fields, `{}' is not.", decorator, field.name()));
     }
+    Ok(())
+}
+
+/// Stores an enum field as a string column.
+fn mysql_string_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    ensure_enum_field(field, "string")?;
+    field.database_type = Some(DatabaseType::VarChar { m: 191, n: None, c: None });
+    Ok(())
+}
+
+fn psql_string_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    ensure_enum_field(field, "string")?;
+    field.database_type = Some(DatabaseType::Text { m: None, n: None, c: None });
+    Ok(())
+}
+
+/// Stores an enum field with the native enum type of the database. This is the default.
+fn native_enum_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    ensure_enum_field(field, "enum")?;
+    field.database_type = None;
+    Ok(())
 }
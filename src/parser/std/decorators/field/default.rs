@@ -1,15 +1,17 @@
 use crate::core::field::Field;
 use crate::parser::ast::argument::Argument;
 use crate::parser::ast::entity::Entity;
+use crate::parser::std::decorators::Required;
 
-pub(crate) fn default_decorator(args: Vec<Argument>, field: &mut Field) {
-    match args.get(0).unwrap().resolved.as_ref().unwrap() {
+pub(crate) fn default_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    match args.get(0).and_then(|a| a.resolved.as_ref()).required()? {
         Entity::Value(value) => {
             field.default = Some(value.clone());
             field.input_omissible = true;
         }
         _ => {
-            panic!("Only value default is supported for now.")
+            return Err("Only value default is supported for now.".to_owned())
         }
     }
+    Ok(())
 }
@@ -1,6 +1,7 @@
 use crate::core::field::Field;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn dropped_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn dropped_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.dropped = true;
+    Ok(())
 }
@@ -2,6 +2,7 @@ use crate::core::field::Field;
 
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn foreign_key_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn foreign_key_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.foreign_key = true;
+    Ok(())
 }
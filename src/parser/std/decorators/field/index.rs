@@ -1,5 +1,6 @@
 use crate::core::field::{Field, FieldIndex, IndexSettings, Sort};
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::Required;
 
 static VALID_NAMES: [&str; 3] = ["map", "length", "sort"];
 
@@ -7,51 +8,52 @@ static FIELD_INDEX_PRIMARY: u8 = 0;
 static FIELD_INDEX_INDEX: u8 = 1;
 static FIELD_INDEX_UNIQUE: u8 = 2;
 
-pub(crate) fn id_decorator(args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn id_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     decorator(args, field, FIELD_INDEX_PRIMARY)
 }
 
-pub(crate) fn unique_decorator(args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn unique_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     decorator(args, field, FIELD_INDEX_UNIQUE)
 }
 
-pub(crate) fn index_decorator(args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn index_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     decorator(args, field, FIELD_INDEX_INDEX)
 }
 
-fn decorator(args: Vec<Argument>, field: &mut Field, index_kind: u8) {
+fn decorator(args: Vec<Argument>, field: &mut Field, index_kind: u8) -> Result<(), String> {
     if index_kind == FIELD_INDEX_PRIMARY {
         field.primary = true;
     }
     let mut settings = IndexSettings::default();
     for arg in args {
-        if arg.name.is_none() {
-            panic!("@migration requires argument name.");
+        let Some(name) = arg.name() else {
+            return Err("@migration requires argument name.".to_owned());
+        };
+        if !VALID_NAMES.contains(&name) {
+            return Err(format!("Unknown argument name: {}", name));
         }
-        if !VALID_NAMES.contains(&arg.name.as_ref().unwrap().name.as_str()) {
-            panic!("Unknown argument name: {}", arg.name.as_ref().unwrap().name.as_str());
-        }
-        match arg.name.as_ref().unwrap().name.as_str() {
+        match name {
             "sort" => {
-                match arg.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap() {
+                match arg.resolved_value().required()?.as_raw_enum_choice().required()? {
                     "asc" => settings.sort = Sort::Asc,
                     "desc" => settings.sort = Sort::Desc,
-                    _ => unreachable!()
+                    _ => return Err("Invalid decorator arguments.".to_owned()),
                 }
             }
             "length" => {
-                settings.length = Some(arg.resolved.as_ref().unwrap().as_value().unwrap().as_usize().unwrap());
+                settings.length = Some(arg.resolved_value().required()?.as_usize().required()?);
             }
             "map" => {
-                settings.name = Some(arg.resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap().to_owned())
+                settings.name = Some(arg.resolved_value().required()?.as_str().required()?.to_owned())
             }
-            _ => unreachable!()
+            _ => return Err("Invalid decorator arguments.".to_owned()),
         }
     }
     field.index = Some(match index_kind {
         0 => FieldIndex::Primary(settings),
         2 => FieldIndex::Unique(settings),
         1 => FieldIndex::Index(settings),
-        _ => unreachable!()
+        _ => return Err("Invalid decorator arguments.".to_owned()),
     });
+    Ok(())
 }
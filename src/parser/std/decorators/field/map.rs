@@ -2,7 +2,9 @@ use crate::core::field::Field;
 
 
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn map_decorator(args: Vec<Argument>, field: &mut Field) {
-    field.column_name = Some(args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap().to_string());
+pub(crate) fn map_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    field.column_name = Some(first_value(&args)?.as_str().required()?.to_string());
+    Ok(())
 }
@@ -2,52 +2,54 @@ use crate::core::field::Field;
 use crate::core::field::migration::FieldMigration;
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::Required;
 
 static VALID_NAMES: [&str; 5] = ["renamed", "default", "version", "action", "priority"];
 
-pub(crate) fn migration_decorator(args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn migration_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     let mut migration = FieldMigration { renamed: vec![], default: None, version: None, action: None, priority: None };
     for arg in args {
-        if arg.name.is_none() {
-            panic!("@migration requires argument name.");
+        let Some(name) = arg.name() else {
+            return Err("@migration requires argument name.".to_owned());
+        };
+        if !VALID_NAMES.contains(&name) {
+            return Err(format!("Unknown argument name: {}", name));
         }
-        if !VALID_NAMES.contains(&arg.name.as_ref().unwrap().name.as_str()) {
-            panic!("Unknown argument name: {}", arg.name.as_ref().unwrap().name.as_str());
-        }
-        match arg.name.as_ref().unwrap().name.as_str() {
+        match name {
             "renamed" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
+                let value = arg.resolved_value().required()?;
                 match value {
                     Value::String(s) => {
                         migration.renamed = vec![s.clone()];
                     }
                     Value::Vec(v) => {
-                        migration.renamed = v.iter().map(|v| v.as_str().unwrap().to_owned()).collect();
+                        migration.renamed = v.iter().map(|v| v.as_str().map(|s| s.to_owned()).required()).collect::<Result<_, _>>()?;
                     }
-                    _ => unreachable!()
+                    _ => return Err("Invalid decorator arguments.".to_owned()),
                 }
             }
             "version" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
-                let str = value.as_str().unwrap();
+                let value = arg.resolved_value().required()?;
+                let str = value.as_str().required()?;
                 migration.version = Some(str.to_owned());
             }
             "default" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
+                let value = arg.resolved_value().required()?;
                 migration.default = Some(value.clone());
             }
             "action" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
-                let p = value.as_pipeline().unwrap();
+                let value = arg.resolved_value().required()?;
+                let p = value.as_pipeline().required()?;
                 migration.action = Some(p.clone());
             }
             "priority" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
-                let u = value.as_usize().unwrap();
+                let value = arg.resolved_value().required()?;
+                let u = value.as_usize().required()?;
                 migration.priority = Some(u as i64);
             }
-            _ => unreachable!()
+            _ => return Err("Invalid decorator arguments.".to_owned()),
         }
         field.migration = Some(migration.clone());
     }
+    Ok(())
 }
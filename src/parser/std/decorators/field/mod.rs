@@ -34,6 +34,7 @@ pub(crate) mod can_read;
 pub(crate) mod can_mutate;
 pub(crate) mod migration_decorator;
 pub(crate) mod dropped;
+pub(crate) mod version;
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
@@ -49,6 +50,7 @@ use crate::parser::std::decorators::field::can_read::can_read_decorator;
 use crate::parser::std::decorators::field::db::db_container;
 use crate::parser::std::decorators::field::default::default_decorator;
 use crate::parser::std::decorators::field::dropped::dropped_decorator;
+use crate::parser::std::decorators::field::version::version_decorator;
 use crate::parser::std::decorators::field::foreign_key::foreign_key_decorator;
 use crate::parser::std::decorators::field::index::{id_decorator, index_decorator, unique_decorator};
 use crate::parser::std::decorators::field::input_omissible::input_omissible_decorator;
@@ -89,9 +91,11 @@ impl Debug for GlobalFieldDecorators {
 
 impl GlobalFieldDecorators {
 
-    pub(crate) fn new(database_name: DatabaseName) -> Self {
+    pub(crate) fn new(database_name: Option<DatabaseName>) -> Self {
         let mut objects: HashMap<String, Accessible> = HashMap::new();
-        objects.insert("db".to_owned(), Accessible::Container(db_container(database_name)));
+        if let Some(database_name) = database_name {
+            objects.insert("db".to_owned(), Accessible::Container(db_container(database_name)));
+        }
         objects.insert("id".to_owned(), Accessible::FieldDecorator(id_decorator));
         objects.insert("map".to_owned(), Accessible::FieldDecorator(map_decorator));
         objects.insert("unique".to_owned(), Accessible::FieldDecorator(unique_decorator));
@@ -129,13 +133,15 @@ impl GlobalFieldDecorators {
         objects.insert("canRead".to_owned(), Accessible::FieldDecorator(can_read_decorator));
         objects.insert("migration".to_owned(), Accessible::FieldDecorator(migration_decorator));
         objects.insert("dropped".to_owned(), Accessible::FieldDecorator(dropped_decorator));
+        objects.insert("version".to_owned(), Accessible::FieldDecorator(version_decorator));
         Self { objects }
     }
 
-    pub(crate) fn get(&self, key: &str) -> &Accessible {
-        match self.objects.get(key) {
-            Some(o) => o,
-            None => panic!("Object with key '{}' is not found.", key),
-        }
+    pub(crate) fn get(&self, key: &str) -> Option<&Accessible> {
+        self.objects.get(key)
+    }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.objects.keys().map(|k| k.as_str()).collect()
     }
 }
@@ -2,12 +2,14 @@ use crate::core::field::Field;
 
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::first_value;
 
-pub(crate) fn on_save_decorator(args: Vec<Argument>, field: &mut Field) {
-    match args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap() {
+pub(crate) fn on_save_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    match first_value(&args)? {
         Value::Pipeline(p) => {
             field.on_save_pipeline = p.clone();
         }
-        _ => panic!("Wrong argument passed to onSave.")
+        _ => return Err("Wrong argument passed to onSave.".to_owned())
     }
+    Ok(())
 }
@@ -2,19 +2,21 @@ use crate::core::field::Field;
 use crate::core::field::optionality::Optionality;
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::Required;
 
-pub(crate) fn present_with_decorator(args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn present_with_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     let mut result: Vec<Value> = vec![];
     for arg in args.iter() {
-        match arg.resolved.as_ref().unwrap().as_value().unwrap() {
+        match arg.resolved_value().required()? {
             Value::RawEnumChoice(enum_choice, _) => {
                 result.push(Value::RawEnumChoice(enum_choice.clone(), None))
             }
             Value::Vec(vec) => {
                 result.push(Value::Vec(vec.clone()));
             }
-            _ => panic!("Wrong argument passed to presentWith.")
+            _ => return Err("Wrong argument passed to presentWith.".to_owned())
         }
     }
     field.optionality = Optionality::PresentWith(result);
+    Ok(())
 }
@@ -2,6 +2,7 @@ use crate::core::field::Field;
 use crate::core::field::{QueryAbility};
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn queryable_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn queryable_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.query_ability = QueryAbility::Queryable;
+    Ok(())
 }
@@ -4,12 +4,14 @@ use crate::core::field::read_rule::ReadRule;
 
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::first_value;
 
-pub(crate) fn read_if_decorator(args: Vec<Argument>, field: &mut Field) {
-    match args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap() {
+pub(crate) fn read_if_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    match first_value(&args)? {
         Value::Pipeline(p) => {
             field.read_rule = ReadRule::ReadIf(p.clone());
         }
-        _ => panic!("Wrong argument passed to readIf.")
+        _ => return Err("Wrong argument passed to readIf.".to_owned())
     }
+    Ok(())
 }
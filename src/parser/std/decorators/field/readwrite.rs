@@ -4,7 +4,8 @@ use crate::core::field::read_rule::ReadRule;
 use crate::core::field::write_rule::WriteRule;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn readwrite_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn readwrite_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.read_rule = ReadRule::Read;
     field.write_rule = WriteRule::Write;
+    Ok(())
 }
@@ -1,6 +1,7 @@
 use crate::core::field::{Field, PreviousValueRule};
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn record_previous_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn record_previous_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.previous_value_rule = PreviousValueRule::Keep;
+    Ok(())
 }
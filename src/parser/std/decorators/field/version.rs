@@ -0,0 +1,9 @@
+use crate::core::field::Field;
+
+use crate::parser::ast::argument::Argument;
+
+pub(crate) fn version_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    field.version = true;
+    field.input_omissible = true;
+    Ok(())
+}
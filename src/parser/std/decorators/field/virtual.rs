@@ -3,6 +3,7 @@ use crate::core::field::Field;
 
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn virtual_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn virtual_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.r#virtual = true;
+    Ok(())
 }
@@ -4,12 +4,14 @@ use crate::core::field::write_rule::WriteRule;
 
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::first_value;
 
-pub(crate) fn write_if_decorator(args: Vec<Argument>, field: &mut Field) {
-    match args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap() {
+pub(crate) fn write_if_decorator(args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
+    match first_value(&args)? {
         Value::Pipeline(p) => {
             field.write_rule = WriteRule::WriteIf(p.clone());
         }
-        _ => panic!("Wrong argument passed to writeIf.")
+        _ => return Err("Wrong argument passed to writeIf.".to_owned())
     }
+    Ok(())
 }
@@ -3,6 +3,7 @@ use crate::core::field::Field;
 use crate::core::field::write_rule::WriteRule;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn write_once_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn write_once_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.write_rule = WriteRule::WriteOnce;
+    Ok(())
 }
@@ -3,6 +3,7 @@ use crate::core::field::Field;
 use crate::core::field::read_rule::ReadRule;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn writeonly_decorator(_args: Vec<Argument>, field: &mut Field) {
+pub(crate) fn writeonly_decorator(_args: Vec<Argument>, field: &mut Field) -> Result<(), String> {
     field.read_rule = ReadRule::NoRead;
+    Ok(())
 }
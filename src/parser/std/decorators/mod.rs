@@ -2,3 +2,21 @@ pub(crate) mod field;
 pub(crate) mod relation;
 pub(crate) mod property;
 pub(crate) mod model;
+
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+/// Decorators report unexpected arguments with this.
+pub(crate) trait Required<T> {
+    fn required(self) -> Result<T, String>;
+}
+
+impl<T> Required<T> for Option<T> {
+    fn required(self) -> Result<T, String> {
+        self.ok_or_else(|| "Invalid decorator arguments.".to_owned())
+    }
+}
+
+pub(crate) fn first_value(args: &Vec<Argument>) -> Result<&Value, String> {
+    args.get(0).and_then(|a| a.resolved_value()).required()
+}
@@ -1,8 +1,10 @@
 use crate::core::model::builder::ModelBuilder;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn action_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    let pipeline = value.as_pipeline().unwrap();
+pub(crate) fn action_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    let value = first_value(&args)?;
+    let pipeline = value.as_pipeline().required()?;
     model.add_action_transformer(pipeline.clone());
+    Ok(())
 }
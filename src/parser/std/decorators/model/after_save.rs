@@ -1,6 +1,8 @@
 use crate::core::model::builder::ModelBuilder;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn after_save_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
-    model.after_save_pipeline = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap().clone();
+pub(crate) fn after_save_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    model.after_save_pipeline = first_value(&args)?.as_pipeline().required()?.clone();
+    Ok(())
 }
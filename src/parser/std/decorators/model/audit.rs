@@ -0,0 +1,8 @@
+use crate::core::model::builder::ModelBuilder;
+
+use crate::parser::ast::argument::Argument;
+
+pub(crate) fn audit_decorator(_args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    model.audit = true;
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use crate::core::model::builder::ModelBuilder;
+
+use crate::parser::ast::argument::Argument;
+
+pub(crate) fn connector_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    let Some(name) = args.get(0).and_then(|a| a.resolved_value()).and_then(|v| v.as_str()) else {
+        return Err("@@connector requires a connector name.".to_owned());
+    };
+    model.connector(name);
+    Ok(())
+}
@@ -3,66 +3,80 @@ use crate::core::model::builder::ModelBuilder;
 use crate::core::model::index::{ModelIndex, ModelIndexItem, ModelIndexType};
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::Required;
 
 static MODEL_INDEX_PRIMARY: u8 = 0;
 static MODEL_INDEX_INDEX: u8 = 1;
 static MODEL_INDEX_UNIQUE: u8 = 2;
+static MODEL_INDEX_FULLTEXT: u8 = 3;
 
-pub(crate) fn id_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+pub(crate) fn id_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
     decorator(args, model, MODEL_INDEX_PRIMARY)
 }
 
-pub(crate) fn index_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+pub(crate) fn index_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
     decorator(args, model, MODEL_INDEX_INDEX)
 }
 
-pub(crate) fn unique_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+pub(crate) fn unique_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
     decorator(args, model, MODEL_INDEX_UNIQUE)
 }
 
-fn decorator(args: Vec<Argument>, model: &mut ModelBuilder, index_kind: u8) {
+/// Full-text indices are searched with the `search` filter and ranked with `_relevance`. MySQL only
+/// searches a single field with a full-text index of that field alone.
+pub(crate) fn fulltext_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    decorator(args, model, MODEL_INDEX_FULLTEXT)
+}
+
+fn decorator(args: Vec<Argument>, model: &mut ModelBuilder, index_kind: u8) -> Result<(), String> {
     let mut items: Vec<ModelIndexItem> = vec![];
     let mut map: Option<String> = None;
     if args.is_empty() {
-        panic!("Model index decorator takes at least one argument.")
+        return Err("Model index decorator takes at least one argument.".to_owned())
     }
-    let arg0 = args.get(0).unwrap();
-    if arg0.name.is_some() && (arg0.name.as_ref().unwrap().name.as_str() != "fields") {
-        panic!("Model index decorator's first argument should be fields or no name.")
+    let arg0 = args.get(0).required()?;
+    if arg0.name().map_or(false, |n| n != "fields") {
+        return Err("Model index decorator's first argument should be fields or no name.".to_owned())
     }
-    let arg0_value = arg0.resolved.as_ref().unwrap().as_value().unwrap();
+    let arg0_value = arg0.resolved_value().required()?;
     match arg0_value {
         Value::Vec(vec) => {
             for value in vec {
                 match value {
                     Value::RawEnumChoice(name, args) => {
-                        items.push(model_index_item(name, args));
+                        items.push(model_index_item(name, args)?);
                     }
-                    _ => unreachable!(),
+                    _ => return Err("Invalid decorator arguments.".to_owned()),
                 }
             }
         }
         Value::RawEnumChoice(name, args) => {
-            items.push(model_index_item(name, args));
+            items.push(model_index_item(name, args)?);
         }
-        _ => unreachable!(),
+        _ => return Err("Invalid decorator arguments.".to_owned()),
     }
     // map name
     if let Some(arg1) = args.get(1) {
-        if arg1.name.is_none() || (arg1.name.as_ref().unwrap().name.as_str() != "map") {
-            panic!("Model index decorator's second argument should be map.")
+        if arg1.name() != Some("map") {
+            return Err("Model index decorator's second argument should be map.".to_owned())
         }
-        map = Some(arg1.resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap().to_owned());
+        map = Some(arg1.resolved_value().required()?.as_str().required()?.to_owned());
     }
     match index_kind {
-        0 => model.indices.push(ModelIndex::new(ModelIndexType::Primary, map, items)),
+        0 => {
+            let primary = ModelIndex::new(ModelIndexType::Primary, map, items);
+            model.indices.push(primary.clone());
+            model.primary = Some(primary);
+        }
         1 => model.indices.push(ModelIndex::new(ModelIndexType::Index, map, items)),
         2 => model.indices.push(ModelIndex::new(ModelIndexType::Unique, map, items)),
-        _ => unreachable!(),
+        3 => model.indices.push(ModelIndex::new(ModelIndexType::FullText, map, items)),
+        _ => return Err("Invalid decorator arguments.".to_owned()),
     }
+    Ok(())
 }
 
-fn model_index_item(name: &String, args: &Option<Vec<(Option<String>, Value)>>) -> ModelIndexItem {
+fn model_index_item(name: &String, args: &Option<Vec<(Option<String>, Value)>>) -> Result<ModelIndexItem, String> {
     let name: String = name.clone();
     let mut sort = Sort::Asc;
     let mut len: Option<usize> = None;
@@ -70,24 +84,24 @@ fn model_index_item(name: &String, args: &Option<Vec<(Option<String>, Value)>>)
         for (index, (arg_name, arg_value)) in args.iter().enumerate() {
             match index {
                 0 => {
-                    if arg_name.is_some() && (arg_name.as_ref().unwrap().as_str() != "fields") {
-                        panic!("Unknown argument {}", arg_name.as_ref().unwrap());
+                    if let Some(arg_name) = arg_name.as_ref().filter(|n| n.as_str() != "fields") {
+                        return Err(format!("Unknown argument {}", arg_name));
                     }
-                    match arg_value.as_raw_enum_choice().unwrap() {
+                    match arg_value.as_raw_enum_choice().required()? {
                         "asc" => sort = Sort::Asc,
                         "desc" => sort = Sort::Desc,
-                        _ => unreachable!(),
+                        _ => return Err("Invalid decorator arguments.".to_owned()),
                     }
                 }
                 1 => {
-                    if arg_name.is_none() || (arg_name.as_ref().unwrap().as_str() != "length") {
-                        panic!("Second argument should be length.");
+                    if arg_name.as_ref().map(|n| n.as_str()) != Some("length") {
+                        return Err("Second argument should be length.".to_owned());
                     }
-                    len = Some(arg_value.as_usize().unwrap());
+                    len = Some(arg_value.as_usize().required()?);
                 }
-                _ => unreachable!(),
+                _ => return Err("Invalid decorator arguments.".to_owned()),
             }
         }
     }
-    ModelIndexItem::new(name, sort, len)
+    Ok(ModelIndexItem::new(name, sort, len))
 }
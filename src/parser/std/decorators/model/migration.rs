@@ -2,44 +2,46 @@ use crate::core::model::builder::ModelBuilder;
 use crate::core::model::migration::ModelMigration;
 use crate::parser::ast::argument::Argument;
 use crate::prelude::Value;
+use crate::parser::std::decorators::Required;
 
 static VALID_NAMES: [&str; 3] = ["renamed", "version", "drop"];
 
-pub(crate) fn migration_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+pub(crate) fn migration_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
     let mut migration = ModelMigration { renamed: vec![], version: None, drop: false };
     for arg in args {
-        if arg.name.is_none() {
-            panic!("@migration requires argument name.");
+        let Some(name) = arg.name() else {
+            return Err("@migration requires argument name.".to_owned());
+        };
+        if !VALID_NAMES.contains(&name) {
+            return Err(format!("Unknown argument name: {}", name));
         }
-        if !VALID_NAMES.contains(&arg.name.as_ref().unwrap().name.as_str()) {
-            panic!("Unknown argument name: {}", arg.name.as_ref().unwrap().name.as_str());
-        }
-        match arg.name.as_ref().unwrap().name.as_str() {
+        match name {
             "renamed" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
+                let value = arg.resolved_value().required()?;
                 match value {
                     Value::String(s) => {
                         migration.renamed = vec![s.clone()];
                     }
                     Value::Vec(v) => {
-                        migration.renamed = v.iter().map(|v| v.as_str().unwrap().to_owned()).collect();
+                        migration.renamed = v.iter().map(|v| v.as_str().map(|s| s.to_owned()).required()).collect::<Result<_, _>>()?;
                     }
-                    _ => unreachable!()
+                    _ => return Err("Invalid decorator arguments.".to_owned()),
                 }
             }
             "version" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
-                let str = value.as_str().unwrap();
+                let value = arg.resolved_value().required()?;
+                let str = value.as_str().required()?;
                 migration.version = Some(str.to_owned());
             }
             "drop" => {
-                let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
-                let b = value.as_bool().unwrap();
+                let value = arg.resolved_value().required()?;
+                let b = value.as_bool().required()?;
                 migration.drop = b;
             }
-            _ => unreachable!()
+            _ => return Err("Invalid decorator arguments.".to_owned()),
         }
 
     }
     model.migration = Some(migration);
+    Ok(())
 }
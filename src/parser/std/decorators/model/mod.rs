@@ -12,23 +12,29 @@ pub(crate) mod can_mutate;
 pub(crate) mod disable;
 pub(crate) mod action;
 pub(crate) mod migration;
+pub(crate) mod soft_delete;
+pub(crate) mod audit;
+pub(crate) mod connector;
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use crate::parser::ast::accessible::Accessible;
 use crate::parser::std::decorators::model::action::action_decorator;
+use crate::parser::std::decorators::model::audit::audit_decorator;
 use crate::parser::std::decorators::model::after_delete::after_delete_decorator;
 use crate::parser::std::decorators::model::after_save::after_save_decorator;
 use crate::parser::std::decorators::model::before_delete::before_delete_decorator;
 use crate::parser::std::decorators::model::before_save::before_save_decorator;
 use crate::parser::std::decorators::model::can_mutate::can_mutate_decorator;
 use crate::parser::std::decorators::model::can_read::can_read_decorator;
+use crate::parser::std::decorators::model::connector::connector_decorator;
 use crate::parser::std::decorators::model::disable::disable_decorator;
 use crate::parser::std::decorators::model::identity::identity_decorator;
-use crate::parser::std::decorators::model::index::{index_decorator, id_decorator, unique_decorator};
+use crate::parser::std::decorators::model::index::{index_decorator, id_decorator, unique_decorator, fulltext_decorator};
 use crate::parser::std::decorators::model::map::map_decorator;
 use crate::parser::std::decorators::model::migration::migration_decorator;
 use crate::parser::std::decorators::model::r#virtual::virtual_decorator;
+use crate::parser::std::decorators::model::soft_delete::soft_delete_decorator;
 use crate::parser::std::decorators::model::url::url_decorator;
 
 pub(crate) struct GlobalModelDecorators {
@@ -51,6 +57,7 @@ impl GlobalModelDecorators {
         objects.insert("id".to_owned(), Accessible::ModelDecorator(id_decorator));
         objects.insert("unique".to_owned(), Accessible::ModelDecorator(unique_decorator));
         objects.insert("index".to_owned(), Accessible::ModelDecorator(index_decorator));
+        objects.insert("fulltext".to_owned(), Accessible::ModelDecorator(fulltext_decorator));
         objects.insert("virtual".to_owned(), Accessible::ModelDecorator(virtual_decorator));
         objects.insert("beforeSave".to_owned(), Accessible::ModelDecorator(before_save_decorator));
         objects.insert("afterSave".to_owned(), Accessible::ModelDecorator(after_save_decorator));
@@ -61,13 +68,17 @@ impl GlobalModelDecorators {
         objects.insert("canRead".to_owned(), Accessible::ModelDecorator(can_read_decorator));
         objects.insert("canMutate".to_owned(), Accessible::ModelDecorator(can_mutate_decorator));
         objects.insert("migration".to_owned(), Accessible::ModelDecorator(migration_decorator));
+        objects.insert("softDelete".to_owned(), Accessible::ModelDecorator(soft_delete_decorator));
+        objects.insert("audit".to_owned(), Accessible::ModelDecorator(audit_decorator));
+        objects.insert("connector".to_owned(), Accessible::ModelDecorator(connector_decorator));
         Self { objects }
     }
 
-    pub(crate) fn get(&self, key: &str) -> &Accessible {
-        match self.objects.get(key) {
-            Some(o) => o,
-            None => panic!("Object with key '{}' is not found.", key),
-        }
+    pub(crate) fn get(&self, key: &str) -> Option<&Accessible> {
+        self.objects.get(key)
+    }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.objects.keys().map(|k| k.as_str()).collect()
     }
 }
@@ -0,0 +1,21 @@
+use crate::core::model::builder::ModelBuilder;
+use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::Required;
+
+/// Deleted records are kept with the deletion time recorded in `field`. They're hidden from
+/// queries unless `withDeleted` or `onlyDeleted` is given, and brought back with `restore`.
+pub(crate) fn soft_delete_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    if args.len() != 1 {
+        return Err("@softDelete requires exactly one argument.".to_owned());
+    }
+    let arg = args.get(0).required()?;
+    if arg.name.is_some() && (arg.name().required()? != "field") {
+        return Err("@softDelete's argument should be field or no name.".to_owned())
+    }
+    let value = arg.resolved_value().required()?;
+    match value.as_raw_enum_choice() {
+        Some(field) => { model.soft_delete(field); }
+        None => return Err("@softDelete's field should be a field name like `.deletedAt`.".to_owned()),
+    }
+    Ok(())
+}
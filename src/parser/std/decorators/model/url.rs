@@ -1,7 +1,9 @@
 use crate::core::model::builder::ModelBuilder;
 
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn url_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
-    model.url_segment_name(args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap());
+pub(crate) fn url_decorator(args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
+    model.url_segment_name(first_value(&args)?.as_str().required()?);
+    Ok(())
 }
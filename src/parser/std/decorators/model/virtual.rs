@@ -2,6 +2,7 @@ use crate::core::model::builder::ModelBuilder;
 
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn virtual_decorator(_args: Vec<Argument>, model: &mut ModelBuilder) {
+pub(crate) fn virtual_decorator(_args: Vec<Argument>, model: &mut ModelBuilder) -> Result<(), String> {
     model.r#virtual = true;
+    Ok(())
 }
@@ -1,6 +1,7 @@
 use crate::core::property::Property;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn cached_decorator(_args: Vec<Argument>, property: &mut Property) {
+pub(crate) fn cached_decorator(_args: Vec<Argument>, property: &mut Property) -> Result<(), String> {
     property.cached = true;
+    Ok(())
 }
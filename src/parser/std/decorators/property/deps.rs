@@ -1,8 +1,10 @@
 use crate::core::property::Property;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn deps_decorator(args: Vec<Argument>, property: &mut Property) {
-    let vec = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_vec().unwrap();
-    let dependencies = vec.iter().map(|v| v.as_raw_enum_choice().unwrap().to_owned()).collect();
+pub(crate) fn deps_decorator(args: Vec<Argument>, property: &mut Property) -> Result<(), String> {
+    let vec = first_value(&args)?.as_vec().required()?;
+    let dependencies = vec.iter().map(|v| v.as_raw_enum_choice().map(|c| c.to_owned()).required()).collect::<Result<_, _>>()?;
     property.dependencies = dependencies;
+    Ok(())
 }
@@ -1,7 +1,9 @@
 use crate::core::property::Property;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::{first_value, Required};
 
-pub(crate) fn setter_decorator(args: Vec<Argument>, property: &mut Property) {
-    let pipeline = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap();
+pub(crate) fn setter_decorator(args: Vec<Argument>, property: &mut Property) -> Result<(), String> {
+    let pipeline = first_value(&args)?.as_pipeline().required()?;
     property.setter = Some(pipeline.clone());
+    Ok(())
 }
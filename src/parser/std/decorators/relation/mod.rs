@@ -23,10 +23,11 @@ impl GlobalRelationDecorators {
         Self { objects }
     }
 
-    pub(crate) fn get(&self, key: &str) -> &Accessible {
-        match self.objects.get(key) {
-            Some(o) => o,
-            None => panic!("Object with key '{}' is not found.", key),
-        }
+    pub(crate) fn get(&self, key: &str) -> Option<&Accessible> {
+        self.objects.get(key)
+    }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.objects.keys().map(|k| k.as_str()).collect()
     }
 }
@@ -1,66 +1,66 @@
 use crate::core::relation::Relation;
+use crate::core::relation::delete_rule::DeleteRule;
+use crate::core::relation::update_rule::UpdateRule;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::decorators::Required;
+use crate::prelude::Value;
 
-pub(crate) fn relation_decorator(args: Vec<Argument>, relation: &mut Relation) {
-    let fields_arg = args.iter().find(|a| {
-        a.name.as_ref().unwrap().name == "fields"
-    });
-    let through_arg = args.iter().find(|a| {
-        a.name.as_ref().unwrap().name == "through"
-    });
+pub(crate) fn relation_decorator(args: Vec<Argument>, relation: &mut Relation) -> Result<(), String> {
+    let fields_arg = args.iter().find(|a| a.name() == Some("fields"));
+    let through_arg = args.iter().find(|a| a.name() == Some("through"));
     if fields_arg.is_some() && through_arg.is_some() {
-        panic!("A relation cannot have both 'fields' and 'through'.");
-    } else if fields_arg.is_some() {
+        return Err("A relation cannot have both 'fields' and 'through'.".to_owned());
+    } else if let Some(fields) = fields_arg {
         // use fields and references
-        let fields = fields_arg.unwrap();
-        let references = args.iter().find(|a| {
-            a.name.as_ref().unwrap().name == "references"
-        });
-        if references.is_none() {
-            panic!("A relation with 'fields' must have 'references'.");
-        }
-        let references = references.unwrap();
-        let fields_value = fields.resolved.as_ref().unwrap().as_value().unwrap();
-        let _references_value = references.resolved.as_ref().unwrap().as_value().unwrap();
-        if let Some(_) = fields_value.as_vec() {
-            let fields_vec: Vec<String> = fields.resolved.as_ref().unwrap().as_value().unwrap().as_vec().unwrap().iter().map(|v| {
-                v.as_raw_enum_choice().unwrap().to_owned()
-            }).collect();
-            relation.set_fields(fields_vec);
-            let references_vec: Vec<String> = references.resolved.as_ref().unwrap().as_value().unwrap().as_vec().unwrap().iter().map(|v| {
-                v.as_raw_enum_choice().unwrap().to_owned()
-            }).collect();
-            relation.set_references(references_vec);
-        } else if let Some(_) = fields_value.as_raw_enum_choice() {
-            let field = fields.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap().to_owned();
-            relation.set_fields(vec![field]);
-            let reference = references.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap().to_owned();
-            relation.set_references(vec![reference]);
+        let Some(references) = args.iter().find(|a| a.name() == Some("references")) else {
+            return Err("A relation with 'fields' must have 'references'.".to_owned());
+        };
+        let fields_value = fields.resolved_value().required()?;
+        let references_value = references.resolved_value().required()?;
+        if let Some(fields_vec) = fields_value.as_vec() {
+            relation.set_fields(enum_choices(fields_vec)?);
+            relation.set_references(enum_choices(references_value.as_vec().required()?)?);
+        } else if let Some(field) = fields_value.as_raw_enum_choice() {
+            relation.set_fields(vec![field.to_owned()]);
+            relation.set_references(vec![references_value.as_raw_enum_choice().required()?.to_owned()]);
         }
-    } else if through_arg.is_some() {
+    } else if let Some(through) = through_arg {
         // use through, local and foreign
-        let through_model_ref = through_arg.unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap().to_owned();
-        relation.set_through(through_model_ref);
-        let local = args.iter().find(|a| {
-            a.name.as_ref().unwrap().name == "local"
-        }).unwrap();
-        relation.set_local(local.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap().to_owned());
-        let foreign = args.iter().find(|a| {
-            a.name.as_ref().unwrap().name == "foreign"
-        }).unwrap();
-        relation.set_foreign(foreign.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap().to_owned());
+        relation.set_through(through.resolved_value().required()?.as_raw_enum_choice().required()?.to_owned());
+        let local = args.iter().find(|a| a.name() == Some("local")).required()?;
+        relation.set_local(local.resolved_value().required()?.as_raw_enum_choice().required()?.to_owned());
+        let foreign = args.iter().find(|a| a.name() == Some("foreign")).required()?;
+        relation.set_foreign(foreign.resolved_value().required()?.as_raw_enum_choice().required()?.to_owned());
     } else {
-        panic!("One of 'fields' or 'through' must be provided.")
+        return Err("One of 'fields' or 'through' must be provided.".to_owned())
     }
     // delete rule
-    // let on_delete_arg = args.iter().find(|a| {
-    //     &a.name.unwrap().name == "onDelete"
-    // });
-    // if on_delete_arg.is_some() {
-    //     let rule = on_delete_arg.unwrap().resolved.unwrap().as_value().unwrap().as_raw_enum_choice().unwrap();
-    //     match rule {
-    //
-    //     }
-    // }
+    let on_delete_arg = args.iter().find(|a| a.name() == Some("onDelete"));
+    if let Some(on_delete_arg) = on_delete_arg {
+        let rule = on_delete_arg.resolved_value().required()?.as_raw_enum_choice().required()?;
+        relation.set_delete_rule(match rule {
+            "default" => DeleteRule::Default,
+            "nullify" => DeleteRule::Nullify,
+            "cascade" => DeleteRule::Cascade,
+            "deny" => DeleteRule::Deny,
+            _ => return Err(format!("Unrecognized delete rule '{}'.", rule)),
+        });
+    }
     // update rule
+    let on_update_arg = args.iter().find(|a| a.name() == Some("onUpdate"));
+    if let Some(on_update_arg) = on_update_arg {
+        let rule = on_update_arg.resolved_value().required()?.as_raw_enum_choice().required()?;
+        relation.set_update_rule(match rule {
+            "default" => UpdateRule::Default,
+            "nullify" => UpdateRule::Nullify,
+            "cascade" => UpdateRule::Cascade,
+            "deny" => UpdateRule::Deny,
+            _ => return Err(format!("Unrecognized update rule '{}'.", rule)),
+        });
+    }
+    Ok(())
+}
+
+fn enum_choices(values: &Vec<Value>) -> Result<Vec<String>, String> {
+    values.iter().map(|v| v.as_raw_enum_choice().map(|c| c.to_owned()).required()).collect()
 }
@@ -4,37 +4,34 @@ use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::action::redirect::RedirectItem;
 use crate::core::pipeline::items::action::when::WhenItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value, arg_pipeline};
 use crate::prelude::Value;
 
-pub(crate) fn when(args: Vec<Argument>) -> Arc<dyn Item> {
-    let pipeline = args.get(1).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap();
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
+pub(crate) fn when(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let pipeline = arg_pipeline(&args, 1)?;
+    let value = arg_value(&args, 0)?;
     match value {
         Value::RawOptionChoice(action_value) => {
-            Arc::new(WhenItem::new(vec![Action::from_u32(*action_value)], pipeline.clone()))
+            Ok(Arc::new(WhenItem::new(vec![Action::from_u32(*action_value)], pipeline.clone())))
         }
         Value::RawEnumChoice(enum_member, _) => {
-            let action = Action::from_name(enum_member);
-            Arc::new(WhenItem::new(vec![action], pipeline.clone()))
-        }
-        _ => {
-            panic!()
+            let action = Action::from_name(enum_member)?;
+            Ok(Arc::new(WhenItem::new(vec![action], pipeline.clone())))
         }
+        _ => Err("Invalid action.".to_owned())
     }
 }
 
-pub(crate) fn redirect(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
+pub(crate) fn redirect(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
     match value {
         Value::RawOptionChoice(action_value) => {
-            Arc::new(RedirectItem::new(Action::from_u32(*action_value)))
+            Ok(Arc::new(RedirectItem::new(Action::from_u32(*action_value))))
         }
         Value::RawEnumChoice(enum_member, _) => {
-            let action = Action::from_name(enum_member);
-            Arc::new(RedirectItem::new(action))
-        }
-        _ => {
-            panic!()
+            let action = Action::from_name(enum_member)?;
+            Ok(Arc::new(RedirectItem::new(action)))
         }
+        _ => Err("Invalid action.".to_owned())
     }
 }
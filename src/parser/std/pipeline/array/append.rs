@@ -2,8 +2,9 @@ use std::sync::Arc;
 use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::array::append::AppendItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn append(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(AppendItem::new(value))
+pub(crate) fn append(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(AppendItem::new(value)))
 }
@@ -3,6 +3,6 @@ use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::array::get_length::GetLengthItem;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn get_length(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(GetLengthItem::new())
+pub(crate) fn get_length(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(GetLengthItem::new()))
 }
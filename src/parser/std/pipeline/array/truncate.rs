@@ -4,8 +4,9 @@ use crate::core::pipeline::item::Item;
 
 use crate::core::pipeline::items::array::truncate::TruncateItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn truncate(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(TruncateItem::new(value.clone()))
+pub(crate) fn truncate(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(TruncateItem::new(value.clone())))
 }
@@ -3,6 +3,6 @@ use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::bcrypt::bcrypt_salt::BcryptSaltItem;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn bcrypt_salt(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(BcryptSaltItem::new())
+pub(crate) fn bcrypt_salt(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(BcryptSaltItem::new()))
 }
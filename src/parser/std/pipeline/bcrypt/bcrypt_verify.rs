@@ -4,8 +4,9 @@ use crate::core::pipeline::item::Item;
 
 use crate::core::pipeline::items::bcrypt::bcrypt_verify::BcryptVerifyItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_pipeline};
 
-pub(crate) fn bcrypt_verify(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(BcryptVerifyItem::new(value.as_pipeline().unwrap().clone()))
+pub(crate) fn bcrypt_verify(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let pipeline = arg_pipeline(&args, 0)?;
+    Ok(Arc::new(BcryptVerifyItem::new(pipeline.clone())))
 }
@@ -1,72 +1,50 @@
 use std::sync::{Arc, Mutex};
 use crate::core::app::builder::CallbackLookupTable;
 use crate::core::pipeline::item::Item;
-
-
-
 use crate::core::pipeline::items::logical::transform_with::TransformWithItem;
 use crate::core::pipeline::items::logical::validate_with::ValidateWithItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 use crate::prelude::Value;
 
-pub(crate) fn custom_transform(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg_value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
+pub(crate) fn custom_transform(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg_value = arg_value(&args, 0)?;
     match arg_value {
         Value::String(s) => {
             let name = s.as_str();
-            let lookup_table = lookup_table.lock().unwrap();
-            let modifier = lookup_table.transforms.get(name);
-            if let Some(modifier) = modifier {
-                modifier.clone()
-            } else {
-                panic!("Cannot find a transform named '{}'.", name)
-            }
+            let modifier = lookup_table.lock().unwrap().transforms.get(name).cloned();
+            modifier.ok_or_else(|| format!("Cannot find a transform named '{}'.", name))
         }
         Value::Pipeline(p) => {
-            Arc::new(TransformWithItem::new(p.clone()))
+            Ok(Arc::new(TransformWithItem::new(p.clone())))
         }
-        _ => panic!("Argument to `transform` should be string or pipeline.")
+        _ => Err("Argument to `transform` should be string or pipeline.".to_owned())
     }
 }
 
-pub(crate) fn custom_callback(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Arc<dyn Item> {
-    let name = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    let lookup_table = lookup_table.lock().unwrap();
-    let modifier = lookup_table.callbacks.get(name);
-    if let Some(modifier) = modifier {
-        modifier.clone()
-    } else {
-        panic!("Cannot find a callback named '{}'.", name)
-    }
+pub(crate) fn custom_callback(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let name = arg_value(&args, 0)?.as_str().ok_or_else(|| "Argument to `callback` should be string.".to_owned())?;
+    let modifier = lookup_table.lock().unwrap().callbacks.get(name).cloned();
+    modifier.ok_or_else(|| format!("Cannot find a callback named '{}'.", name))
 }
 
-pub(crate) fn custom_validate(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg_value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
+pub(crate) fn custom_validate(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg_value = arg_value(&args, 0)?;
     match arg_value {
         Value::String(s) => {
             let name = s.as_str();
-            let lookup_table = lookup_table.lock().unwrap();
-            let modifier = lookup_table.validators.get(name);
-            if let Some(modifier) = modifier {
-                modifier.clone()
-            } else {
-                panic!("Cannot find a validate named '{}'.", name)
-            }
+            let modifier = lookup_table.lock().unwrap().validators.get(name).cloned();
+            modifier.ok_or_else(|| format!("Cannot find a validate named '{}'.", name))
         }
         Value::Pipeline(p) => {
-            Arc::new(ValidateWithItem::new(p.clone()))
+            Ok(Arc::new(ValidateWithItem::new(p.clone())))
         }
-        _ => panic!("Argument to `validate` should be string or pipeline.")
+        _ => Err("Argument to `validate` should be string or pipeline.".to_owned())
     }
 }
 
-pub(crate) fn custom_compare(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Arc<dyn Item> {
-    let name = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_str().unwrap();
-    let lookup_table = lookup_table.lock().unwrap();
-    let modifier = lookup_table.compares.get(name);
-    if let Some(modifier) = modifier {
-        modifier.clone()
-    } else {
-        panic!("Cannot find a compare named '{}'.", name)
-    }
+pub(crate) fn custom_compare(lookup_table: Arc<Mutex<CallbackLookupTable>>, args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let name = arg_value(&args, 0)?.as_str().ok_or_else(|| "Argument to `compare` should be string.".to_owned())?;
+    let modifier = lookup_table.lock().unwrap().compares.get(name).cloned();
+    modifier.ok_or_else(|| format!("Cannot find a compare named '{}'.", name))
 }
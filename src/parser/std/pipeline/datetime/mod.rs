@@ -4,10 +4,10 @@ use crate::core::pipeline::items::datetime::now::NowItem;
 use crate::core::pipeline::items::datetime::today::TodayItem;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn now(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(NowItem::new())
+pub(crate) fn now(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(NowItem::new()))
 }
 
-pub(crate) fn today(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(TodayItem::new())
+pub(crate) fn today(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(TodayItem::new()))
 }
@@ -2,11 +2,12 @@ use std::sync::Arc;
 use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::debug::print::PrintItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn print(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn print(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() == 0 {
-        Arc::new(PrintItem::new(None))
+        Ok(Arc::new(PrintItem::new(None)))
     } else {
-        Arc::new(PrintItem::new(Some(args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().clone())))
+        Ok(Arc::new(PrintItem::new(Some(arg_value(&args, 0)?.clone()))))
     }
 }
@@ -145,6 +145,10 @@ impl GlobalPipelineInstallers {
     pub(crate) fn get(&self, key: &str) -> Option<&ASTPipelineInstaller> {
         self.objects.get(key)
     }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.objects.keys().map(|k| k.as_str()).collect()
+    }
 }
 
 pub(crate) struct GlobalFunctionInstallers {
@@ -172,4 +176,8 @@ impl GlobalFunctionInstallers {
     pub(crate) fn get(&self, key: &str) -> Option<&ASTFunctionInstaller> {
         self.objects.get(key)
     }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.objects.keys().map(|k| k.as_str()).collect()
+    }
 }
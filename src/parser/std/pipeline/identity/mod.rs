@@ -2,8 +2,9 @@ use std::sync::Arc;
 use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::identity::identity::IdentityItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_pipeline};
 
-pub(crate) fn identity(args: Vec<Argument>) -> Arc<dyn Item> {
-    let pipeline = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap();
-    Arc::new(IdentityItem::new(pipeline.clone()))
+pub(crate) fn identity(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let pipeline = arg_pipeline(&args, 0)?;
+    Ok(Arc::new(IdentityItem::new(pipeline.clone())))
 }
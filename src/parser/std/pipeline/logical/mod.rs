@@ -10,97 +10,95 @@ use crate::core::pipeline::items::logical::or::OrItem;
 use crate::core::pipeline::items::logical::passed::PassedItem;
 use crate::core::pipeline::items::logical::r#if::IfItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value, arg_pipeline};
 
-pub(crate) fn valid(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(ValidItem::new())
+pub(crate) fn valid(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(ValidItem::new()))
 }
 
-pub(crate) fn invalid(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(InvalidItem::new())
+pub(crate) fn invalid(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(InvalidItem::new()))
 }
 
-pub(crate) fn if_modifier(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn if_modifier(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() == 0 {
-        panic!("`if` takes at least 1 argument.")
+        return Err("`if` takes at least 1 argument.".to_owned());
     }
-    let arg0 = args.get(0).unwrap();
-    if !arg0.name.is_none() && &arg0.name.as_ref().unwrap().name != "cond" {
-        panic!("First argument of `if` must be nameless or with name 'cond'.")
+    let arg0 = &args[0];
+    if arg0.name().map_or(false, |name| name != "cond") {
+        return Err("First argument of `if` must be nameless or with name 'cond'.".to_owned());
     }
-    let cond = arg0.resolved.as_ref().unwrap().as_value().unwrap();
+    let cond = arg_value(&args, 0)?;
     let mut then = None;
     let mut r#else = None;
     for (index, arg) in args.iter().enumerate() {
         if index != 0 {
-            if arg.name.is_none() {
-                panic!("Second and third argument of `if` should have name 'then' or 'else'.")
-            }
-            match arg.name.as_ref().unwrap().name.as_str() {
-                "then" => {
-                    then = Some(arg.resolved.as_ref().unwrap().as_value().unwrap().clone())
+            match arg.name() {
+                Some("then") => {
+                    then = Some(arg_value(&args, index)?.clone())
                 }
-                "else" => {
-                    r#else = Some(arg.resolved.as_ref().unwrap().as_value().unwrap().clone())
+                Some("else") => {
+                    r#else = Some(arg_value(&args, index)?.clone())
                 }
-                _ => panic!("Second and third argument of `if` should have name 'then' or 'else'.")
+                _ => return Err("Second and third argument of `if` should have name 'then' or 'else'.".to_owned())
             }
         }
     }
-    Arc::new(IfItem::new(cond.clone(), then, r#else))
+    Ok(Arc::new(IfItem::new(cond.clone(), then, r#else)))
 }
 
 
-pub(crate) fn all_modifier(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn all_modifier(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() == 0 {
-        panic!("`all` takes at least 1 argument.")
+        return Err("`all` takes at least 1 argument.".to_owned());
     }
     let mut pipelines = vec![];
-    for arg in args.iter() {
-        pipelines.push(arg.resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap().clone());
+    for index in 0..args.len() {
+        pipelines.push(arg_pipeline(&args, index)?.clone());
     }
-    Arc::new(AllItem::new(pipelines))
+    Ok(Arc::new(AllItem::new(pipelines)))
 }
 
 
-pub(crate) fn any_modifier(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn any_modifier(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() == 0 {
-        panic!("`any` takes at least 1 argument.")
+        return Err("`any` takes at least 1 argument.".to_owned());
     }
     let mut pipelines = vec![];
-    for arg in args.iter() {
-        pipelines.push(arg.resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap().clone());
+    for index in 0..args.len() {
+        pipelines.push(arg_pipeline(&args, index)?.clone());
     }
-    Arc::new(AnyItem::new(pipelines))
+    Ok(Arc::new(AnyItem::new(pipelines)))
 }
 
-pub(crate) fn not_modifier(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn not_modifier(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() != 1 {
-        panic!("`not` takes exactly 1 argument.")
+        return Err("`not` takes exactly 1 argument.".to_owned());
     }
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().clone();
-    Arc::new(NotItem::new(value))
+    let value = arg_value(&args, 0)?.clone();
+    Ok(Arc::new(NotItem::new(value)))
 }
 
-pub(crate) fn passed(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn passed(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() != 1 {
-        panic!("`passed` takes exactly 1 argument.")
+        return Err("`passed` takes exactly 1 argument.".to_owned());
     }
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().as_pipeline().unwrap().clone();
-    Arc::new(PassedItem::new(value))
+    let value = arg_pipeline(&args, 0)?.clone();
+    Ok(Arc::new(PassedItem::new(value)))
 }
 
-pub(crate) fn and_modifier(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn and_modifier(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() != 1 {
-        panic!("`and` takes exactly 1 argument.")
+        return Err("`and` takes exactly 1 argument.".to_owned());
     }
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().clone();
-    Arc::new(AndItem::new(value))
+    let value = arg_value(&args, 0)?.clone();
+    Ok(Arc::new(AndItem::new(value)))
 }
 
-pub(crate) fn or_modifier(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn or_modifier(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     if args.len() != 1 {
-        panic!("`or` takes exactly 1 argument.")
+        return Err("`or` takes exactly 1 argument.".to_owned());
     }
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap().clone();
-    Arc::new(OrItem::new(value))
+    let value = arg_value(&args, 0)?.clone();
+    Ok(Arc::new(OrItem::new(value)))
 }
@@ -16,72 +16,73 @@ use crate::core::pipeline::items::math::round::RoundItem;
 use crate::core::pipeline::items::math::sqrt::SqrtItem;
 use crate::core::pipeline::items::math::subtract::SubtractItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn abs(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(AbsItem::new())
+pub(crate) fn abs(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(AbsItem::new()))
 }
 
-pub(crate) fn add(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(AddItem::new(value.clone()))
+pub(crate) fn add(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(AddItem::new(value.clone())))
 }
 
-pub(crate) fn subtract(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(SubtractItem::new(value))
+pub(crate) fn subtract(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(SubtractItem::new(value)))
 }
 
-pub(crate) fn divide(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(DivideItem::new(value))
+pub(crate) fn divide(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(DivideItem::new(value)))
 }
 
-pub(crate) fn multiply(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(MultiplyItem::new(value))
+pub(crate) fn multiply(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(MultiplyItem::new(value)))
 }
 
-pub(crate) fn modular(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(ModularItem::new(value))
+pub(crate) fn modular(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(ModularItem::new(value)))
 }
 
-pub(crate) fn ceil(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(CeilItem::new())
+pub(crate) fn ceil(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(CeilItem::new()))
 }
 
-pub(crate) fn floor(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(FloorItem::new())
+pub(crate) fn floor(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(FloorItem::new()))
 }
 
-pub(crate) fn round(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(RoundItem::new())
+pub(crate) fn round(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(RoundItem::new()))
 }
 
-pub(crate) fn min(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( MinItem::new(value))
+pub(crate) fn min(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(MinItem::new(value)))
 }
 
-pub(crate) fn max(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( MaxItem::new(value))
+pub(crate) fn max(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(MaxItem::new(value)))
 }
 
-pub(crate) fn pow(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( PowItem::new(value))
+pub(crate) fn pow(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(PowItem::new(value)))
 }
 
-pub(crate) fn root(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( RootItem::new(value))
+pub(crate) fn root(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(RootItem::new(value)))
 }
 
-pub(crate) fn sqrt(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new( SqrtItem::new())
+pub(crate) fn sqrt(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(SqrtItem::new()))
 }
 
-pub(crate) fn cbrt(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new( CbrtItem::new())
+pub(crate) fn cbrt(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(CbrtItem::new()))
 }
@@ -15,3 +15,15 @@ pub(crate) mod vector;
 pub(crate) mod debug;
 pub(crate) mod query;
 pub(crate) mod global;
+
+use crate::core::pipeline::Pipeline;
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+pub(crate) fn arg_value(args: &Vec<Argument>, index: usize) -> Result<&Value, String> {
+    args.get(index).and_then(|a| a.resolved_value()).ok_or_else(|| "Invalid pipeline item arguments.".to_owned())
+}
+
+pub(crate) fn arg_pipeline(args: &Vec<Argument>, index: usize) -> Result<&Pipeline, String> {
+    arg_value(args, index)?.as_pipeline().ok_or_else(|| "Invalid pipeline item arguments.".to_owned())
+}
@@ -4,10 +4,10 @@ use crate::core::pipeline::items::number::is_even::IsEvenItem;
 use crate::core::pipeline::items::number::is_odd::IsOddItem;
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn is_even(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(IsEvenItem::new())
+pub(crate) fn is_even(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(IsEvenItem::new()))
 }
 
-pub(crate) fn is_odd(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(IsOddItem::new())
+pub(crate) fn is_odd(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(IsOddItem::new()))
 }
@@ -8,52 +8,53 @@ use crate::core::pipeline::items::object::set::SetItem;
 use crate::core::pipeline::items::object::get::GetItem;
 use crate::core::pipeline::items::object::is::IsItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn ctx_self(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(SelfItem::new())
+pub(crate) fn ctx_self(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(SelfItem::new()))
 }
 
-pub(crate) fn object_get(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( GetItem::new(value.clone()))
+pub(crate) fn object_get(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(GetItem::new(value.clone())))
 }
 
-pub(crate) fn object_set(args: Vec<Argument>) -> Arc<dyn Item> {
+pub(crate) fn object_set(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
     match args.len() {
         1 => {
-            let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-            Arc::new( SetItem::new(None, value.clone()))
+            let value = arg_value(&args, 0)?;
+            Ok(Arc::new(SetItem::new(None, value.clone())))
         }
         2 => {
-            let key = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-            let value = args.get(1).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-            Arc::new( SetItem::new(Some(key.clone()), value.clone()))
+            let key = arg_value(&args, 0)?;
+            let value = arg_value(&args, 1)?;
+            Ok(Arc::new(SetItem::new(Some(key.clone()), value.clone())))
         }
-        _ => panic!("wrong number of arguments to set")
+        _ => Err("Wrong number of arguments to `set`.".to_owned())
     }
 }
 
-pub(crate) fn object_previous_value(args: Vec<Argument>) -> Arc<dyn Item> {
-    let key = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( GetPreviousItem::new(key))
+pub(crate) fn object_previous_value(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let key = arg_value(&args, 0)?;
+    Ok(Arc::new(GetPreviousItem::new(key)))
 }
 
-pub(crate) fn is_a(args: Vec<Argument>) -> Arc<dyn Item> {
-    let key = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( IsObjectOfItem::new(key.as_raw_enum_choice().unwrap()))
+pub(crate) fn is_a(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let key = arg_value(&args, 0)?;
+    Ok(Arc::new(IsObjectOfItem::new(key.as_raw_enum_choice().ok_or_else(|| "Expect a model name.".to_owned())?)))
 }
 
-pub(crate) fn is(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
+pub(crate) fn is(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
     let relation_name = match args.get(1) {
-        Some(arg) => Some(arg.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap().to_string()),
+        Some(_) => Some(arg_value(&args, 1)?.as_raw_enum_choice().ok_or_else(|| "Expect a relation name.".to_owned())?.to_string()),
         None => None,
     };
-    Arc::new( IsItem::new(value.clone(), relation_name))
+    Ok(Arc::new(IsItem::new(value.clone(), relation_name)))
 }
 
-pub(crate) fn assign(args: Vec<Argument>) -> Arc<dyn Item> {
-    let key = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    let value = args.get(1).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(AssignItem::new(key.clone(), value.clone()))
+pub(crate) fn assign(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let key = arg_value(&args, 0)?;
+    let value = arg_value(&args, 1)?;
+    Ok(Arc::new(AssignItem::new(key.clone(), value.clone())))
 }
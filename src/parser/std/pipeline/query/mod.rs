@@ -1,9 +1,41 @@
 use std::sync::Arc;
+use indexmap::IndexMap;
 use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::query::query_raw::QueryRawItem;
+use crate::core::teon::Value;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::arg_value;
+use crate::parser::ast::expression::ExpressionKind;
 
-pub(crate) fn query_raw(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new( QueryRawItem::new(value.clone()))
+pub(crate) fn query_raw(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(QueryRawItem::new(source_ordered(&args[0].value, value))))
+}
+
+/// Dictionary literals resolve into hash maps. MongoDB commands and sort stages depend on the
+/// order of keys, so the order written in the schema is restored.
+fn source_ordered(expression: &ExpressionKind, value: &Value) -> Value {
+    match (expression, value) {
+        (ExpressionKind::Unit(unit), _) if unit.expressions.len() == 1 => source_ordered(&unit.expressions[0], value),
+        (ExpressionKind::DictionaryLiteral(dictionary), Value::HashMap(map)) => {
+            let mut result: IndexMap<String, Value> = IndexMap::new();
+            for (key, expression) in dictionary.expressions.iter() {
+                let key = key.to_string();
+                let key = key.trim_matches('"');
+                if let Some(value) = map.get(key) {
+                    result.insert(key.to_owned(), source_ordered(expression, value));
+                }
+            }
+            for (key, value) in map {
+                if !result.contains_key(key) {
+                    result.insert(key.clone(), value.clone());
+                }
+            }
+            Value::IndexMap(result)
+        }
+        (ExpressionKind::ArrayLiteral(array), Value::Vec(values)) if array.expressions.len() == values.len() => {
+            Value::Vec(array.expressions.iter().zip(values.iter()).map(|(e, v)| source_ordered(e, v)).collect())
+        }
+        _ => value.clone(),
+    }
 }
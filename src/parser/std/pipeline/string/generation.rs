@@ -6,24 +6,25 @@ use crate::core::pipeline::items::string::generation::random_digits::RandomDigit
 use crate::core::pipeline::items::string::generation::slug::SlugItem;
 use crate::core::pipeline::items::string::generation::uuid::UUIDItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn cuid(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(CUIDItem::new())
+pub(crate) fn cuid(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(CUIDItem::new()))
 }
 
-pub(crate) fn cuid2(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(CUID2Item::new())
+pub(crate) fn cuid2(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(CUID2Item::new()))
 }
 
-pub(crate) fn random_digits(args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(RandomDigitsItem::new(arg))
+pub(crate) fn random_digits(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg = arg_value(&args, 0)?;
+    Ok(Arc::new(RandomDigitsItem::new(arg)))
 }
 
-pub(crate) fn slug(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(SlugItem::new())
+pub(crate) fn slug(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(SlugItem::new()))
 }
 
-pub(crate) fn uuid(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(UUIDItem::new())
+pub(crate) fn uuid(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(UUIDItem::new()))
 }
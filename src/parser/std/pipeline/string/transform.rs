@@ -7,36 +7,42 @@ use crate::core::pipeline::items::string::transform::regex_replace::RegexReplace
 use crate::core::pipeline::items::string::transform::split::SplitItem;
 use crate::core::pipeline::items::string::transform::trim::TrimItem;
 use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn regex_replace(args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg1 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    let arg2 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(RegexReplaceItem::new(arg1, arg2))
+pub(crate) fn regex_replace(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg1 = arg_value(&args, 0)?;
+    let arg2 = arg_value(&args, 0)?;
+    Ok(Arc::new(RegexReplaceItem::new(arg1, arg2)))
 }
 
-pub(crate) fn trim(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(TrimItem::new())
+pub(crate) fn trim(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(TrimItem::new()))
 }
 
-pub(crate) fn split(args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(SplitItem::new(arg))
+pub(crate) fn split(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg = arg_value(&args, 0)?;
+    Ok(Arc::new(SplitItem::new(arg)))
 }
 
-pub(crate) fn pad_start(args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg1 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    let arg2 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(PadStartItem::new(arg1.as_str().unwrap().chars().nth(0).unwrap(), arg2))
+pub(crate) fn pad_start(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg1 = arg_value(&args, 0)?;
+    let arg2 = arg_value(&args, 0)?;
+    Ok(Arc::new(PadStartItem::new(pad_char(arg1)?, arg2)))
 }
 
-pub(crate) fn pad_end(args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg1 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    let arg2 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(PadEndItem::new(arg1.as_str().unwrap().chars().nth(0).unwrap(), arg2))
+pub(crate) fn pad_end(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg1 = arg_value(&args, 0)?;
+    let arg2 = arg_value(&args, 0)?;
+    Ok(Arc::new(PadEndItem::new(pad_char(arg1)?, arg2)))
 }
 
-pub(crate) fn ellipsis(args: Vec<Argument>) -> Arc<dyn Item> {
-    let arg1 = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    let arg2 = args.get(1).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(EllipsisItem::new(arg1.clone(), arg2))
+pub(crate) fn ellipsis(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let arg1 = arg_value(&args, 0)?;
+    let arg2 = arg_value(&args, 1)?;
+    Ok(Arc::new(EllipsisItem::new(arg1.clone(), arg2)))
+}
+
+fn pad_char(value: &Value) -> Result<char, String> {
+    value.as_str().and_then(|s| s.chars().nth(0)).ok_or_else(|| "Expect a padding character.".to_owned())
 }
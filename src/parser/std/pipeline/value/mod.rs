@@ -12,54 +12,55 @@ use crate::core::pipeline::items::value::lte::LteItem;
 use crate::core::pipeline::items::value::neq::NeqItem;
 use crate::core::pipeline::items::value::one_of::OneOfItem;
 use crate::parser::ast::argument::Argument;
+use crate::parser::std::pipeline::{arg_value};
 
-pub(crate) fn eq(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(EqItem::new(value))
+pub(crate) fn eq(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(EqItem::new(value)))
 }
 
-pub(crate) fn gt(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(GtItem::new(value))
+pub(crate) fn gt(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(GtItem::new(value)))
 }
 
-pub(crate) fn gte(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(GteItem::new(value))
+pub(crate) fn gte(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(GteItem::new(value)))
 }
 
-pub(crate) fn exists(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(ExistsItem::new())
+pub(crate) fn exists(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(ExistsItem::new()))
 }
 
-pub(crate) fn is_false(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(IsFalseItem::new())
+pub(crate) fn is_false(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(IsFalseItem::new()))
 }
 
-pub(crate) fn is_null(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(IsNullItem::new())
+pub(crate) fn is_null(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(IsNullItem::new()))
 }
 
-pub(crate) fn is_true(_args: Vec<Argument>) -> Arc<dyn Item> {
-    Arc::new(IsTrueItem::new())
+pub(crate) fn is_true(_args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    Ok(Arc::new(IsTrueItem::new()))
 }
 
-pub(crate) fn lt(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(LtItem::new(value))
+pub(crate) fn lt(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(LtItem::new(value)))
 }
 
-pub(crate) fn lte(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(LteItem::new(value))
+pub(crate) fn lte(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(LteItem::new(value)))
 }
 
-pub(crate) fn neq(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(NeqItem::new(value))
+pub(crate) fn neq(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(NeqItem::new(value)))
 }
 
-pub(crate) fn one_of(args: Vec<Argument>) -> Arc<dyn Item> {
-    let value = args.get(0).unwrap().resolved.as_ref().unwrap().as_value().unwrap();
-    Arc::new(OneOfItem::new(value.clone()))
+pub(crate) fn one_of(args: Vec<Argument>) -> Result<Arc<dyn Item>, String> {
+    let value = arg_value(&args, 0)?;
+    Ok(Arc::new(OneOfItem::new(value.clone())))
 }